@@ -8,10 +8,28 @@ use super::*;
 #[derive(Clone, Eq, PartialEq)]
 pub struct EscapedCommand {
     pub cmd: String,
+    /// Count of the fence marks
     pub level: u8,
+    /// Offset of the open fence
     pub start: usize,
+    /// Offset of the close fence
     pub end: usize,
     pub options: CommandOptions,
     pub pattern: CommandPattern,
     pub body: Literal<String>,
 }
+
+impl EscapedCommand {
+    #[inline]
+    pub fn new(
+        cmd: String,
+        level: u8,
+        pattern: CommandPattern,
+        options: CommandOptions,
+        body: Literal<String>,
+        start: usize,
+        end: usize,
+    ) -> Self {
+        Self { cmd, level, start, end, options, pattern, body }
+    }
+}
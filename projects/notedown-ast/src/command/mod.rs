@@ -5,9 +5,12 @@ mod options;
 mod traits;
 mod xml;
 
-pub use self::xml::{XMLCommand, XMLCommandKind};
+pub use self::{
+    escaped::EscapedCommand,
+    xml::{XMLCommand, XMLCommandKind},
+};
 use crate::{
-    command::{escaped::EscapedCommand, external::ExternalCommand, normal::NormalCommand},
+    command::{external::ExternalCommand, normal::NormalCommand},
     nodes::{Literal, MaybeRanged},
     value::*,
     ASTKind, ASTNode,
@@ -51,7 +54,39 @@ impl Command {
         }
     }
     #[inline]
+    pub fn get_pattern(&self) -> Option<&CommandPattern> {
+        match self {
+            Self::Normal(v) => Some(&v.pattern),
+            Self::Escaped(v) => Some(&v.pattern),
+            Self::XML(v) => Some(&v.pattern),
+            Self::External(_) => None,
+        }
+    }
+    #[inline]
+    pub fn get_options(&self) -> Option<&CommandOptions> {
+        match self {
+            Self::Normal(v) => Some(&v.options),
+            Self::Escaped(v) => Some(&v.options),
+            Self::XML(v) => Some(&v.options),
+            Self::External(_) => None,
+        }
+    }
+    #[inline]
     pub fn into_node(self, range: MaybeRanged) -> ASTNode {
         ASTNode { value: ASTKind::Command(box self), range }
     }
+    /// Range of the name in the source, `range` is the range of the whole command
+    /// ```md
+    /// \|cmd|
+    /// <|cmd|>
+    /// ```
+    pub fn name_range(&self, range: &Range<usize>) -> Option<Range<usize>> {
+        let start = match self {
+            Self::Normal(_) => range.start + 1,
+            Self::Escaped(v) => v.start + v.level as usize,
+            Self::XML(v) => return Some(v.kind.start_range(&v.cmd)),
+            Self::External(_) => return None,
+        };
+        Some(Range { start, end: start + self.command().len() })
+    }
 }
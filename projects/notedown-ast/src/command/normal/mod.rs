@@ -22,3 +22,17 @@ pub struct NormalCommand {
     pub pattern: CommandPattern,
     pub body: Literal<String>,
 }
+
+impl NormalCommand {
+    #[inline]
+    pub fn one_line(cmd: String, pattern: CommandPattern, options: CommandOptions, body: Literal<String>) -> Self {
+        Self { cmd, kind: NormalCommandKind::OneLine, options, pattern, body }
+    }
+}
+
+impl Command {
+    #[inline]
+    pub fn one_line(cmd: String, pattern: CommandPattern, options: CommandOptions, body: Literal<String>) -> Self {
+        Self::Normal(NormalCommand::one_line(cmd, pattern, options, body))
+    }
+}
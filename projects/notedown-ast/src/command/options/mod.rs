@@ -1,6 +1,19 @@
 use super::*;
+use crate::{NoteError, Result};
 
 impl CommandPattern {
+    #[inline]
+    pub fn new(pts: Vec<Literal<String>>) -> Self {
+        Self { pts }
+    }
+    #[inline]
+    pub fn push(&mut self, pattern: Literal<String>) {
+        self.pts.push(pattern)
+    }
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&Literal<String>> {
+        self.pts.get(index)
+    }
     #[inline]
     pub fn get_view(&self) -> Vec<String> {
         self.pts.iter().cloned().map(|s| s.value).collect()
@@ -14,3 +27,14 @@ impl CommandPattern {
         self.pts.is_empty()
     }
 }
+
+impl CommandOptions {
+    /// Read the key-value pairs into a typed struct, unknown keys are pushed into `warnings`
+    /// ```note
+    /// \img(src = "a.png", caption = "logo")
+    /// ```
+    pub fn read_kvs<T: FromValue>(&self, warnings: &mut Vec<NoteError>) -> Result<T> {
+        let value = Value::Object(Box::new(self.kvs.to_owned()));
+        T::from_value(Literal { value, range: None }, warnings)
+    }
+}
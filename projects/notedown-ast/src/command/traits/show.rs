@@ -47,9 +47,12 @@ impl Debug for XMLCommand {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         let w = &mut f.debug_struct("Command");
         w.field("kind", &self.kind.to_string());
+        w.field("name", &self.cmd);
         if !self.pattern.is_empty() {
             w.field("pattern", &self.pattern);
         }
+        w.field("option", &self.options);
+        w.field("body", &self.body);
         w.finish()
     }
 }
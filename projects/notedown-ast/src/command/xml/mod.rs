@@ -17,7 +17,7 @@ pub enum XMLCommandKind {
     SelfClose {
         // <cmd
         start: usize,
-        // </cmd>
+        // />
         end: usize,
     },
 }
@@ -36,39 +36,70 @@ pub struct XMLCommand {
 }
 
 impl XMLCommandKind {
+    /// Range of the name in the open tag
+    /// ```md
+    /// <|cmd|>
+    /// ```
     pub fn start_range(&self, name: &str) -> Range<usize> {
         match self {
             | Self::OpenClose { start, middle: _, end: _ } // \n
             | Self::SelfClose { start, end: _ } => {
-                Range { start: *start, end: start + 1 + name.len() }
+                Range { start: start + 1, end: start + 1 + name.len() }
             }
         }
     }
-    pub fn end_range(&self, name: &str) -> Range<usize> {
+    /// Range of the name in the close tag, self close tag has no close name
+    /// ```md
+    /// </|cmd|>
+    /// ```
+    pub fn end_range(&self, name: &str) -> Option<Range<usize>> {
         match self {
-            | Self::OpenClose { start: _, middle: _, end } // \n
-            | Self::SelfClose { start: _, end } => {
-                Range { start: *end, end: end + 1 + name.len() }
-            }
+            Self::OpenClose { start: _, middle: _, end } => Some(Range { start: end + 2, end: end + 2 + name.len() }),
+            Self::SelfClose { .. } => None,
+        }
+    }
+    /// Range of the body between the tags
+    /// ```md
+    /// <cmd>|body|</cmd>
+    /// ```
+    pub fn body_range(&self) -> Option<Range<usize>> {
+        match self {
+            Self::OpenClose { start: _, middle, end } => Some(Range { start: middle + 1, end: *end }),
+            Self::SelfClose { .. } => None,
         }
     }
 }
 
 impl XMLCommand {
-    pub fn open_close(body: ASTNodes, literal: CommandPattern, options: CommandOptions) -> Self {
-        let kind = XMLCommandKind::OpenClose { start: 0, middle: 0, end: 0 };
-        Self { cmd: "".to_string(), kind, pattern: literal, options, body }
+    #[inline]
+    pub fn open_close(
+        cmd: String,
+        pattern: CommandPattern,
+        options: CommandOptions,
+        body: ASTNodes,
+        start: usize,
+        middle: usize,
+        end: usize,
+    ) -> Self {
+        let kind = XMLCommandKind::OpenClose { start, middle, end };
+        Self { cmd, kind, pattern, options, body }
     }
-
-    pub fn self_close(literal: CommandPattern, options: CommandOptions) -> Self {
-        let kind = XMLCommandKind::SelfClose { start: 0, end: 0 };
-        Self { cmd: "".to_string(), kind, pattern: literal, options, body: vec![] }
+    #[inline]
+    pub fn self_close(cmd: String, pattern: CommandPattern, options: CommandOptions, start: usize, end: usize) -> Self {
+        let kind = XMLCommandKind::SelfClose { start, end };
+        Self { cmd, kind, pattern, options, body: vec![] }
     }
 }
 
 impl XMLCommand {
-    pub fn modify_name(&mut self, new: String) -> (Range<usize>, Range<usize>) {
-        let out = (self.kind.start_range(&self.cmd), self.kind.end_range(&self.cmd));
+    /// Ranges of the name in open tag and close tag
+    #[inline]
+    pub fn name_ranges(&self) -> (Range<usize>, Option<Range<usize>>) {
+        (self.kind.start_range(&self.cmd), self.kind.end_range(&self.cmd))
+    }
+    /// Rename the command, returns the ranges of old names which should be replaced
+    pub fn modify_name(&mut self, new: String) -> (Range<usize>, Option<Range<usize>>) {
+        let out = self.name_ranges();
         self.cmd = new;
         return out;
     }
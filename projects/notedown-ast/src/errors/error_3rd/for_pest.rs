@@ -1,5 +1,5 @@
 use crate::NoteError;
-use pest::error::{Error, ErrorVariant};
+use pest::error::{Error, ErrorVariant, InputLocation};
 use std::fmt::Debug;
 
 impl<R> From<Error<R>> for NoteError
@@ -7,7 +7,11 @@ where
     R: Debug,
 {
     fn from(e: Error<R>) -> Self {
-        let error = Self::from(e.variant);
+        let mut error = Self::from(e.variant);
+        match e.location {
+            InputLocation::Pos(p) => error.set_range(p, p),
+            InputLocation::Span((start, end)) => error.set_range(start, end),
+        }
         error
     }
 }
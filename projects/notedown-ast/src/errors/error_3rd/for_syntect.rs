@@ -0,0 +1,14 @@
+use crate::NoteError;
+use syntect::{Error, LoadingError};
+
+impl From<Error> for NoteError {
+    fn from(e: Error) -> Self {
+        NoteError::runtime_error(e.to_string())
+    }
+}
+
+impl From<LoadingError> for NoteError {
+    fn from(e: LoadingError) -> Self {
+        NoteError::runtime_error(e.to_string())
+    }
+}
@@ -5,3 +5,5 @@ mod for_num;
 mod for_pest;
 #[cfg(feature = "rsass")]
 mod for_sass;
+#[cfg(feature = "syntect")]
+mod for_syntect;
@@ -67,6 +67,21 @@ impl NoteError {
     }
 }
 
+impl NoteErrorKind {
+    /// Stable identifier of the error kind, used as the diagnostic code
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::IOError(_) => "io-error",
+            Self::FormatError(_) => "format-error",
+            Self::SyntaxError(_) => "syntax-error",
+            Self::TypeMismatch(_) => "type-mismatch",
+            Self::RuntimeError(_) => "runtime-error",
+            Self::UndefinedVariable { .. } => "undefined-variable",
+            Self::Unreachable => "unreachable",
+        }
+    }
+}
+
 impl NoteError {
     /// Deprecated or obsolete code.
     /// Clients are allowed to rendered diagnostics with this tag strike through.
@@ -31,15 +31,16 @@ impl NoteError {
         }
         return Some(tags);
     }
+    /// Errors without a level are reported as errors
     #[inline]
     pub fn build_diagnostic(&self, text: &TextIndex) -> Diagnostic {
         Diagnostic {
             range: self.get_lsp_range(text),
-            severity: self.level.into_severity(),
-            code: None,
+            severity: self.level.into_severity().or(Some(DiagnosticSeverity::ERROR)),
+            code: Some(NumberOrString::String(self.kind.code().to_string())),
             code_description: None,
-            source: None,
-            message: "".to_string(),
+            source: Some("notedown".to_string()),
+            message: self.kind.to_string(),
             related_information: None,
             tags: self.get_lsp_tags(),
             data: None,
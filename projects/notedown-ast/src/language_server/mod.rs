@@ -1,8 +1,7 @@
 mod diagnostic;
+mod text_index;
 mod toc;
 
+pub use self::text_index::TextIndex;
 use crate::{errors::DiagnosticLevel, traits::TocNode, NoteError};
-use yggdrasil_shared::records::{
-    lsp_types::{Diagnostic, DiagnosticSeverity, DiagnosticTag, DocumentSymbol, SymbolKind},
-    LSPRange, TextIndex,
-};
+use lsp_types::{Diagnostic, DiagnosticSeverity, DiagnosticTag, DocumentSymbol, NumberOrString, Range as LSPRange, SymbolKind};
@@ -0,0 +1,46 @@
+use lsp_types::{Position as LSPPosition, Range as LSPRange};
+
+/// Cache the start of every line, convert byte offsets to lsp positions
+#[derive(Clone, Debug, Default)]
+pub struct TextIndex {
+    text: String,
+    lines: Vec<usize>,
+}
+
+impl TextIndex {
+    #[inline]
+    pub fn new(input: impl Into<String>) -> Self {
+        let mut out = Self::default();
+        out.update(input);
+        return out;
+    }
+    pub fn update(&mut self, input: impl Into<String>) {
+        self.text = input.into();
+        self.lines = std::iter::once(0).chain(self.text.match_indices('\n').map(|(i, _)| i + 1)).collect();
+    }
+    #[inline]
+    pub fn get_text(&self) -> &'_ str {
+        &self.text
+    }
+    #[inline]
+    pub fn get_nth_line(&self, line: usize) -> Option<&'_ str> {
+        self.text.lines().nth(line)
+    }
+}
+
+impl TextIndex {
+    #[inline]
+    pub fn get_lsp_range(&self, start: usize, end: usize) -> LSPRange {
+        LSPRange { start: self.get_lsp_position(start), end: self.get_lsp_position(end) }
+    }
+    /// The character is counted in utf-16 code units, offsets out of the text are clamped to the end
+    pub fn get_lsp_position(&self, offset: usize) -> LSPPosition {
+        let mut offset = offset.min(self.text.len());
+        while !self.text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let line = self.lines.partition_point(|start| *start <= offset) - 1;
+        let character = self.text[self.lines[line]..offset].encode_utf16().count();
+        LSPPosition { line: line as u32, character: character as u32 }
+    }
+}
@@ -5,19 +5,20 @@ impl TocNode {
     pub fn get_lsp_range(&self, text: &TextIndex) -> LSPRange {
         text.get_lsp_range(self.range.start, self.range.end)
     }
-    /// Get table of content from element in lsp form
+    /// Get table of content from element in lsp form, the range is the section and the selection is the header
     #[inline]
     #[allow(deprecated)]
     pub fn as_document_symbol(&self, text: &TextIndex) -> DocumentSymbol {
+        let children: Vec<_> = self.children.iter().map(|node| node.as_document_symbol(text)).collect();
         DocumentSymbol {
-            name: "".to_string(),
+            name: self.name.to_owned(),
             detail: Some(self.detail.to_owned()),
             kind: SymbolKind::NAMESPACE,
             tags: None,
             deprecated: None,
             range: self.get_lsp_range(text),
-            selection_range: self.get_lsp_range(text),
-            children: None,
+            selection_range: text.get_lsp_range(self.selection_range.start, self.selection_range.end),
+            children: if children.is_empty() { None } else { Some(children) },
         }
     }
 }
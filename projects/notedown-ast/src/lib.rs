@@ -3,17 +3,26 @@
 #![feature(map_first_last)]
 #![feature(arbitrary_enum_discriminant)]
 
+// the derive macros refer to `::notedown_ast`, which also needs to resolve inside this crate
+extern crate self as notedown_ast;
+
 pub mod command;
 mod errors;
 #[cfg(feature = "lsp")]
 mod language_server;
 pub mod nodes;
 #[deny(missing_docs)]
+/// Select nodes with css like selectors
+pub mod query;
+#[deny(missing_docs)]
 /// Traits for notedown
 pub mod traits;
 #[deny(missing_docs)]
 /// Value and value types of notedown
 pub mod value;
+#[deny(missing_docs)]
+/// Visitors and folders over the tree
+pub mod visit;
 
 pub use self::{
     command::Command,
@@ -28,7 +37,10 @@ pub mod utils {
     pub use text_utils;
     #[cfg(feature = "lsp")]
     mod lsp_wrap {
-        pub use yggdrasil_shared::records::{lsp_types, DashMap, DashSet, LSPPosition, LSPRange, Rope, TextIndex, Url};
+        pub use crate::language_server::TextIndex;
+        pub use dashmap::{DashMap, DashSet};
+        pub use lsp_types::{self, Position as LSPPosition, Range as LSPRange, Url};
+        pub use ropey::Rope;
     }
     #[cfg(feature = "lsp")]
     pub use lsp_wrap::*;
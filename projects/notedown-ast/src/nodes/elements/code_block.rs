@@ -1,5 +1,10 @@
-use crate::nodes::*;
-use std::ops::RangeInclusive;
+use crate::{
+    command::CommandOptions,
+    nodes::*,
+    value::{expect_type, FromValue},
+    NoteError, Result,
+};
+use std::{ops::RangeInclusive, str::FromStr};
 
 /// # Code Block
 ///
@@ -25,6 +30,11 @@ use std::ops::RangeInclusive;
 /// following code
 /// another code
 /// ```
+/// 
+/// // `#` shows line numbers, `*` disables highlighting
+/// ```#lang
+/// some code
+/// ```
 /// ````
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct CodeNode {
@@ -106,6 +116,97 @@ impl CodeNode {
     }
 }
 
+impl CodeNode {
+    /// Apply the options of a code block, unknown keys are pushed into `warnings`
+    /// ````note
+    /// ```rust {file = "main.rs", line_number = 1, highlight = [1, "3-5"], hide = "7-9"}
+    /// ````
+    pub fn set_options(&mut self, options: &CommandOptions, warnings: &mut Vec<NoteError>) -> Result<()> {
+        let value = Literal { value: Value::Object(Box::new(options.kvs.to_owned())), range: None };
+        let options = CodeOptions::from_value(value, warnings)?;
+        if let Some(name) = options.file {
+            self.show_file_name = Some(name)
+        }
+        if let Some(LineNumber(n)) = options.line_number {
+            self.show_line_number = n
+        }
+        if let Some(LineRanges(lines)) = options.highlight {
+            self.highlight_lines.extend(lines)
+        }
+        if let Some(LineRanges(lines)) = options.hide {
+            self.hide_lines.extend(lines)
+        }
+        Ok(())
+    }
+}
+
+/// Options of [`CodeNode`], missing keys keep the current settings
+#[derive(FromValue)]
+struct CodeOptions {
+    #[note(alias = "file_name", alias = "title", default)]
+    file: Option<String>,
+    #[note(alias = "line", default)]
+    line_number: Option<LineNumber>,
+    #[note(alias = "highlight_lines", default)]
+    highlight: Option<LineRanges>,
+    #[note(alias = "hide_lines", default)]
+    hide: Option<LineRanges>,
+}
+
+/// `true`, `false` or the first line number
+struct LineNumber(Option<usize>);
+
+/// `3`, `"3-5"` or `[1, "3-5"]`
+struct LineRanges(Vec<RangeInclusive<usize>>);
+
+impl FromValue for LineNumber {
+    fn from_value(value: Literal<Value>, warnings: &mut Vec<NoteError>) -> Result<Self> {
+        match value.value {
+            Value::Boolean(true) => Ok(Self(Some(1))),
+            Value::Boolean(false) => Ok(Self(None)),
+            Value::Integer(_) => Ok(Self(Some(usize::from_value(value, warnings)?))),
+            _ => Err(expect_type("Boolean | Integer", &value)),
+        }
+    }
+}
+
+impl FromValue for LineRanges {
+    fn from_value(value: Literal<Value>, warnings: &mut Vec<NoteError>) -> Result<Self> {
+        match &value.value {
+            Value::Integer(_) => {
+                let line = usize::from_value(value, warnings)?;
+                Ok(Self(vec![RangeInclusive::new(line, line)]))
+            }
+            Value::String(s) => match parse_line_range(s) {
+                Some(s) => Ok(Self(vec![s])),
+                None => {
+                    let mut error = NoteError::syntax_error(format!("Invalid line range `{}`, try `3-5`", s));
+                    error.range = value.range;
+                    Err(error)
+                }
+            },
+            Value::Array(_) => {
+                let items = Vec::<Self>::from_value(value, warnings)?;
+                Ok(Self(items.into_iter().flat_map(|f| f.0).collect()))
+            }
+            _ => Err(expect_type("Integer | String | Array", &value)),
+        }
+    }
+}
+
+fn parse_line_range(s: &str) -> Option<RangeInclusive<usize>> {
+    let (start, end) = match s.split_once('-') {
+        Some((start, end)) => (start, end),
+        None => (s, s),
+    };
+    let start = usize::from_str(start.trim()).ok()?;
+    let end = usize::from_str(end.trim()).ok()?;
+    match start <= end {
+        true => Some(RangeInclusive::new(start, end)),
+        false => None,
+    }
+}
+
 impl CodeNode {
     /// ```notedown
     /// `s`
@@ -55,6 +55,12 @@ impl Header {
         self.level = level;
         self
     }
+    /// Move the header up or down by `delta` levels, still between 1 - 6
+    #[inline]
+    pub fn shift_level(&mut self, delta: i8) -> &mut Self {
+        let level = (self.level as i16 + delta as i16).clamp(1, 6);
+        self.set_level(level as u8)
+    }
     /// Id must slugify
     #[inline]
     pub fn set_id(&mut self, id: String) -> &mut Self {
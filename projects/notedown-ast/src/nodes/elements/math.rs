@@ -63,6 +63,21 @@ impl MathKind {
             Self::BlockDisplay => "\n\n$$",
         }
     }
+    /// The `$` or `$$` before the raw formula
+    pub fn delimiter(&self) -> &'static str {
+        match self {
+            Self::Inline | Self::BlockInline => "$",
+            Self::Display | Self::BlockDisplay => "$$",
+        }
+    }
+    /// Whether the formula should be rendered in display style
+    pub fn is_display(&self) -> bool {
+        matches!(self, Self::Display | Self::BlockDisplay)
+    }
+    /// Whether the formula occupies a whole block
+    pub fn is_block(&self) -> bool {
+        matches!(self, Self::BlockInline | Self::BlockDisplay)
+    }
     /// TODO: doc
     pub fn surround_end(&self) -> &'static str {
         match self {
@@ -98,7 +113,8 @@ impl MathBackend {
     pub fn new(s: &str) -> Option<Self> {
         match s.to_ascii_lowercase().as_str() {
             "tex" | "latex" => Some(Self::LaTeX),
-            "ascii" => Some(Self::AsciiMath),
+            "ascii" | "asciimath" | "am" => Some(Self::AsciiMath),
+            "mathml" | "mml" => Some(Self::MathML),
             _ => None,
         }
     }
@@ -131,6 +131,7 @@ macro_rules! styled_node {
 }
 
 styled_node![
+    plain       => Plain,
     bold        => Strong,
     strong      => Strong,
     italic      => Emphasis,
@@ -0,0 +1,58 @@
+use super::*;
+use crate::{traits::IntoASTNode, NoteError};
+use url::Url;
+
+/// ## Include Block
+/// Content transcluded from another document
+/// ```note
+/// \include[./chapter.note]
+/// \include[./chapter.note#installation](shift = 1)
+/// ```
+/// The range of this node is the range of the `\include` command,
+/// the ranges of `children` are offsets in the `source` file.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct IncludeNode {
+    /// The file where the children come from
+    pub source: Url,
+    /// Only the section under this header id is included
+    pub section: Option<String>,
+    /// Relative change of the header levels
+    pub shift: i8,
+    /// Nodes parsed from the source file
+    pub children: ASTNodes,
+}
+
+impl IncludeNode {
+    /// Constructor of [`IncludeNode`]
+    #[inline]
+    pub fn new(source: Url, section: Option<String>, children: ASTNodes) -> Self {
+        Self { source, section, shift: 0, children }
+    }
+    /// Point the error to the source file
+    #[inline]
+    pub fn attribute(&self, error: &mut NoteError) {
+        error.set_url(self.source.to_owned())
+    }
+}
+
+impl Display for IncludeNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "\\include[{}", self.source)?;
+        if let Some(s) = &self.section {
+            write!(f, "#{}", s)?;
+        }
+        f.write_str("]")?;
+        if self.shift != 0 {
+            write!(f, "(shift = {})", self.shift)?;
+        }
+        Ok(())
+    }
+}
+
+impl ASTKind {
+    /// Construct a include node
+    #[inline]
+    pub fn include(source: Url, section: Option<String>, children: ASTNodes, range: MaybeRanged) -> ASTNode {
+        IncludeNode::new(source, section, children).into_node(range)
+    }
+}
@@ -1,4 +1,11 @@
 use super::*;
+use crate::{
+    value::{expect_type, FromValue},
+    Result,
+};
+
+/// Extensions which make a path an image
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "svg", "webp", "bmp", "ico"];
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub enum ImageLayout {
@@ -33,6 +40,11 @@ pub struct ImageLink {
 }
 
 impl ImageLink {
+    /// Whether the path ends with an image extension, e.g. `[img/logo.png]`
+    pub fn is_image_path(path: &str) -> bool {
+        let extension = path.rsplit_once('.').map(|(_, ext)| ext.to_ascii_lowercase()).unwrap_or_default();
+        IMAGE_EXTENSIONS.contains(&extension.as_str())
+    }
     #[inline]
     pub fn into_node(self, range: MaybeRanged) -> ASTNode {
         SmartLink::Image(box self).into_node(range)
@@ -58,38 +70,59 @@ impl ImageLink {
         self.layout = Some(layout);
     }
 
-    pub fn set_options(&mut self, options: CommandOptions) -> Vec<NoteError> {
-        let mut options = options;
-        let mut errors = vec![];
-
-        options.kvs.extract_string("src").map(|f| self.set_src(f));
-        options.kvs.extract_string("source").map(|f| self.set_src(f));
-
-        self.parse_layout(&mut options, &mut errors);
-
-        options.kvs.extract_string("alt").map(|f| self.set_alt(f));
-        options.kvs.extract_string("caption").map(|f| self.set_alt(f));
-        options.kvs.extract_string("description").map(|f| self.set_alt(f));
-
-        options.kvs.extract_bool("force_caption").map(|f| self.force_caption = Some(f));
-
+    /// Apply the options of an image, unknown keys are pushed into `warnings`
+    /// ```note
+    /// [!alt][./logo.png](layout = "left", caption = "the logo", force_caption = true)
+    /// ```
+    pub fn set_options(&mut self, options: CommandOptions, warnings: &mut Vec<NoteError>) -> Result<()> {
+        let value = Literal { value: Value::Object(Box::new(options.kvs.to_owned())), range: None };
+        let typed = ImageOptions::from_value(value, warnings)?;
+        if let Some(src) = typed.src {
+            self.set_src(src)
+        }
+        if let Some(layout) = typed.layout {
+            self.set_layout(layout)
+        }
+        if let Some(alt) = typed.alt {
+            self.set_alt(alt)
+        }
+        if let Some(force_caption) = typed.force_caption {
+            self.force_caption = Some(force_caption)
+        }
         self.options = Some(options);
-        return errors;
+        Ok(())
     }
+}
+
+/// Options of [`ImageLink`], missing keys keep the current settings
+#[derive(FromValue)]
+struct ImageOptions {
+    #[note(alias = "source", default)]
+    src: Option<String>,
+    #[note(default)]
+    layout: Option<ImageLayout>,
+    #[note(alias = "caption", alias = "description", default)]
+    alt: Option<String>,
+    #[note(default)]
+    force_caption: Option<bool>,
+}
 
-    fn parse_layout(&mut self, options: &mut CommandOptions, errors: &mut Vec<NoteError>) {
-        let value = match options.kvs.extract("layout") {
-            None => return,
-            Some(s) => s,
-        };
-        match value {
-            Value::Integer(i) if i.is_negative() => self.layout = Some(ImageLayout::Left),
-            Value::Integer(i) if i.is_zero() => self.layout = Some(ImageLayout::Center),
-            Value::Integer(i) if i.is_zero() => self.layout = Some(ImageLayout::Right),
-            Value::String(s) if s.to_ascii_lowercase().eq("left") => self.layout = Some(ImageLayout::Left),
-            Value::String(s) if s.to_ascii_lowercase().eq("center") => self.layout = Some(ImageLayout::Center),
-            Value::String(s) if s.to_ascii_lowercase().eq("right") => self.layout = Some(ImageLayout::Right),
-            _ => errors.push(NoteError::runtime_error(format!("Unknown layout option {}", value))),
+/// `"left"`, `"center"`, `"right"`, or the sign of an integer
+impl FromValue for ImageLayout {
+    fn from_value(value: Literal<Value>, _: &mut Vec<NoteError>) -> Result<Self> {
+        match &value.value {
+            Value::Integer(i) if i.is_negative() => Ok(Self::Left),
+            Value::Integer(i) if i.is_zero() => Ok(Self::Center),
+            Value::Integer(_) => Ok(Self::Right),
+            Value::String(s) if s.eq_ignore_ascii_case("left") => Ok(Self::Left),
+            Value::String(s) if s.eq_ignore_ascii_case("center") => Ok(Self::Center),
+            Value::String(s) if s.eq_ignore_ascii_case("right") => Ok(Self::Right),
+            Value::String(s) => {
+                let mut error = NoteError::type_mismatch(format!("Unknown layout `{}`, expect `left`, `center` or `right`", s));
+                error.range = value.range;
+                Err(error)
+            }
+            _ => Err(expect_type("String | Integer", &value)),
         }
     }
 }
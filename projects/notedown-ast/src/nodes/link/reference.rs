@@ -1,3 +1,5 @@
+use super::*;
+
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct TagReference {
     /// ## Tag Block
@@ -21,3 +23,33 @@ pub struct TagReference {
     /// ```
     pub text: Vec<String>,
 }
+
+impl TagReference {
+    #[inline]
+    pub fn into_node(self, range: MaybeRanged) -> ASTNode {
+        SmartLink::Reference(box self).into_node(range)
+    }
+    /// Whether this is the `[^tag]: text` definition block
+    #[inline]
+    pub fn is_definition(&self) -> bool {
+        !self.inline
+    }
+}
+
+impl ASTKind {
+    /// `[^tag]`
+    #[inline]
+    pub fn tag_reference(tag: impl Into<String>, range: MaybeRanged) -> ASTNode {
+        TagReference { inline: true, tag: tag.into(), text: vec![] }.into_node(range)
+    }
+    /// `[^tag][text]`
+    #[inline]
+    pub fn tag_inline(tag: impl Into<String>, text: impl Into<String>, range: MaybeRanged) -> ASTNode {
+        TagReference { inline: true, tag: tag.into(), text: vec![text.into()] }.into_node(range)
+    }
+    /// `[^tag]: text`
+    #[inline]
+    pub fn tag_definition(tag: impl Into<String>, text: impl Into<String>, range: MaybeRanged) -> ASTNode {
+        TagReference { inline: false, tag: tag.into(), text: vec![text.into()] }.into_node(range)
+    }
+}
@@ -11,3 +11,25 @@ pub struct TwoWayLink {
     link: String,
     id: Option<String>,
 }
+
+impl TwoWayLink {
+    /// Parse `link > id`
+    pub fn new(input: &str) -> Self {
+        match input.split_once('>') {
+            Some((link, id)) => Self { link: link.trim().to_string(), id: Some(id.trim().to_string()) },
+            None => Self { link: input.trim().to_string(), id: None },
+        }
+    }
+    #[inline]
+    pub fn into_node(self, range: MaybeRanged) -> ASTNode {
+        SmartLink::TwoWay(box self).into_node(range)
+    }
+    #[inline]
+    pub fn get_link(&self) -> &str {
+        &self.link
+    }
+    #[inline]
+    pub fn get_id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+}
@@ -52,7 +52,9 @@ impl Default for ListPrefixSymbol {
 impl ListPrefixSymbol {
     pub fn parse(input: &str) -> Self {
         match input {
-            s if s.starts_with(">") => Self::Quote,
+            s if s.starts_with('>') => Self::Quote,
+            s if s.starts_with('-') || s.starts_with('+') => Self::Hyphen,
+            s if s.trim_end().strip_suffix('.').is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit())) => Self::Arabic,
             _ => Self::Unknown,
         }
     }
@@ -1,5 +1,6 @@
 mod as_element;
 mod elements;
+mod include;
 mod link;
 mod list;
 mod literal;
@@ -8,6 +9,7 @@ mod table;
 
 pub use self::{
     elements::*,
+    include::IncludeNode,
     link::{EmailLink, HyperLink, HyperLinkTarget, ImageLayout, ImageLink, ResourceDescriptor, SmartLink, TagReference, TwoWayLink},
     list::{DetailedList, ListItem, ListPrefixSymbol, ListView},
     literal::Literal,
@@ -55,6 +57,8 @@ pub enum ASTKind {
     ListView(Box<ListView>),
     /// - block only
     QuoteNode(Box<QuoteBlock>),
+    /// - block only
+    IncludeNode(Box<IncludeNode>),
     /// - block + inline
     CodeNode(Box<CodeNode>),
     /// - block + inline
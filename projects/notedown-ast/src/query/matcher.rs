@@ -0,0 +1,275 @@
+use super::*;
+use crate::{
+    command::Command,
+    nodes::{Delimiter, MathKind, SmartLink, StyleKind, TableView, TextSpan},
+    traits::PlainText,
+    visit::{walk_node, ControlFlow, VisitContext, Visitor},
+    ASTKind, Value,
+};
+use num::BigUint;
+
+/// Flattened tree in document order
+pub(super) struct Matcher<'ast> {
+    entries: Vec<Entry<'ast>>,
+}
+
+struct Entry<'ast> {
+    node: &'ast ASTNode,
+    parent: Option<usize>,
+    /// The header whose section contains this node
+    section: Option<usize>,
+}
+
+#[derive(Default)]
+struct Collector<'ast> {
+    entries: Vec<Entry<'ast>>,
+    stack: Vec<usize>,
+}
+
+impl<'ast> Visitor<'ast> for Collector<'ast> {
+    fn visit_node(&mut self, node: &'ast ASTNode, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        let index = self.entries.len();
+        self.entries.push(Entry { node, parent: self.stack.last().copied(), section: None });
+        self.stack.push(index);
+        let flow = walk_node(self, node, ctx);
+        self.stack.pop();
+        flow
+    }
+}
+
+impl<'ast> Matcher<'ast> {
+    pub fn new(root: &'ast ASTNode) -> Self {
+        let mut collector = Collector::default();
+        let _ = root.accept(&mut collector);
+        let mut matcher = Self { entries: collector.entries };
+        matcher.assign_sections();
+        matcher
+    }
+    /// The section of a header ends before the next header at the same or higher level
+    fn assign_sections(&mut self) {
+        let mut headers: Vec<Vec<(u8, usize)>> = vec![vec![]; self.entries.len()];
+        for index in 0..self.entries.len() {
+            let parent = match self.entries[index].parent {
+                Some(parent) if matches!(self.entries[parent].node.value, ASTKind::Statements(_)) => parent,
+                _ => continue,
+            };
+            let opened = &mut headers[parent];
+            if let ASTKind::Header(header) = &self.entries[index].node.value {
+                while opened.last().map(|(level, _)| *level >= header.level).unwrap_or_default() {
+                    opened.pop();
+                }
+                self.entries[index].section = opened.last().map(|(_, i)| *i);
+                opened.push((header.level, index));
+            }
+            else {
+                self.entries[index].section = opened.last().map(|(_, i)| *i);
+            }
+        }
+    }
+    pub fn select(&self, selector: &Selector) -> Vec<QueryMatch<'ast>> {
+        let mut out = vec![];
+        for (index, entry) in self.entries.iter().enumerate() {
+            let matched = selector.alternatives.iter().any(|complex| self.match_complex(index, &complex.parts));
+            if matched {
+                out.push(QueryMatch { node: entry.node, range: entry.node.range.to_owned() })
+            }
+        }
+        return out;
+    }
+    /// Match the last part at the node, then the rest at its ancestors
+    fn match_complex(&self, index: usize, parts: &[(Combinator, CompoundSelector)]) -> bool {
+        let ((combinator, compound), rest) = match parts.split_last() {
+            Some(s) => s,
+            None => return true,
+        };
+        if !self.match_compound(index, compound) {
+            return false;
+        }
+        if rest.is_empty() {
+            return true;
+        }
+        match combinator {
+            Combinator::Child => self.entries[index].parent.map(|parent| self.match_complex(parent, rest)).unwrap_or_default(),
+            Combinator::Descendant => self.scopes(index).into_iter().any(|scope| self.match_complex(scope, rest)),
+        }
+    }
+    /// Ancestors of the node, and the headers whose sections contain the node or its ancestors
+    fn scopes(&self, index: usize) -> Vec<usize> {
+        let mut out: Vec<usize> = vec![];
+        let mut stack = vec![index];
+        while let Some(current) = stack.pop() {
+            let entry = &self.entries[current];
+            for next in [entry.parent, entry.section].into_iter().flatten() {
+                if !out.contains(&next) {
+                    out.push(next);
+                    stack.push(next);
+                }
+            }
+        }
+        return out;
+    }
+    fn match_compound(&self, index: usize, compound: &CompoundSelector) -> bool {
+        let node = self.entries[index].node;
+        if let Some(kind) = &compound.kind {
+            if kind != "*" && kind != kind_name(node) {
+                return false;
+            }
+        }
+        compound.classes.iter().all(|class| has_class(node, class))
+            && compound.attributes.iter().all(|attribute| match_attribute(node, attribute))
+            && !compound.negations.iter().any(|negation| self.match_compound(index, negation))
+    }
+}
+
+fn match_attribute(node: &ASTNode, attribute: &AttributeSelector) -> bool {
+    let value = match get_attribute(node, &attribute.name) {
+        Some(s) => s,
+        None => return false,
+    };
+    match &attribute.test {
+        None => true,
+        Some((AttributeOperator::Equal, rhs)) => value.eq(rhs),
+        Some((AttributeOperator::NotEqual, rhs)) => value.ne(rhs),
+        Some((AttributeOperator::Prefix, rhs)) => value.starts_with(rhs.as_str()),
+        Some((AttributeOperator::Suffix, rhs)) => value.ends_with(rhs.as_str()),
+        Some((AttributeOperator::Contains, rhs)) => value.contains(rhs.as_str()),
+    }
+}
+
+fn kind_name(node: &ASTNode) -> &'static str {
+    match &node.value {
+        ASTKind::Statements(_) => "document",
+        ASTKind::Paragraph(_) => "paragraph",
+        ASTKind::Delimiter(_) => "delimiter",
+        ASTKind::Header(_) => "header",
+        ASTKind::TableView(_) => "table",
+        ASTKind::ListView(_) => "list",
+        ASTKind::QuoteNode(_) => "quote",
+        ASTKind::IncludeNode(_) => "include",
+        ASTKind::CodeNode(_) => "code",
+        ASTKind::MathNode(_) => "math",
+        ASTKind::LinkNode(_) => "link",
+        ASTKind::TextSpan(_) => "text",
+        ASTKind::StyledSpan(_) => "styled",
+        ASTKind::Command(_) => "command",
+        ASTKind::Value(_) => "value",
+    }
+}
+
+fn has_class(node: &ASTNode, class: &str) -> bool {
+    match &node.value {
+        ASTKind::Delimiter(v) => match **v {
+            Delimiter::HorizontalRule => class == "hr",
+            Delimiter::HTMLRawBlock(_) => class == "html",
+        },
+        ASTKind::CodeNode(v) => class == if v.inline { "inline" } else { "block" },
+        ASTKind::MathNode(v) => match v.kind {
+            MathKind::Inline => class == "inline",
+            MathKind::Display => class == "display",
+            MathKind::BlockInline => class == "block",
+            MathKind::BlockDisplay => class == "block" || class == "display",
+        },
+        ASTKind::LinkNode(v) => match v {
+            SmartLink::ExternalResource(_) => class == "resource",
+            SmartLink::EMail(_) => class == "email",
+            SmartLink::Normal(_) => class == "hyper",
+            SmartLink::Image(_) => class == "image",
+            SmartLink::Reference(_) => class == "reference",
+            SmartLink::TwoWay(_) => class == "twoway",
+        },
+        ASTKind::TextSpan(v) => match **v {
+            TextSpan::Empty => false,
+            TextSpan::Normal(_) => class == "normal",
+            TextSpan::Raw(_) => class == "raw",
+            TextSpan::HTMLRawInline(_) => class == "html",
+            TextSpan::Emoji(_) => class == "emoji",
+            TextSpan::Escaped(_) => class == "escaped",
+            TextSpan::SoftNewline | TextSpan::HardNewline => class == "newline",
+            TextSpan::CheckBox(_) => class == "checkbox",
+        },
+        ASTKind::StyledSpan(v) => match v.kind {
+            StyleKind::Plain => false,
+            StyleKind::Emphasis => class == "emphasis",
+            StyleKind::Strong => class == "strong",
+            StyleKind::ItalicBold => class == "emphasis" || class == "strong",
+            StyleKind::Underline => class == "underline",
+            StyleKind::Undercover => class == "undercover",
+            StyleKind::Marking => class == "marking",
+            StyleKind::Color(..) => class == "color",
+            StyleKind::Delete => class == "delete",
+            StyleKind::Insert => class == "insert",
+            StyleKind::Subscript => class == "subscript",
+            StyleKind::Superscript => class == "superscript",
+        },
+        ASTKind::Command(v) => match **v {
+            Command::Normal(_) => class == "normal",
+            Command::Escaped(_) => class == "escaped",
+            Command::XML(_) => class == "xml",
+            Command::External(_) => class == "external",
+        },
+        _ => false,
+    }
+}
+
+fn get_attribute(node: &ASTNode, name: &str) -> Option<String> {
+    if name == "text" {
+        return Some(node.plain_text());
+    }
+    match &node.value {
+        ASTKind::Header(v) => match name {
+            "level" => Some(v.level.to_string()),
+            "id" => v.id.to_owned(),
+            _ => None,
+        },
+        ASTKind::CodeNode(v) => match name {
+            "language" | "lang" => Some(v.language.to_owned()).filter(|s| !s.is_empty()),
+            "file" => v.show_file_name.to_owned(),
+            _ => None,
+        },
+        ASTKind::LinkNode(v) => match (v, name) {
+            (SmartLink::Normal(link), "src") => Some(link.src.to_owned()),
+            (SmartLink::Image(link), "src") => Some(link.source.to_owned()),
+            (SmartLink::Image(link), "alt") => link.description.to_owned().filter(|s| !s.trim().is_empty()),
+            (SmartLink::TwoWay(link), "src") => Some(link.get_link().to_string()),
+            (SmartLink::TwoWay(link), "id") => link.get_id().map(String::from),
+            (SmartLink::Reference(link), "tag") => Some(link.tag.to_owned()),
+            (SmartLink::EMail(link), "name") => Some(link.name.to_owned()),
+            _ => None,
+        },
+        ASTKind::QuoteNode(v) => match name {
+            "style" => v.style.to_owned(),
+            _ => None,
+        },
+        ASTKind::IncludeNode(v) => match name {
+            "source" => Some(v.source.to_string()),
+            "section" => v.section.to_owned(),
+            _ => None,
+        },
+        ASTKind::TableView(v) => match (&**v, name) {
+            (TableView::SimpleTable { column, .. }, "columns") => Some(column.to_string()),
+            _ => None,
+        },
+        ASTKind::Command(v) => command_attribute(v, name),
+        _ => None,
+    }
+}
+
+/// The name, positional arguments from 1, and then the options of the command
+fn command_attribute(cmd: &Command, name: &str) -> Option<String> {
+    if name == "name" {
+        return Some(cmd.command().to_string());
+    }
+    let options = cmd.get_options()?;
+    let value = match name.parse::<BigUint>() {
+        Ok(index) => options.args.get(&index)?,
+        Err(_) => options.kvs.get(name)?,
+    };
+    match value {
+        Value::Null => Some(String::from("null")),
+        Value::Boolean(v) => Some(v.to_string()),
+        Value::Integer(v) => Some(v.to_string()),
+        Value::Decimal(v) => Some(v.to_string()),
+        Value::String(v) => Some(v),
+        _ => None,
+    }
+}
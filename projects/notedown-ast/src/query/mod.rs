@@ -0,0 +1,128 @@
+//! Select nodes with css like selectors
+//!
+//! ```note
+//! header[level=2]
+//! header[text="Installation"] code[language=rust]
+//! link.image:not([alt])
+//! command[name=img][width=100], paragraph > styled.strong
+//! ```
+//!
+//! - `kind` or `*` matches the kind of the node, see [`Selector`] for the kinds, classes and attributes.
+//! - `.class` matches the variant of the node, `link.image` or `code.block` for example.
+//! - `[attr]` tests the attribute exists, `[attr=value]`, `[attr!=value]`, `[attr^=value]`, `[attr$=value]` and `[attr*=value]` compare the value.
+//! - `:not(...)` matches the nodes which don't match the inner selector.
+//! - `a b` matches `b` inside `a`, the section of a header is inside the header.
+//! - `a > b` matches `b` whose parent is `a`.
+//! - `a, b` matches `a` or `b`.
+mod matcher;
+mod parser;
+
+use crate::{nodes::MaybeRanged, ASTNode, Result};
+use std::str::FromStr;
+
+/// Compiled selector, parse once and select many times
+///
+/// | Kind        | Classes                                                            | Attributes                                      |
+/// | :---------- | :----------------------------------------------------------------- | :---------------------------------------------- |
+/// | `document`  |                                                                    |                                                 |
+/// | `paragraph` |                                                                    |                                                 |
+/// | `header`    |                                                                    | `level`, `id`                                   |
+/// | `code`      | `inline`, `block`                                                  | `language`, `lang`, `file`                      |
+/// | `math`      | `inline`, `display`, `block`                                       |                                                 |
+/// | `link`      | `hyper`, `image`, `email`, `twoway`, `reference`, `resource`       | `src`, `alt`, `id`, `tag`, `name`               |
+/// | `styled`    | `emphasis`, `strong`, `underline`, `delete`, ...                   |                                                 |
+/// | `command`   | `normal`, `escaped`, `xml`, `external`                             | `name`, positional `1`, `2`..., and the options |
+/// | `quote`     |                                                                    | `style`                                         |
+/// | `include`   |                                                                    | `source`, `section`                             |
+/// | `table`     |                                                                    | `columns`                                       |
+/// | `list`      |                                                                    |                                                 |
+/// | `text`      | `normal`, `raw`, `html`, `emoji`, `escaped`, `newline`, `checkbox` |                                                 |
+/// | `delimiter` | `hr`, `html`                                                       |                                                 |
+/// | `value`     |                                                                    |                                                 |
+///
+/// Every node has the `text` attribute, which is the plain text of the node.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Selector {
+    alternatives: Vec<ComplexSelector>,
+}
+
+/// The node matched by the selector
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QueryMatch<'ast> {
+    /// The matched node
+    pub node: &'ast ASTNode,
+    /// Range of the node in the source
+    pub range: MaybeRanged,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct ComplexSelector {
+    /// The first combinator is ignored
+    parts: Vec<(Combinator, CompoundSelector)>,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Combinator {
+    Descendant,
+    Child,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+struct CompoundSelector {
+    kind: Option<String>,
+    classes: Vec<String>,
+    attributes: Vec<AttributeSelector>,
+    negations: Vec<CompoundSelector>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct AttributeSelector {
+    name: String,
+    test: Option<(AttributeOperator, String)>,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum AttributeOperator {
+    Equal,
+    NotEqual,
+    Prefix,
+    Suffix,
+    Contains,
+}
+
+impl Selector {
+    /// Parse the selector, report the position of the syntax error
+    #[inline]
+    pub fn parse(input: &str) -> Result<Self> {
+        parser::SelectorParser::new(input).parse()
+    }
+    /// Matches under the root in document order, the root itself included
+    #[inline]
+    pub fn select<'ast>(&self, root: &'ast ASTNode) -> Vec<QueryMatch<'ast>> {
+        matcher::Matcher::new(root).select(self)
+    }
+}
+
+impl FromStr for Selector {
+    type Err = crate::NoteError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl ASTNode {
+    /// Select the nodes under this node
+    /// ```
+    /// # use notedown_ast::ASTKind;
+    /// let doc = ASTKind::statements(
+    ///     vec![ASTKind::header(vec![ASTKind::text("Intro", None)], 2, None)],
+    ///     None,
+    /// );
+    /// assert_eq!(doc.select("header[level=2]").unwrap().len(), 1);
+    /// ```
+    #[inline]
+    pub fn select(&self, selector: &str) -> Result<Vec<QueryMatch<'_>>> {
+        Ok(Selector::parse(selector)?.select(self))
+    }
+}
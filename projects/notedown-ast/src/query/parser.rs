@@ -0,0 +1,202 @@
+use super::*;
+use crate::NoteError;
+use std::{iter::Peekable, str::CharIndices};
+
+const KINDS: &[&str] = &[
+    "*",
+    "document",
+    "paragraph",
+    "header",
+    "code",
+    "math",
+    "link",
+    "styled",
+    "command",
+    "quote",
+    "include",
+    "table",
+    "list",
+    "text",
+    "delimiter",
+    "value",
+];
+
+pub(super) struct SelectorParser<'i> {
+    input: &'i str,
+    chars: Peekable<CharIndices<'i>>,
+}
+
+impl<'i> SelectorParser<'i> {
+    pub fn new(input: &'i str) -> Self {
+        Self { input, chars: input.char_indices().peekable() }
+    }
+    pub fn parse(mut self) -> Result<Selector> {
+        let mut alternatives = vec![self.parse_complex()?];
+        while self.eat(',') {
+            alternatives.push(self.parse_complex()?);
+        }
+        match self.chars.peek() {
+            None => Ok(Selector { alternatives }),
+            Some(_) => Err(self.error("expect `,` or the end of the selector")),
+        }
+    }
+    fn parse_complex(&mut self) -> Result<ComplexSelector> {
+        self.skip_whitespace();
+        let mut parts = vec![(Combinator::Descendant, self.parse_compound()?)];
+        loop {
+            let spaced = self.skip_whitespace();
+            let combinator = match self.chars.peek() {
+                Some((_, '>')) => {
+                    self.chars.next();
+                    self.skip_whitespace();
+                    Combinator::Child
+                }
+                Some((_, ',')) | None => break,
+                Some(_) if spaced => Combinator::Descendant,
+                Some(_) => return Err(self.error("expect a combinator")),
+            };
+            parts.push((combinator, self.parse_compound()?));
+        }
+        Ok(ComplexSelector { parts })
+    }
+    fn parse_compound(&mut self) -> Result<CompoundSelector> {
+        let mut compound = CompoundSelector::default();
+        let start = self.position();
+        if self.eat('*') {
+            compound.kind = Some(String::from("*"))
+        }
+        else if let Some(kind) = self.parse_ident() {
+            if !KINDS.contains(&kind.as_str()) {
+                let mut error = NoteError::syntax_error(format!("unknown node kind `{}`", kind));
+                error.set_range(start, self.position());
+                return Err(error);
+            }
+            compound.kind = Some(kind)
+        }
+        loop {
+            match self.chars.peek() {
+                Some((_, '.')) => {
+                    self.chars.next();
+                    compound.classes.push(self.expect_ident()?)
+                }
+                Some((_, '[')) => {
+                    self.chars.next();
+                    compound.attributes.push(self.parse_attribute()?)
+                }
+                Some((_, ':')) => {
+                    self.chars.next();
+                    let pseudo = self.expect_ident()?;
+                    if pseudo != "not" {
+                        return Err(self.error(format!("unknown pseudo class `:{}`", pseudo)));
+                    }
+                    self.expect('(')?;
+                    self.skip_whitespace();
+                    compound.negations.push(self.parse_compound()?);
+                    self.skip_whitespace();
+                    self.expect(')')?;
+                }
+                _ => break,
+            }
+        }
+        if compound == CompoundSelector::default() {
+            return Err(self.error("expect a node kind, `.class`, `[attribute]` or `:not()`"));
+        }
+        Ok(compound)
+    }
+    fn parse_attribute(&mut self) -> Result<AttributeSelector> {
+        self.skip_whitespace();
+        let name = self.expect_ident()?;
+        self.skip_whitespace();
+        let operator = match self.chars.peek().map(|(_, c)| *c) {
+            Some(']') => {
+                self.chars.next();
+                return Ok(AttributeSelector { name, test: None });
+            }
+            Some('=') => AttributeOperator::Equal,
+            Some('!') => AttributeOperator::NotEqual,
+            Some('^') => AttributeOperator::Prefix,
+            Some('$') => AttributeOperator::Suffix,
+            Some('*') => AttributeOperator::Contains,
+            _ => return Err(self.error("expect `]` or an operator")),
+        };
+        self.chars.next();
+        if operator != AttributeOperator::Equal {
+            self.expect('=')?;
+        }
+        self.skip_whitespace();
+        let value = match self.chars.peek().map(|(_, c)| *c) {
+            Some(quote @ ('"' | '\'')) => {
+                self.chars.next();
+                self.parse_quoted(quote)?
+            }
+            _ => {
+                let start = self.position();
+                while !matches!(self.chars.peek(), Some((_, ']')) | None) {
+                    self.chars.next();
+                }
+                self.input[start..self.position()].trim_end().to_string()
+            }
+        };
+        self.skip_whitespace();
+        self.expect(']')?;
+        Ok(AttributeSelector { name, test: Some((operator, value)) })
+    }
+    fn parse_quoted(&mut self, quote: char) -> Result<String> {
+        let mut out = String::new();
+        while let Some((_, c)) = self.chars.next() {
+            match c {
+                '\\' => match self.chars.next() {
+                    Some((_, c)) => out.push(c),
+                    None => break,
+                },
+                c if c == quote => return Ok(out),
+                c => out.push(c),
+            }
+        }
+        Err(self.error(format!("expect `{}` to close the string", quote)))
+    }
+    fn parse_ident(&mut self) -> Option<String> {
+        let start = self.position();
+        while let Some((_, c)) = self.chars.peek() {
+            if !(c.is_alphanumeric() || *c == '_' || *c == '-') {
+                break;
+            }
+            self.chars.next();
+        }
+        let end = self.position();
+        if start == end { None } else { Some(self.input[start..end].to_string()) }
+    }
+    fn expect_ident(&mut self) -> Result<String> {
+        match self.parse_ident() {
+            Some(s) => Ok(s),
+            None => Err(self.error("expect a name")),
+        }
+    }
+    fn expect(&mut self, c: char) -> Result<()> {
+        match self.eat(c) {
+            true => Ok(()),
+            false => Err(self.error(format!("expect `{}`", c))),
+        }
+    }
+    fn eat(&mut self, c: char) -> bool {
+        self.chars.next_if(|(_, next)| *next == c).is_some()
+    }
+    /// Return true if any whitespace is skipped
+    fn skip_whitespace(&mut self) -> bool {
+        let mut skipped = false;
+        while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {
+            skipped = true
+        }
+        skipped
+    }
+    fn position(&mut self) -> usize {
+        self.chars.peek().map(|(i, _)| *i).unwrap_or(self.input.len())
+    }
+    fn error(&mut self, msg: impl Into<String>) -> NoteError {
+        let start = self.position();
+        let end = self.input[start..].chars().next().map(|c| start + c.len_utf8()).unwrap_or(start);
+        let mut error = NoteError::syntax_error(msg);
+        error.set_range(start, end);
+        error
+    }
+}
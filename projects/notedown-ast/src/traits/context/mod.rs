@@ -1,59 +1,265 @@
 use crate::{
-    nodes::{ASTKind, ASTNode},
+    command::{Command, CommandOptions},
+    nodes::{ASTNode, CodeNode, Header, IncludeNode, ListView, Literal, MathNode, MaybeRanged, QuoteBlock, SmartLink, StyleNode, TextSpan},
     traits::ContextAware,
+    visit::{walk_command, walk_node, walk_nodes, walk_quote, ControlFlow, VisitContext, Visitor},
     ASTNodes,
 };
+use std::ops::Range;
 
-/// todo
+/// The innermost element under the cursor
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ContextKind {
-    /// todo
+    /// Not in any element
     None,
-    /// todo
+    /// Plain text
     Text,
-    /// todo
-    Function,
-    /// todo
-    Math,
-    /// todo
-    Code,
+    /// Text of a header with the level
+    HeaderText(u8),
+    /// Text in a cell of the table, the head is the first row
+    TableCell {
+        /// Index of the row
+        row: usize,
+        /// Index of the column
+        column: usize,
+    },
+    /// Text in the nth item of the list
+    ListItem(usize),
+    /// Inline code or code block with the language
+    CodeBody(String),
+    /// `$math$` or `$$math$$`
+    Math(Box<Literal<MathNode>>),
+    /// `\|cmd|` or `<|cmd|>`
+    CommandName(Box<Literal<Command>>),
+    /// `\cmd(|key| = value)`
+    ArgumentKey(Box<Literal<Command>>, String),
+    /// `\cmd(key = |value|)`, the key is none for positional arguments
+    ArgumentValue(Box<Literal<Command>>, Option<String>),
+    /// Other parts of the command, e.g. `\cmd( | )`
+    Function(Box<Literal<Command>>),
+    /// `[|target|]`, `[text][|target|]` or `[^|tag|]`
+    LinkTarget(Literal<SmartLink>),
+    /// `[|text|][target]`, `[!|alt|][source]` or `[^tag][|text|]`
+    LinkText(Literal<SmartLink>),
+}
+
+impl ContextKind {
+    /// Range of the element under the cursor
+    #[inline]
+    pub fn range(&self) -> MaybeRanged {
+        match self {
+            Self::Math(v) => v.range.to_owned(),
+            Self::LinkTarget(v) | Self::LinkText(v) => v.range.to_owned(),
+            _ => self.command()?.range.to_owned(),
+        }
+    }
+    /// The command under the cursor
+    #[inline]
+    pub fn command(&self) -> Option<&Literal<Command>> {
+        match self {
+            Self::CommandName(v) | Self::ArgumentKey(v, _) | Self::ArgumentValue(v, _) | Self::Function(v) => Some(v),
+            _ => None,
+        }
+    }
+    /// The link under the cursor
+    #[inline]
+    pub fn link(&self) -> Option<&Literal<SmartLink>> {
+        match self {
+            Self::LinkTarget(v) | Self::LinkText(v) => Some(v),
+            _ => None,
+        }
+    }
+    /// Plain text in a block takes the context of the block
+    #[inline]
+    fn text_as(self, block: ContextKind) -> Self {
+        match self {
+            Self::None | Self::Text => block,
+            _ => self,
+        }
+    }
 }
 
 impl ContextAware for ASTNodes {
     fn context_aware(&self, offset: u32) -> ContextKind {
-        for item in self {
-            let e = item.value.context_aware(offset);
-            if e != ContextKind::None {
-                return e;
-            }
-        }
-        return ContextKind::None;
+        let mut finder = ContextFinder { offset, found: ContextKind::None };
+        let _ = walk_nodes(&mut finder, self, &mut VisitContext::default());
+        return finder.found;
     }
 }
 
 impl ContextAware for ASTNode {
     fn context_aware(&self, offset: u32) -> ContextKind {
-        self.value.context_aware(offset)
+        let mut finder = ContextFinder { offset, found: ContextKind::None };
+        let _ = self.accept(&mut finder);
+        return finder.found;
     }
 }
 
-impl ContextAware for ASTKind {
-    fn context_aware(&self, offset: u32) -> ContextKind {
-        match self {
-            Self::Statements(children) => children.context_aware(offset),
-            Self::Header { .. } => unimplemented!(),
-            Self::Paragraph(children) => children.context_aware(offset),
-            Self::TableView { .. } => unimplemented!(),
-            Self::ListView { .. } => unimplemented!(),
-            Self::TextSpan(_) => unimplemented!(),
-            Self::MathNode(_) => ContextKind::Math,
-            Self::Command { .. } => unimplemented!(),
-            Self::Value { .. } => unimplemented!(),
-            Self::Delimiter(_) => unimplemented!(),
-            Self::CodeNode(_) => unimplemented!(),
-            Self::LinkNode(_) => unimplemented!(),
-            Self::StyledSpan(_) => unimplemented!(),
-            Self::QuoteNode(_) => unimplemented!(),
+/// Breaks at the innermost element under the cursor, the blocks around it are applied on the way back
+struct ContextFinder {
+    offset: u32,
+    found: ContextKind,
+}
+
+impl ContextFinder {
+    /// Take the context of the block if the cursor is on its plain text
+    #[inline]
+    fn found_in(&mut self, block: ContextKind) -> ControlFlow<()> {
+        self.found = std::mem::replace(&mut self.found, ContextKind::None).text_as(block);
+        ControlFlow::Break(())
+    }
+}
+
+impl<'ast> Visitor<'ast> for ContextFinder {
+    fn visit_node(&mut self, node: &'ast ASTNode, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        // nodes without range contain everything, e.g. the root
+        match &node.range {
+            Some(s) if !s.contains(&(self.offset as usize)) => ControlFlow::Continue(()),
+            _ => walk_node(self, node, ctx),
         }
     }
+    fn visit_header(&mut self, header: &'ast Header, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        let _ = walk_nodes(self, &header.children, ctx);
+        self.found_in(ContextKind::HeaderText(header.level))
+    }
+    fn visit_table_cell(&mut self, cell: &'ast ASTNode, row: usize, column: usize, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        match self.visit_node(cell, ctx) {
+            ControlFlow::Break(()) => self.found_in(ContextKind::TableCell { row, column }),
+            ControlFlow::Continue(()) => ControlFlow::Continue(()),
+        }
+    }
+    fn visit_list(&mut self, list: &'ast ListView, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        for (index, item) in list.children.iter().enumerate() {
+            if walk_nodes(self, &item.rest, ctx).is_break() || contains(&item.prefix.range, self.offset) {
+                return self.found_in(ContextKind::ListItem(index));
+            }
+        }
+        ControlFlow::Continue(())
+    }
+    fn visit_quote(&mut self, quote: &'ast QuoteBlock, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        let _ = walk_quote(self, quote, ctx);
+        self.found_in(ContextKind::Text)
+    }
+    fn visit_styled(&mut self, styled: &'ast StyleNode, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        let _ = walk_nodes(self, &styled.children, ctx);
+        self.found_in(ContextKind::Text)
+    }
+    /// Ranges of the children are offsets in another file
+    fn visit_include(&mut self, _: &'ast IncludeNode, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+    fn visit_link(&mut self, link: &'ast SmartLink, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.found = match ctx.node().and_then(|node| node.range.as_ref()) {
+            Some(range) => link_context(link, range, self.offset),
+            None => ContextKind::LinkTarget(Literal { value: link.to_owned(), range: None }),
+        };
+        ControlFlow::Break(())
+    }
+    fn visit_command(&mut self, cmd: &'ast Command, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        let range = ctx.node().and_then(|node| node.range.as_ref());
+        if let Some(e) = range.and_then(|range| command_context(cmd, range, self.offset)) {
+            self.found = e;
+            return ControlFlow::Break(());
+        }
+        // the body of xml command is another context
+        walk_command(self, cmd, ctx)?;
+        self.found = match cmd {
+            Command::Escaped(v) if range.is_some() && contains(&v.body.range, self.offset) => ContextKind::CodeBody(v.cmd.to_owned()),
+            _ => function(cmd, range),
+        };
+        ControlFlow::Break(())
+    }
+    fn visit_code(&mut self, code: &'ast CodeNode, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.found = ContextKind::CodeBody(code.language.to_owned());
+        ControlFlow::Break(())
+    }
+    fn visit_math(&mut self, math: &'ast MathNode, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        let range = ctx.node().and_then(|node| node.range.to_owned());
+        self.found = ContextKind::Math(Box::new(Literal { value: math.to_owned(), range }));
+        ControlFlow::Break(())
+    }
+    fn visit_text(&mut self, _: &'ast TextSpan, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.found = ContextKind::Text;
+        ControlFlow::Break(())
+    }
+}
+
+#[inline]
+fn function(cmd: &Command, range: Option<&Range<usize>>) -> ContextKind {
+    ContextKind::Function(Box::new(Literal { value: cmd.to_owned(), range: range.cloned() }))
+}
+
+#[inline]
+fn contains(range: &MaybeRanged, offset: u32) -> bool {
+    match range {
+        Some(s) => s.contains(&(offset as usize)),
+        None => false,
+    }
+}
+
+/// Tell apart the name and the arguments of the command
+fn command_context(cmd: &Command, range: &Range<usize>, offset: u32) -> Option<ContextKind> {
+    let literal = || Box::new(Literal { value: cmd.to_owned(), range: Some(range.to_owned()) });
+    let close_name = match cmd {
+        Command::XML(v) => v.kind.end_range(&v.cmd),
+        _ => None,
+    };
+    if contains(&cmd.name_range(range), offset) || contains(&close_name, offset) {
+        return Some(ContextKind::CommandName(literal()));
+    }
+    match argument_context(cmd.get_options()?, offset)? {
+        Argument::Key(key) => Some(ContextKind::ArgumentKey(literal(), key)),
+        Argument::Value(key) => Some(ContextKind::ArgumentValue(literal(), key)),
+    }
+}
+
+enum Argument {
+    Key(String),
+    Value(Option<String>),
+}
+
+fn argument_context(options: &CommandOptions, offset: u32) -> Option<Argument> {
+    for (key, value) in options.kvs.iter_raw() {
+        if contains(&key.range, offset) {
+            return Some(Argument::Key(key.value.to_owned()));
+        }
+        if contains(&value.range, offset) {
+            return Some(Argument::Value(Some(key.value.to_owned())));
+        }
+    }
+    for (index, _) in options.args.iter_sparse() {
+        let item = match options.args.get_literal(index) {
+            Some(s) => s,
+            None => continue,
+        };
+        if contains(&item.range, offset) {
+            return Some(Argument::Value(None));
+        }
+    }
+    return None;
+}
+
+/// Tell apart the text and the target of the link
+fn link_context(link: &SmartLink, range: &Range<usize>, offset: u32) -> ContextKind {
+    let offset = offset as usize;
+    let is_text = match link {
+        // [text][target]
+        SmartLink::Normal(v) if !v.is_bare => match &v.text {
+            Some(text) => offset <= range.start + 1 + text.len(),
+            None => false,
+        },
+        // [!alt][source]
+        SmartLink::Image(v) => match &v.description {
+            Some(alt) => offset <= range.start + 2 + alt.len(),
+            None => false,
+        },
+        // [^tag][text] or [^tag]: text
+        SmartLink::Reference(v) => !v.text.is_empty() && offset > range.start + 2 + v.tag.len(),
+        _ => false,
+    };
+    let literal = Literal { value: link.to_owned(), range: Some(range.to_owned()) };
+    match is_text {
+        true => ContextKind::LinkText(literal),
+        false => ContextKind::LinkTarget(literal),
+    }
 }
@@ -31,6 +31,7 @@ impl Display for ASTKind {
             ASTKind::CodeNode(inner) => Display::fmt(inner, f),
             ASTKind::LinkNode(inner) => Display::fmt(inner, f),
             ASTKind::QuoteNode(inner) => Display::fmt(inner, f),
+            ASTKind::IncludeNode(inner) => Display::fmt(inner, f),
             ASTKind::Command(inner) => Display::fmt(inner, f),
             ASTKind::Value(inner) => Display::fmt(inner, f),
         }
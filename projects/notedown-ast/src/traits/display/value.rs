@@ -22,8 +22,12 @@ impl Display for ValueType {
                 let t = v.iter().map(|e| e.to_string()).join(" | ");
                 write!(f, "List<{}, {}>", t, v.len())
             }
-            ValueType::Object(_) => {
-                unimplemented!()
+            ValueType::Object(v) => {
+                if v.is_empty() {
+                    return f.write_str("Object<?>");
+                }
+                let t = v.iter().map(|(k, v)| format!("{}: {}", k, v)).join(", ");
+                write!(f, "Object<{}>", t)
             }
         }
     }
@@ -43,14 +43,16 @@ macro_rules! into_node_boxed {
 }
 
 into_node_boxed![
-    QuoteBlock => QuoteNode,
-    Header     => Header,
-    Delimiter  => Delimiter,
-    ListView   => ListView,
-    CodeNode   => CodeNode ,
-    MathNode   => MathNode,
-    TextSpan   => TextSpan,
-    StyleNode  => StyledSpan,
-    Command    => Command,
-    Value      => Value,
+    QuoteBlock  => QuoteNode,
+    IncludeNode => IncludeNode,
+    Header      => Header,
+    Delimiter   => Delimiter,
+    ListView    => ListView,
+    TableView   => TableView,
+    CodeNode    => CodeNode ,
+    MathNode    => MathNode,
+    TextSpan    => TextSpan,
+    StyleNode   => StyledSpan,
+    Command     => Command,
+    Value       => Value,
 ];
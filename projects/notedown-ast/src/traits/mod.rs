@@ -7,6 +7,7 @@ mod toc;
 pub use self::{
     context::ContextKind,
     into_node::IntoASTNode,
+    slugify::SlugScheme,
     toc::{TableOfContent, TocConfig, TocNode},
 };
 
@@ -15,6 +16,11 @@ pub trait Slugify {
     /// Slugify the element of notedown
     fn slugify(&self) -> String;
 }
+/// Text of the element without styles and markups
+pub trait PlainText {
+    /// Text of the element without styles and markups
+    fn plain_text(&self) -> String;
+}
 /// Aware the context in which the cursor is located
 pub trait ContextAware {
     /// Aware the context in which the cursor is located
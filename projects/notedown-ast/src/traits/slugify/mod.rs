@@ -1,19 +1,18 @@
 use crate::{
     nodes::{ASTKind, ASTNode, ASTNodes, Header},
-    traits::Slugify,
+    traits::{PlainText, Slugify},
 };
 pub use text_utils::slugify;
 
+mod plain_text;
+mod scheme;
+mod unique;
+
+pub use self::scheme::SlugScheme;
+
 impl Slugify for ASTNodes {
     fn slugify(&self) -> String {
-        let mut out = String::new();
-        for span in self {
-            if !out.is_empty() {
-                out.push('-');
-            }
-            out.push_str(&span.slugify());
-        }
-        return out;
+        self.plain_text().slugify()
     }
 }
 
@@ -25,15 +24,13 @@ impl Slugify for ASTNode {
 
 impl Slugify for ASTKind {
     fn slugify(&self) -> String {
-        match self {
-            _ => format!("Slugify: {:#?}", self),
-        }
+        self.plain_text().slugify()
     }
 }
 
 impl Slugify for Header {
     fn slugify(&self) -> String {
-        todo!()
+        self.children.slugify()
     }
 }
 
@@ -0,0 +1,101 @@
+use super::*;
+use crate::{
+    nodes::{CodeNode, EmailLink, HyperLink, ImageLink, ListItem, MathNode, TextSpan, TwoWayLink},
+    visit::{walk_node, walk_nodes, ControlFlow, VisitContext, Visitor},
+};
+
+impl PlainText for ASTNodes {
+    fn plain_text(&self) -> String {
+        let mut visitor = PlainTextVisitor::default();
+        let _ = walk_nodes(&mut visitor, self, &mut VisitContext::default());
+        normalize(&visitor.out)
+    }
+}
+
+impl PlainText for ASTNode {
+    fn plain_text(&self) -> String {
+        let mut visitor = PlainTextVisitor::default();
+        let _ = self.accept(&mut visitor);
+        normalize(&visitor.out)
+    }
+}
+
+impl PlainText for ASTKind {
+    fn plain_text(&self) -> String {
+        ASTNode { value: self.to_owned(), range: None }.plain_text()
+    }
+}
+
+impl PlainText for Header {
+    fn plain_text(&self) -> String {
+        self.children.plain_text()
+    }
+}
+
+/// Texts of styled spans, code, math and the texts of links
+#[derive(Default)]
+struct PlainTextVisitor {
+    out: String,
+}
+
+impl<'ast> Visitor<'ast> for PlainTextVisitor {
+    fn visit_node(&mut self, node: &'ast ASTNode, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        walk_node(self, node, ctx)?;
+        // blocks are separated
+        let in_statements = ctx.parent().map(|p| matches!(p.value, ASTKind::Statements(_))).unwrap_or_default();
+        if in_statements || matches!(node.value, ASTKind::Paragraph(_) | ASTKind::Header(_)) {
+            self.out.push(' ')
+        }
+        ControlFlow::Continue(())
+    }
+    fn visit_table_cell(&mut self, cell: &'ast ASTNode, _: usize, _: usize, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.visit_node(cell, ctx)?;
+        self.out.push(' ');
+        ControlFlow::Continue(())
+    }
+    fn visit_list_item(&mut self, item: &'ast ListItem, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        walk_nodes(self, &item.rest, ctx)?;
+        self.out.push(' ');
+        ControlFlow::Continue(())
+    }
+    fn visit_hyper_link(&mut self, link: &'ast HyperLink, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.out.push_str(link.text.as_deref().unwrap_or(&link.src));
+        ControlFlow::Continue(())
+    }
+    fn visit_image_link(&mut self, link: &'ast ImageLink, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.out.push_str(link.description.as_deref().unwrap_or_default());
+        ControlFlow::Continue(())
+    }
+    fn visit_two_way_link(&mut self, link: &'ast TwoWayLink, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.out.push_str(link.get_link());
+        ControlFlow::Continue(())
+    }
+    fn visit_email_link(&mut self, link: &'ast EmailLink, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.out.push_str(&link.name);
+        ControlFlow::Continue(())
+    }
+    fn visit_code(&mut self, code: &'ast CodeNode, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.out.push_str(&code.code);
+        ControlFlow::Continue(())
+    }
+    fn visit_math(&mut self, math: &'ast MathNode, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.out.push_str(&math.raw);
+        ControlFlow::Continue(())
+    }
+    fn visit_text(&mut self, text: &'ast TextSpan, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        match text {
+            TextSpan::Normal(s) | TextSpan::Raw(s) => self.out.push_str(s),
+            TextSpan::Emoji(s) => self.out.push_str(s),
+            TextSpan::Escaped(c) => self.out.push(*c),
+            TextSpan::SoftNewline | TextSpan::HardNewline => self.out.push(' '),
+            _ => {}
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// Collapse the whitespaces
+#[inline]
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
@@ -0,0 +1,53 @@
+use super::*;
+use crate::{NoteError, Result};
+use std::str::FromStr;
+
+/// How the text of a header becomes the anchor id
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum SlugScheme {
+    /// Transliterate to ascii, `中文标题` becomes `zhong-wen-biao-ti`
+    #[default]
+    Ascii,
+    /// Same as the anchors on GitHub, punctuations are removed and every space becomes `-`
+    GitHub,
+    /// Keep the letters of all languages, for example CJK headings, other characters collapse into `-`
+    Unicode,
+}
+
+impl SlugScheme {
+    /// Slugify the text with the scheme
+    pub fn slugify(&self, text: &str) -> String {
+        match self {
+            Self::Ascii => slugify!(text),
+            Self::GitHub => text
+                .trim()
+                .to_lowercase()
+                .chars()
+                .filter_map(|c| match c {
+                    c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+                    c if c.is_whitespace() => Some('-'),
+                    _ => None,
+                })
+                .collect(),
+            Self::Unicode => {
+                let lower = text.to_lowercase();
+                let words: Vec<_> = lower.split(|c: char| !c.is_alphanumeric()).filter(|s| !s.is_empty()).collect();
+                words.join("-")
+            }
+        }
+    }
+}
+
+/// `ascii`, `github` or `unicode`, case insensitive
+impl FromStr for SlugScheme {
+    type Err = NoteError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "ascii" => Ok(Self::Ascii),
+            "github" => Ok(Self::GitHub),
+            "unicode" => Ok(Self::Unicode),
+            _ => Err(NoteError::type_mismatch(format!("Unknown slug scheme `{}`, expect `ascii`, `github` or `unicode`", s))),
+        }
+    }
+}
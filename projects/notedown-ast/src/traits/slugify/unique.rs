@@ -0,0 +1,57 @@
+use super::*;
+use crate::command::Command;
+use std::collections::{HashMap, HashSet};
+
+impl ASTNode {
+    /// Give every top level header an unique id, `\args(id = "custom-id")` before the header takes precedence
+    ///
+    /// Generated ids which are taken get a number, `intro`, `intro-1`, `intro-2`.
+    pub fn set_header_ids(&mut self, scheme: SlugScheme) {
+        let terms = match &mut self.value {
+            ASTKind::Statements(v) => v,
+            _ => return,
+        };
+        let mut explicit = HashMap::new();
+        let mut pending = None;
+        for (index, term) in terms.iter().enumerate() {
+            match &term.value {
+                ASTKind::Command(cmd) if cmd.is("args") => pending = explicit_id(cmd),
+                ASTKind::Header(_) => {
+                    if let Some(id) = pending.take() {
+                        explicit.insert(index, id);
+                    }
+                }
+                _ => pending = None,
+            }
+        }
+        let mut used: HashSet<String> = explicit.values().cloned().collect();
+        for (index, term) in terms.iter_mut().enumerate() {
+            if let ASTKind::Header(header) = &mut term.value {
+                header.id = match explicit.remove(&index) {
+                    Some(id) => Some(id),
+                    None => unique_id(scheme.slugify(&header.plain_text()), &mut used),
+                };
+            }
+        }
+    }
+}
+
+/// `\args(id = "custom-id")`
+fn explicit_id(cmd: &Command) -> Option<String> {
+    let id = cmd.get_options()?.kvs.get_string("id")?;
+    Some(id.trim().to_string()).filter(|s| !s.is_empty())
+}
+
+fn unique_id(base: String, used: &mut HashSet<String>) -> Option<String> {
+    if base.is_empty() {
+        return None;
+    }
+    let mut id = base.to_owned();
+    let mut count = 0;
+    while used.contains(&id) {
+        count += 1;
+        id = format!("{}-{}", base, count);
+    }
+    used.insert(id.to_owned());
+    return Some(id);
+}
@@ -17,31 +17,46 @@ pub trait TableOfContent {
 
 /// Config of table of content
 pub struct TocConfig {
+    /// Headers above the level are skipped, 2 skips the title `# title`
+    pub min_depth: u8,
     /// Calculate the level from the root, 0 means infinite, and root it self is the first level
     pub max_depth: u8,
+    /// Prefix the names with the section numbers, `1.2 name`
+    pub numbering: bool,
 }
 
 /// Node of table of content
 #[derive(Debug)]
 pub struct TocNode {
-    /// Depth of the node from root
+    /// Level of the header
     pub level: u8,
-    /// Addition information of the node
+    /// Text of the header
+    pub name: String,
+    /// Addition information of the node, the id of the header
     pub detail: String,
-    /// Range of this node
+    /// Range of the section, from the header to the next header at the same or higher level
     pub range: Range<usize>,
+    /// Range of the header
+    pub selection_range: Range<usize>,
     /// Children elements of the node
     pub children: Vec<TocNode>,
 }
 
 impl Default for TocConfig {
     fn default() -> Self {
-        Self { max_depth: u8::MAX }
+        Self { min_depth: 1, max_depth: u8::MAX, numbering: false }
     }
 }
 
 impl Default for TocNode {
     fn default() -> Self {
-        Self { level: 0, detail: String::from("ROOT"), range: Default::default(), children: vec![] }
+        Self {
+            level: 0,
+            name: String::new(),
+            detail: String::from("ROOT"),
+            range: Default::default(),
+            selection_range: Default::default(),
+            children: vec![],
+        }
     }
 }
@@ -1,40 +1,98 @@
 use super::*;
+use crate::{
+    command::Command,
+    nodes::Header,
+    traits::PlainText,
+    visit::{walk_node, ControlFlow, VisitContext, Visitor},
+};
+
+/// Top level headers with their index, and whether they are shown in the toc
+struct TocHeaders<'a, 'ast> {
+    config: &'a TocConfig,
+    min_depth: u8,
+    /// `\toc_ignore` hides the next header
+    toc_ignore: bool,
+    headers: Vec<(usize, &'ast Header, bool)>,
+}
+
+impl<'ast> Visitor<'ast> for TocHeaders<'_, 'ast> {
+    fn visit_node(&mut self, node: &'ast ASTNode, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        // sections are only made of the top level blocks
+        match ctx.path().depth() {
+            0 | 1 => walk_node(self, node, ctx),
+            _ => ControlFlow::Continue(()),
+        }
+    }
+    fn visit_header(&mut self, header: &'ast Header, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        let level = header.level;
+        let visible = !std::mem::take(&mut self.toc_ignore)
+            && !header.hide_in_toc
+            && level >= self.min_depth
+            && (self.config.max_depth == 0 || level <= self.config.max_depth);
+        self.headers.extend(ctx.path().index().map(|index| (index, header, visible)));
+        ControlFlow::Continue(())
+    }
+    fn visit_command(&mut self, cmd: &'ast Command, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        if cmd.is("toc_ignore") {
+            self.toc_ignore = true
+        }
+        ControlFlow::Continue(())
+    }
+}
 
 impl TocNode {
     fn last_at_level(&mut self, depth: u8) -> &mut TocNode {
         if depth == 0 || self.children.is_empty() { self } else { self.children.last_mut().unwrap().last_at_level(depth - 1) }
     }
+    fn set_numbering(&mut self, prefix: &str) {
+        for (index, child) in self.children.iter_mut().enumerate() {
+            let number = format!("{}{}", prefix, index + 1);
+            child.name = format!("{} {}", number, child.name);
+            child.set_numbering(&format!("{}.", number));
+        }
+    }
 }
 
 impl TableOfContent for ASTNode {
     fn toc_configurable(&self, config: &TocConfig) -> TocNode {
-        let mut root = TocNode::default();
-        let mut toc_ignore = false;
-        if let ASTKind::Statements(terms) = &self.value {
-            for term in terms {
-                match &term.value {
-                    ASTKind::Header(header) => {
-                        let level = header.level;
-                        if toc_ignore {
-                            toc_ignore = false;
-                            continue;
-                        }
-                        if level > config.max_depth {
-                            continue;
-                        }
-                        let parent = root.last_at_level(level - 1);
-                        let new =
-                            TocNode { level, detail: header.slugify(), range: self.range.to_owned().unwrap_or_default(), children: vec![] };
-                        parent.children.push(new);
-                    }
-                    ASTKind::Command(cmd) => {
-                        if cmd.is("toc_ignore") {
-                            toc_ignore = true
-                        }
-                    }
-                    _ => (),
-                }
+        let mut root = TocNode { range: self.range.to_owned().unwrap_or_default(), ..TocNode::default() };
+        let terms = match &self.value {
+            ASTKind::Statements(terms) => terms,
+            _ => return root,
+        };
+        let min_depth = config.min_depth.max(1);
+        let mut collector = TocHeaders { config, min_depth, toc_ignore: false, headers: vec![] };
+        let _ = self.accept(&mut collector);
+        let headers = collector.headers;
+        // levels of the sections in the toc which contain the current header
+        let mut open: Vec<u8> = vec![];
+        for (i, (index, header, visible)) in headers.iter().enumerate() {
+            let level = header.level;
+            // hidden headers still end the sections, and their subsections are moved up
+            while open.last().map(|l| *l >= level).unwrap_or(false) {
+                open.pop();
+            }
+            if !visible {
+                continue;
             }
+            // the section ends before the next header at the same or higher level
+            let next = headers[i + 1..].iter().find(|(_, h, _)| h.level <= level).map(|(n, _, _)| *n).unwrap_or(terms.len());
+            let selection_range = terms[*index].range.to_owned().unwrap_or_default();
+            let end = terms[*index..next].iter().filter_map(|t| t.range.as_ref()).map(|r| r.end).max().unwrap_or(selection_range.end);
+            let parent = root.last_at_level(open.len() as u8);
+            open.push(level);
+            let new = TocNode {
+                level,
+                name: header.plain_text(),
+                detail: header.id.to_owned().unwrap_or_else(|| header.slugify()),
+                range: selection_range.start..end,
+                selection_range,
+                children: vec![],
+            };
+            parent.children.push(new);
+        }
+        if config.numbering {
+            root.set_numbering("")
         }
         return root;
     }
@@ -2,6 +2,7 @@ mod ordered_map;
 mod sparse_array;
 
 pub use ordered_map::*;
+pub use sparse_array::*;
 
 use super::*;
 
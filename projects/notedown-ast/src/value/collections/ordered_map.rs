@@ -20,11 +20,32 @@ impl OrderedMap {
 }
 
 impl OrderedMap {
+    /// Insert value into Ordered Map, return the old value if the key exists
+    #[inline]
+    pub fn insert(&mut self, key: Literal<String>, value: Literal<Value>) -> Option<Literal<Value>> {
+        let pair = LiteralPair { key, value };
+        self.inner.insert(pair.key.value.to_owned(), pair).map(|f| f.value)
+    }
+    /// Merge another map into this map, values in the other map win, existing keys keep their position
+    /// ```note
+    /// \{{a = 1} ++ {a = 2, b = 3}}
+    /// ```
+    #[inline]
+    pub fn merge(&mut self, other: OrderedMap) {
+        for (key, pair) in other.inner {
+            self.inner.insert(key, pair);
+        }
+    }
     /// Extract value from Ordered Map
     #[inline]
     pub fn extract(&mut self, key: &str) -> Option<Value> {
         self.inner.remove(key).map(|f| f.value.value.to_owned())
     }
+    /// Extract key and value with their positions from Ordered Map
+    #[inline]
+    pub fn extract_raw(&mut self, key: &str) -> Option<(Literal<String>, Literal<Value>)> {
+        self.inner.shift_remove(key).map(|f| (f.key, f.value))
+    }
     /// Extract value from Ordered Map
     #[inline]
     pub fn extract_bool(&mut self, key: &str) -> Option<bool> {
@@ -1,23 +1,114 @@
 use super::*;
-use num::One;
+use num::{BigInt, One, Signed, Zero};
+use std::{
+    cmp::Ordering,
+    collections::btree_map::{IntoIter, Iter, IterMut, Range},
+    iter::{FromIterator, Peekable},
+    ops::RangeBounds,
+};
+
+/// Holes filled by the dense iteration at most, the holes after are skipped
+///
+/// A document can write `[1000000000000 = x]`, filling every hole of it would never end.
+pub const MAX_DENSE_HOLES: usize = 1 << 12;
+
+impl SparseArray {
+    /// Create an empty array, holes will be filled with the given value
+    #[inline]
+    pub fn new(default: Value) -> Self {
+        Self { default, inner: Default::default() }
+    }
+    /// The value used to fill the holes
+    #[inline]
+    pub fn get_default(&self) -> &Value {
+        &self.default
+    }
+    /// Number of entries that are actually set
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+    /// Returns `true` if no entry is set
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+    /// Length of the dense view, the last index or `0` if empty
+    #[inline]
+    pub fn last_index(&self) -> BigUint {
+        self.inner.last_key_value().map(|f| f.0.to_owned()).unwrap_or_default()
+    }
+    /// Number of holes in `1..=last_index`
+    #[inline]
+    pub fn holes(&self) -> BigUint {
+        self.last_index() - self.len()
+    }
+    /// Returns `true` if the dense iteration fills every hole, see [`MAX_DENSE_HOLES`]
+    #[inline]
+    pub fn is_dense(&self) -> bool {
+        self.holes() <= BigUint::from(MAX_DENSE_HOLES)
+    }
+}
 
 impl SparseArray {
-    /// TODO: doc
+    /// Get the value at the 1-based index, holes return `None`
     #[inline]
     pub fn get(&self, index: &BigUint) -> Option<Value> {
         self.inner.get(index).map(|f| f.value.to_owned())
     }
-    /// TODO: doc
+    /// Get the value at the index and convert to `bool`
     #[inline]
     pub fn get_bool(&self, index: &BigUint) -> Option<bool> {
         self.get(index).and_then(|f| bool::try_from(f.to_owned()).ok())
     }
-    /// TODO: doc
+    /// Get the value at the index and convert to `String`
     #[inline]
     pub fn get_string(&self, index: &BigUint) -> Option<String> {
         self.get(index).and_then(|f| String::try_from(f.to_owned()).ok())
     }
-    /// TODO: doc
+    /// Get the mutable value at the 1-based index
+    #[inline]
+    pub fn get_mut(&mut self, index: &BigUint) -> Option<&mut Value> {
+        self.inner.get_mut(index).map(|f| &mut f.value)
+    }
+    /// Get the value with position at the 1-based index
+    #[inline]
+    pub fn get_literal(&self, index: &BigUint) -> Option<&Literal<Value>> {
+        self.inner.get(index)
+    }
+    /// Get the value by signed index, `-1` is the last index
+    ///
+    /// Holes inside `1..=last_index` are filled with the default value like [`SparseArray::iter`],
+    /// `None` means the index is out of range.
+    /// ```note
+    /// \{list[-1]}
+    /// ```
+    #[inline]
+    pub fn get_signed(&self, index: &BigInt) -> Option<Value> {
+        let index = self.resolve_index(index)?;
+        match self.get(&index) {
+            Some(s) => Some(s),
+            None => Some(self.default.to_owned()),
+        }
+    }
+    /// Convert signed index to 1-based index in `1..=last_index`, holes are still valid indexes
+    ///
+    /// `0` and index out of range return `None`
+    pub fn resolve_index(&self, index: &BigInt) -> Option<BigUint> {
+        let last = self.last_index();
+        if index.is_negative() {
+            let offset = (-index).to_biguint()?;
+            if offset > last {
+                return None;
+            }
+            return Some(last - offset + 1u8);
+        }
+        match index.to_biguint() {
+            Some(s) if !s.is_zero() && s <= last => Some(s),
+            _ => None,
+        }
+    }
+    /// Get the last entry that is actually set
     #[inline]
     pub fn last_key_value(&self) -> Option<(&BigUint, &Value)> {
         self.inner.last_key_value().map(|(k, v)| (k, &v.value))
@@ -25,15 +116,36 @@ impl SparseArray {
 }
 
 impl SparseArray {
-    /// TODO: doc
+    /// Remove the value at the index and leave a hole
     #[inline]
     pub fn extract(&mut self, index: &BigUint) -> Option<Value> {
         self.inner.remove(index).map(|f| f.value)
     }
+    /// Remove the value at the index and shift all subsequent entries to the left
+    pub fn remove(&mut self, index: &BigUint) -> Option<Literal<Value>> {
+        let out = self.inner.remove(index);
+        let rest = self.inner.split_off(index);
+        for (i, value) in rest {
+            self.inner.insert(i - 1u8, value);
+        }
+        return out;
+    }
+    /// Remove all holes, entries are re-indexed from 1 in order
+    pub fn compact(&mut self) {
+        let inner = std::mem::take(&mut self.inner);
+        for (i, (_, value)) in inner.into_iter().enumerate() {
+            self.inner.insert(BigUint::from(i + 1), value);
+        }
+    }
+    /// Remove all entries
+    #[inline]
+    pub fn clear(&mut self) {
+        self.inner.clear()
+    }
 }
 
 impl SparseArray {
-    /// TODO: doc
+    /// Insert the value after the last index
     #[inline]
     #[allow(mutable_borrow_reservation_conflict)]
     pub fn push(&mut self, value: Literal<Value>) {
@@ -43,7 +155,7 @@ impl SparseArray {
             Some(s) => self.inner.insert(s + 1u8, value),
         };
     }
-    /// TODO: doc
+    /// Insert the value at the index, return the old value if the index was set
     #[inline]
     pub fn insert(&mut self, index: BigUint, value: Literal<Value>) -> Option<Literal<Value>> {
         self.inner.insert(index, value)
@@ -51,16 +163,100 @@ impl SparseArray {
 }
 
 impl SparseArray {
-    /// Return an iterator over array with default value if not set
+    /// Append all elements of another array after the last element of this array
+    /// ```note
+    /// \{[1, 2] ++ [3]}
+    /// ```
+    pub fn concat(&mut self, other: SparseArray) {
+        let offset = self.last_index();
+        for (index, value) in other.inner {
+            self.inner.insert(&offset + index, value);
+        }
+    }
+    /// Take elements in `start..=end` as a new array, re-indexed from 1
+    pub fn slice(&self, start: &BigUint, end: &BigUint) -> SparseArray {
+        let mut out = SparseArray::new(self.default.to_owned());
+        if start > end {
+            return out;
+        }
+        let offset = start - if start.is_zero() { BigUint::zero() } else { BigUint::one() };
+        for (index, value) in self.inner.range(start..=end) {
+            out.inner.insert(index - &offset, value.to_owned());
+        }
+        return out;
+    }
+    /// Compare the elements in index order
+    pub(crate) fn compare(&self, other: &Self) -> Ordering {
+        let mut lhs = self.inner.iter();
+        let mut rhs = other.inner.iter();
+        loop {
+            let (l, r) = match (lhs.next(), rhs.next()) {
+                (None, None) => return Ordering::Equal,
+                (None, Some(_)) => return Ordering::Less,
+                (Some(_), None) => return Ordering::Greater,
+                (Some(l), Some(r)) => (l, r),
+            };
+            match l.0.cmp(r.0).then_with(|| l.1.value.compare(&r.1.value)) {
+                Ordering::Equal => continue,
+                other => return other,
+            }
+        }
+    }
+}
+
+impl SparseArray {
+    /// Return an iterator over `1..=last_index`, holes are filled with the default value
+    ///
+    /// At most [`MAX_DENSE_HOLES`] holes are filled, check [`SparseArray::is_dense`] before relying on the positions.
     #[inline]
     pub fn iter(&self) -> SparseArrayIter {
-        SparseArrayIter { current: BigUint::one(), default: &self.default, inner: &self.inner }
+        SparseArrayIter { current: BigUint::one(), end: self.last_index(), holes: MAX_DENSE_HOLES, default: &self.default, inner: &self.inner }
+    }
+    /// Return an owned iterator over `1..=last_index` with the position of each entry, holes are filled with the default value without position
+    ///
+    /// At most [`MAX_DENSE_HOLES`] holes are filled like [`SparseArray::iter`]
+    #[inline]
+    pub fn into_literals(self) -> SparseArrayLiterals {
+        SparseArrayLiterals { inner: self.into_iter() }
+    }
+    /// Return an iterator over the entries that are actually set
+    #[inline]
+    pub fn iter_sparse(&self) -> SparseArrayEntries {
+        SparseArrayEntries { inner: self.inner.iter() }
+    }
+    /// Return a mutable iterator over the entries that are actually set
+    #[inline]
+    pub fn iter_mut(&mut self) -> SparseArrayEntriesMut {
+        SparseArrayEntriesMut { inner: self.inner.iter_mut() }
+    }
+    /// Return an iterator over the values that are actually set
+    #[inline]
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &Value> {
+        self.inner.values().map(|f| &f.value)
+    }
+    /// Return an iterator over the entries set in the given index range
+    /// ```
+    /// # use notedown_ast::{value::SparseArray, Value};
+    /// # use num::BigUint;
+    /// let array = SparseArray::from(vec![Value::integer(1), Value::integer(2), Value::integer(3)]);
+    /// let range = BigUint::from(2u8)..;
+    /// assert_eq!(array.range(range).count(), 2);
+    /// ```
+    #[inline]
+    pub fn range<R>(&self, range: R) -> SparseArrayRange
+    where
+        R: RangeBounds<BigUint>,
+    {
+        SparseArrayRange { inner: self.inner.range(range) }
     }
 }
 
-/// Wrapper type of [`SparseArray::values`]
+/// Wrapper type of [`SparseArray::iter`]
 pub struct SparseArrayIter<'a> {
     current: BigUint,
+    end: BigUint,
+    /// Holes left to fill
+    holes: usize,
     default: &'a Value,
     inner: &'a BTreeMap<BigUint, Literal<Value>>,
 }
@@ -69,10 +265,178 @@ impl<'a> Iterator for SparseArrayIter<'a> {
     type Item = &'a Value;
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        self.current += 1u8;
-        match self.inner.get(&self.current) {
-            None => Some(&self.default),
-            Some(s) => Some(&s.value),
+        if self.current > self.end {
+            return None;
         }
+        let out = match self.inner.get(&self.current) {
+            Some(s) => &s.value,
+            None if self.holes > 0 => {
+                self.holes -= 1;
+                self.default
+            }
+            // out of holes, jump to the next entry
+            None => {
+                let (index, value) = self.inner.range(&self.current..).next()?;
+                self.current = index.to_owned();
+                &value.value
+            }
+        };
+        self.current += 1u8;
+        Some(out)
+    }
+}
+
+/// Wrapper type of [`SparseArray::iter_sparse`]
+pub struct SparseArrayEntries<'a> {
+    inner: Iter<'a, BigUint, Literal<Value>>,
+}
+
+impl<'a> Iterator for SparseArrayEntries<'a> {
+    type Item = (&'a BigUint, &'a Value);
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(k, v)| (k, &v.value))
+    }
+}
+
+impl<'a> DoubleEndedIterator for SparseArrayEntries<'a> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(k, v)| (k, &v.value))
+    }
+}
+
+impl<'a> ExactSizeIterator for SparseArrayEntries<'a> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+/// Wrapper type of [`SparseArray::iter_mut`]
+pub struct SparseArrayEntriesMut<'a> {
+    inner: IterMut<'a, BigUint, Literal<Value>>,
+}
+
+impl<'a> Iterator for SparseArrayEntriesMut<'a> {
+    type Item = (&'a BigUint, &'a mut Value);
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(k, v)| (k, &mut v.value))
+    }
+}
+
+/// Wrapper type of [`SparseArray::range`]
+pub struct SparseArrayRange<'a> {
+    inner: Range<'a, BigUint, Literal<Value>>,
+}
+
+impl<'a> Iterator for SparseArrayRange<'a> {
+    type Item = (&'a BigUint, &'a Value);
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(k, v)| (k, &v.value))
+    }
+}
+
+impl<'a> DoubleEndedIterator for SparseArrayRange<'a> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(k, v)| (k, &v.value))
+    }
+}
+
+/// Owned iterator over `1..=last_index`, at most [`MAX_DENSE_HOLES`] holes are filled with the default value
+pub struct SparseArrayIntoIter {
+    current: BigUint,
+    /// Holes left to fill
+    holes: usize,
+    default: Value,
+    inner: Peekable<IntoIter<BigUint, Literal<Value>>>,
+}
+
+impl SparseArrayIntoIter {
+    fn next_literal(&mut self) -> Option<Literal<Value>> {
+        let out = match self.inner.peek() {
+            None => return None,
+            Some((index, _)) if index > &self.current && self.holes > 0 => {
+                self.holes -= 1;
+                Literal { value: self.default.to_owned(), range: None }
+            }
+            Some(_) => {
+                let (index, value) = self.inner.next()?;
+                self.current = index;
+                value
+            }
+        };
+        self.current += 1u8;
+        Some(out)
+    }
+}
+
+impl Iterator for SparseArrayIntoIter {
+    type Item = Value;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_literal().map(|f| f.value)
+    }
+}
+
+/// Wrapper type of [`SparseArray::into_literals`]
+pub struct SparseArrayLiterals {
+    inner: SparseArrayIntoIter,
+}
+
+impl Iterator for SparseArrayLiterals {
+    type Item = Literal<Value>;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next_literal()
+    }
+}
+
+impl IntoIterator for SparseArray {
+    type Item = Value;
+    type IntoIter = SparseArrayIntoIter;
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        SparseArrayIntoIter { current: BigUint::one(), holes: MAX_DENSE_HOLES, default: self.default, inner: self.inner.into_iter().peekable() }
+    }
+}
+
+impl<'a> IntoIterator for &'a SparseArray {
+    type Item = &'a Value;
+    type IntoIter = SparseArrayIter<'a>;
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<Literal<Value>> for SparseArray {
+    fn from_iter<T: IntoIterator<Item = Literal<Value>>>(iter: T) -> Self {
+        let inner = iter.into_iter().enumerate().map(|(i, v)| (BigUint::from(i + 1), v)).collect();
+        Self { default: Value::Null, inner }
+    }
+}
+
+impl FromIterator<Value> for SparseArray {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = Value>>(iter: T) -> Self {
+        iter.into_iter().map(|value| Literal { value, range: None }).collect()
+    }
+}
+
+impl From<Vec<Value>> for SparseArray {
+    #[inline]
+    fn from(values: Vec<Value>) -> Self {
+        values.into_iter().collect()
+    }
+}
+
+impl From<Vec<Literal<Value>>> for SparseArray {
+    #[inline]
+    fn from(values: Vec<Literal<Value>>) -> Self {
+        values.into_iter().collect()
     }
 }
@@ -0,0 +1,265 @@
+mod scope;
+
+pub use self::scope::Scope;
+
+use super::*;
+use crate::{nodes::MaybeRanged, NoteError, Result};
+use num::Zero;
+use std::{
+    cmp::Ordering,
+    ops::{Add, BitAnd, BitOr, Div, Mul, Neg, Not, Range, Rem, Shr, Sub},
+};
+
+/// Expression with position
+pub type Expression = Literal<ExpressionKind>;
+
+/// Expression in command arguments and `\{ }` interpolation
+/// ```note
+/// \set[name]: "notedown"
+/// \img(src = "logo-" ++ name ++ ".png", width = size * 2)
+/// \{name.upper()}
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ExpressionKind {
+    /// Constant value
+    Value(Value),
+    /// Variable or bare word
+    Symbol(String),
+    /// `-a`
+    Prefix(PrefixOperator, Box<Expression>),
+    /// `a + b`
+    Infix(InfixOperator, Box<Expression>, Box<Expression>),
+    /// `a[b]`
+    Index(Box<Expression>, Box<Expression>),
+    /// `a.method(b, c)`
+    Method(Box<Expression>, Literal<String>, Vec<Expression>),
+    /// `[a, b, 5 = c]`, the index is explicit if given
+    Array(Vec<(Option<Literal<BigUint>>, Expression)>),
+    /// `{a = 1, "b" = 2}`
+    Object(Vec<(Literal<String>, Expression)>),
+    /// `{a, b}`
+    Set(Vec<Expression>),
+}
+
+/// Operator before the expression
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum PrefixOperator {
+    /// `-a`
+    Negative,
+    /// `!a`
+    Not,
+}
+
+/// Operator between two expressions
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum InfixOperator {
+    /// `a + b`
+    Plus,
+    /// `a - b`
+    Minus,
+    /// `a * b`
+    Multiply,
+    /// `a / b`
+    Divide,
+    /// `a % b`
+    Modulo,
+    /// `a ^ b`
+    Power,
+    /// `a ++ b`
+    Join,
+    /// `a | b`
+    Union,
+    /// `a & b`
+    Intersection,
+    /// `a == b`
+    Equal,
+    /// `a != b`
+    NotEqual,
+    /// `a < b`
+    Less,
+    /// `a <= b`
+    LessEqual,
+    /// `a > b`
+    Greater,
+    /// `a >= b`
+    GreaterEqual,
+    /// `a && b`
+    And,
+    /// `a || b`
+    Or,
+}
+
+impl ExpressionKind {
+    /// Constant value with position
+    #[inline]
+    pub fn value(value: Value, range: MaybeRanged) -> Expression {
+        Expression { value: Self::Value(value), range }
+    }
+    /// Variable with position
+    #[inline]
+    pub fn symbol(name: impl Into<String>, range: MaybeRanged) -> Expression {
+        Expression { value: Self::Symbol(name.into()), range }
+    }
+    /// Prefix expression, the range covers the operator and the operand
+    #[inline]
+    pub fn prefix(op: PrefixOperator, rhs: Expression, range: MaybeRanged) -> Expression {
+        Expression { value: Self::Prefix(op, Box::new(rhs)), range }
+    }
+    /// Infix expression, the range covers both sides
+    #[inline]
+    pub fn infix(op: InfixOperator, lhs: Expression, rhs: Expression) -> Expression {
+        let range = join_range(&lhs.range, &rhs.range);
+        Expression { value: Self::Infix(op, Box::new(lhs), Box::new(rhs)), range }
+    }
+    /// Index expression, the range covers the base and the brackets
+    #[inline]
+    pub fn index(base: Expression, index: Expression, range: MaybeRanged) -> Expression {
+        let range = join_range(&base.range, &range);
+        Expression { value: Self::Index(Box::new(base), Box::new(index)), range }
+    }
+    /// Method call, the range covers the base and the arguments
+    #[inline]
+    pub fn method(base: Expression, name: Literal<String>, args: Vec<Expression>, range: MaybeRanged) -> Expression {
+        let range = join_range(&base.range, &range);
+        Expression { value: Self::Method(Box::new(base), name, args), range }
+    }
+    /// Array literal with position
+    #[inline]
+    pub fn array(items: Vec<(Option<Literal<BigUint>>, Expression)>, range: MaybeRanged) -> Expression {
+        Expression { value: Self::Array(items), range }
+    }
+    /// Object literal with position
+    #[inline]
+    pub fn object(items: Vec<(Literal<String>, Expression)>, range: MaybeRanged) -> Expression {
+        Expression { value: Self::Object(items), range }
+    }
+    /// Set literal with position
+    #[inline]
+    pub fn set(items: Vec<Expression>, range: MaybeRanged) -> Expression {
+        Expression { value: Self::Set(items), range }
+    }
+}
+
+impl Expression {
+    /// Evaluate the expression, all symbols must be defined in the scope
+    #[inline]
+    pub fn evaluate(&self, scope: &Scope) -> Result<Value> {
+        self.evaluate_inner(scope, false)
+    }
+    /// Evaluate as the value of an argument, undefined bare word is treated as string,
+    /// so as the items of array, object and set literals
+    /// ```note
+    /// \image(layout = left, class = [wide, center])
+    /// ```
+    #[inline]
+    pub fn evaluate_argument(&self, scope: &Scope) -> Result<Value> {
+        self.evaluate_inner(scope, true)
+    }
+    fn evaluate_inner(&self, scope: &Scope, bare: bool) -> Result<Value> {
+        let out = match &self.value {
+            ExpressionKind::Value(v) => Ok(v.to_owned()),
+            ExpressionKind::Symbol(name) => match scope.get(name) {
+                Some(s) => Ok(s.value.to_owned()),
+                None if bare => Ok(Value::string(name)),
+                None => Err(NoteError::undefined_variable(name)),
+            },
+            ExpressionKind::Prefix(op, rhs) => {
+                let rhs = rhs.evaluate(scope)?;
+                match op {
+                    PrefixOperator::Negative => rhs.neg(),
+                    PrefixOperator::Not => rhs.not(),
+                }
+            }
+            ExpressionKind::Infix(op, lhs, rhs) => {
+                let lhs = lhs.evaluate(scope)?;
+                // short circuit, the right side will not be evaluated
+                match (op, &lhs) {
+                    (InfixOperator::And, Value::Boolean(false)) => return Ok(Value::Boolean(false)),
+                    (InfixOperator::Or, Value::Boolean(true)) => return Ok(Value::Boolean(true)),
+                    _ => {}
+                }
+                let rhs = rhs.evaluate(scope)?;
+                match op {
+                    InfixOperator::Plus => lhs.add(rhs),
+                    InfixOperator::Minus => lhs.sub(rhs),
+                    InfixOperator::Multiply => lhs.mul(rhs),
+                    InfixOperator::Divide => lhs.div(rhs),
+                    InfixOperator::Modulo => lhs.rem(rhs),
+                    InfixOperator::Power => lhs.pow(rhs),
+                    InfixOperator::Join => lhs.shr(rhs),
+                    InfixOperator::Union => lhs.bitor(rhs),
+                    InfixOperator::Intersection => lhs.bitand(rhs),
+                    InfixOperator::Equal => Ok(Value::Boolean(lhs.equal(&rhs))),
+                    InfixOperator::NotEqual => Ok(Value::Boolean(!lhs.equal(&rhs))),
+                    InfixOperator::Less => Ok(Value::Boolean(lhs.compare(&rhs) == Ordering::Less)),
+                    InfixOperator::LessEqual => Ok(Value::Boolean(lhs.compare(&rhs) != Ordering::Greater)),
+                    InfixOperator::Greater => Ok(Value::Boolean(lhs.compare(&rhs) == Ordering::Greater)),
+                    InfixOperator::GreaterEqual => Ok(Value::Boolean(lhs.compare(&rhs) != Ordering::Less)),
+                    InfixOperator::And => lhs.and(rhs),
+                    InfixOperator::Or => lhs.or(rhs),
+                }
+            }
+            ExpressionKind::Index(base, index) => {
+                let base = base.evaluate(scope)?;
+                let index = index.evaluate(scope)?;
+                base.get_index_by(&index)
+            }
+            ExpressionKind::Method(base, name, args) => {
+                let base = base.evaluate(scope)?;
+                let mut values = Vec::with_capacity(args.len());
+                for arg in args {
+                    values.push(arg.evaluate(scope)?)
+                }
+                base.call_method(&name.value, &values)
+            }
+            ExpressionKind::Array(items) => {
+                let mut out = SparseArray::default();
+                for (index, item) in items {
+                    let value = Literal { value: item.evaluate_inner(scope, bare)?, range: item.range.to_owned() };
+                    match index {
+                        Some(i) if i.value.is_zero() => {
+                            let mut error = NoteError::runtime_error("Index of `Array` starts from 1");
+                            error.range = i.range.to_owned();
+                            return Err(error);
+                        }
+                        Some(i) => {
+                            out.insert(i.value.to_owned(), value);
+                        }
+                        None => out.push(value),
+                    }
+                }
+                Ok(Value::Array(Box::new(out)))
+            }
+            ExpressionKind::Object(items) => {
+                let mut out = OrderedMap::default();
+                for (key, item) in items {
+                    out.insert(key.to_owned(), Literal { value: item.evaluate_inner(scope, bare)?, range: item.range.to_owned() });
+                }
+                Ok(Value::Object(Box::new(out)))
+            }
+            ExpressionKind::Set(items) => {
+                let mut out = OrderedSet::default();
+                for item in items {
+                    out.insert(Literal { value: item.evaluate_inner(scope, bare)?, range: item.range.to_owned() });
+                }
+                Ok(Value::Set(out))
+            }
+        };
+        out.map_err(|e| self.attach_range(e))
+    }
+    /// The inner most error has the most precise range
+    fn attach_range(&self, mut error: NoteError) -> NoteError {
+        if error.range.is_none() {
+            error.range = self.range.to_owned();
+        }
+        error
+    }
+}
+
+fn join_range(lhs: &MaybeRanged, rhs: &MaybeRanged) -> MaybeRanged {
+    match (lhs, rhs) {
+        (Some(l), Some(r)) => Some(Range { start: l.start.min(r.start), end: l.end.max(r.end) }),
+        (Some(s), None) | (None, Some(s)) => Some(s.to_owned()),
+        (None, None) => None,
+    }
+}
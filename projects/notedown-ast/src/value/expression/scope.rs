@@ -0,0 +1,55 @@
+use super::*;
+
+/// Variables defined by `\set`, the inner scope shadows the outer scope
+/// ```note
+/// \set[name]: "notedown"
+/// ```
+#[derive(Clone, Debug)]
+pub struct Scope {
+    frames: Vec<IndexMap<String, Literal<Value>>>,
+}
+
+impl Default for Scope {
+    fn default() -> Self {
+        Self { frames: vec![IndexMap::new()] }
+    }
+}
+
+impl Scope {
+    /// Enter a new scope
+    #[inline]
+    pub fn push(&mut self) {
+        self.frames.push(IndexMap::new())
+    }
+    /// Leave the current scope, the global scope never pops
+    #[inline]
+    pub fn pop(&mut self) {
+        if self.frames.len() > 1 {
+            self.frames.pop();
+        }
+    }
+    /// Define variable in the current scope, return the old value if redefined
+    #[inline]
+    pub fn define(&mut self, name: impl Into<String>, value: Literal<Value>) -> Option<Literal<Value>> {
+        match self.frames.last_mut() {
+            Some(s) => s.insert(name.into(), value),
+            None => unreachable!("global scope never pops"),
+        }
+    }
+    /// Find the variable from the inner most scope
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&Literal<Value>> {
+        self.frames.iter().rev().find_map(|f| f.get(name))
+    }
+    /// Check if the variable is defined in any scope
+    #[inline]
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+    /// Return an iterator over the visible variables, inner most first
+    #[inline]
+    pub fn variables(&self) -> impl Iterator<Item = (&String, &Literal<Value>)> {
+        let mut seen = BTreeSet::new();
+        self.frames.iter().rev().flat_map(|f| f.iter()).filter(move |(k, _)| seen.insert(k.as_str()))
+    }
+}
@@ -1 +1,59 @@
+use super::*;
+use crate::{NoteError, Result};
+use num::{Signed, ToPrimitive};
 
+/// Bytes of a string built by `repeat` at most
+const MAX_STRING_LENGTH: usize = 1 << 20;
+
+impl Value {
+    /// Call builtin method of the value, `a.method(b, c)`
+    /// ```note
+    /// \{title.upper()}
+    /// \{"=".repeat(8)}
+    /// ```
+    pub fn call_method(&self, name: &str, args: &[Value]) -> Result<Value> {
+        let out = match (self, name, args) {
+            (_, "type", []) => Self::string(self.get_type_name()),
+            (Self::String(s), "length", []) => Self::integer(s.chars().count()),
+            (Self::String(s), "upper", []) => Self::string(s.to_uppercase()),
+            (Self::String(s), "lower", []) => Self::string(s.to_lowercase()),
+            (Self::String(s), "trim", []) => Self::string(s.trim()),
+            (Self::String(s), "contains", [Self::String(p)]) => Self::Boolean(s.contains(p.as_str())),
+            (Self::String(s), "starts_with", [Self::String(p)]) => Self::Boolean(s.starts_with(p.as_str())),
+            (Self::String(s), "ends_with", [Self::String(p)]) => Self::Boolean(s.ends_with(p.as_str())),
+            (Self::String(s), "repeat", [Self::Integer(n)]) => match n.to_usize() {
+                Some(n) if s.len().saturating_mul(n) <= MAX_STRING_LENGTH => Self::string(s.repeat(n)),
+                Some(_) => return Err(NoteError::runtime_error(format!("Repeat `String` for {} times is too long", n))),
+                None => return Err(NoteError::runtime_error(format!("Can not repeat `String` for {} times", n))),
+            },
+            (Self::Integer(v), "abs", []) => Self::Integer(v.abs()),
+            (Self::Decimal(v), "abs", []) => Self::Decimal(v.abs()),
+            (Self::Decimal(v), "floor", []) => Self::Decimal(v.floor()),
+            (Self::Decimal(v), "ceil", []) => Self::Decimal(v.ceil()),
+            (Self::Decimal(v), "round", []) => Self::Decimal(v.round()),
+            (Self::Set(v), "length", []) => Self::integer(v.len()),
+            (Self::Set(v), "contains", [item]) => Self::Boolean(v.iter().any(|f| f.value.eq(item))),
+            (Self::Set(v), "union", [Self::Set(o)]) => Self::Set(Self::set_union(v.to_owned(), o.to_owned())),
+            (Self::Set(v), "intersection", [Self::Set(o)]) => Self::Set(Self::set_intersection(v.to_owned(), o)),
+            (Self::Set(v), "difference", [Self::Set(o)]) => Self::Set(Self::set_difference(v.to_owned(), o)),
+            (Self::Array(v), "slice", [Self::Integer(start), Self::Integer(end)]) => match (start.to_biguint(), end.to_biguint()) {
+                (Some(start), Some(end)) => Self::Array(Box::new(v.slice(&start, &end))),
+                _ => return Err(NoteError::runtime_error(format!("Can not slice `Array` with range {}..={}", start, end))),
+            },
+            (Self::Object(v), "length", []) => Self::integer(v.iter().len()),
+            (Self::Object(v), "contains", [Self::String(key)]) => Self::Boolean(v.get(key).is_some()),
+            (Self::Object(v), "keys", []) => {
+                let mut out = SparseArray::default();
+                v.keys().for_each(|key| out.push(Literal { value: Self::string(key), range: None }));
+                Self::Array(Box::new(out))
+            }
+            _ => return Err(self.method_mismatch(name, args)),
+        };
+        return Ok(out);
+    }
+    fn method_mismatch(&self, name: &str, args: &[Value]) -> NoteError {
+        let args: Vec<_> = args.iter().map(|f| f.get_type_name()).collect();
+        let msg = format!("Type `{}` has no method `{}({})`", self.get_type_name(), name, args.join(", "));
+        NoteError::type_mismatch(msg)
+    }
+}
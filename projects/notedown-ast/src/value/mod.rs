@@ -1,11 +1,18 @@
 mod collections;
+mod expression;
 mod methods;
 mod traits;
 mod typing;
 
-pub use self::{collections::*, typing::ValueType};
+pub use self::{
+    collections::*,
+    expression::{Expression, ExpressionKind, InfixOperator, PrefixOperator, Scope},
+    traits::{expect_type, FromValue, IntoValue, ObjectReader},
+    typing::ValueType,
+};
 use crate::nodes::Literal;
 use indexmap::{IndexMap, IndexSet};
+pub use notedown_derive::{FromValue, IntoValue};
 use num::{BigInt, BigUint};
 use rust_decimal::Decimal;
 use std::collections::{BTreeMap, BTreeSet};
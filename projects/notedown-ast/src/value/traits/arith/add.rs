@@ -1,26 +1,48 @@
 use super::*;
-use num::FromPrimitive;
-use rust_decimal::Decimal;
 
 impl Add for Value {
     type Output = Result<Self>;
 
     /// a + b
     fn add(self, other: Self) -> Self::Output {
-        let msg = format!("Can not apply `+` on lhs: {}, rhs: {}", self.get_type_name(), other.get_type_name());
-        let type_mismatch = Err(NoteError::type_mismatch(msg));
-
         let out = match (self, other) {
             (Self::String(lhs), Self::String(rhs)) => Self::String(lhs + &rhs),
-            (Self::Integer(lhs), Self::Integer(rhs)) => Self::Integer(lhs + &rhs),
-            (Self::Integer(int), Self::Decimal(dec)) | (Self::Decimal(dec), Self::Integer(int)) => {
-                match int.to_i128().and_then(|s| Decimal::from_i128(s)) {
-                    Some(s) => Self::Decimal(s + dec),
-                    None => return fail_int2dec(int),
-                }
-            }
-            (Self::Decimal(lhs), Self::Decimal(rhs)) => Self::Decimal(lhs + rhs),
-            _ => return type_mismatch,
+            (Self::Integer(lhs), Self::Integer(rhs)) => Self::Integer(lhs + rhs),
+            (Self::Integer(lhs), Self::Decimal(rhs)) => return decimal_op("+", int2dec(&lhs)?, rhs, Decimal::checked_add),
+            (Self::Decimal(lhs), Self::Integer(rhs)) => return decimal_op("+", lhs, int2dec(&rhs)?, Decimal::checked_add),
+            (Self::Decimal(lhs), Self::Decimal(rhs)) => return decimal_op("+", lhs, rhs, Decimal::checked_add),
+            (lhs, rhs) => return Err(type_mismatch("+", &lhs, &rhs)),
+        };
+        return Ok(out);
+    }
+}
+
+impl Sub for Value {
+    type Output = Result<Self>;
+
+    /// a - b
+    fn sub(self, other: Self) -> Self::Output {
+        let out = match (self, other) {
+            (Self::Integer(lhs), Self::Integer(rhs)) => Self::Integer(lhs - rhs),
+            (Self::Integer(lhs), Self::Decimal(rhs)) => return decimal_op("-", int2dec(&lhs)?, rhs, Decimal::checked_sub),
+            (Self::Decimal(lhs), Self::Integer(rhs)) => return decimal_op("-", lhs, int2dec(&rhs)?, Decimal::checked_sub),
+            (Self::Decimal(lhs), Self::Decimal(rhs)) => return decimal_op("-", lhs, rhs, Decimal::checked_sub),
+            (Self::Set(lhs), Self::Set(rhs)) => Self::Set(Self::set_difference(lhs, &rhs)),
+            (lhs, rhs) => return Err(type_mismatch("-", &lhs, &rhs)),
+        };
+        return Ok(out);
+    }
+}
+
+impl Neg for Value {
+    type Output = Result<Self>;
+
+    /// -a
+    fn neg(self) -> Self::Output {
+        let out = match self {
+            Self::Integer(v) => Self::Integer(-v),
+            Self::Decimal(v) => Self::Decimal(-v),
+            _ => return Err(type_mismatch_unary("-", &self)),
         };
         return Ok(out);
     }
@@ -31,11 +53,18 @@ impl Shr for Value {
 
     /// a ++ b
     fn shr(self, other: Self) -> Self::Output {
-        let msg = format!("Can not apply `++` on lhs: {}, rhs: {}", self.get_type_name(), other.get_type_name());
-        let type_mismatch = Err(NoteError::type_mismatch(msg));
         let out = match (self, other) {
             (Self::String(lhs), rhs) => Self::string_join(lhs, rhs)?,
-            _ => return type_mismatch,
+            (Self::Set(lhs), Self::Set(rhs)) => Self::Set(Self::set_union(lhs, rhs)),
+            (Self::Array(mut lhs), Self::Array(rhs)) => {
+                lhs.concat(*rhs);
+                Self::Array(lhs)
+            }
+            (Self::Object(mut lhs), Self::Object(rhs)) => {
+                lhs.merge(*rhs);
+                Self::Object(lhs)
+            }
+            (lhs, rhs) => return Err(type_mismatch("++", &lhs, &rhs)),
         };
         return Ok(out);
     }
@@ -44,8 +73,6 @@ impl Shr for Value {
 impl Value {
     /// join a value to the string
     pub fn string_join(lhs: String, other: Value) -> Result<Self> {
-        let msg = format!("Can not apply `++` on lhs: `String`, rhs: {}", other.get_type_name());
-        let type_mismatch = Err(NoteError::type_mismatch(msg));
         let out = match other {
             Value::Null => lhs,
             Value::Boolean(v) => {
@@ -60,38 +87,10 @@ impl Value {
             Value::String(v) => {
                 format!("{}{}", lhs, v)
             }
-            Value::Set(_) => {
-                return type_mismatch;
-            }
-            Value::Array(_) => {
-                return type_mismatch;
-            }
-            Value::Object(_) => {
-                return type_mismatch;
+            Value::Set(_) | Value::Array(_) | Value::Object(_) => {
+                return Err(type_mismatch("++", &Value::String(lhs), &other));
             }
         };
         Ok(Value::String(out))
     }
 }
-
-impl Sub for Value {
-    type Output = Result<Self>;
-    /// a- b
-    fn sub(self, other: Self) -> Self::Output {
-        let msg = format!("Can not apply `-` on lhs: {}, rhs: {}", self.get_type_name(), other.get_type_name());
-        let type_mismatch = Err(NoteError::type_mismatch(msg));
-
-        let out = match (self, other) {
-            (Self::Integer(lhs), Self::Integer(rhs)) => Self::Integer(lhs - rhs),
-            (Self::Integer(int), Self::Decimal(dec)) | (Self::Decimal(dec), Self::Integer(int)) => {
-                match int.to_i128().and_then(|s| Decimal::from_i128(s)) {
-                    Some(s) => Self::Decimal(s - dec),
-                    None => return fail_int2dec(int),
-                }
-            }
-            (Self::Decimal(lhs), Self::Decimal(rhs)) => Self::Decimal(lhs - rhs),
-            _ => return type_mismatch,
-        };
-        return Ok(out);
-    }
-}
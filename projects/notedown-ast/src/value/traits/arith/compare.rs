@@ -0,0 +1,79 @@
+use super::*;
+use std::cmp::Ordering;
+
+impl Value {
+    /// Total order of all values, different types are ordered by
+    /// `Null < Boolean < Integer | Decimal < String < Set < Array < Object`
+    ///
+    /// `Integer` and `Decimal` are compared by their numeric value.
+    pub fn compare(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Null, Self::Null) => Ordering::Equal,
+            (Self::Boolean(lhs), Self::Boolean(rhs)) => lhs.cmp(rhs),
+            (Self::Integer(lhs), Self::Integer(rhs)) => lhs.cmp(rhs),
+            (Self::Decimal(lhs), Self::Decimal(rhs)) => lhs.cmp(rhs),
+            (Self::Integer(lhs), Self::Decimal(rhs)) => compare_int_dec(lhs, rhs),
+            (Self::Decimal(lhs), Self::Integer(rhs)) => compare_int_dec(rhs, lhs).reverse(),
+            (Self::String(lhs), Self::String(rhs)) => lhs.cmp(rhs),
+            (Self::Set(lhs), Self::Set(rhs)) => compare_iter(lhs.iter().map(|f| &f.value), rhs.iter().map(|f| &f.value)),
+            (Self::Array(lhs), Self::Array(rhs)) => lhs.compare(rhs),
+            (Self::Object(lhs), Self::Object(rhs)) => {
+                let mut lhs = lhs.iter();
+                let mut rhs = rhs.iter();
+                loop {
+                    let (l, r) = match (lhs.next(), rhs.next()) {
+                        (None, None) => return Ordering::Equal,
+                        (None, Some(_)) => return Ordering::Less,
+                        (Some(_), None) => return Ordering::Greater,
+                        (Some(l), Some(r)) => (l, r),
+                    };
+                    match l.0.cmp(r.0).then_with(|| l.1.compare(r.1)) {
+                        Ordering::Equal => continue,
+                        other => return other,
+                    }
+                }
+            }
+            _ => self.type_rank().cmp(&other.type_rank()),
+        }
+    }
+    /// Equality used by `==`, `1 == 1.0` is `true`
+    #[inline]
+    pub fn equal(&self, other: &Self) -> bool {
+        self.compare(other) == Ordering::Equal
+    }
+    #[inline]
+    fn type_rank(&self) -> u8 {
+        match self {
+            Self::Null => 0,
+            Self::Boolean(_) => 1,
+            Self::Integer(_) | Self::Decimal(_) => 2,
+            Self::String(_) => 3,
+            Self::Set(_) => 4,
+            Self::Array(_) => 5,
+            Self::Object(_) => 6,
+        }
+    }
+}
+
+/// Integer too large for decimal is decided by its sign
+fn compare_int_dec(lhs: &BigInt, rhs: &Decimal) -> Ordering {
+    match int2dec(lhs) {
+        Ok(o) => o.cmp(rhs),
+        Err(_) if lhs.is_negative() => Ordering::Less,
+        Err(_) => Ordering::Greater,
+    }
+}
+
+fn compare_iter<'a>(mut lhs: impl Iterator<Item = &'a Value>, mut rhs: impl Iterator<Item = &'a Value>) -> Ordering {
+    loop {
+        match (lhs.next(), rhs.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(l), Some(r)) => match l.compare(r) {
+                Ordering::Equal => continue,
+                other => return other,
+            },
+        }
+    }
+}
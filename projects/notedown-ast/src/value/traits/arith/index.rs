@@ -19,7 +19,12 @@ impl Index<BigInt> for Value {
                         Some(u) => {
                             let max = s.chars().count();
                             // (u <= max).then_some(max);
-                            if u <= max { Some(max) } else { None }
+                            if u <= max {
+                                Some(max)
+                            }
+                            else {
+                                None
+                            }
                         }
                         None => None,
                     }
@@ -46,31 +51,11 @@ impl Index<BigInt> for Value {
                     None => Err(NoteError::runtime_error(format!("Index `{}` of `Set` out of range.", index))),
                 }
             }
-            Self::Array(v) => {
-                let out = if index.is_negative() {
-                    match v.last_key_value() {
-                        None => None,
-                        Some((max, _)) => match index.neg().to_biguint() {
-                            None => None,
-                            Some(i) => {
-                                if &i < max {
-                                    v.get(&(max - i))
-                                }
-                                else {
-                                    None
-                                }
-                            }
-                        },
-                    }
-                }
-                else {
-                    index.to_biguint().and_then(|i| v.get(&i))
-                };
-                match out {
-                    Some(s) => Ok(s.to_owned()),
-                    None => Err(NoteError::runtime_error(format!("Index `{}` of `Array` out of range.", index))),
-                }
-            }
+            Self::Array(v) => match v.get_signed(index) {
+                Some(s) => Ok(s),
+                None if index.is_zero() => Err(NoteError::runtime_error("Index of `Array` starts from 1, `0` is not a valid index.")),
+                None => Err(NoteError::runtime_error(format!("Index `{}` of `Array` out of range.", index))),
+            },
             Self::Object(v) => {
                 // let v : BTreeMap<String, Literal<Value>>;
                 let out = if index.is_negative() {
@@ -104,3 +89,14 @@ impl Index<String> for Value {
         }
     }
 }
+
+impl Value {
+    /// Take index by integer or string, `a[b]`
+    pub fn get_index_by(&self, index: &Value) -> Result<Self> {
+        match index {
+            Self::Integer(i) => self.get_index(i),
+            Self::String(s) => self.get_index(s),
+            _ => Err(NoteError::type_mismatch(format!("Can not take `{}` index of type `{}`", index.get_type_name(), self.get_type_name()))),
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use super::*;
+
+impl Not for Value {
+    type Output = Result<Self>;
+
+    /// !a
+    fn not(self) -> Self::Output {
+        match self {
+            Self::Boolean(v) => Ok(Self::Boolean(!v)),
+            _ => Err(type_mismatch_unary("!", &self)),
+        }
+    }
+}
+
+impl BitOr for Value {
+    type Output = Result<Self>;
+
+    /// a | b
+    fn bitor(self, other: Self) -> Self::Output {
+        match (self, other) {
+            (Self::Set(lhs), Self::Set(rhs)) => Ok(Self::Set(Self::set_union(lhs, rhs))),
+            (lhs, rhs) => Err(type_mismatch("|", &lhs, &rhs)),
+        }
+    }
+}
+
+impl BitAnd for Value {
+    type Output = Result<Self>;
+
+    /// a & b
+    fn bitand(self, other: Self) -> Self::Output {
+        match (self, other) {
+            (Self::Set(lhs), Self::Set(rhs)) => Ok(Self::Set(Self::set_intersection(lhs, &rhs))),
+            (lhs, rhs) => Err(type_mismatch("&", &lhs, &rhs)),
+        }
+    }
+}
+
+impl Value {
+    /// a && b, both sides must be `Boolean`
+    pub fn and(self, other: Self) -> Result<Self> {
+        match (self, other) {
+            (Self::Boolean(lhs), Self::Boolean(rhs)) => Ok(Self::Boolean(lhs && rhs)),
+            (lhs, rhs) => Err(type_mismatch("&&", &lhs, &rhs)),
+        }
+    }
+    /// a || b, both sides must be `Boolean`
+    pub fn or(self, other: Self) -> Result<Self> {
+        match (self, other) {
+            (Self::Boolean(lhs), Self::Boolean(rhs)) => Ok(Self::Boolean(lhs || rhs)),
+            (lhs, rhs) => Err(type_mismatch("||", &lhs, &rhs)),
+        }
+    }
+}
+
+impl Value {
+    /// Elements in either set, keeps the order of the left side
+    pub fn set_union(mut lhs: OrderedSet, rhs: OrderedSet) -> OrderedSet {
+        lhs.extend(rhs);
+        lhs
+    }
+    /// Elements in both sets, keeps the order of the left side
+    pub fn set_intersection(mut lhs: OrderedSet, rhs: &OrderedSet) -> OrderedSet {
+        lhs.retain(|f| rhs.contains(f));
+        lhs
+    }
+    /// Elements in the left set but not in the right set
+    pub fn set_difference(mut lhs: OrderedSet, rhs: &OrderedSet) -> OrderedSet {
+        lhs.retain(|f| !rhs.contains(f));
+        lhs
+    }
+}
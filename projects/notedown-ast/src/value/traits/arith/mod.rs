@@ -1,11 +1,41 @@
 mod add;
+mod compare;
 mod index;
+mod logic;
 mod mul;
 
-use crate::{NoteError, Result, Value};
-use num::{BigInt, Signed, ToPrimitive};
-use std::ops::{Add, Div, Mul, Neg, Shr, Sub};
+use crate::{value::OrderedSet, NoteError, Result, Value};
+use num::{BigInt, FromPrimitive, Signed, ToPrimitive, Zero};
+use rust_decimal::Decimal;
+use std::ops::{Add, BitAnd, BitOr, Div, Mul, Neg, Not, Rem, Shr, Sub};
 
-fn fail_int2dec(n: BigInt) -> Result<Value> {
-    Err(NoteError::runtime_error(format!("Can not convert `Integer` {} to `Decimal`", n)))
+fn fail_int2dec(n: &BigInt) -> NoteError {
+    NoteError::runtime_error(format!("Can not convert `Integer` {} to `Decimal`", n))
+}
+
+fn int2dec(n: &BigInt) -> Result<Decimal> {
+    match n.to_i128().and_then(Decimal::from_i128) {
+        Some(s) => Ok(s),
+        None => Err(fail_int2dec(n)),
+    }
+}
+
+fn overflow(op: &str) -> NoteError {
+    NoteError::runtime_error(format!("Overflow when applying `{}` on `Decimal`", op))
+}
+
+fn type_mismatch(op: &str, lhs: &Value, rhs: &Value) -> NoteError {
+    NoteError::type_mismatch(format!("Can not apply `{}` on lhs: `{}`, rhs: `{}`", op, lhs.get_type_name(), rhs.get_type_name()))
+}
+
+fn type_mismatch_unary(op: &str, value: &Value) -> NoteError {
+    NoteError::type_mismatch(format!("Can not apply `{}` on type: `{}`", op, value.get_type_name()))
+}
+
+/// Apply a decimal operation, promote the integer side if needed
+fn decimal_op(op: &str, lhs: Decimal, rhs: Decimal, f: fn(Decimal, Decimal) -> Option<Decimal>) -> Result<Value> {
+    match f(lhs, rhs) {
+        Some(s) => Ok(Value::Decimal(s)),
+        None => Err(overflow(op)),
+    }
 }
@@ -1,23 +1,19 @@
 use super::*;
-use num::FromPrimitive;
-use rust_decimal::Decimal;
+
+/// Integer power larger than this will be rejected
+const MAX_INTEGER_BITS: u64 = 1 << 20;
 
 impl Mul for Value {
     type Output = Result<Self>;
-    // a * b
+
+    /// a * b
     fn mul(self, other: Self) -> Self::Output {
-        let msg = format!("Can not apply `*` on lhs: {}, rhs: {}", self.get_type_name(), other.get_type_name());
-        let type_mismatch = Err(NoteError::type_mismatch(msg));
         let out = match (self, other) {
             (Self::Integer(lhs), Self::Integer(rhs)) => Self::Integer(lhs * rhs),
-            (Self::Integer(lhs), Self::Decimal(rhs)) | (Self::Decimal(rhs), Self::Integer(lhs)) => {
-                match lhs.to_i128().and_then(|s| Decimal::from_i128(s)) {
-                    Some(s) => Self::Decimal(s * rhs),
-                    None => return fail_int2dec(lhs),
-                }
-            }
-            (Self::Decimal(lhs), Self::Decimal(rhs)) => Self::Decimal(lhs * rhs),
-            _ => return type_mismatch,
+            (Self::Integer(lhs), Self::Decimal(rhs)) => return decimal_op("*", int2dec(&lhs)?, rhs, Decimal::checked_mul),
+            (Self::Decimal(lhs), Self::Integer(rhs)) => return decimal_op("*", lhs, int2dec(&rhs)?, Decimal::checked_mul),
+            (Self::Decimal(lhs), Self::Decimal(rhs)) => return decimal_op("*", lhs, rhs, Decimal::checked_mul),
+            (lhs, rhs) => return Err(type_mismatch("*", &lhs, &rhs)),
         };
         return Ok(out);
     }
@@ -26,20 +22,100 @@ impl Mul for Value {
 impl Div for Value {
     type Output = Result<Self>;
 
+    /// a / b, integer division promotes to decimal if not divisible
     fn div(self, other: Self) -> Self::Output {
-        let msg = format!("Can not apply `/` on lhs: {}, rhs: {}", self.get_type_name(), other.get_type_name());
-        let type_mismatch = Err(NoteError::type_mismatch(msg));
+        if other.is_zero() {
+            return Err(NoteError::runtime_error("Division by zero"));
+        }
+        let out = match (self, other) {
+            (Self::Integer(lhs), Self::Integer(rhs)) => match (&lhs % &rhs).is_zero() {
+                true => Self::Integer(lhs / rhs),
+                false => return decimal_op("/", int2dec(&lhs)?, int2dec(&rhs)?, Decimal::checked_div),
+            },
+            (Self::Integer(lhs), Self::Decimal(rhs)) => return decimal_op("/", int2dec(&lhs)?, rhs, Decimal::checked_div),
+            (Self::Decimal(lhs), Self::Integer(rhs)) => return decimal_op("/", lhs, int2dec(&rhs)?, Decimal::checked_div),
+            (Self::Decimal(lhs), Self::Decimal(rhs)) => return decimal_op("/", lhs, rhs, Decimal::checked_div),
+            (lhs, rhs) => return Err(type_mismatch("/", &lhs, &rhs)),
+        };
+        return Ok(out);
+    }
+}
+
+impl Rem for Value {
+    type Output = Result<Self>;
+
+    /// a % b, the sign follows the dividend
+    fn rem(self, other: Self) -> Self::Output {
+        if other.is_zero() {
+            return Err(NoteError::runtime_error("Division by zero"));
+        }
         let out = match (self, other) {
-            (Self::Integer(lhs), Self::Integer(rhs)) => Self::Integer(lhs / rhs),
-            (Self::Integer(lhs), Self::Decimal(rhs)) | (Self::Decimal(rhs), Self::Integer(lhs)) => {
-                match lhs.to_i128().and_then(|s| Decimal::from_i128(s)) {
-                    Some(s) => Self::Decimal(s / rhs),
-                    None => return fail_int2dec(lhs),
-                }
+            (Self::Integer(lhs), Self::Integer(rhs)) => Self::Integer(lhs % rhs),
+            (Self::Integer(lhs), Self::Decimal(rhs)) => return decimal_op("%", int2dec(&lhs)?, rhs, Decimal::checked_rem),
+            (Self::Decimal(lhs), Self::Integer(rhs)) => return decimal_op("%", lhs, int2dec(&rhs)?, Decimal::checked_rem),
+            (Self::Decimal(lhs), Self::Decimal(rhs)) => return decimal_op("%", lhs, rhs, Decimal::checked_rem),
+            (lhs, rhs) => return Err(type_mismatch("%", &lhs, &rhs)),
+        };
+        return Ok(out);
+    }
+}
+
+impl Value {
+    /// a ^ b, negative exponent promotes to decimal
+    pub fn pow(self, other: Self) -> Result<Self> {
+        let out = match (self, other) {
+            // 0, 1 and -1 never grow, whatever the exponent is
+            (Self::Integer(lhs), Self::Integer(rhs)) if !rhs.is_negative() && lhs.abs() <= BigInt::from(1) => match rhs.bit(0) {
+                _ if rhs.is_zero() => Self::Integer(BigInt::from(1)),
+                true => Self::Integer(lhs),
+                false => Self::Integer(lhs.abs()),
+            },
+            (Self::Integer(lhs), Self::Integer(rhs)) if !rhs.is_negative() => {
+                let exp = match rhs.to_u32() {
+                    Some(s) if lhs.bits().saturating_mul(s as u64) <= MAX_INTEGER_BITS => s,
+                    _ => {
+                        return Err(NoteError::runtime_error(format!(
+                            "Overflow when applying `^` on `Integer`, exponent {} is too large",
+                            rhs
+                        )));
+                    }
+                };
+                Self::Integer(lhs.pow(exp))
             }
-            (Self::Decimal(lhs), Self::Decimal(rhs)) => Self::Decimal(lhs / rhs),
-            _ => return type_mismatch,
+            (Self::Integer(lhs), Self::Integer(rhs)) => Self::Decimal(decimal_pow(int2dec(&lhs)?, &rhs)?),
+            (Self::Decimal(lhs), Self::Integer(rhs)) => Self::Decimal(decimal_pow(lhs, &rhs)?),
+            (lhs, rhs) => return Err(type_mismatch("^", &lhs, &rhs)),
         };
         return Ok(out);
     }
+    fn is_zero(&self) -> bool {
+        match self {
+            Self::Integer(v) => v.is_zero(),
+            Self::Decimal(v) => v.is_zero(),
+            _ => false,
+        }
+    }
+}
+
+/// Exponentiation by squaring with overflow check
+fn decimal_pow(base: Decimal, exp: &BigInt) -> Result<Decimal> {
+    let mut n = match exp.abs().to_u64() {
+        Some(s) => s,
+        None => return Err(overflow("^")),
+    };
+    let mut base = base;
+    let mut out = Decimal::ONE;
+    while n > 0 {
+        if n & 1 == 1 {
+            out = out.checked_mul(base).ok_or_else(|| overflow("^"))?;
+        }
+        n >>= 1;
+        if n > 0 {
+            base = base.checked_mul(base).ok_or_else(|| overflow("^"))?;
+        }
+    }
+    match exp.is_negative() {
+        true => Decimal::ONE.checked_div(out).ok_or_else(|| NoteError::runtime_error("Division by zero")),
+        false => Ok(out),
+    }
 }
@@ -0,0 +1,287 @@
+use super::*;
+use crate::Result;
+
+/// Convert a [`Value`] with position into a rust type
+/// ```
+/// use notedown_ast::value::FromValue;
+///
+/// #[derive(FromValue)]
+/// struct ImageOptions {
+///     src: String,
+///     #[note(alias = "caption")]
+///     alt: Option<String>,
+///     #[note(default)]
+///     width: usize,
+/// }
+/// ```
+pub trait FromValue: Sized {
+    /// Convert the value, problems that do not stop the conversion such as unknown keys are pushed into `warnings`
+    fn from_value(value: Literal<Value>, warnings: &mut Vec<NoteError>) -> Result<Self>;
+    /// Value used when the key is missing, `None` means the key is required
+    #[inline]
+    fn from_missing() -> Option<Self> {
+        None
+    }
+}
+
+/// Type mismatch error with the range of the value
+/// ```txt
+/// Expect `String`, found `Integer`
+/// ```
+pub fn expect_type(expect: &str, value: &Literal<Value>) -> NoteError {
+    let mut error = NoteError::type_mismatch(format!("Expect `{}`, found `{}`", expect, value.get_type_name()));
+    error.range = value.range.to_owned();
+    error
+}
+
+impl FromValue for Value {
+    #[inline]
+    fn from_value(value: Literal<Value>, _: &mut Vec<NoteError>) -> Result<Self> {
+        Ok(value.value)
+    }
+}
+
+impl<T: FromValue> FromValue for Literal<T> {
+    #[inline]
+    fn from_value(value: Literal<Value>, warnings: &mut Vec<NoteError>) -> Result<Self> {
+        let range = value.range.to_owned();
+        Ok(Literal { value: T::from_value(value, warnings)?, range })
+    }
+}
+
+impl<T: FromValue> FromValue for Option<T> {
+    #[inline]
+    fn from_value(value: Literal<Value>, warnings: &mut Vec<NoteError>) -> Result<Self> {
+        match value.value {
+            Value::Null => Ok(None),
+            _ => Ok(Some(T::from_value(value, warnings)?)),
+        }
+    }
+    #[inline]
+    fn from_missing() -> Option<Self> {
+        Some(None)
+    }
+}
+
+impl FromValue for bool {
+    #[inline]
+    fn from_value(value: Literal<Value>, _: &mut Vec<NoteError>) -> Result<Self> {
+        match value.value {
+            Value::Boolean(v) => Ok(v),
+            _ => Err(expect_type("Boolean", &value)),
+        }
+    }
+}
+
+impl FromValue for String {
+    #[inline]
+    fn from_value(value: Literal<Value>, _: &mut Vec<NoteError>) -> Result<Self> {
+        match value.value {
+            Value::String(v) => Ok(v),
+            _ => Err(expect_type("String", &value)),
+        }
+    }
+}
+
+impl FromValue for BigInt {
+    #[inline]
+    fn from_value(value: Literal<Value>, _: &mut Vec<NoteError>) -> Result<Self> {
+        match value.value {
+            Value::Integer(v) => Ok(v),
+            _ => Err(expect_type("Integer", &value)),
+        }
+    }
+}
+
+impl FromValue for Decimal {
+    #[inline]
+    fn from_value(value: Literal<Value>, _: &mut Vec<NoteError>) -> Result<Self> {
+        match &value.value {
+            Value::Decimal(v) => Ok(*v),
+            Value::Integer(v) => match v.to_i128().and_then(Decimal::from_i128) {
+                Some(s) => Ok(s),
+                None => Err(out_of_range(v, "Decimal", &value)),
+            },
+            _ => Err(expect_type("Decimal", &value)),
+        }
+    }
+}
+
+impl FromValue for f64 {
+    #[inline]
+    fn from_value(value: Literal<Value>, warnings: &mut Vec<NoteError>) -> Result<Self> {
+        let range = value.range.to_owned();
+        let out = Decimal::from_value(value, warnings)?;
+        match out.to_f64() {
+            Some(s) => Ok(s),
+            None => Err(out_of_range(out, "f64", &Literal { value: Value::Decimal(out), range })),
+        }
+    }
+}
+
+macro_rules! from_integer {
+    ($($t:ty => $f:ident),+ $(,)?) => {$(
+        impl FromValue for $t {
+            #[inline]
+            fn from_value(value: Literal<Value>, _: &mut Vec<NoteError>) -> Result<Self> {
+                match &value.value {
+                    Value::Integer(v) => match v.$f() {
+                        Some(s) => Ok(s),
+                        None => Err(out_of_range(v, stringify!($t), &value)),
+                    },
+                    _ => Err(expect_type("Integer", &value)),
+                }
+            }
+        }
+    )+};
+}
+
+from_integer![
+    u8 => to_u8,
+    u16 => to_u16,
+    u32 => to_u32,
+    u64 => to_u64,
+    u128 => to_u128,
+    usize => to_usize,
+    i8 => to_i8,
+    i16 => to_i16,
+    i32 => to_i32,
+    i64 => to_i64,
+    i128 => to_i128,
+    isize => to_isize,
+];
+
+fn out_of_range(n: impl Display, target: &str, value: &Literal<Value>) -> NoteError {
+    let mut error = NoteError::type_mismatch(format!("Number {} out of range for `{}`", n, target));
+    error.range = value.range.to_owned();
+    error
+}
+
+impl<T: FromValue> FromValue for Vec<T> {
+    fn from_value(value: Literal<Value>, warnings: &mut Vec<NoteError>) -> Result<Self> {
+        let range = value.range;
+        let array = match value.value {
+            Value::Array(v) => v,
+            value => return Err(expect_type("Array", &Literal { value, range })),
+        };
+        if !array.is_dense() {
+            let mut error = NoteError::type_mismatch(format!("Array with {} holes can not be converted to a list", array.holes()));
+            error.range = range;
+            return Err(error);
+        }
+        let mut out = Vec::with_capacity(array.len());
+        // holes have no position, point to the array instead
+        for item in array.into_literals() {
+            let range = item.range.or_else(|| range.to_owned());
+            out.push(T::from_value(Literal { value: item.value, range }, warnings)?)
+        }
+        Ok(out)
+    }
+}
+
+impl FromValue for SparseArray {
+    #[inline]
+    fn from_value(value: Literal<Value>, _: &mut Vec<NoteError>) -> Result<Self> {
+        match value.value {
+            Value::Array(v) => Ok(*v),
+            _ => Err(expect_type("Array", &value)),
+        }
+    }
+}
+
+impl FromValue for OrderedSet {
+    #[inline]
+    fn from_value(value: Literal<Value>, _: &mut Vec<NoteError>) -> Result<Self> {
+        match value.value {
+            Value::Set(v) => Ok(v),
+            _ => Err(expect_type("Set", &value)),
+        }
+    }
+}
+
+impl FromValue for OrderedMap {
+    #[inline]
+    fn from_value(value: Literal<Value>, _: &mut Vec<NoteError>) -> Result<Self> {
+        match value.value {
+            Value::Object(v) => Ok(*v),
+            _ => Err(expect_type("Object", &value)),
+        }
+    }
+}
+
+/// Read fields from an object one by one, used by `#[derive(FromValue)]`
+pub struct ObjectReader<'w> {
+    name: &'static str,
+    range: MaybeRanged,
+    map: OrderedMap,
+    warnings: &'w mut Vec<NoteError>,
+}
+
+impl<'w> ObjectReader<'w> {
+    /// Start reading the object, `name` is used in error messages
+    pub fn new(name: &'static str, value: Literal<Value>, warnings: &'w mut Vec<NoteError>) -> Result<Self> {
+        match value.value {
+            Value::Object(map) => Ok(Self { name, range: value.range, map: *map, warnings }),
+            _ => Err(expect_type(name, &value)),
+        }
+    }
+    /// Take the field by key or aliases, missing key falls back to [`FromValue::from_missing`]
+    pub fn field<T: FromValue>(&mut self, key: &str, aliases: &[&str]) -> Result<T> {
+        match self.take(key, aliases) {
+            Some(value) => self.convert(value),
+            None => match T::from_missing() {
+                Some(s) => Ok(s),
+                None => {
+                    let mut error = NoteError::type_mismatch(format!("Missing key `{}` of `{}`", key, self.name));
+                    error.range = self.range.to_owned();
+                    Err(error)
+                }
+            },
+        }
+    }
+    /// Take the field by key or aliases, missing key falls back to the given default
+    pub fn field_or<T: FromValue>(&mut self, key: &str, aliases: &[&str], default: impl FnOnce() -> T) -> Result<T> {
+        match self.take(key, aliases) {
+            Some(value) => self.convert(value),
+            None => Ok(default()),
+        }
+    }
+    /// All remaining keys are unknown, report them as warnings
+    pub fn finish(self) {
+        for (key, _) in self.map.iter_raw() {
+            let mut error = NoteError::type_mismatch(format!("Unknown key `{}` of `{}`", key.value, self.name));
+            error.level = DiagnosticLevel::Warning;
+            error.range = key.range.to_owned();
+            self.warnings.push(error)
+        }
+    }
+    fn convert<T: FromValue>(&mut self, value: Literal<Value>) -> Result<T> {
+        let range = value.range.to_owned();
+        T::from_value(value, self.warnings).map_err(|mut e| {
+            if e.range.is_none() {
+                e.range = range
+            }
+            e
+        })
+    }
+    fn take(&mut self, key: &str, aliases: &[&str]) -> Option<Literal<Value>> {
+        let mut out: Option<Literal<Value>> = None;
+        for name in std::iter::once(&key).chain(aliases) {
+            let (k, v) = match self.map.extract_raw(name) {
+                Some(s) => s,
+                None => continue,
+            };
+            match out {
+                None => out = Some(v),
+                Some(_) => {
+                    let mut error =
+                        NoteError::type_mismatch(format!("Duplicate key `{}` of `{}`, already set by an alias", k.value, self.name));
+                    error.level = DiagnosticLevel::Warning;
+                    error.range = k.range;
+                    self.warnings.push(error)
+                }
+            }
+        }
+        return out;
+    }
+}
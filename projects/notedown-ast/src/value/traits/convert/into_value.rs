@@ -0,0 +1,100 @@
+use super::*;
+
+/// Convert a rust type into [`Value`]
+pub trait IntoValue {
+    /// Convert into value
+    fn into_value(self) -> Value;
+}
+
+impl IntoValue for Value {
+    #[inline]
+    fn into_value(self) -> Value {
+        self
+    }
+}
+
+impl<T: IntoValue> IntoValue for Literal<T> {
+    #[inline]
+    fn into_value(self) -> Value {
+        self.value.into_value()
+    }
+}
+
+impl<T: IntoValue> IntoValue for Option<T> {
+    #[inline]
+    fn into_value(self) -> Value {
+        match self {
+            Some(s) => s.into_value(),
+            None => Value::Null,
+        }
+    }
+}
+
+impl IntoValue for bool {
+    #[inline]
+    fn into_value(self) -> Value {
+        Value::Boolean(self)
+    }
+}
+
+impl IntoValue for String {
+    #[inline]
+    fn into_value(self) -> Value {
+        Value::String(self)
+    }
+}
+
+impl IntoValue for &str {
+    #[inline]
+    fn into_value(self) -> Value {
+        Value::string(self)
+    }
+}
+
+impl IntoValue for Decimal {
+    #[inline]
+    fn into_value(self) -> Value {
+        Value::Decimal(self)
+    }
+}
+
+macro_rules! into_integer {
+    ($($t:ty),+ $(,)?) => {$(
+        impl IntoValue for $t {
+            #[inline]
+            fn into_value(self) -> Value {
+                Value::integer(self)
+            }
+        }
+    )+};
+}
+
+into_integer![BigInt, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize];
+
+impl<T: IntoValue> IntoValue for Vec<T> {
+    #[inline]
+    fn into_value(self) -> Value {
+        Value::Array(Box::new(self.into_iter().map(IntoValue::into_value).collect()))
+    }
+}
+
+impl IntoValue for SparseArray {
+    #[inline]
+    fn into_value(self) -> Value {
+        Value::Array(Box::new(self))
+    }
+}
+
+impl IntoValue for OrderedSet {
+    #[inline]
+    fn into_value(self) -> Value {
+        Value::Set(self)
+    }
+}
+
+impl IntoValue for OrderedMap {
+    #[inline]
+    fn into_value(self) -> Value {
+        Value::Object(Box::new(self))
+    }
+}
@@ -1,5 +1,18 @@
-use crate::{NoteError, Value};
-use std::convert::TryFrom;
+mod from_value;
+mod into_value;
+
+pub use self::{
+    from_value::{expect_type, FromValue, ObjectReader},
+    into_value::IntoValue,
+};
+use crate::{
+    nodes::{Literal, MaybeRanged},
+    value::{OrderedMap, OrderedSet, SparseArray},
+    DiagnosticLevel, NoteError, Value,
+};
+use num::{BigInt, FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use std::{convert::TryFrom, fmt::Display};
 
 macro_rules! from_value {
     ($source:tt => $target:ty) => {
@@ -9,7 +22,7 @@ macro_rules! from_value {
             fn try_from(value: Value) -> Result<Self, Self::Error> {
                 match value {
                     Value::$source(v) => Ok(Self::try_from(v)?),
-                    _ => Err(NoteError::unreachable()),
+                    _ => Err(NoteError::type_mismatch(format!("Expect `{}`, found `{}`", stringify!($source), value.get_type_name()))),
                 }
             }
         }
@@ -36,3 +49,38 @@ from_value! [
     Integer => isize,
     String => String,
 ];
+
+impl<T> TryFrom<SparseArray> for Vec<T>
+where
+    T: TryFrom<Value>,
+    NoteError: From<T::Error>,
+{
+    type Error = NoteError;
+
+    /// Holes are filled with the default value of the array, too many holes are an error
+    fn try_from(value: SparseArray) -> Result<Self, Self::Error> {
+        if !value.is_dense() {
+            return Err(NoteError::type_mismatch(format!("Array with {} holes can not be converted to `Vec`", value.holes())));
+        }
+        let mut out = vec![];
+        for item in value {
+            out.push(T::try_from(item)?)
+        }
+        Ok(out)
+    }
+}
+
+impl<T> TryFrom<Value> for Vec<T>
+where
+    T: TryFrom<Value>,
+    NoteError: From<T::Error>,
+{
+    type Error = NoteError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Array(v) => Self::try_from(*v),
+            _ => Err(NoteError::type_mismatch(format!("Can not convert type `{}` to `Array`", value.get_type_name()))),
+        }
+    }
+}
@@ -1,6 +1,8 @@
 mod arith;
 mod convert;
 
+pub use self::convert::{expect_type, FromValue, IntoValue, ObjectReader};
+
 use super::*;
 use std::hash::{Hash, Hasher};
 
@@ -0,0 +1,169 @@
+use super::*;
+
+/// Rebuild the tree by value
+///
+/// Override [`Fold::fold_nodes`] to remove nodes or to expand one node into many.
+pub trait Fold {
+    /// Any node, dispatch to the method of the kind
+    fn fold_node(&mut self, node: ASTNode) -> ASTNode {
+        fold_node(self, node)
+    }
+    /// Children of blocks, spans, cells and items
+    fn fold_nodes(&mut self, nodes: ASTNodes) -> ASTNodes {
+        fold_nodes(self, nodes)
+    }
+    /// `# header`, the children are the spans of the text
+    fn fold_header(&mut self, header: Header) -> Header {
+        fold_header(self, header)
+    }
+    /// Tables, the cells of each row are folded as children
+    fn fold_table(&mut self, table: TableView) -> TableView {
+        fold_table(self, table)
+    }
+    /// Ordered and orderless lists
+    fn fold_list(&mut self, list: ListView) -> ListView {
+        fold_list(self, list)
+    }
+    /// Item of the list, the children are the rest after the prefix
+    fn fold_list_item(&mut self, item: ListItem) -> ListItem {
+        fold_list_item(self, item)
+    }
+    /// Quotes, the head is folded before the body
+    fn fold_quote(&mut self, quote: QuoteBlock) -> QuoteBlock {
+        fold_quote(self, quote)
+    }
+    /// Nodes included from another note
+    fn fold_include(&mut self, mut include: IncludeNode) -> IncludeNode {
+        include.children = self.fold_nodes(include.children);
+        include
+    }
+    /// `*italic*`, `**bold**` and other styles
+    fn fold_styled(&mut self, mut styled: StyleNode) -> StyleNode {
+        styled.children = self.fold_nodes(styled.children);
+        styled
+    }
+    /// Links of all kinds
+    fn fold_link(&mut self, link: SmartLink) -> SmartLink {
+        link
+    }
+    /// Commands, the body of xml commands is folded
+    fn fold_command(&mut self, cmd: Command) -> Command {
+        fold_command(self, cmd)
+    }
+    /// Inline code and code blocks
+    fn fold_code(&mut self, code: CodeNode) -> CodeNode {
+        code
+    }
+    /// Inline math and math blocks
+    fn fold_math(&mut self, math: MathNode) -> MathNode {
+        math
+    }
+    /// Texts, escaped characters and line breaks
+    fn fold_text(&mut self, text: TextSpan) -> TextSpan {
+        text
+    }
+    /// `---` and raw html blocks
+    fn fold_delimiter(&mut self, delimiter: Delimiter) -> Delimiter {
+        delimiter
+    }
+    /// Values of interpolations
+    fn fold_value(&mut self, value: Value) -> Value {
+        value
+    }
+}
+
+/// Fold the value of the node by its kind, the range is kept
+pub fn fold_node<F>(f: &mut F, node: ASTNode) -> ASTNode
+where
+    F: Fold + ?Sized,
+{
+    let value = match node.value {
+        ASTKind::Statements(children) => ASTKind::Statements(f.fold_nodes(children)),
+        ASTKind::Paragraph(children) => ASTKind::Paragraph(f.fold_nodes(children)),
+        ASTKind::Delimiter(inner) => ASTKind::Delimiter(Box::new(f.fold_delimiter(*inner))),
+        ASTKind::Header(inner) => ASTKind::Header(Box::new(f.fold_header(*inner))),
+        ASTKind::TableView(inner) => ASTKind::TableView(Box::new(f.fold_table(*inner))),
+        ASTKind::ListView(inner) => ASTKind::ListView(Box::new(f.fold_list(*inner))),
+        ASTKind::QuoteNode(inner) => ASTKind::QuoteNode(Box::new(f.fold_quote(*inner))),
+        ASTKind::IncludeNode(inner) => ASTKind::IncludeNode(Box::new(f.fold_include(*inner))),
+        ASTKind::CodeNode(inner) => ASTKind::CodeNode(Box::new(f.fold_code(*inner))),
+        ASTKind::MathNode(inner) => ASTKind::MathNode(Box::new(f.fold_math(*inner))),
+        ASTKind::LinkNode(inner) => ASTKind::LinkNode(f.fold_link(inner)),
+        ASTKind::TextSpan(inner) => ASTKind::TextSpan(Box::new(f.fold_text(*inner))),
+        ASTKind::StyledSpan(inner) => ASTKind::StyledSpan(Box::new(f.fold_styled(*inner))),
+        ASTKind::Command(inner) => ASTKind::Command(Box::new(f.fold_command(*inner))),
+        ASTKind::Value(inner) => ASTKind::Value(Box::new(f.fold_value(*inner))),
+    };
+    ASTNode { value, range: node.range }
+}
+
+/// Fold the nodes one by one
+pub fn fold_nodes<F>(f: &mut F, nodes: ASTNodes) -> ASTNodes
+where
+    F: Fold + ?Sized,
+{
+    nodes.into_iter().map(|node| f.fold_node(node)).collect()
+}
+
+/// Fold the text of the header
+pub fn fold_header<F>(f: &mut F, mut header: Header) -> Header
+where
+    F: Fold + ?Sized,
+{
+    header.children = f.fold_nodes(header.children);
+    header
+}
+
+/// Fold the head and the rows of the table
+pub fn fold_table<F>(f: &mut F, table: TableView) -> TableView
+where
+    F: Fold + ?Sized,
+{
+    match table {
+        TableView::SimpleTable { head, align, terms, column } => {
+            TableView::SimpleTable { head: f.fold_nodes(head), align, terms: terms.into_iter().map(|row| f.fold_nodes(row)).collect(), column }
+        }
+    }
+}
+
+/// Fold the items of the list
+pub fn fold_list<F>(f: &mut F, mut list: ListView) -> ListView
+where
+    F: Fold + ?Sized,
+{
+    list.children = list.children.into_iter().map(|item| f.fold_list_item(item)).collect();
+    list
+}
+
+/// Fold the rest of the item
+pub fn fold_list_item<F>(f: &mut F, mut item: ListItem) -> ListItem
+where
+    F: Fold + ?Sized,
+{
+    item.rest = f.fold_nodes(item.rest);
+    item
+}
+
+/// Fold the head and the body of the quote
+pub fn fold_quote<F>(f: &mut F, mut quote: QuoteBlock) -> QuoteBlock
+where
+    F: Fold + ?Sized,
+{
+    quote.head = f.fold_nodes(quote.head);
+    quote.body = f.fold_nodes(quote.body);
+    quote
+}
+
+/// Fold the body of xml commands, other commands have no children
+pub fn fold_command<F>(f: &mut F, cmd: Command) -> Command
+where
+    F: Fold + ?Sized,
+{
+    match cmd {
+        Command::XML(mut xml) => {
+            xml.body = f.fold_nodes(xml.body);
+            Command::XML(xml)
+        }
+        other => other,
+    }
+}
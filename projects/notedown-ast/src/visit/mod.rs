@@ -0,0 +1,84 @@
+//! Walk the tree with [`Visitor`], change it in place with [`VisitorMut`], or rebuild it with [`Fold`]
+//!
+//! Every method has a default which goes into the children, override the methods of the interesting nodes,
+//! and call the `walk_*` function of the node to keep going into its children.
+//! Return [`ControlFlow::Break`] to stop the whole walk.
+mod fold;
+mod visitor;
+mod visitor_mut;
+
+pub use self::{
+    fold::{fold_command, fold_header, fold_list, fold_list_item, fold_node, fold_nodes, fold_quote, fold_table, Fold},
+    visitor::{walk_command, walk_link, walk_list, walk_node, walk_nodes, walk_quote, walk_table, VisitContext, Visitor},
+    visitor_mut::{walk_command_mut, walk_link_mut, walk_list_mut, walk_node_mut, walk_nodes_mut, walk_quote_mut, walk_table_mut, VisitorMut},
+};
+use crate::{
+    command::Command,
+    nodes::{
+        CodeNode, Delimiter, EmailLink, Header, HyperLink, ImageLink, IncludeNode, ListItem, ListView, MathNode, QuoteBlock,
+        ResourceDescriptor, SmartLink, StyleNode, TableView, TagReference, TextSpan, TwoWayLink,
+    },
+    ASTKind, ASTNode, ASTNodes, Value,
+};
+pub use std::ops::ControlFlow;
+
+/// Indexes of the visited node from the root
+///
+/// Each index counts the nodes visited before it in the same parent,
+/// so the cells of tables, the items of lists, and the head and the body of quotes are numbered in order.
+#[derive(Clone, Debug, Default)]
+pub struct VisitPath {
+    path: Vec<usize>,
+    counters: Vec<usize>,
+}
+
+impl VisitPath {
+    /// Indexes from the root, empty for the root
+    #[inline]
+    pub fn indexes(&self) -> &[usize] {
+        &self.path
+    }
+    /// Depth of the node, 0 for the root
+    #[inline]
+    pub fn depth(&self) -> usize {
+        self.path.len()
+    }
+    /// Index of the node in its parent, `None` for the root
+    #[inline]
+    pub fn index(&self) -> Option<usize> {
+        self.path.last().copied()
+    }
+    fn enter(&mut self) {
+        if let Some(counter) = self.counters.last_mut() {
+            self.path.push(*counter);
+            *counter += 1;
+        }
+        self.counters.push(0);
+    }
+    fn leave(&mut self) {
+        self.counters.pop();
+        if !self.counters.is_empty() {
+            self.path.pop();
+        }
+    }
+}
+
+impl ASTNode {
+    /// Walk the tree from this node
+    #[inline]
+    pub fn accept<'ast>(&'ast self, visitor: &mut impl Visitor<'ast>) -> ControlFlow<()> {
+        let mut ctx = VisitContext::default();
+        visitor::visit_child(visitor, self, &mut ctx)
+    }
+    /// Walk the tree from this node, the visitor can change the nodes
+    #[inline]
+    pub fn accept_mut(&mut self, visitor: &mut impl VisitorMut) -> ControlFlow<()> {
+        let mut path = VisitPath::default();
+        visitor_mut::visit_child_mut(visitor, self, &mut path)
+    }
+    /// Rebuild the tree with the folder
+    #[inline]
+    pub fn fold_with(self, folder: &mut impl Fold) -> ASTNode {
+        folder.fold_node(self)
+    }
+}
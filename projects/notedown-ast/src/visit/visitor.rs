@@ -0,0 +1,250 @@
+use super::*;
+
+/// The visited node with its ancestors and its path
+#[derive(Clone, Debug, Default)]
+pub struct VisitContext<'ast> {
+    nodes: Vec<&'ast ASTNode>,
+    path: VisitPath,
+}
+
+impl<'ast> VisitContext<'ast> {
+    /// The node being visited, the header node in [`Visitor::visit_header`] for example
+    #[inline]
+    pub fn node(&self) -> Option<&'ast ASTNode> {
+        self.nodes.last().copied()
+    }
+    /// The node containing this node, `None` for the root
+    #[inline]
+    pub fn parent(&self) -> Option<&'ast ASTNode> {
+        self.nodes.iter().rev().nth(1).copied()
+    }
+    /// Nodes containing this node, from the parent to the root
+    #[inline]
+    pub fn ancestors(&self) -> impl Iterator<Item = &'ast ASTNode> + '_ {
+        self.nodes.iter().rev().skip(1).copied()
+    }
+    /// Indexes of the node from the root
+    #[inline]
+    pub fn path(&self) -> &VisitPath {
+        &self.path
+    }
+}
+
+/// Read only walk over the tree
+///
+/// The context is entered before [`Visitor::visit_node`], so all methods see the node of the element as [`VisitContext::node`].
+#[allow(unused_variables)]
+pub trait Visitor<'ast> {
+    /// Any node, dispatch to the method of the kind
+    fn visit_node(&mut self, node: &'ast ASTNode, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        walk_node(self, node, ctx)
+    }
+    /// Top level blocks of the document
+    fn visit_statements(&mut self, nodes: &'ast ASTNodes, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        walk_nodes(self, nodes, ctx)
+    }
+    /// Spans of the paragraph
+    fn visit_paragraph(&mut self, nodes: &'ast ASTNodes, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        walk_nodes(self, nodes, ctx)
+    }
+    /// `# header`, the children are the spans of the text
+    fn visit_header(&mut self, header: &'ast Header, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        walk_nodes(self, &header.children, ctx)
+    }
+    /// Tables, the cells are visited row by row
+    fn visit_table(&mut self, table: &'ast TableView, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        walk_table(self, table, ctx)
+    }
+    /// Cell of the table, the row 0 is the head
+    fn visit_table_cell(&mut self, cell: &'ast ASTNode, row: usize, column: usize, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.visit_node(cell, ctx)
+    }
+    /// Ordered and orderless lists
+    fn visit_list(&mut self, list: &'ast ListView, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        walk_list(self, list, ctx)
+    }
+    /// Item of the list, the children are the rest after the prefix
+    fn visit_list_item(&mut self, item: &'ast ListItem, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        walk_nodes(self, &item.rest, ctx)
+    }
+    /// Quotes, the head is visited before the body
+    fn visit_quote(&mut self, quote: &'ast QuoteBlock, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        walk_quote(self, quote, ctx)
+    }
+    /// Nodes included from another note
+    fn visit_include(&mut self, include: &'ast IncludeNode, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        walk_nodes(self, &include.children, ctx)
+    }
+    /// `*italic*`, `**bold**` and other styles
+    fn visit_styled(&mut self, styled: &'ast StyleNode, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        walk_nodes(self, &styled.children, ctx)
+    }
+    /// Any link, dispatch to the method of the kind
+    fn visit_link(&mut self, link: &'ast SmartLink, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        walk_link(self, link, ctx)
+    }
+    /// `[path]` or `[text][path]`
+    fn visit_hyper_link(&mut self, link: &'ast HyperLink, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+    /// `[!alt][path]`
+    fn visit_image_link(&mut self, link: &'ast ImageLink, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+    /// `[[link > id]]`
+    fn visit_two_way_link(&mut self, link: &'ast TwoWayLink, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+    /// `[name@example.com]`
+    fn visit_email_link(&mut self, link: &'ast EmailLink, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+    /// `[^tag]` or `[^tag]: text`
+    fn visit_tag_reference(&mut self, tag: &'ast TagReference, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+    /// Resource descriptors
+    fn visit_resource(&mut self, resource: &'ast ResourceDescriptor, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+    /// Commands, the body of xml commands is visited
+    fn visit_command(&mut self, cmd: &'ast Command, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        walk_command(self, cmd, ctx)
+    }
+    /// Inline code and code blocks
+    fn visit_code(&mut self, code: &'ast CodeNode, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+    /// Inline math and math blocks
+    fn visit_math(&mut self, math: &'ast MathNode, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+    /// Texts, escaped characters and line breaks
+    fn visit_text(&mut self, text: &'ast TextSpan, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+    /// `---` and raw html blocks
+    fn visit_delimiter(&mut self, delimiter: &'ast Delimiter, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+    /// Values of interpolations
+    fn visit_value(&mut self, value: &'ast Value, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+}
+
+/// Enter the child, visit it and leave
+pub(super) fn visit_child<'ast, V>(v: &mut V, node: &'ast ASTNode, ctx: &mut VisitContext<'ast>) -> ControlFlow<()>
+where
+    V: Visitor<'ast> + ?Sized,
+{
+    ctx.nodes.push(node);
+    ctx.path.enter();
+    let flow = v.visit_node(node, ctx);
+    ctx.path.leave();
+    ctx.nodes.pop();
+    return flow;
+}
+
+/// Dispatch the node to the method of its kind
+pub fn walk_node<'ast, V>(v: &mut V, node: &'ast ASTNode, ctx: &mut VisitContext<'ast>) -> ControlFlow<()>
+where
+    V: Visitor<'ast> + ?Sized,
+{
+    match &node.value {
+        ASTKind::Statements(children) => v.visit_statements(children, ctx),
+        ASTKind::Paragraph(children) => v.visit_paragraph(children, ctx),
+        ASTKind::Delimiter(inner) => v.visit_delimiter(inner, ctx),
+        ASTKind::Header(inner) => v.visit_header(inner, ctx),
+        ASTKind::TableView(inner) => v.visit_table(inner, ctx),
+        ASTKind::ListView(inner) => v.visit_list(inner, ctx),
+        ASTKind::QuoteNode(inner) => v.visit_quote(inner, ctx),
+        ASTKind::IncludeNode(inner) => v.visit_include(inner, ctx),
+        ASTKind::CodeNode(inner) => v.visit_code(inner, ctx),
+        ASTKind::MathNode(inner) => v.visit_math(inner, ctx),
+        ASTKind::LinkNode(inner) => v.visit_link(inner, ctx),
+        ASTKind::TextSpan(inner) => v.visit_text(inner, ctx),
+        ASTKind::StyledSpan(inner) => v.visit_styled(inner, ctx),
+        ASTKind::Command(inner) => v.visit_command(inner, ctx),
+        ASTKind::Value(inner) => v.visit_value(inner, ctx),
+    }
+}
+
+/// Visit the nodes in order
+pub fn walk_nodes<'ast, V>(v: &mut V, nodes: &'ast [ASTNode], ctx: &mut VisitContext<'ast>) -> ControlFlow<()>
+where
+    V: Visitor<'ast> + ?Sized,
+{
+    for node in nodes {
+        visit_child(v, node, ctx)?
+    }
+    ControlFlow::Continue(())
+}
+
+/// Visit the head and the rows of the table
+pub fn walk_table<'ast, V>(v: &mut V, table: &'ast TableView, ctx: &mut VisitContext<'ast>) -> ControlFlow<()>
+where
+    V: Visitor<'ast> + ?Sized,
+{
+    match table {
+        TableView::SimpleTable { head, terms, .. } => {
+            for (row, cells) in std::iter::once(head).chain(terms).enumerate() {
+                for (column, cell) in cells.iter().enumerate() {
+                    ctx.nodes.push(cell);
+                    ctx.path.enter();
+                    let flow = v.visit_table_cell(cell, row, column, ctx);
+                    ctx.path.leave();
+                    ctx.nodes.pop();
+                    flow?
+                }
+            }
+        }
+    }
+    ControlFlow::Continue(())
+}
+
+/// Visit the items of the list
+pub fn walk_list<'ast, V>(v: &mut V, list: &'ast ListView, ctx: &mut VisitContext<'ast>) -> ControlFlow<()>
+where
+    V: Visitor<'ast> + ?Sized,
+{
+    for item in &list.children {
+        v.visit_list_item(item, ctx)?
+    }
+    ControlFlow::Continue(())
+}
+
+/// Visit the head and the body of the quote
+pub fn walk_quote<'ast, V>(v: &mut V, quote: &'ast QuoteBlock, ctx: &mut VisitContext<'ast>) -> ControlFlow<()>
+where
+    V: Visitor<'ast> + ?Sized,
+{
+    walk_nodes(v, &quote.head, ctx)?;
+    walk_nodes(v, &quote.body, ctx)
+}
+
+/// Dispatch the link to the method of its kind
+pub fn walk_link<'ast, V>(v: &mut V, link: &'ast SmartLink, ctx: &mut VisitContext<'ast>) -> ControlFlow<()>
+where
+    V: Visitor<'ast> + ?Sized,
+{
+    match link {
+        SmartLink::ExternalResource(inner) => v.visit_resource(inner, ctx),
+        SmartLink::EMail(inner) => v.visit_email_link(inner, ctx),
+        SmartLink::Normal(inner) => v.visit_hyper_link(inner, ctx),
+        SmartLink::Image(inner) => v.visit_image_link(inner, ctx),
+        SmartLink::Reference(inner) => v.visit_tag_reference(inner, ctx),
+        SmartLink::TwoWay(inner) => v.visit_two_way_link(inner, ctx),
+    }
+}
+
+/// Visit the body of xml commands, other commands have no children
+pub fn walk_command<'ast, V>(v: &mut V, cmd: &'ast Command, ctx: &mut VisitContext<'ast>) -> ControlFlow<()>
+where
+    V: Visitor<'ast> + ?Sized,
+{
+    match cmd {
+        Command::XML(xml) => walk_nodes(v, &xml.body, ctx),
+        _ => ControlFlow::Continue(()),
+    }
+}
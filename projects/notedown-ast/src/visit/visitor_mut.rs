@@ -0,0 +1,216 @@
+use super::*;
+
+/// Walk over the tree which can change the nodes in place
+///
+/// The parents are borrowed while their children are visited, so only the path is known.
+#[allow(unused_variables)]
+pub trait VisitorMut {
+    /// Any node, dispatch to the method of the kind
+    fn visit_node_mut(&mut self, node: &mut ASTNode, path: &mut VisitPath) -> ControlFlow<()> {
+        walk_node_mut(self, node, path)
+    }
+    /// Top level blocks of the document
+    fn visit_statements_mut(&mut self, nodes: &mut ASTNodes, path: &mut VisitPath) -> ControlFlow<()> {
+        walk_nodes_mut(self, nodes, path)
+    }
+    /// Spans of the paragraph
+    fn visit_paragraph_mut(&mut self, nodes: &mut ASTNodes, path: &mut VisitPath) -> ControlFlow<()> {
+        walk_nodes_mut(self, nodes, path)
+    }
+    /// `# header`, the children are the spans of the text
+    fn visit_header_mut(&mut self, header: &mut Header, path: &mut VisitPath) -> ControlFlow<()> {
+        walk_nodes_mut(self, &mut header.children, path)
+    }
+    /// Tables, the cells are visited row by row
+    fn visit_table_mut(&mut self, table: &mut TableView, path: &mut VisitPath) -> ControlFlow<()> {
+        walk_table_mut(self, table, path)
+    }
+    /// Cell of the table, the row 0 is the head
+    fn visit_table_cell_mut(&mut self, cell: &mut ASTNode, row: usize, column: usize, path: &mut VisitPath) -> ControlFlow<()> {
+        self.visit_node_mut(cell, path)
+    }
+    /// Ordered and orderless lists
+    fn visit_list_mut(&mut self, list: &mut ListView, path: &mut VisitPath) -> ControlFlow<()> {
+        walk_list_mut(self, list, path)
+    }
+    /// Item of the list, the children are the rest after the prefix
+    fn visit_list_item_mut(&mut self, item: &mut ListItem, path: &mut VisitPath) -> ControlFlow<()> {
+        walk_nodes_mut(self, &mut item.rest, path)
+    }
+    /// Quotes, the head is visited before the body
+    fn visit_quote_mut(&mut self, quote: &mut QuoteBlock, path: &mut VisitPath) -> ControlFlow<()> {
+        walk_quote_mut(self, quote, path)
+    }
+    /// Nodes included from another note
+    fn visit_include_mut(&mut self, include: &mut IncludeNode, path: &mut VisitPath) -> ControlFlow<()> {
+        walk_nodes_mut(self, &mut include.children, path)
+    }
+    /// `*italic*`, `**bold**` and other styles
+    fn visit_styled_mut(&mut self, styled: &mut StyleNode, path: &mut VisitPath) -> ControlFlow<()> {
+        walk_nodes_mut(self, &mut styled.children, path)
+    }
+    /// Any link, dispatch to the method of the kind
+    fn visit_link_mut(&mut self, link: &mut SmartLink, path: &mut VisitPath) -> ControlFlow<()> {
+        walk_link_mut(self, link, path)
+    }
+    /// `[path]` or `[text][path]`
+    fn visit_hyper_link_mut(&mut self, link: &mut HyperLink, path: &mut VisitPath) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+    /// `[!alt][path]`
+    fn visit_image_link_mut(&mut self, link: &mut ImageLink, path: &mut VisitPath) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+    /// `[[link > id]]`
+    fn visit_two_way_link_mut(&mut self, link: &mut TwoWayLink, path: &mut VisitPath) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+    /// `[name@example.com]`
+    fn visit_email_link_mut(&mut self, link: &mut EmailLink, path: &mut VisitPath) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+    /// `[^tag]` or `[^tag]: text`
+    fn visit_tag_reference_mut(&mut self, tag: &mut TagReference, path: &mut VisitPath) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+    /// Resource descriptors
+    fn visit_resource_mut(&mut self, resource: &mut ResourceDescriptor, path: &mut VisitPath) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+    /// Commands, the body of xml commands is visited
+    fn visit_command_mut(&mut self, cmd: &mut Command, path: &mut VisitPath) -> ControlFlow<()> {
+        walk_command_mut(self, cmd, path)
+    }
+    /// Inline code and code blocks
+    fn visit_code_mut(&mut self, code: &mut CodeNode, path: &mut VisitPath) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+    /// Inline math and math blocks
+    fn visit_math_mut(&mut self, math: &mut MathNode, path: &mut VisitPath) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+    /// Texts, escaped characters and line breaks
+    fn visit_text_mut(&mut self, text: &mut TextSpan, path: &mut VisitPath) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+    /// `---` and raw html blocks
+    fn visit_delimiter_mut(&mut self, delimiter: &mut Delimiter, path: &mut VisitPath) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+    /// Values of interpolations
+    fn visit_value_mut(&mut self, value: &mut Value, path: &mut VisitPath) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+}
+
+/// Enter the child, visit it and leave
+pub(super) fn visit_child_mut<V>(v: &mut V, node: &mut ASTNode, path: &mut VisitPath) -> ControlFlow<()>
+where
+    V: VisitorMut + ?Sized,
+{
+    path.enter();
+    let flow = v.visit_node_mut(node, path);
+    path.leave();
+    return flow;
+}
+
+/// Dispatch the node to the method of its kind
+pub fn walk_node_mut<V>(v: &mut V, node: &mut ASTNode, path: &mut VisitPath) -> ControlFlow<()>
+where
+    V: VisitorMut + ?Sized,
+{
+    match &mut node.value {
+        ASTKind::Statements(children) => v.visit_statements_mut(children, path),
+        ASTKind::Paragraph(children) => v.visit_paragraph_mut(children, path),
+        ASTKind::Delimiter(inner) => v.visit_delimiter_mut(inner, path),
+        ASTKind::Header(inner) => v.visit_header_mut(inner, path),
+        ASTKind::TableView(inner) => v.visit_table_mut(inner, path),
+        ASTKind::ListView(inner) => v.visit_list_mut(inner, path),
+        ASTKind::QuoteNode(inner) => v.visit_quote_mut(inner, path),
+        ASTKind::IncludeNode(inner) => v.visit_include_mut(inner, path),
+        ASTKind::CodeNode(inner) => v.visit_code_mut(inner, path),
+        ASTKind::MathNode(inner) => v.visit_math_mut(inner, path),
+        ASTKind::LinkNode(inner) => v.visit_link_mut(inner, path),
+        ASTKind::TextSpan(inner) => v.visit_text_mut(inner, path),
+        ASTKind::StyledSpan(inner) => v.visit_styled_mut(inner, path),
+        ASTKind::Command(inner) => v.visit_command_mut(inner, path),
+        ASTKind::Value(inner) => v.visit_value_mut(inner, path),
+    }
+}
+
+/// Visit the nodes in order
+pub fn walk_nodes_mut<V>(v: &mut V, nodes: &mut [ASTNode], path: &mut VisitPath) -> ControlFlow<()>
+where
+    V: VisitorMut + ?Sized,
+{
+    for node in nodes {
+        visit_child_mut(v, node, path)?
+    }
+    ControlFlow::Continue(())
+}
+
+/// Visit the head and the rows of the table
+pub fn walk_table_mut<V>(v: &mut V, table: &mut TableView, path: &mut VisitPath) -> ControlFlow<()>
+where
+    V: VisitorMut + ?Sized,
+{
+    match table {
+        TableView::SimpleTable { head, terms, .. } => {
+            for (row, cells) in std::iter::once(head).chain(terms).enumerate() {
+                for (column, cell) in cells.iter_mut().enumerate() {
+                    path.enter();
+                    let flow = v.visit_table_cell_mut(cell, row, column, path);
+                    path.leave();
+                    flow?
+                }
+            }
+        }
+    }
+    ControlFlow::Continue(())
+}
+
+/// Visit the items of the list
+pub fn walk_list_mut<V>(v: &mut V, list: &mut ListView, path: &mut VisitPath) -> ControlFlow<()>
+where
+    V: VisitorMut + ?Sized,
+{
+    for item in &mut list.children {
+        v.visit_list_item_mut(item, path)?
+    }
+    ControlFlow::Continue(())
+}
+
+/// Visit the head and the body of the quote
+pub fn walk_quote_mut<V>(v: &mut V, quote: &mut QuoteBlock, path: &mut VisitPath) -> ControlFlow<()>
+where
+    V: VisitorMut + ?Sized,
+{
+    walk_nodes_mut(v, &mut quote.head, path)?;
+    walk_nodes_mut(v, &mut quote.body, path)
+}
+
+/// Dispatch the link to the method of its kind
+pub fn walk_link_mut<V>(v: &mut V, link: &mut SmartLink, path: &mut VisitPath) -> ControlFlow<()>
+where
+    V: VisitorMut + ?Sized,
+{
+    match link {
+        SmartLink::ExternalResource(inner) => v.visit_resource_mut(inner, path),
+        SmartLink::EMail(inner) => v.visit_email_link_mut(inner, path),
+        SmartLink::Normal(inner) => v.visit_hyper_link_mut(inner, path),
+        SmartLink::Image(inner) => v.visit_image_link_mut(inner, path),
+        SmartLink::Reference(inner) => v.visit_tag_reference_mut(inner, path),
+        SmartLink::TwoWay(inner) => v.visit_two_way_link_mut(inner, path),
+    }
+}
+
+/// Visit the body of xml commands, other commands have no children
+pub fn walk_command_mut<V>(v: &mut V, cmd: &mut Command, path: &mut VisitPath) -> ControlFlow<()>
+where
+    V: VisitorMut + ?Sized,
+{
+    match cmd {
+        Command::XML(xml) => walk_nodes_mut(v, &mut xml.body, path),
+        _ => ControlFlow::Continue(()),
+    }
+}
@@ -0,0 +1,59 @@
+use notedown_ast::{
+    nodes::Literal,
+    value::{ExpressionKind, InfixOperator, OrderedSet, Scope},
+    Value,
+};
+use rust_decimal::Decimal;
+use std::{cmp::Ordering, str::FromStr};
+
+fn set(items: &[i32]) -> Value {
+    let set: OrderedSet = items.iter().map(|i| Literal { value: Value::integer(*i), range: None }).collect();
+    Value::Set(set)
+}
+
+#[test]
+fn integer_promotion() {
+    assert_eq!((Value::integer(6) / Value::integer(3)).unwrap(), Value::integer(2));
+    assert_eq!((Value::integer(1) / Value::integer(4)).unwrap(), Value::decimal(Decimal::from_str("0.25").unwrap()));
+    assert_eq!((Value::integer(7) % Value::integer(-4)).unwrap(), Value::integer(3));
+    assert_eq!(Value::integer(2).pow(Value::integer(-2)).unwrap(), Value::decimal(Decimal::from_str("0.25").unwrap()));
+    assert!((Value::integer(1) / Value::integer(0)).is_err());
+    assert!((Value::decimal(Decimal::MAX) * Value::integer(2)).is_err());
+}
+
+#[test]
+fn power_overflow() {
+    let huge = Value::integer(u64::MAX);
+    assert!(Value::integer(2).pow(Value::integer(1 << 24)).is_err());
+    assert!(Value::integer(2).pow(huge.to_owned()).is_err());
+    assert_eq!(Value::integer(1).pow(huge.to_owned()).unwrap(), Value::integer(1));
+    assert_eq!(Value::integer(-1).pow(huge.to_owned()).unwrap(), Value::integer(-1));
+    assert_eq!(Value::integer(-1).pow(Value::integer(2)).unwrap(), Value::integer(1));
+    assert_eq!(Value::integer(0).pow(huge).unwrap(), Value::integer(0));
+    assert_eq!(Value::integer(0).pow(Value::integer(0)).unwrap(), Value::integer(1));
+}
+
+#[test]
+fn cross_type_order() {
+    assert_eq!(Value::integer(1).compare(&Value::decimal(Decimal::ONE)), Ordering::Equal);
+    assert_eq!(Value::Null.compare(&Value::Boolean(false)), Ordering::Less);
+    assert_eq!(Value::string("a").compare(&Value::integer(9)), Ordering::Greater);
+}
+
+#[test]
+fn set_operators() {
+    let scope = Scope::default();
+    let union =
+        ExpressionKind::infix(InfixOperator::Union, ExpressionKind::value(set(&[1, 2]), None), ExpressionKind::value(set(&[2, 3]), None));
+    assert_eq!(union.evaluate(&scope).unwrap(), set(&[1, 2, 3]));
+    assert_eq!((set(&[1, 2, 3]) - set(&[2])).unwrap(), set(&[1, 3]));
+    assert_eq!((set(&[1, 2, 3]) & set(&[3, 1])).unwrap(), set(&[1, 3]));
+}
+
+#[test]
+fn short_circuit() {
+    let scope = Scope::default();
+    let undefined = ExpressionKind::symbol("undefined", None);
+    let expr = ExpressionKind::infix(InfixOperator::And, ExpressionKind::value(Value::Boolean(false), None), undefined);
+    assert_eq!(expr.evaluate(&scope).unwrap(), Value::Boolean(false));
+}
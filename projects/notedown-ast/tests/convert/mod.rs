@@ -0,0 +1,103 @@
+use notedown_ast::{
+    command::CommandOptions,
+    nodes::Literal,
+    value::{FromValue, IntoValue, SparseArray},
+    DiagnosticLevel, NoteErrorKind, Value,
+};
+use num::BigUint;
+
+#[derive(Debug, PartialEq, FromValue, IntoValue)]
+enum ImageLayout {
+    Left,
+    Center,
+    #[note(alias = "end")]
+    Right,
+}
+
+#[derive(Debug, PartialEq, FromValue, IntoValue)]
+struct ImageOptions {
+    #[note(alias = "source")]
+    src: String,
+    layout: Option<ImageLayout>,
+    #[note(alias = "caption")]
+    alt: Option<String>,
+    #[note(default)]
+    width: usize,
+}
+
+fn options(kvs: &[(&str, Value, usize)]) -> CommandOptions {
+    let mut options = CommandOptions::default();
+    for (key, value, start) in kvs {
+        let key = Literal { value: key.to_string(), range: Some(*start..start + key.len()) };
+        options.kvs.insert(key, Literal { value: value.to_owned(), range: Some(start + 4..start + 8) });
+    }
+    options
+}
+
+#[test]
+fn derive_struct() {
+    let mut warnings = vec![];
+    let input = options(&[
+        ("source", Value::string("a.png"), 0),
+        ("layout", Value::string("END"), 10),
+        ("caption", Value::string("logo"), 20),
+        ("unknown", Value::Null, 30),
+    ]);
+    let out: ImageOptions = input.read_kvs(&mut warnings).unwrap();
+    let expected = ImageOptions { src: "a.png".to_string(), layout: Some(ImageLayout::Right), alt: Some("logo".to_string()), width: 0 };
+    assert_eq!(out, expected);
+    assert_eq!(warnings.len(), 1);
+    assert!(matches!(warnings[0].level, DiagnosticLevel::Warning));
+    assert_eq!(warnings[0].range, Some(30..37));
+    let value = expected.into_value();
+    let back = ImageOptions::from_value(Literal { value, range: None }, &mut warnings).unwrap();
+    assert_eq!(back.layout, Some(ImageLayout::Right));
+}
+
+#[test]
+fn ranged_type_mismatch() {
+    let mut warnings = vec![];
+    let input = options(&[("src", Value::string("a.png"), 0), ("width", Value::Boolean(true), 10)]);
+    let error = input.read_kvs::<ImageOptions>(&mut warnings).unwrap_err();
+    assert!(matches!(&*error.kind, NoteErrorKind::TypeMismatch(s) if s == "Expect `Integer`, found `Boolean`"));
+    assert_eq!(error.range, Some(14..18));
+    let error = options(&[]).read_kvs::<ImageOptions>(&mut warnings).unwrap_err();
+    assert!(matches!(&*error.kind, NoteErrorKind::TypeMismatch(s) if s.contains("Missing key `src`")));
+}
+
+#[test]
+fn ranged_array_item() {
+    let mut warnings = vec![];
+    let items = vec![Literal { value: Value::integer(1), range: Some(1..2) }, Literal { value: Value::Boolean(true), range: Some(4..8) }];
+    let array = Literal { value: Value::Array(Box::new(items.into())), range: Some(0..9) };
+    let error = Vec::<usize>::from_value(array, &mut warnings).unwrap_err();
+    assert!(matches!(&*error.kind, NoteErrorKind::TypeMismatch(s) if s == "Expect `Integer`, found `Boolean`"));
+    assert_eq!(error.range, Some(4..8));
+}
+
+#[test]
+fn sparse_array_to_vec() {
+    let mut warnings = vec![];
+    let mut array = SparseArray::default();
+    array.insert(BigUint::from(99999999999u64), Literal { value: Value::integer(1), range: Some(11..12) });
+    let array = Literal { value: Value::Array(Box::new(array)), range: Some(0..13) };
+    let error = Vec::<usize>::from_value(array, &mut warnings).unwrap_err();
+    assert!(matches!(&*error.kind, NoteErrorKind::TypeMismatch(s) if s.contains("holes")));
+    assert_eq!(error.range, Some(0..13));
+}
+
+#[test]
+fn image_link_options() {
+    use notedown_ast::nodes::{ImageLayout as Layout, ImageLink};
+    let mut warnings = vec![];
+    let mut image = ImageLink::default();
+    let input = options(&[("caption", Value::string("logo"), 0), ("layout", Value::integer(-1), 10), ("width", Value::integer(3), 20)]);
+    image.set_options(input, &mut warnings).unwrap();
+    assert_eq!(image.description, Some("logo".to_string()));
+    assert_eq!(image.layout, Some(Layout::Left));
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].range, Some(20..25));
+    let error = image.set_options(options(&[("layout", Value::string("top"), 0)]), &mut warnings).unwrap_err();
+    assert!(matches!(&*error.kind, NoteErrorKind::TypeMismatch(s) if s.contains("Unknown layout `top`")));
+    assert_eq!(error.range, Some(4..8));
+}
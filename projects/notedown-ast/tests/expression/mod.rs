@@ -0,0 +1,36 @@
+use notedown_ast::{
+    nodes::Literal,
+    value::{ExpressionKind, InfixOperator, Scope},
+    NoteErrorKind, Value,
+};
+
+#[test]
+fn scoped_lookup() {
+    let mut scope = Scope::default();
+    scope.define("a", Literal { value: Value::integer(1), range: None });
+    scope.push();
+    scope.define("a", Literal { value: Value::integer(2), range: None });
+    assert_eq!(scope.get("a").map(|f| f.value.to_owned()), Some(Value::integer(2)));
+    scope.pop();
+    assert_eq!(scope.get("a").map(|f| f.value.to_owned()), Some(Value::integer(1)));
+    assert!(!scope.contains("b"));
+}
+
+#[test]
+fn type_mismatch_range() {
+    let scope = Scope::default();
+    let lhs = ExpressionKind::value(Value::integer(1), Some(0..1));
+    let rhs = ExpressionKind::value(Value::string("a"), Some(4..7));
+    let expr = ExpressionKind::infix(InfixOperator::Plus, lhs, rhs);
+    let error = expr.evaluate(&scope).unwrap_err();
+    assert!(matches!(*error.kind, NoteErrorKind::TypeMismatch(_)));
+    assert_eq!(error.range, Some(0..7));
+}
+
+#[test]
+fn bare_word_argument() {
+    let scope = Scope::default();
+    let expr = ExpressionKind::symbol("left", Some(0..4));
+    assert_eq!(expr.evaluate_argument(&scope).unwrap(), Value::string("left"));
+    assert!(expr.evaluate(&scope).is_err());
+}
@@ -1,4 +1,12 @@
+mod arith;
+mod convert;
+mod expression;
 mod keep_size;
+mod query;
+mod sparse_array;
+#[cfg(feature = "lsp")]
+mod text_index;
+mod visit;
 #[test]
 fn ready() {
     println!("it, works!")
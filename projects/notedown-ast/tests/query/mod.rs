@@ -0,0 +1,93 @@
+use notedown_ast::{
+    command::{CommandOptions, CommandPattern, XMLCommand},
+    nodes::Literal,
+    query::Selector,
+    value::IntoValue,
+    ASTKind, ASTNode, Command, Value,
+};
+
+fn text(s: &str) -> ASTNode {
+    ASTKind::text(s, None)
+}
+
+fn header(s: &str, level: u8, start: usize) -> ASTNode {
+    ASTKind::header(vec![text(s)], level, Some(start..start + 1))
+}
+
+fn code(language: &str, start: usize) -> ASTNode {
+    ASTKind::code_block("", language, Some(start..start + 1))
+}
+
+fn image(width: i32, start: usize) -> ASTNode {
+    let mut options = CommandOptions::default();
+    options.args.push(Literal { value: Value::string("a.png"), range: None });
+    options.kvs.insert(Literal { value: String::from("width"), range: None }, Literal { value: width.into_value(), range: None });
+    Command::XML(XMLCommand::self_close(String::from("img"), CommandPattern::default(), options, start, start + 1))
+        .into_node(Some(start..start + 1))
+}
+
+fn document() -> ASTNode {
+    ASTKind::statements(
+        vec![
+            header("Readme", 1, 0),
+            code("rust", 10),
+            header("Installation", 2, 20),
+            code("rust", 30),
+            code("bash", 40),
+            header("Details", 3, 50),
+            ASTKind::paragraph(
+                vec![ASTKind::image_link("b.png", Some(61..62)), ASTKind::image_link_alt("c.png", "c", Some(63..64))],
+                Some(60..65),
+            ),
+            code("rust", 70),
+            header("Usage", 2, 80),
+            code("rust", 90),
+            image(100, 100),
+            image(50, 110),
+        ],
+        Some(0..120),
+    )
+}
+
+fn starts(doc: &ASTNode, selector: &str) -> Vec<usize> {
+    doc.select(selector).unwrap().iter().map(|m| m.range.as_ref().unwrap().start).collect()
+}
+
+#[test]
+fn select_kind() {
+    let doc = document();
+    assert_eq!(starts(&doc, "header[level=2]"), [20, 80]);
+    assert_eq!(starts(&doc, "code[language=rust]"), [10, 30, 70, 90]);
+    assert_eq!(starts(&doc, "header[text^=Inst], code[lang!=rust]"), [20, 40]);
+    assert_eq!(starts(&doc, "link.image:not([alt])"), [61]);
+    assert_eq!(starts(&doc, "link.image[src$=png]"), [61, 63]);
+}
+
+#[test]
+fn select_section() {
+    let doc = document();
+    assert_eq!(starts(&doc, "header[text=Installation] code[language=rust]"), [30, 70]);
+    assert_eq!(starts(&doc, "header[level=3] link.image"), [61, 63]);
+    assert_eq!(starts(&doc, "header[text=Readme] header"), [20, 50, 80]);
+    assert_eq!(starts(&doc, "header[text=Usage] > code"), Vec::<usize>::new());
+    assert_eq!(starts(&doc, "paragraph > link"), [61, 63]);
+    assert_eq!(starts(&doc, "document > code"), [10, 30, 40, 70, 90]);
+}
+
+#[test]
+fn select_command() {
+    let doc = document();
+    assert_eq!(starts(&doc, "command[name=img]"), [100, 110]);
+    assert_eq!(starts(&doc, "command.xml[width=100]"), [100]);
+    assert_eq!(starts(&doc, "command[1=\"a.png\"][width]"), [100, 110]);
+    assert_eq!(starts(&doc, "command[height]"), Vec::<usize>::new());
+}
+
+#[test]
+fn selector_errors() {
+    assert!(Selector::parse("header[level=2").is_err());
+    assert!(Selector::parse("heading").is_err());
+    assert!(Selector::parse("code:first").is_err());
+    assert!(Selector::parse("header,").is_err());
+    assert!(Selector::parse("  header  >  code[ lang = 'rust' ] ,*").is_ok());
+}
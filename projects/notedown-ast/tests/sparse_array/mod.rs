@@ -0,0 +1,102 @@
+use notedown_ast::{
+    nodes::Literal,
+    value::{SparseArray, MAX_DENSE_HOLES},
+    Value,
+};
+use num::{BigInt, BigUint};
+use proptest::{collection::btree_map, prelude::*};
+use std::{collections::BTreeMap, convert::TryFrom};
+
+fn build(entries: &BTreeMap<u32, i64>) -> SparseArray {
+    let mut out = SparseArray::default();
+    for (k, v) in entries {
+        out.insert(BigUint::from(*k), Literal { value: Value::integer(*v), range: None });
+    }
+    out
+}
+
+proptest! {
+    #[test]
+    fn dense_iteration(entries in btree_map(1u32..64, any::<i64>(), 0..16)) {
+        let array = build(&entries);
+        let last = entries.keys().last().copied().unwrap_or(0);
+        let dense: Vec<_> = array.iter().cloned().collect();
+        prop_assert_eq!(dense.len(), last as usize);
+        for (i, value) in dense.iter().enumerate() {
+            let expected = entries.get(&(i as u32 + 1)).map(|v| Value::integer(*v)).unwrap_or_default();
+            prop_assert_eq!(value, &expected);
+        }
+        let owned: Vec<_> = array.clone().into_iter().collect();
+        prop_assert_eq!(owned, dense);
+        prop_assert_eq!(array.iter_sparse().count(), entries.len());
+    }
+
+    #[test]
+    fn huge_index(first in any::<i64>(), index in 1_000_000_000_000u64..u64::MAX, value in any::<i64>()) {
+        let mut array = SparseArray::default();
+        array.push(Literal { value: Value::integer(first), range: None });
+        array.insert(BigUint::from(index), Literal { value: Value::integer(value), range: None });
+        prop_assert_eq!(array.is_dense(), false);
+        // holes after the limit are skipped, the entries are still reached
+        let dense: Vec<_> = array.iter().cloned().collect();
+        prop_assert_eq!(dense.len(), MAX_DENSE_HOLES + 2);
+        prop_assert_eq!(dense.first(), Some(&Value::integer(first)));
+        prop_assert_eq!(dense.last(), Some(&Value::integer(value)));
+        let owned: Vec<_> = array.into_literals().map(|f| f.value).collect();
+        prop_assert_eq!(owned, dense);
+    }
+
+    #[test]
+    fn vector_round_trip(items in proptest::collection::vec(any::<i64>(), 0..32)) {
+        let array: SparseArray = items.iter().map(|v| Value::integer(*v)).collect();
+        prop_assert_eq!(array.len(), items.len());
+        prop_assert_eq!(Vec::<i64>::try_from(array).unwrap(), items);
+    }
+
+    #[test]
+    fn compact_keeps_order(entries in btree_map(1u32..64, any::<i64>(), 0..16)) {
+        let mut array = build(&entries);
+        array.compact();
+        prop_assert_eq!(array.last_index(), BigUint::from(entries.len()));
+        let values: Vec<_> = array.values().cloned().collect();
+        let expected: Vec<_> = entries.values().map(|v| Value::integer(*v)).collect();
+        prop_assert_eq!(values, expected);
+    }
+
+    #[test]
+    fn negative_index(entries in btree_map(1u32..64, any::<i64>(), 1..16), k in 1u32..64) {
+        let array = build(&entries);
+        let last = *entries.keys().last().unwrap();
+        let expected = if k <= last { Some(entries.get(&(last - k + 1)).map(|v| Value::integer(*v)).unwrap_or_default()) } else { None };
+        prop_assert_eq!(array.get_signed(&-BigInt::from(k)), expected.clone());
+        prop_assert_eq!(Value::Array(Box::new(array)).get_index_by(&Value::integer(-(k as i64))).ok(), expected);
+    }
+}
+
+#[test]
+fn remove_shifts() {
+    let mut array = SparseArray::from(vec![Value::integer(1), Value::integer(2), Value::integer(3)]);
+    assert_eq!(array.remove(&BigUint::from(1u8)).map(|f| f.value), Some(Value::integer(1)));
+    assert_eq!(array.get(&BigUint::from(1u8)), Some(Value::integer(2)));
+    assert_eq!(array.last_index(), BigUint::from(2u8));
+}
+
+#[test]
+fn holes_in_range() {
+    let mut array = SparseArray::new(Value::integer(0));
+    array.insert(BigUint::from(1u8), Literal { value: Value::integer(1), range: None });
+    array.insert(BigUint::from(3u8), Literal { value: Value::integer(3), range: None });
+    // holes inside the range are filled with the default value, like the dense iteration
+    assert_eq!(array.get(&BigUint::from(2u8)), None);
+    assert_eq!(array.get_signed(&BigInt::from(2)), Some(Value::integer(0)));
+    assert_eq!(array.get_signed(&BigInt::from(-2)), Some(Value::integer(0)));
+    assert_eq!(array.resolve_index(&BigInt::from(-2)), Some(BigUint::from(2u8)));
+    // out of range
+    assert_eq!(array.get_signed(&BigInt::from(0)), None);
+    assert_eq!(array.get_signed(&BigInt::from(4)), None);
+    assert_eq!(array.get_signed(&BigInt::from(-4)), None);
+    let value = Value::Array(Box::new(array));
+    assert_eq!(value.get_index_by(&Value::integer(2)).unwrap(), Value::integer(0));
+    assert!(value.get_index_by(&Value::integer(4)).unwrap_err().to_string().contains("out of range"));
+    assert!(value.get_index_by(&Value::integer(0)).unwrap_err().to_string().contains("starts from 1"));
+}
@@ -0,0 +1,15 @@
+use notedown_ast::utils::{LSPPosition, TextIndex};
+
+#[test]
+fn lsp_position() {
+    let index = TextIndex::new("# 标题\r\n😀 text\n");
+    assert_eq!(index.get_lsp_position(0), LSPPosition { line: 0, character: 0 });
+    // `标` is 3 bytes but 1 utf-16 unit
+    assert_eq!(index.get_lsp_position(5), LSPPosition { line: 0, character: 3 });
+    assert_eq!(index.get_lsp_position(10), LSPPosition { line: 1, character: 0 });
+    // `😀` is 4 bytes and 2 utf-16 units, offsets inside the char go back to its start
+    assert_eq!(index.get_lsp_position(12), LSPPosition { line: 1, character: 0 });
+    assert_eq!(index.get_lsp_position(14), LSPPosition { line: 1, character: 2 });
+    assert_eq!(index.get_lsp_position(99), LSPPosition { line: 2, character: 0 });
+    assert_eq!(index.get_nth_line(1), Some("😀 text"));
+}
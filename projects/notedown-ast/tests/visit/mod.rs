@@ -0,0 +1,166 @@
+use notedown_ast::{
+    nodes::{Header, ListItem, QuoteBlock, TextSpan},
+    traits::PlainText,
+    visit::{fold_nodes, ControlFlow, Fold, VisitContext, VisitPath, Visitor, VisitorMut},
+    ASTKind, ASTNode, ASTNodes,
+};
+
+fn text(s: &str) -> ASTNode {
+    ASTKind::text(s, None)
+}
+
+fn document() -> ASTNode {
+    ASTKind::statements(
+        vec![
+            ASTKind::header(vec![text("Intro")], 1, None),
+            ASTKind::paragraph(vec![text("a "), ASTKind::strong(vec![text("b")], None)], None),
+            ASTKind::quote(vec![ASTKind::paragraph(vec![text("c")], None)], None),
+            ASTKind::orderless_list(vec![ListItem::from(vec![text("d")]), ListItem::from(vec![text("e")])], None),
+        ],
+        None,
+    )
+}
+
+#[derive(Default)]
+struct Texts {
+    found: Vec<(String, Vec<usize>, bool)>,
+    stop_at: Option<&'static str>,
+}
+
+impl<'ast> Visitor<'ast> for Texts {
+    fn visit_text(&mut self, text: &'ast TextSpan, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        let s = match text {
+            TextSpan::Normal(s) => s.to_owned(),
+            _ => return ControlFlow::Continue(()),
+        };
+        let in_paragraph = ctx.parent().map(|p| matches!(p.value, ASTKind::Paragraph(_))).unwrap_or_default();
+        self.found.push((s.clone(), ctx.path().indexes().to_vec(), in_paragraph));
+        match self.stop_at {
+            Some(stop) if stop == s => ControlFlow::Break(()),
+            _ => ControlFlow::Continue(()),
+        }
+    }
+}
+
+#[test]
+fn visit_with_context() {
+    let doc = document();
+    let mut texts = Texts::default();
+    assert_eq!(doc.accept(&mut texts), ControlFlow::Continue(()));
+    let names: Vec<_> = texts.found.iter().map(|(s, _, _)| s.as_str()).collect();
+    assert_eq!(names, ["Intro", "a ", "b", "c", "d", "e"]);
+    assert_eq!(texts.found[0].1, [0, 0]);
+    assert_eq!(texts.found[2].1, [1, 1, 0]);
+    assert_eq!(texts.found[3].1, [2, 0, 0]);
+    assert_eq!(texts.found[5].1, [3, 1]);
+    assert!(!texts.found[0].2);
+    assert!(texts.found[1].2);
+    assert!(!texts.found[2].2);
+}
+
+#[test]
+fn visit_early_exit() {
+    let doc = document();
+    let mut texts = Texts { stop_at: Some("b"), ..Texts::default() };
+    assert_eq!(doc.accept(&mut texts), ControlFlow::Break(()));
+    assert_eq!(texts.found.len(), 3);
+}
+
+/// Skip the quotes but count the depth of the rest
+#[derive(Default)]
+struct SkipQuote {
+    quotes: usize,
+    depth: usize,
+}
+
+impl<'ast> Visitor<'ast> for SkipQuote {
+    fn visit_quote(&mut self, _: &'ast QuoteBlock, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.quotes += 1;
+        ControlFlow::Continue(())
+    }
+    fn visit_text(&mut self, _: &'ast TextSpan, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.depth = self.depth.max(ctx.ancestors().count());
+        ControlFlow::Continue(())
+    }
+}
+
+#[test]
+fn visit_skip_children() {
+    let doc = document();
+    let mut skip = SkipQuote::default();
+    let _ = doc.accept(&mut skip);
+    assert_eq!(skip.quotes, 1);
+    assert_eq!(skip.depth, 3);
+}
+
+struct Upper;
+
+impl VisitorMut for Upper {
+    fn visit_header_mut(&mut self, header: &mut Header, _: &mut VisitPath) -> ControlFlow<()> {
+        header.id = Some(header.plain_text().to_lowercase());
+        ControlFlow::Continue(())
+    }
+    fn visit_text_mut(&mut self, text: &mut TextSpan, _: &mut VisitPath) -> ControlFlow<()> {
+        if let TextSpan::Normal(s) = text {
+            *s = s.to_uppercase()
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+#[test]
+fn visit_mut() {
+    let mut doc = document();
+    let _ = doc.accept_mut(&mut Upper);
+    assert_eq!(doc.plain_text(), "Intro A B C D E");
+    match &doc.value {
+        ASTKind::Statements(terms) => match &terms[0].value {
+            ASTKind::Header(header) => assert_eq!(header.id.as_deref(), Some("intro")),
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    }
+}
+
+/// Remove the quotes and split the texts into words
+struct Words;
+
+impl Fold for Words {
+    fn fold_nodes(&mut self, nodes: ASTNodes) -> ASTNodes {
+        let nodes = nodes.into_iter().filter(|node| !matches!(node.value, ASTKind::QuoteNode(_))).collect();
+        let mut out = vec![];
+        for node in fold_nodes(self, nodes) {
+            match &node.value {
+                ASTKind::TextSpan(span) => match &**span {
+                    TextSpan::Normal(s) => out.extend(s.split_whitespace().map(text)),
+                    _ => out.push(node),
+                },
+                _ => out.push(node),
+            }
+        }
+        out
+    }
+}
+
+#[test]
+fn fold() {
+    let doc = ASTKind::statements(
+        vec![
+            ASTKind::paragraph(vec![text("one two "), ASTKind::emphasis(vec![text("three four")], None)], None),
+            ASTKind::quote(vec![text("five")], None),
+        ],
+        None,
+    );
+    let doc = doc.fold_with(&mut Words);
+    assert_eq!(doc.plain_text(), "onetwothreefour");
+    match &doc.value {
+        ASTKind::Statements(terms) => {
+            assert_eq!(terms.len(), 1);
+            match &terms[0].value {
+                ASTKind::Paragraph(spans) => assert_eq!(spans.len(), 3),
+                _ => unreachable!(),
+            }
+        }
+        _ => unreachable!(),
+    }
+}
@@ -0,0 +1,54 @@
+use crate::{config::Project, render::OutputFormat, report::is_error, workspace::Workspace};
+use clap::Args;
+use notedown_ast::Result;
+use std::{fs, path::PathBuf};
+
+#[derive(Args)]
+pub struct BuildArgs {
+    /// Format of the output files, `build.format` of the config by default
+    #[clap(long, value_enum)]
+    format: Option<OutputFormat>,
+    /// Output directory, `build.output` of the config by default
+    #[clap(short, long)]
+    output: Option<PathBuf>,
+}
+
+impl BuildArgs {
+    /// Notes keep their relative paths in the output directory, notes with errors are skipped
+    ///
+    /// The `\include` commands are replaced by the included notes.
+    pub async fn run(self, project: &Project) -> Result<bool> {
+        let mut workspace = Workspace::load(project).await?;
+        let format = self.format.unwrap_or(project.config.build.format);
+        let output = match self.output {
+            Some(s) => s,
+            None => project.root.join(&project.config.build.output),
+        };
+        let mut failed = 0;
+        for url in workspace.fs.get_urls() {
+            let (ast, includes) = workspace.expand(&url).await;
+            let file = match workspace.fs.get_file(&url) {
+                Some(s) => s,
+                None => continue,
+            };
+            let path = workspace.relative_path(&url);
+            let errors: Vec<_> = file.get_meta().errors().iter().chain(includes.iter()).filter(|e| is_error(e)).collect();
+            if !errors.is_empty() {
+                errors.iter().for_each(|e| eprintln!("{}", workspace.render_error(e, &url)));
+                failed += 1;
+                continue;
+            }
+            let target = output.join(&path).with_extension(format.extension());
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?
+            }
+            let title = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+            fs::write(&target, format.render(&ast, &title))?;
+            println!("{} -> {}", path.display(), target.display())
+        }
+        if failed > 0 {
+            eprintln!("{} notes are skipped because of errors", failed)
+        }
+        Ok(failed == 0)
+    }
+}
@@ -0,0 +1,114 @@
+use crate::{
+    config::Project,
+    report::{is_error, render_lint},
+    workspace::Workspace,
+};
+use clap::Args;
+use notedown_ast::{
+    utils::lsp_types::{DiagnosticSeverity, Url},
+    ASTNode, DiagnosticLevel, NoteError, Result,
+};
+use notedown_rt::{check_commands, check_lints, flatten, LinkTarget, Lint, LintConfig, WorkspaceIndex};
+use std::path::PathBuf;
+
+#[derive(Args)]
+pub struct CheckArgs {
+    /// Notes to check, all the notes of the workspace by default
+    files: Vec<PathBuf>,
+    /// Fail on warnings too
+    #[clap(long)]
+    deny_warnings: bool,
+}
+
+impl CheckArgs {
+    pub async fn run(self, project: &Project) -> Result<bool> {
+        let mut workspace = Workspace::load(project).await?;
+        let index = workspace.fs.workspace_index();
+        let urls = workspace.select(&self.files)?;
+        let (mut errors, mut warnings) = (0, 0);
+        for url in &urls {
+            let (_, includes) = workspace.expand(url).await;
+            let file = match workspace.fs.get_file(url) {
+                Some(s) => s,
+                None => continue,
+            };
+            let links = check_links(&workspace, &index, url);
+            for error in file.get_meta().errors().iter().chain(includes.iter()).chain(links.iter()) {
+                match is_error(error) {
+                    true => errors += 1,
+                    false => warnings += 1,
+                }
+                eprintln!("{}", workspace.render_error(error, url))
+            }
+            let text = file.get_text();
+            for lint in check_document(&text, file.get_ast(), &project.config.lint) {
+                match lint.severity {
+                    DiagnosticSeverity::ERROR => errors += 1,
+                    DiagnosticSeverity::WARNING => warnings += 1,
+                    _ => {}
+                }
+                eprintln!("{}", render_lint(&lint, &workspace.relative_path(url), &text))
+            }
+        }
+        eprintln!("checked {} notes: {} errors, {} warnings", urls.len(), errors, warnings);
+        Ok(errors == 0 && !(self.deny_warnings && warnings > 0))
+    }
+}
+
+/// Unknown commands, options of the wrong type and the writing lints, the same as the language server
+fn check_document(text: &str, ast: &ASTNode, config: &LintConfig) -> Vec<Lint> {
+    let mut nodes = vec![];
+    flatten(ast, &mut nodes);
+    let mut out = check_commands(&nodes);
+    out.extend(check_lints(text, ast, &nodes, config));
+    return out;
+}
+
+/// Links to missing notes, headers, footnotes and local files
+fn check_links(workspace: &Workspace, index: &WorkspaceIndex, url: &Url) -> Vec<NoteError> {
+    let document = match index.get(url) {
+        Some(s) => s,
+        None => return vec![],
+    };
+    let mut out = vec![];
+    for link in &document.links {
+        let message = match &link.target {
+            LinkTarget::Note { url: target, id } => match (index.get(target), id) {
+                (Some(note), Some(id)) if !note.headers.iter().any(|h| &h.id == id) => {
+                    Some(format!("cannot find header `#{}` in `{}`", id, workspace.relative_path(target).display()))
+                }
+                // notes outside the workspace are not indexed
+                (None, _) if !exists(target) => Some(format!("cannot find note `{}`", workspace.relative_path(target).display())),
+                _ => None,
+            },
+            LinkTarget::External(target) if target.scheme() == "file" && !exists(target) => {
+                Some(format!("cannot find file `{}`", workspace.relative_path(target).display()))
+            }
+            LinkTarget::Footnote(tag) if !document.footnotes.iter().any(|f| &f.tag == tag) => {
+                Some(format!("footnote `[^{}]` is not defined", tag))
+            }
+            _ => None,
+        };
+        if let Some(message) = message {
+            let mut error = NoteError::runtime_error(message);
+            error.level = DiagnosticLevel::Warning;
+            error.set_url(url.to_owned());
+            error.set_range(link.range.start, link.range.end);
+            out.push(error)
+        }
+    }
+    return out;
+}
+
+#[inline]
+fn exists(url: &Url) -> bool {
+    url.to_file_path().is_ok_and(|path| path.exists())
+}
+
+#[test]
+fn shared_checks() {
+    let text = "\\unknown text\n\n\\toc(max_depth = \"a\")\n\nthe the end";
+    let (ast, _) = crate::workspace::parse_text(text, Default::default());
+    let lints = check_document(text, &ast, &LintConfig::default());
+    assert_eq!(lints.iter().map(|lint| lint.rule).collect::<Vec<_>>(), ["unknown-command", "type-mismatch", "repeated-word"]);
+}
@@ -0,0 +1,49 @@
+use crate::{config::Project, render::OutputFormat, report::is_error, workspace::Workspace};
+use clap::Args;
+use notedown_ast::{utils::lsp_types::Url, NoteError, Result};
+use std::{fs, path::PathBuf};
+
+#[derive(Args)]
+pub struct ConvertArgs {
+    /// The note to convert
+    input: PathBuf,
+    /// Output file, print to stdout if missing
+    #[clap(short, long)]
+    output: Option<PathBuf>,
+    /// Format of the output, guessed from the extension of the output file, html by default
+    #[clap(long, value_enum)]
+    format: Option<OutputFormat>,
+}
+
+impl ConvertArgs {
+    /// The notes included by the input are resolved from its folder, the project only gives the settings
+    pub async fn run(self, project: &Project) -> Result<bool> {
+        let format = match (self.format, self.output.as_ref().and_then(|p| p.extension())) {
+            (Some(format), _) => format,
+            (None, Some(extension)) => OutputFormat::from_extension(&extension.to_string_lossy()).ok_or_else(|| {
+                NoteError::runtime_error(format!("unknown format of `{}`, specify it with `--format`", extension.to_string_lossy()))
+            })?,
+            (None, None) => OutputFormat::Html,
+        };
+        let mut workspace = Workspace::single(&self.input, project.config.workspace.slug).await?;
+        let url = Url::from_file_path(self.input.canonicalize()?)?;
+        let (ast, includes) = workspace.expand(&url).await;
+        let mut failed = false;
+        if let Some(file) = workspace.fs.get_file(&url) {
+            for error in file.get_meta().errors().iter().chain(includes.iter()) {
+                failed |= is_error(error);
+                eprintln!("{}", workspace.render_error(error, &url))
+            }
+        }
+        if failed {
+            return Ok(false);
+        }
+        let title = self.input.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        let rendered = format.render(&ast, &title);
+        match &self.output {
+            Some(path) => fs::write(path, rendered)?,
+            None => print!("{}", rendered),
+        }
+        Ok(true)
+    }
+}
@@ -0,0 +1,130 @@
+use crate::{config::Project, workspace::Workspace};
+use clap::Args;
+use notedown_ast::Result;
+use std::{fs, path::PathBuf};
+
+#[derive(Args)]
+pub struct FmtArgs {
+    /// Notes to format, all the notes of the workspace by default
+    files: Vec<PathBuf>,
+    /// Print the notes need formatting instead of writing them, exit with 1 if any
+    #[clap(long)]
+    check: bool,
+}
+
+impl FmtArgs {
+    pub async fn run(self, project: &Project) -> Result<bool> {
+        let workspace = Workspace::load(project).await?;
+        let mut changed = 0;
+        for url in workspace.select(&self.files)? {
+            let text = workspace.fs.get_text(&url).unwrap_or_default();
+            let formatted = format_text(&text);
+            if formatted == text {
+                continue;
+            }
+            changed += 1;
+            let path = workspace.relative_path(&url);
+            match self.check {
+                true => println!("{}", path.display()),
+                false => {
+                    fs::write(url.to_file_path()?, formatted)?;
+                    println!("formatted {}", path.display())
+                }
+            }
+        }
+        Ok(!(self.check && changed > 0))
+    }
+}
+
+/// Normalize the layout of the note without changing its meaning
+///
+/// - line endings are `\n`, the file ends with exactly one newline
+/// - trailing whitespaces are removed, runs of blank lines are collapsed into one
+/// - headers are `# title`, with a blank line before and after
+///
+/// Code blocks and math blocks are kept as is.
+pub fn format_text(text: &str) -> String {
+    let mut lines: Vec<String> = vec![];
+    // the closing marker of the block
+    let mut fence: Option<String> = None;
+    for line in text.replace("\r\n", "\n").split('\n') {
+        if let Some(close) = &fence {
+            if is_fence_end(line.trim(), close) {
+                fence = None;
+                lines.push(line.trim_end().to_string())
+            }
+            else {
+                lines.push(line.to_string())
+            }
+            continue;
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            push_blank(&mut lines);
+        }
+        else if let Some(header) = format_header(line) {
+            push_blank(&mut lines);
+            lines.push(header);
+            push_blank(&mut lines);
+        }
+        else {
+            fence = fence_start(line);
+            lines.push(line.to_string())
+        }
+    }
+    while lines.last().is_some_and(|s| s.is_empty()) {
+        lines.pop();
+    }
+    match lines.is_empty() {
+        true => String::new(),
+        false => lines.join("\n") + "\n",
+    }
+}
+
+/// Blank line between blocks, none at the start of the file
+fn push_blank(lines: &mut Vec<String>) {
+    if lines.last().is_some_and(|s| !s.is_empty()) {
+        lines.push(String::new())
+    }
+}
+
+/// `#   title` to `# title`
+fn format_header(line: &str) -> Option<String> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    let rest = &line[level..];
+    if !(1..=6).contains(&level) || !rest.starts_with(char::is_whitespace) || rest.trim().is_empty() {
+        return None;
+    }
+    Some(format!("{} {}", &line[..level], rest.trim_start()))
+}
+
+/// ```` ``` ```` with 3 or more backticks, or `$$` alone on the line
+fn fence_start(line: &str) -> Option<String> {
+    let line = line.trim_start();
+    let ticks = line.chars().take_while(|c| *c == '`').count();
+    if ticks >= 3 {
+        return Some("`".repeat(ticks));
+    }
+    match line == "$$" {
+        true => Some(String::from("$$")),
+        false => None,
+    }
+}
+
+#[inline]
+fn is_fence_end(line: &str, close: &str) -> bool {
+    match close {
+        "$$" => line == "$$",
+        _ => line.len() >= close.len() && line.chars().all(|c| c == '`'),
+    }
+}
+
+#[test]
+fn format_note() {
+    let text = "\r\n#   Title  \r\ntext  \n\n\n\n##  Section\n```rust\nfn main() {  \n\n\n}\n```  \n$$\nx  \n$$\n\n";
+    let formatted = "# Title\n\ntext\n\n## Section\n\n```rust\nfn main() {  \n\n\n}\n```\n$$\nx  \n$$\n";
+    assert_eq!(format_text(text), formatted);
+    assert_eq!(format_text(formatted), formatted);
+    assert_eq!(format_text("#hashtag\n\n"), "#hashtag\n");
+    assert_eq!(format_text("\n\n"), "");
+}
@@ -0,0 +1,45 @@
+mod build;
+mod check;
+mod convert;
+mod fmt;
+mod query;
+mod toc;
+
+pub use self::{build::BuildArgs, check::CheckArgs, convert::ConvertArgs, fmt::FmtArgs, query::QueryArgs, toc::TocArgs};
+
+use crate::config::Project;
+use clap::Subcommand;
+use notedown_ast::Result;
+use std::path::Path;
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Parse the notes and check the links, exit with 1 if any error is found
+    Check(CheckArgs),
+    /// Format the notes in place
+    Fmt(FmtArgs),
+    /// Render all the notes into the output directory
+    Build(BuildArgs),
+    /// Convert a single note to another format
+    Convert(ConvertArgs),
+    /// Print the table of contents
+    Toc(TocArgs),
+    /// Find nodes with css like selectors, `header[level=2] code[lang=rust]`
+    Query(QueryArgs),
+}
+
+impl Command {
+    /// Run the command in the project found from the root, `Ok(false)` if the command failed
+    ///
+    /// `convert` works on a single file, and only reads the settings of the project.
+    pub async fn run(self, root: &Path) -> Result<bool> {
+        match self {
+            Self::Check(args) => args.run(&Project::discover(root)?).await,
+            Self::Fmt(args) => args.run(&Project::discover(root)?).await,
+            Self::Build(args) => args.run(&Project::discover(root)?).await,
+            Self::Convert(args) => args.run(&Project::discover(root)?).await,
+            Self::Toc(args) => args.run(&Project::discover(root)?).await,
+            Self::Query(args) => args.run(&Project::discover(root)?).await,
+        }
+    }
+}
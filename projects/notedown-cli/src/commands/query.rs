@@ -0,0 +1,52 @@
+use crate::{
+    config::Project,
+    report::{line_col, render_error},
+    workspace::Workspace,
+};
+use clap::Args;
+use notedown_ast::{query::Selector, traits::PlainText, Result};
+use std::path::{Path, PathBuf};
+
+#[derive(Args)]
+pub struct QueryArgs {
+    /// Css like selector, see the `query` module of notedown_ast
+    selector: String,
+    /// Notes to search, all the notes of the workspace by default
+    files: Vec<PathBuf>,
+}
+
+impl QueryArgs {
+    /// Print the matches as `path:line:column  text`
+    pub async fn run(self, project: &Project) -> Result<bool> {
+        let selector = match Selector::parse(&self.selector) {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("{}", render_error(&e, Path::new("<selector>"), &self.selector));
+                return Ok(false);
+            }
+        };
+        let workspace = Workspace::load(project).await?;
+        for url in workspace.select(&self.files)? {
+            let file = match workspace.fs.get_file(&url) {
+                Some(s) => s,
+                None => continue,
+            };
+            let text = file.get_text();
+            let path = workspace.relative_path(&url);
+            for item in selector.select(file.get_ast()) {
+                let (line, column) = item.range.map_or((0, 0), |r| line_col(&text, r.start));
+                println!("{}:{}:{}  {}", path.display(), line, column, summary(&item.node.plain_text()))
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// The first line of the text, at most 60 chars
+fn summary(text: &str) -> String {
+    let line = text.lines().next().unwrap_or_default();
+    match line.char_indices().nth(60) {
+        Some((i, _)) => format!("{}...", &line[..i]),
+        None => line.to_string(),
+    }
+}
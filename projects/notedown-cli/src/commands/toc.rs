@@ -0,0 +1,56 @@
+use crate::{config::Project, workspace::Workspace};
+use clap::Args;
+use notedown_ast::{
+    traits::{TableOfContent, TocConfig, TocNode},
+    Result,
+};
+use std::path::PathBuf;
+
+#[derive(Args)]
+pub struct TocArgs {
+    /// Notes to print, all the notes of the workspace by default
+    files: Vec<PathBuf>,
+    /// Skip the headers above the level, `toc.min_depth` of the config by default
+    #[clap(long)]
+    min_depth: Option<u8>,
+    /// Skip the headers below the level, `toc.max_depth` of the config by default
+    #[clap(long)]
+    max_depth: Option<u8>,
+    /// Prefix the headers with the section numbers
+    #[clap(long)]
+    numbering: bool,
+}
+
+impl TocArgs {
+    pub async fn run(self, project: &Project) -> Result<bool> {
+        let workspace = Workspace::load(project).await?;
+        let default = TocConfig::default();
+        let config = TocConfig {
+            min_depth: self.min_depth.or(project.config.toc.min_depth).unwrap_or(default.min_depth),
+            max_depth: self.max_depth.or(project.config.toc.max_depth).unwrap_or(default.max_depth),
+            numbering: self.numbering || project.config.toc.numbering,
+        };
+        for url in workspace.select(&self.files)? {
+            if let Some(file) = workspace.fs.get_file(&url) {
+                println!("{}", workspace.relative_path(&url).display());
+                let mut out = String::new();
+                file.get_ast().toc_configurable(&config).children.iter().for_each(|node| write_node(&mut out, node, 1));
+                print!("{}", out)
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// `- name #id`, indented by the depth
+fn write_node(out: &mut String, node: &TocNode, depth: usize) {
+    out.push_str(&"  ".repeat(depth));
+    out.push_str("- ");
+    out.push_str(&node.name);
+    if !node.detail.is_empty() {
+        out.push_str(" #");
+        out.push_str(&node.detail)
+    }
+    out.push('\n');
+    node.children.iter().for_each(|child| write_node(out, child, depth + 1))
+}
@@ -0,0 +1,149 @@
+use crate::{render::OutputFormat, report::message};
+use notedown_ast::{traits::SlugScheme, NoteError, Result};
+use notedown_rt::LintConfig;
+use serde::{de::Error, Deserialize, Deserializer};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Name of the project config file, the directory containing it is the workspace root
+pub const CONFIG_FILE: &str = "notedown.toml";
+
+/// `notedown.toml`
+///
+/// ```toml
+/// [workspace]
+/// include = ["**/*.note"]
+/// exclude = ["target", "drafts/**"]
+/// slug = "github"
+///
+/// [build]
+/// format = "html"
+/// output = "target/notedown"
+///
+/// [toc]
+/// max_depth = 3
+/// numbering = true
+///
+/// [lint]
+/// rules = { repeated-word = "error", long-paragraph = "off" }
+/// maxParagraphLength = 800
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ProjectConfig {
+    pub workspace: WorkspaceConfig,
+    pub build: BuildConfig,
+    pub toc: TocSection,
+    /// Same as the `notedown.lint` setting of the editor
+    pub lint: LintConfig,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct WorkspaceConfig {
+    /// Globs of the notes, relative to the root
+    pub include: Vec<String>,
+    /// Globs of the files and directories to skip
+    pub exclude: Vec<String>,
+    /// How the ids of headers are generated, `ascii`, `github` or `unicode`
+    #[serde(deserialize_with = "slug_scheme")]
+    pub slug: SlugScheme,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct BuildConfig {
+    pub format: OutputFormat,
+    /// Relative to the root
+    pub output: PathBuf,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct TocSection {
+    pub min_depth: Option<u8>,
+    pub max_depth: Option<u8>,
+    pub numbering: bool,
+}
+
+impl Default for WorkspaceConfig {
+    fn default() -> Self {
+        Self { include: vec![String::from("**/*.note")], exclude: vec![String::from("target")], slug: SlugScheme::default() }
+    }
+}
+
+impl Default for BuildConfig {
+    fn default() -> Self {
+        Self { format: OutputFormat::Html, output: PathBuf::from("target/notedown") }
+    }
+}
+
+impl WorkspaceConfig {
+    /// Patterns for [`notedown_rt::VMFileSystem::load_pattern_text`]
+    pub fn as_pattern_text(&self) -> String {
+        let include = self.include.iter().map(|s| s.to_owned());
+        let exclude = self.exclude.iter().map(|s| format!("!{}", s));
+        include.chain(exclude).collect::<Vec<_>>().join("\n")
+    }
+}
+
+fn slug_scheme<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<SlugScheme, D::Error> {
+    String::deserialize(deserializer)?.parse().map_err(|e: NoteError| D::Error::custom(message(&e.kind)))
+}
+
+/// The project found from the directory
+pub struct Project {
+    pub root: PathBuf,
+    pub config: ProjectConfig,
+}
+
+impl Project {
+    /// Search `notedown.toml` from the directory up to the file system root,
+    /// the directory itself is the root with the default config if none is found
+    pub fn discover(start: &Path) -> Result<Self> {
+        let start = start.canonicalize()?;
+        for dir in start.ancestors() {
+            let file = dir.join(CONFIG_FILE);
+            if file.is_file() {
+                let config = ProjectConfig::parse(&fs::read_to_string(&file)?).map_err(|mut e| {
+                    e.set_path(&file);
+                    e
+                })?;
+                return Ok(Self { root: dir.to_path_buf(), config });
+            }
+        }
+        Ok(Self { root: start, config: ProjectConfig::default() })
+    }
+}
+
+impl ProjectConfig {
+    pub fn parse(text: &str) -> Result<Self> {
+        toml::from_str(text).map_err(|e| {
+            let mut error = NoteError::syntax_error(e.to_string());
+            if let Some((line, column)) = e.line_col() {
+                let start = text.lines().take(line).map(|s| s.len() + 1).sum::<usize>() + column;
+                error.set_range(start, start)
+            }
+            error
+        })
+    }
+}
+
+#[test]
+fn parse_config() {
+    let config = ProjectConfig::parse("[workspace]\nexclude = ['drafts/**']\n[build]\nformat = 'latex'\n[toc]\nmax_depth = 2").unwrap();
+    assert_eq!(config.workspace.include, ["**/*.note"]);
+    assert_eq!(config.workspace.as_pattern_text(), "**/*.note\n!drafts/**");
+    assert_eq!(config.workspace.slug, SlugScheme::Ascii);
+    assert_eq!(ProjectConfig::parse("[workspace]\nslug = 'unicode'").unwrap().workspace.slug, SlugScheme::Unicode);
+    assert!(ProjectConfig::parse("[workspace]\nslug = 'kebab'").is_err());
+    assert_eq!(config.build.format, OutputFormat::Latex);
+    assert_eq!(config.toc.max_depth, Some(2));
+    assert!(ProjectConfig::parse("[build]\nformat = 'pdf'").is_err());
+    assert_eq!(config.lint.max_paragraph_length, 800);
+    let lint = ProjectConfig::parse("[lint]\nrules = { repeated-word = 'off' }\nmaxParagraphLength = 100").unwrap().lint;
+    assert_eq!(lint.rules["repeated-word"], notedown_rt::LintLevel::Off);
+    assert_eq!(lint.max_paragraph_length, 100);
+}
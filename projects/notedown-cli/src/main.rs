@@ -0,0 +1,43 @@
+mod commands;
+mod config;
+mod render;
+mod report;
+mod workspace;
+
+use crate::commands::Command;
+use clap::Parser;
+use notedown_ast::NoteError;
+use std::{fs, path::PathBuf, process::ExitCode};
+
+/// Check, format, build and query notedown documents
+#[derive(Parser)]
+#[clap(name = "notedown", version)]
+struct Cli {
+    /// Directory to search `notedown.toml` from
+    #[clap(long, global = true, default_value = ".")]
+    root: PathBuf,
+    #[clap(subcommand)]
+    command: Command,
+}
+
+/// Exit with 1 if the command failed, 2 if it could not run
+#[async_std::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match cli.command.run(&cli.root).await {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(e) => {
+            eprintln!("{}", render_fatal(&e));
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Errors in the config file are shown with the source line
+fn render_fatal(error: &NoteError) -> String {
+    match error.file.as_ref().and_then(|url| url.to_file_path().ok()) {
+        Some(path) => report::render_error(error, &path, &fs::read_to_string(&path).unwrap_or_default()),
+        None => format!("error[{}]: {}", error.kind.code(), report::message(&error.kind)),
+    }
+}
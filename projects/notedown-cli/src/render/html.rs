@@ -0,0 +1,229 @@
+use super::*;
+use notedown_ast::{
+    nodes::{
+        CodeNode, Delimiter, EmailLink, Header, HyperLink, ImageLink, ListItem, MathKind, MathNode, QuoteBlock, StyleKind, StyleNode,
+        TableView, TextSpan,
+    },
+    visit::{walk_nodes, walk_quote},
+    ASTNodes,
+};
+
+/// Render notes to html, math is left to KaTeX or MathJax
+pub struct HtmlRenderer {
+    out: String,
+    footnotes: Footnotes,
+}
+
+impl HtmlRenderer {
+    pub fn render(ast: &ASTNode, title: &str) -> String {
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n{}</body>\n</html>\n",
+            escape(title),
+            Self::render_fragment(ast)
+        )
+    }
+    pub fn render_fragment(ast: &ASTNode) -> String {
+        let mut renderer = Self { out: String::new(), footnotes: Footnotes::collect(ast) };
+        let _ = ast.accept(&mut renderer);
+        return renderer.out;
+    }
+    fn table_row<'ast>(&mut self, cells: &'ast [ASTNode], tag: &str, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.out.push_str("<tr>");
+        for cell in cells {
+            self.out.push_str(&format!("<{}>", tag));
+            walk_nodes(self, std::slice::from_ref(cell), ctx)?;
+            self.out.push_str(&format!("</{}>", tag));
+        }
+        self.out.push_str("</tr>\n");
+        ControlFlow::Continue(())
+    }
+}
+
+impl<'ast> Visitor<'ast> for HtmlRenderer {
+    fn visit_paragraph(&mut self, nodes: &'ast ASTNodes, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.out.push_str("<p>");
+        walk_nodes(self, nodes, ctx)?;
+        self.out.push_str("</p>\n");
+        ControlFlow::Continue(())
+    }
+    fn visit_header(&mut self, header: &'ast Header, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        match &header.id {
+            Some(id) => self.out.push_str(&format!("<h{} id=\"{}\">", header.level, escape(id))),
+            None => self.out.push_str(&format!("<h{}>", header.level)),
+        }
+        walk_nodes(self, &header.children, ctx)?;
+        self.out.push_str(&format!("</h{}>\n", header.level));
+        ControlFlow::Continue(())
+    }
+    fn visit_table(&mut self, table: &'ast TableView, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        match table {
+            TableView::SimpleTable { head, terms, .. } => {
+                self.out.push_str("<table>\n");
+                if !head.is_empty() {
+                    self.out.push_str("<thead>\n");
+                    self.table_row(head, "th", ctx)?;
+                    self.out.push_str("</thead>\n");
+                }
+                self.out.push_str("<tbody>\n");
+                for row in terms {
+                    self.table_row(row, "td", ctx)?;
+                }
+                self.out.push_str("</tbody>\n</table>\n");
+            }
+        }
+        ControlFlow::Continue(())
+    }
+    fn visit_list(&mut self, list: &'ast ListView, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        let tag = if is_ordered(list) { "ol" } else { "ul" };
+        self.out.push_str(&format!("<{}>\n", tag));
+        for item in &list.children {
+            self.visit_list_item(item, ctx)?
+        }
+        self.out.push_str(&format!("</{}>\n", tag));
+        ControlFlow::Continue(())
+    }
+    fn visit_list_item(&mut self, item: &'ast ListItem, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.out.push_str("<li>");
+        walk_nodes(self, &item.rest, ctx)?;
+        self.out.push_str("</li>\n");
+        ControlFlow::Continue(())
+    }
+    fn visit_quote(&mut self, quote: &'ast QuoteBlock, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.out.push_str("<blockquote>\n");
+        walk_quote(self, quote, ctx)?;
+        self.out.push_str("</blockquote>\n");
+        ControlFlow::Continue(())
+    }
+    fn visit_styled(&mut self, styled: &'ast StyleNode, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        let (open, close) = match styled.kind {
+            StyleKind::Plain => (String::new(), String::new()),
+            StyleKind::Emphasis => (String::from("<em>"), String::from("</em>")),
+            StyleKind::Strong => (String::from("<strong>"), String::from("</strong>")),
+            StyleKind::ItalicBold => (String::from("<em><strong>"), String::from("</strong></em>")),
+            StyleKind::Underline => (String::from("<u>"), String::from("</u>")),
+            StyleKind::Undercover => (String::from("<span class=\"undercover\">"), String::from("</span>")),
+            StyleKind::Marking => (String::from("<mark>"), String::from("</mark>")),
+            StyleKind::Color(r, g, b, a) => {
+                (format!("<span style=\"color: rgba({}, {}, {}, {:.3})\">", r, g, b, a as f32 / 255.0), String::from("</span>"))
+            }
+            StyleKind::Delete => (String::from("<del>"), String::from("</del>")),
+            StyleKind::Insert => (String::from("<ins>"), String::from("</ins>")),
+            StyleKind::Subscript => (String::from("<sub>"), String::from("</sub>")),
+            StyleKind::Superscript => (String::from("<sup>"), String::from("</sup>")),
+        };
+        self.out.push_str(&open);
+        walk_nodes(self, &styled.children, ctx)?;
+        self.out.push_str(&close);
+        ControlFlow::Continue(())
+    }
+    fn visit_hyper_link(&mut self, link: &'ast HyperLink, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        let text = link.text.as_deref().unwrap_or(&link.src);
+        self.out.push_str(&format!("<a href=\"{}\">{}</a>", escape(&note_href(&link.src, "html")), escape(text)));
+        ControlFlow::Continue(())
+    }
+    fn visit_image_link(&mut self, link: &'ast ImageLink, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        let alt = link.description.as_deref().unwrap_or_default();
+        self.out.push_str(&format!("<img src=\"{}\" alt=\"{}\">", escape(&link.source), escape(alt)));
+        ControlFlow::Continue(())
+    }
+    fn visit_two_way_link(&mut self, link: &'ast TwoWayLink, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.out.push_str(&format!("<a href=\"{}\">{}</a>", escape(&two_way_href(link, "html")), escape(link.get_link())));
+        ControlFlow::Continue(())
+    }
+    fn visit_email_link(&mut self, link: &'ast EmailLink, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.out.push_str(&format!("<a href=\"mailto:{0}\">{0}</a>", escape(&link.name)));
+        ControlFlow::Continue(())
+    }
+    fn visit_tag_reference(&mut self, tag: &'ast TagReference, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        let id = escape(&tag.tag);
+        let number = self.footnotes.number(&tag.tag).map(|n| n.to_string()).unwrap_or_else(|| id.to_owned());
+        match tag.is_definition() {
+            true => self.out.push_str(&format!(
+                "<span class=\"footnote\" id=\"fn-{}\"><sup>{}</sup> {}</span>",
+                id,
+                number,
+                escape(&tag.text.join(" "))
+            )),
+            false => self.out.push_str(&format!("<sup id=\"fnref-{0}\"><a href=\"#fn-{0}\">{1}</a></sup>", id, number)),
+        }
+        ControlFlow::Continue(())
+    }
+    fn visit_code(&mut self, code: &'ast CodeNode, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        match code.inline {
+            true => self.out.push_str(&format!("<code>{}</code>", escape(&code.code))),
+            false => {
+                self.out.push_str(&format!("<pre><code class=\"language-{}\">{}</code></pre>\n", escape(&code.language), escape(&code.code)))
+            }
+        }
+        ControlFlow::Continue(())
+    }
+    fn visit_math(&mut self, math: &'ast MathNode, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        let raw = escape(&math.raw);
+        match math.kind {
+            MathKind::Inline => self.out.push_str(&format!("<span class=\"math inline\">\\({}\\)</span>", raw)),
+            MathKind::Display => self.out.push_str(&format!("<span class=\"math display\">\\[{}\\]</span>", raw)),
+            MathKind::BlockInline | MathKind::BlockDisplay => self.out.push_str(&format!("<div class=\"math display\">\\[{}\\]</div>\n", raw)),
+        }
+        ControlFlow::Continue(())
+    }
+    fn visit_text(&mut self, text: &'ast TextSpan, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        match text {
+            TextSpan::Empty => {}
+            TextSpan::Normal(s) | TextSpan::Raw(s) => self.out.push_str(&escape(s)),
+            TextSpan::HTMLRawInline(s) => self.out.push_str(s),
+            TextSpan::Emoji(s) => self.out.push_str(s),
+            TextSpan::Escaped(c) => self.out.push_str(&escape(&c.to_string())),
+            TextSpan::SoftNewline => self.out.push('\n'),
+            TextSpan::HardNewline => self.out.push_str("<br>\n"),
+            TextSpan::CheckBox(true) => self.out.push_str("<input type=\"checkbox\" disabled checked>"),
+            TextSpan::CheckBox(false) => self.out.push_str("<input type=\"checkbox\" disabled>"),
+        }
+        ControlFlow::Continue(())
+    }
+    fn visit_delimiter(&mut self, delimiter: &'ast Delimiter, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        match delimiter {
+            Delimiter::HorizontalRule => self.out.push_str("<hr>\n"),
+            Delimiter::HTMLRawBlock(s) => {
+                self.out.push_str(s);
+                self.out.push('\n')
+            }
+        }
+        ControlFlow::Continue(())
+    }
+    fn visit_value(&mut self, value: &'ast Value, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.out.push_str(&escape(&value_text(value)));
+        ControlFlow::Continue(())
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    return out;
+}
+
+#[test]
+fn render_html() {
+    use notedown_ast::ASTKind;
+    let ast = ASTKind::statements(
+        vec![
+            ASTKind::header(vec![ASTKind::text("A & B", None)], 2, None),
+            ASTKind::paragraph(vec![ASTKind::strong(vec![ASTKind::text("bold", None)], None), ASTKind::hyper_link("./b.note#c", None)], None),
+            ASTKind::code_block("fn main() {}", "rust", None),
+        ],
+        None,
+    );
+    assert_eq!(
+        HtmlRenderer::render_fragment(&ast),
+        "<h2 id=\"a-b\">A &amp; B</h2>\n<p><strong>bold</strong><a href=\"./b.html#c\">./b.note#c</a></p>\n<pre><code class=\"language-rust\">fn main() {}</code></pre>\n"
+    );
+}
@@ -0,0 +1,234 @@
+use super::*;
+use notedown_ast::{
+    nodes::{
+        CodeNode, Delimiter, EmailLink, Header, HyperLink, ImageLink, ListItem, MathKind, MathNode, QuoteBlock, StyleKind, StyleNode,
+        TableView, TextSpan,
+    },
+    visit::{walk_nodes, walk_quote},
+    ASTNodes,
+};
+
+/// Render notes to a LaTeX article, footnotes are inlined at the first reference
+pub struct LatexRenderer {
+    out: String,
+    footnotes: Footnotes,
+}
+
+impl LatexRenderer {
+    pub fn render(ast: &ASTNode, title: &str) -> String {
+        let mut out = String::from("\\documentclass{article}\n");
+        for package in ["amsmath", "graphicx", "hyperref", "xcolor"] {
+            out.push_str(&format!("\\usepackage{{{}}}\n", package))
+        }
+        out.push_str("\\usepackage[normalem]{ulem}\n");
+        if !title.is_empty() {
+            out.push_str(&format!("\\title{{{}}}\n\\date{{}}\n", escape(title)))
+        }
+        out.push_str("\\begin{document}\n");
+        if !title.is_empty() {
+            out.push_str("\\maketitle\n")
+        }
+        out.push_str(&Self::render_fragment(ast));
+        out.push_str("\\end{document}\n");
+        return out;
+    }
+    pub fn render_fragment(ast: &ASTNode) -> String {
+        let mut renderer = Self { out: String::new(), footnotes: Footnotes::collect(ast) };
+        let _ = ast.accept(&mut renderer);
+        return renderer.out;
+    }
+    fn table_row<'ast>(&mut self, cells: &'ast [ASTNode], ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        for (column, cell) in cells.iter().enumerate() {
+            if column > 0 {
+                self.out.push_str(" & ")
+            }
+            walk_nodes(self, std::slice::from_ref(cell), ctx)?;
+        }
+        self.out.push_str(" \\\\\n");
+        ControlFlow::Continue(())
+    }
+}
+
+impl<'ast> Visitor<'ast> for LatexRenderer {
+    fn visit_paragraph(&mut self, nodes: &'ast ASTNodes, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        walk_nodes(self, nodes, ctx)?;
+        self.out.push_str("\n\n");
+        ControlFlow::Continue(())
+    }
+    fn visit_header(&mut self, header: &'ast Header, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        let command = match header.level {
+            1 => "section",
+            2 => "subsection",
+            3 => "subsubsection",
+            4 => "paragraph",
+            _ => "subparagraph",
+        };
+        let star = if header.hide_in_toc { "*" } else { "" };
+        self.out.push_str(&format!("\\{}{}{{", command, star));
+        walk_nodes(self, &header.children, ctx)?;
+        self.out.push('}');
+        if let Some(id) = &header.id {
+            self.out.push_str(&format!("\\label{{{}}}", id))
+        }
+        self.out.push_str("\n\n");
+        ControlFlow::Continue(())
+    }
+    fn visit_table(&mut self, table: &'ast TableView, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        match table {
+            TableView::SimpleTable { head, terms, column, .. } => {
+                let columns = terms.iter().map(|row| row.len()).chain([head.len(), *column]).max().unwrap_or_default();
+                self.out.push_str(&format!("\\begin{{tabular}}{{|{}}}\n\\hline\n", "l|".repeat(columns)));
+                if !head.is_empty() {
+                    self.table_row(head, ctx)?;
+                    self.out.push_str("\\hline\n");
+                }
+                for row in terms {
+                    self.table_row(row, ctx)?;
+                }
+                self.out.push_str("\\hline\n\\end{tabular}\n\n");
+            }
+        }
+        ControlFlow::Continue(())
+    }
+    fn visit_list(&mut self, list: &'ast ListView, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        let environment = if is_ordered(list) { "enumerate" } else { "itemize" };
+        self.out.push_str(&format!("\\begin{{{}}}\n", environment));
+        for item in &list.children {
+            self.visit_list_item(item, ctx)?
+        }
+        self.out.push_str(&format!("\\end{{{}}}\n\n", environment));
+        ControlFlow::Continue(())
+    }
+    fn visit_list_item(&mut self, item: &'ast ListItem, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.out.push_str("\\item ");
+        walk_nodes(self, &item.rest, ctx)?;
+        while self.out.ends_with('\n') {
+            self.out.pop();
+        }
+        self.out.push('\n');
+        ControlFlow::Continue(())
+    }
+    fn visit_quote(&mut self, quote: &'ast QuoteBlock, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.out.push_str("\\begin{quote}\n");
+        walk_quote(self, quote, ctx)?;
+        self.out.push_str("\\end{quote}\n\n");
+        ControlFlow::Continue(())
+    }
+    fn visit_styled(&mut self, styled: &'ast StyleNode, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        let (open, close) = match styled.kind {
+            StyleKind::Plain | StyleKind::Undercover => (String::new(), String::new()),
+            StyleKind::Emphasis => (String::from("\\emph{"), String::from("}")),
+            StyleKind::Strong => (String::from("\\textbf{"), String::from("}")),
+            StyleKind::ItalicBold => (String::from("\\textbf{\\emph{"), String::from("}}")),
+            StyleKind::Underline | StyleKind::Insert => (String::from("\\uline{"), String::from("}")),
+            StyleKind::Marking => (String::from("\\colorbox{yellow}{"), String::from("}")),
+            StyleKind::Color(r, g, b, _) => (format!("\\textcolor[RGB]{{{},{},{}}}{{", r, g, b), String::from("}")),
+            StyleKind::Delete => (String::from("\\sout{"), String::from("}")),
+            StyleKind::Subscript => (String::from("\\textsubscript{"), String::from("}")),
+            StyleKind::Superscript => (String::from("\\textsuperscript{"), String::from("}")),
+        };
+        self.out.push_str(&open);
+        walk_nodes(self, &styled.children, ctx)?;
+        self.out.push_str(&close);
+        ControlFlow::Continue(())
+    }
+    fn visit_hyper_link(&mut self, link: &'ast HyperLink, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        let text = link.text.as_deref().unwrap_or(&link.src);
+        self.out.push_str(&format!("\\href{{{}}}{{{}}}", escape_url(&note_href(&link.src, "tex")), escape(text)));
+        ControlFlow::Continue(())
+    }
+    fn visit_image_link(&mut self, link: &'ast ImageLink, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.out.push_str(&format!("\\includegraphics[width=\\linewidth]{{{}}}", link.source));
+        ControlFlow::Continue(())
+    }
+    fn visit_two_way_link(&mut self, link: &'ast TwoWayLink, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.out.push_str(&format!("\\href{{{}}}{{{}}}", escape_url(&two_way_href(link, "tex")), escape(link.get_link())));
+        ControlFlow::Continue(())
+    }
+    fn visit_email_link(&mut self, link: &'ast EmailLink, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.out.push_str(&format!("\\href{{mailto:{}}}{{{}}}", escape_url(&link.name), escape(&link.name)));
+        ControlFlow::Continue(())
+    }
+    fn visit_tag_reference(&mut self, tag: &'ast TagReference, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        if !tag.is_definition() {
+            match self.footnotes.text(&tag.tag) {
+                Some(text) => self.out.push_str(&format!("\\footnote{{{}}}", escape(text))),
+                None => self.out.push_str(&format!("\\textsuperscript{{{}}}", escape(&tag.tag))),
+            }
+        }
+        ControlFlow::Continue(())
+    }
+    fn visit_code(&mut self, code: &'ast CodeNode, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        match code.inline {
+            true => self.out.push_str(&format!("\\texttt{{{}}}", escape(&code.code))),
+            false => self.out.push_str(&format!("\\begin{{verbatim}}\n{}\n\\end{{verbatim}}\n\n", code.code.trim_end_matches('\n'))),
+        }
+        ControlFlow::Continue(())
+    }
+    fn visit_math(&mut self, math: &'ast MathNode, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        match math.kind {
+            MathKind::Inline => self.out.push_str(&format!("${}$", math.raw)),
+            MathKind::Display => self.out.push_str(&format!("\\[{}\\]", math.raw)),
+            MathKind::BlockInline | MathKind::BlockDisplay => self.out.push_str(&format!("\\[\n{}\n\\]\n\n", math.raw.trim())),
+        }
+        ControlFlow::Continue(())
+    }
+    fn visit_text(&mut self, text: &'ast TextSpan, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        match text {
+            TextSpan::Empty | TextSpan::HTMLRawInline(_) => {}
+            TextSpan::Normal(s) | TextSpan::Raw(s) => self.out.push_str(&escape(s)),
+            TextSpan::Emoji(s) => self.out.push_str(s),
+            TextSpan::Escaped(c) => self.out.push_str(&escape(&c.to_string())),
+            TextSpan::SoftNewline => self.out.push('\n'),
+            TextSpan::HardNewline => self.out.push_str("\\\\\n"),
+            TextSpan::CheckBox(true) => self.out.push_str("$\\boxtimes$ "),
+            TextSpan::CheckBox(false) => self.out.push_str("$\\square$ "),
+        }
+        ControlFlow::Continue(())
+    }
+    fn visit_delimiter(&mut self, delimiter: &'ast Delimiter, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        if let Delimiter::HorizontalRule = delimiter {
+            self.out.push_str("\\noindent\\rule{\\linewidth}{0.4pt}\n\n")
+        }
+        ControlFlow::Continue(())
+    }
+    fn visit_value(&mut self, value: &'ast Value, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.out.push_str(&escape(&value_text(value)));
+        ControlFlow::Continue(())
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                out.push('\\');
+                out.push(c)
+            }
+            '~' => out.push_str("\\textasciitilde{}"),
+            '^' => out.push_str("\\textasciicircum{}"),
+            '\\' => out.push_str("\\textbackslash{}"),
+            c => out.push(c),
+        }
+    }
+    return out;
+}
+
+/// `\href` only needs `%` and `#` to be escaped
+fn escape_url(url: &str) -> String {
+    url.replace('%', "\\%").replace('#', "\\#")
+}
+
+#[test]
+fn render_latex() {
+    use notedown_ast::ASTKind;
+    let ast = ASTKind::statements(
+        vec![
+            ASTKind::header(vec![ASTKind::text("Cost", None)], 2, None),
+            ASTKind::paragraph(vec![ASTKind::emphasis(vec![ASTKind::text("50% & more", None)], None), ASTKind::math_inline("x^2", None)], None),
+        ],
+        None,
+    );
+    assert_eq!(LatexRenderer::render_fragment(&ast), "\\subsection{Cost}\\label{cost}\n\n\\emph{50\\% \\& more}$x^2$\n\n");
+}
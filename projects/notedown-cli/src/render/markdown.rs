@@ -0,0 +1,247 @@
+use super::*;
+use notedown_ast::{
+    nodes::{
+        CodeNode, Delimiter, EmailLink, Header, HyperLink, ImageLink, IncludeNode, ListItem, MathKind, MathNode, QuoteBlock, StyleKind,
+        StyleNode, TableView, TextSpan,
+    },
+    visit::{walk_nodes, walk_quote},
+    ASTNodes,
+};
+
+/// Render notes to CommonMark with the footnote and math extensions
+#[derive(Default)]
+pub struct MarkdownRenderer {
+    out: String,
+}
+
+impl MarkdownRenderer {
+    pub fn render(ast: &ASTNode) -> String {
+        let mut renderer = Self::default();
+        let _ = ast.accept(&mut renderer);
+        let mut out = renderer.out.trim_end().to_string();
+        out.push('\n');
+        return out;
+    }
+    /// Render the nodes into a new buffer
+    fn capture<'ast>(&mut self, nodes: &'ast [ASTNode], ctx: &mut VisitContext<'ast>) -> (String, ControlFlow<()>) {
+        let start = self.out.len();
+        let flow = walk_nodes(self, nodes, ctx);
+        (self.out.split_off(start), flow)
+    }
+    fn table_row<'ast>(&mut self, cells: &'ast [ASTNode], columns: usize, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        let mut row = vec![String::new(); columns];
+        for (column, cell) in cells.iter().enumerate() {
+            let (text, flow) = self.capture(std::slice::from_ref(cell), ctx);
+            row[column] = text.trim().replace('\n', " ").replace('|', "\\|");
+            flow?
+        }
+        self.out.push_str(&format!("| {} |\n", row.join(" | ")));
+        ControlFlow::Continue(())
+    }
+}
+
+impl<'ast> Visitor<'ast> for MarkdownRenderer {
+    fn visit_statements(&mut self, nodes: &'ast ASTNodes, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        for node in nodes {
+            walk_nodes(self, std::slice::from_ref(node), ctx)?;
+            blank_line(&mut self.out)
+        }
+        ControlFlow::Continue(())
+    }
+    /// The included blocks are separated like the other blocks
+    fn visit_include(&mut self, include: &'ast IncludeNode, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.visit_statements(&include.children, ctx)
+    }
+    fn visit_paragraph(&mut self, nodes: &'ast ASTNodes, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        walk_nodes(self, nodes, ctx)?;
+        self.out.push('\n');
+        ControlFlow::Continue(())
+    }
+    fn visit_header(&mut self, header: &'ast Header, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        let (text, flow) = self.capture(&header.children, ctx);
+        self.out.push_str(&format!("{} {}\n", "#".repeat(header.level as usize), text.trim()));
+        flow
+    }
+    fn visit_table(&mut self, table: &'ast TableView, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        match table {
+            TableView::SimpleTable { head, terms, column, .. } => {
+                let columns = terms.iter().map(|row| row.len()).chain([head.len(), *column]).max().unwrap_or_default();
+                self.table_row(head, columns, ctx)?;
+                self.out.push_str(&format!("|{}\n", " --- |".repeat(columns)));
+                for row in terms {
+                    self.table_row(row, columns, ctx)?;
+                }
+            }
+        }
+        ControlFlow::Continue(())
+    }
+    fn visit_list(&mut self, list: &'ast ListView, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        for (index, item) in list.children.iter().enumerate() {
+            let marker = if is_ordered(list) { format!("{}. ", index + 1) } else { String::from("- ") };
+            let (text, flow) = self.capture(&item.rest, ctx);
+            self.out.push_str(&marker);
+            self.out.push_str(&indent(text.trim_end(), &" ".repeat(marker.len()), false));
+            self.out.push('\n');
+            flow?
+        }
+        ControlFlow::Continue(())
+    }
+    fn visit_list_item(&mut self, item: &'ast ListItem, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        walk_nodes(self, &item.rest, ctx)
+    }
+    fn visit_quote(&mut self, quote: &'ast QuoteBlock, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        let start = self.out.len();
+        let flow = walk_quote(self, quote, ctx);
+        let text = self.out.split_off(start);
+        self.out.push_str(&indent(text.trim_end(), "> ", true));
+        self.out.push('\n');
+        flow
+    }
+    fn visit_styled(&mut self, styled: &'ast StyleNode, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        let (open, close) = match styled.kind {
+            StyleKind::Plain | StyleKind::Undercover | StyleKind::Color(..) => ("", ""),
+            StyleKind::Emphasis => ("*", "*"),
+            StyleKind::Strong => ("**", "**"),
+            StyleKind::ItalicBold => ("***", "***"),
+            StyleKind::Underline => ("<u>", "</u>"),
+            StyleKind::Marking => ("<mark>", "</mark>"),
+            StyleKind::Delete => ("~~", "~~"),
+            StyleKind::Insert => ("<ins>", "</ins>"),
+            StyleKind::Subscript => ("<sub>", "</sub>"),
+            StyleKind::Superscript => ("<sup>", "</sup>"),
+        };
+        self.out.push_str(open);
+        walk_nodes(self, &styled.children, ctx)?;
+        self.out.push_str(close);
+        ControlFlow::Continue(())
+    }
+    fn visit_hyper_link(&mut self, link: &'ast HyperLink, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        let text = link.text.as_deref().unwrap_or(&link.src);
+        self.out.push_str(&format!("[{}]({})", text, note_href(&link.src, "md")));
+        ControlFlow::Continue(())
+    }
+    fn visit_image_link(&mut self, link: &'ast ImageLink, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.out.push_str(&format!("![{}]({})", link.description.as_deref().unwrap_or_default(), link.source));
+        ControlFlow::Continue(())
+    }
+    fn visit_two_way_link(&mut self, link: &'ast TwoWayLink, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.out.push_str(&format!("[{}]({})", link.get_link(), two_way_href(link, "md")));
+        ControlFlow::Continue(())
+    }
+    fn visit_email_link(&mut self, link: &'ast EmailLink, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.out.push_str(&format!("<{}>", link.name));
+        ControlFlow::Continue(())
+    }
+    fn visit_tag_reference(&mut self, tag: &'ast TagReference, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        match tag.is_definition() {
+            true => self.out.push_str(&format!("[^{}]: {}", tag.tag, tag.text.join(" "))),
+            false => self.out.push_str(&format!("[^{}]", tag.tag)),
+        }
+        ControlFlow::Continue(())
+    }
+    fn visit_code(&mut self, code: &'ast CodeNode, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        let longest = longest_run(&code.code, '`');
+        match code.inline {
+            true => {
+                let fence = "`".repeat(longest + 1);
+                let padding = if longest > 0 { " " } else { "" };
+                self.out.push_str(&format!("{0}{1}{2}{1}{0}", fence, padding, code.code))
+            }
+            false => {
+                let fence = "`".repeat(longest.max(2) + 1);
+                self.out.push_str(&format!("{0}{1}\n{2}\n{0}\n", fence, code.language, code.code.trim_end_matches('\n')))
+            }
+        }
+        ControlFlow::Continue(())
+    }
+    fn visit_math(&mut self, math: &'ast MathNode, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        match math.kind {
+            MathKind::Inline => self.out.push_str(&format!("${}$", math.raw)),
+            MathKind::Display => self.out.push_str(&format!("$${}$$", math.raw)),
+            MathKind::BlockInline | MathKind::BlockDisplay => self.out.push_str(&format!("$$\n{}\n$$\n", math.raw.trim())),
+        }
+        ControlFlow::Continue(())
+    }
+    fn visit_text(&mut self, text: &'ast TextSpan, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        match text {
+            TextSpan::Empty => {}
+            TextSpan::Normal(s) | TextSpan::Raw(s) | TextSpan::HTMLRawInline(s) => self.out.push_str(s),
+            TextSpan::Emoji(s) => self.out.push_str(s),
+            TextSpan::Escaped(c) => {
+                self.out.push('\\');
+                self.out.push(*c)
+            }
+            TextSpan::SoftNewline => self.out.push('\n'),
+            TextSpan::HardNewline => self.out.push_str("\\\n"),
+            TextSpan::CheckBox(true) => self.out.push_str("[x] "),
+            TextSpan::CheckBox(false) => self.out.push_str("[ ] "),
+        }
+        ControlFlow::Continue(())
+    }
+    fn visit_delimiter(&mut self, delimiter: &'ast Delimiter, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        match delimiter {
+            Delimiter::HorizontalRule => self.out.push_str("---\n"),
+            Delimiter::HTMLRawBlock(s) => {
+                self.out.push_str(s);
+                self.out.push('\n')
+            }
+        }
+        ControlFlow::Continue(())
+    }
+    fn visit_value(&mut self, value: &'ast Value, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.out.push_str(&value_text(value));
+        ControlFlow::Continue(())
+    }
+}
+
+/// End the block with an empty line
+fn blank_line(out: &mut String) {
+    while !out.is_empty() && !out.ends_with("\n\n") {
+        out.push('\n')
+    }
+}
+
+/// Prefix the lines, the first line is kept if `first` is false
+fn indent(text: &str, prefix: &str, first: bool) -> String {
+    let mut out = String::new();
+    for (index, line) in text.lines().enumerate() {
+        if index > 0 {
+            out.push('\n')
+        }
+        if index > 0 || first {
+            match line.is_empty() {
+                true => out.push_str(prefix.trim_end()),
+                false => out.push_str(prefix),
+            }
+        }
+        out.push_str(line)
+    }
+    return out;
+}
+
+fn longest_run(text: &str, c: char) -> usize {
+    let mut longest = 0;
+    let mut current = 0;
+    for next in text.chars() {
+        current = if next == c { current + 1 } else { 0 };
+        longest = longest.max(current)
+    }
+    return longest;
+}
+
+#[test]
+fn render_markdown() {
+    use notedown_ast::ASTKind;
+    let items = vec![ListItem::from(vec![ASTKind::text("one", None)]), ListItem::from(vec![ASTKind::text("two", None)])];
+    let list = ListView { ignore_global_list_style: false, first_symbol: ListPrefixSymbol::Arabic, children: items };
+    let ast = ASTKind::statements(
+        vec![
+            ASTKind::header(vec![ASTKind::text("Title", None)], 1, None),
+            ASTKind::paragraph(vec![ASTKind::text("a ", None), ASTKind::code_inline("x`y", None)], None),
+            ASTKind::quote(vec![ASTKind::paragraph(vec![ASTKind::text("quoted", None)], None), ASTKind::paragraph(vec![], None)], None),
+            list.into(),
+        ],
+        None,
+    );
+    assert_eq!(MarkdownRenderer::render(&ast), "# Title\n\na `` x`y ``\n\n> quoted\n\n1. one\n2. two\n");
+}
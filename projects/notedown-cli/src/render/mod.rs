@@ -0,0 +1,131 @@
+mod html;
+mod latex;
+mod markdown;
+
+pub use self::{html::HtmlRenderer, latex::LatexRenderer, markdown::MarkdownRenderer};
+
+use clap::ValueEnum;
+use notedown_ast::{
+    nodes::{ListPrefixSymbol, ListView, TagReference, TwoWayLink},
+    visit::{ControlFlow, VisitContext, Visitor},
+    ASTNode, Value,
+};
+use serde::Deserialize;
+use std::{collections::BTreeMap, path::Path};
+
+/// Formats of `build` and `convert`
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    Html,
+    Markdown,
+    Latex,
+}
+
+impl OutputFormat {
+    /// Extension of the output files
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Html => "html",
+            Self::Markdown => "md",
+            Self::Latex => "tex",
+        }
+    }
+    /// Guess the format from the extension of the output file
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "html" | "htm" => Some(Self::Html),
+            "md" | "markdown" => Some(Self::Markdown),
+            "tex" | "latex" => Some(Self::Latex),
+            _ => None,
+        }
+    }
+    /// Render the note as a standalone document
+    pub fn render(&self, ast: &ASTNode, title: &str) -> String {
+        match self {
+            Self::Html => HtmlRenderer::render(ast, title),
+            Self::Markdown => MarkdownRenderer::render(ast),
+            Self::Latex => LatexRenderer::render(ast, title),
+        }
+    }
+}
+
+/// Footnotes are numbered by the order of the first reference
+#[derive(Default)]
+pub struct Footnotes {
+    numbers: BTreeMap<String, usize>,
+    texts: BTreeMap<String, String>,
+}
+
+impl Footnotes {
+    pub fn collect(ast: &ASTNode) -> Self {
+        let mut footnotes = Self::default();
+        let _ = ast.accept(&mut footnotes);
+        return footnotes;
+    }
+    pub fn number(&self, tag: &str) -> Option<usize> {
+        self.numbers.get(tag).copied()
+    }
+    pub fn text(&self, tag: &str) -> Option<&str> {
+        self.texts.get(tag).map(|s| s.as_str())
+    }
+}
+
+impl<'ast> Visitor<'ast> for Footnotes {
+    fn visit_tag_reference(&mut self, tag: &'ast TagReference, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        if tag.is_definition() {
+            self.texts.insert(tag.tag.to_owned(), tag.text.join(" "));
+        }
+        else if !self.numbers.contains_key(&tag.tag) {
+            self.numbers.insert(tag.tag.to_owned(), self.numbers.len() + 1);
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// Lists starting with numbers are ordered
+#[inline]
+pub fn is_ordered(list: &ListView) -> bool {
+    matches!(list.first_symbol, ListPrefixSymbol::Arabic | ListPrefixSymbol::ArabicNest { .. } | ListPrefixSymbol::RomanNumerals)
+}
+
+/// Scalars of interpolations, collections are not printed
+pub fn value_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::Boolean(v) => v.to_string(),
+        Value::Integer(v) => v.to_string(),
+        Value::Decimal(v) => v.to_string(),
+        Value::String(v) => v.to_owned(),
+        _ => String::new(),
+    }
+}
+
+/// Links to other notes point to the rendered files, `./a.note#id` to `./a.html#id`
+pub fn note_href(src: &str, extension: &str) -> String {
+    let (path, fragment) = match src.split_once('#') {
+        Some((path, fragment)) => (path, Some(fragment)),
+        None => (src, None),
+    };
+    let path = match path.strip_suffix(".note") {
+        Some(stem) if !path.contains("://") => format!("{}.{}", stem, extension),
+        _ => path.to_string(),
+    };
+    match fragment {
+        Some(fragment) => format!("{}#{}", path, fragment),
+        None => path,
+    }
+}
+
+/// `[[note > id]]` links to the note without the extension
+pub fn two_way_href(link: &TwoWayLink, extension: &str) -> String {
+    let path = match link.get_link() {
+        "" => String::new(),
+        s if Path::new(s).extension().is_some() => note_href(s, extension),
+        s => format!("{}.{}", s, extension),
+    };
+    match link.get_id() {
+        Some(id) => format!("{}#{}", path, id),
+        None => path,
+    }
+}
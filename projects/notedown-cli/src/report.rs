@@ -0,0 +1,98 @@
+use notedown_ast::{utils::lsp_types::DiagnosticSeverity, DiagnosticLevel, NoteError, NoteErrorKind};
+use notedown_rt::Lint;
+use std::{fmt::Write, ops::Range, path::Path};
+
+/// Errors without a level are reported as errors, same as the language server
+#[inline]
+pub fn is_error(error: &NoteError) -> bool {
+    matches!(error.level, DiagnosticLevel::None | DiagnosticLevel::Error)
+}
+
+/// Render the error with the source line, like rustc
+///
+/// ```text
+/// error[syntax-error]: expected header
+///  --> notes/readme.note:3:5
+///   |
+/// 3 | ##title
+///   |     ^^^
+/// ```
+pub fn render_error(error: &NoteError, path: &Path, text: &str) -> String {
+    let level = match error.level {
+        DiagnosticLevel::None | DiagnosticLevel::Error => "error",
+        DiagnosticLevel::Warning => "warning",
+        DiagnosticLevel::Information => "info",
+        DiagnosticLevel::Hint => "hint",
+    };
+    render_source(level, error.kind.code(), &message(&error.kind), error.range.as_ref(), path, text)
+}
+
+/// Render the problem found by the checks shared with the language server, the code is the id of the rule
+pub fn render_lint(lint: &Lint, path: &Path, text: &str) -> String {
+    let level = match lint.severity {
+        DiagnosticSeverity::ERROR => "error",
+        DiagnosticSeverity::WARNING => "warning",
+        DiagnosticSeverity::INFORMATION => "info",
+        _ => "hint",
+    };
+    render_source(level, lint.rule, &lint.message, Some(&lint.range), path, text)
+}
+
+fn render_source(level: &str, code: &str, message: &str, range: Option<&Range<usize>>, path: &Path, text: &str) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "{}[{}]: {}", level, code, message);
+    let range = match range {
+        Some(s) if s.start <= text.len() => s,
+        _ => {
+            let _ = writeln!(out, " --> {}", path.display());
+            return out;
+        }
+    };
+    let (line, column) = line_col(text, range.start);
+    let source = text.lines().nth(line - 1).unwrap_or_default();
+    let gutter = " ".repeat(line.to_string().len());
+    // the carets stop at the end of the line for multiline ranges
+    let line_end = text.get(range.start..).and_then(|s| s.find('\n')).map_or(text.len(), |i| range.start + i);
+    let end = range.end.clamp(range.start, line_end);
+    let width = text.get(range.start..end).map_or(1, |s| s.chars().count()).max(1);
+    let _ = writeln!(out, "{}--> {}:{}:{}", gutter, path.display(), line, column);
+    let _ = writeln!(out, "{} |", gutter);
+    let _ = writeln!(out, "{} | {}", line, source);
+    let _ = writeln!(out, "{} | {}{}", gutter, " ".repeat(column - 1), "^".repeat(width));
+    return out;
+}
+
+/// The message without the name of the kind, which is already in the code
+pub fn message(kind: &NoteErrorKind) -> String {
+    match kind {
+        NoteErrorKind::SyntaxError(msg) | NoteErrorKind::TypeMismatch(msg) | NoteErrorKind::RuntimeError(msg) => msg.trim().to_owned(),
+        _ => kind.to_string(),
+    }
+}
+
+/// Line and column of the byte offset, both start from 1 and the column counts chars
+pub fn line_col(text: &str, offset: usize) -> (usize, usize) {
+    let mut offset = offset.min(text.len());
+    while !text.is_char_boundary(offset) {
+        offset -= 1
+    }
+    let before = &text[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = match before.rfind('\n') {
+        Some(s) => before[s + 1..].chars().count() + 1,
+        None => before.chars().count() + 1,
+    };
+    (line, column)
+}
+
+#[test]
+fn render_snippet() {
+    let text = "# Title\n\nsome ~~text\n";
+    let mut error = NoteError::syntax_error("unclosed delete");
+    error.set_range(14, 20);
+    assert_eq!(line_col(text, 14), (3, 6));
+    assert_eq!(
+        render_error(&error, Path::new("a.note"), text),
+        "error[syntax-error]: unclosed delete\n --> a.note:3:6\n  |\n3 | some ~~text\n  |      ^^^^^^\n"
+    );
+}
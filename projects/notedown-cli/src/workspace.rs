@@ -0,0 +1,125 @@
+use crate::{config::Project, report::render_error};
+use notedown_ast::{traits::SlugScheme, utils::lsp_types::Url, ASTKind, ASTNode, NoteError, Result};
+use notedown_parser::NotedownParser;
+use notedown_rt::{FileMeta, IncludeConfig, Parser, VMFileSystem};
+use std::path::{Path, PathBuf};
+
+/// Notes of the project, loaded and parsed
+pub struct Workspace {
+    pub root: PathBuf,
+    pub fs: VMFileSystem,
+    /// Parse the included notes like the others
+    parser: Parser,
+}
+
+impl Workspace {
+    /// Load all the notes matching the patterns of the project config
+    pub async fn load(project: &Project) -> Result<Self> {
+        let root = Url::from_directory_path(&project.root)
+            .map_err(|_| NoteError::runtime_error(format!("`{}` is not a directory", project.root.display())))?;
+        let mut fs = VMFileSystem::new(root);
+        fs.load_pattern_text(&project.config.workspace.as_pattern_text()).await?;
+        let parser = note_parser(project.config.workspace.slug);
+        for url in fs.get_urls() {
+            fs.update_ast(url, &parser).await?
+        }
+        Ok(Self { root: project.root.to_owned(), fs, parser })
+    }
+    /// Load the note alone, the notes it includes are loaded by [`Workspace::expand`]
+    pub async fn single(file: &Path, slug: SlugScheme) -> Result<Self> {
+        let path = file.canonicalize().map_err(|_| NoteError::runtime_error(format!("cannot find `{}`", file.display())))?;
+        let root = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        let mut fs = VMFileSystem::new(Url::from_directory_path(&root)?);
+        let url = Url::from_file_path(&path)?;
+        let parser = note_parser(slug);
+        fs.load_url(&url).await?;
+        fs.update_ast(url, &parser).await?;
+        Ok(Self { root, fs, parser })
+    }
+    /// The note with the `\include` commands replaced by the included notes
+    ///
+    /// The errors are the include cycles, and the notes or sections which can not be found.
+    pub async fn expand(&mut self, url: &Url) -> (ASTNode, Vec<NoteError>) {
+        let config = IncludeConfig::default();
+        let mut errors = self.fs.load_includes(url, &self.parser, &config).await;
+        let mut ast = self.fs.get_ast(url).unwrap_or_else(|| ASTKind::statements(vec![], None));
+        errors.extend(self.fs.expand_includes(url, &mut ast, &config));
+        (ast, errors)
+    }
+    /// Urls of the notes, or of the given files only
+    pub fn select(&self, files: &[PathBuf]) -> Result<Vec<Url>> {
+        if files.is_empty() {
+            return Ok(self.fs.get_urls());
+        }
+        let mut out = vec![];
+        for file in files {
+            let path = file.canonicalize().map_err(|_| NoteError::runtime_error(format!("cannot find `{}`", file.display())))?;
+            let url = Url::from_file_path(path)?;
+            match self.fs.is_loaded(&url) {
+                true => out.push(url),
+                false => return Err(NoteError::runtime_error(format!("`{}` is not a note of the workspace", file.display()))),
+            }
+        }
+        Ok(out)
+    }
+    /// Render the error with the note it belongs to, which is `url` if the error does not tell
+    pub fn render_error(&self, error: &NoteError, url: &Url) -> String {
+        let url = error.file.as_ref().unwrap_or(url);
+        let text = self.fs.get_text(url).unwrap_or_default();
+        render_error(error, &self.relative_path(url), &text)
+    }
+    /// Path relative to the root for messages
+    pub fn relative_path(&self, url: &Url) -> PathBuf {
+        match url.to_file_path() {
+            Ok(path) => path.strip_prefix(&self.root).map(Path::to_path_buf).unwrap_or(path),
+            Err(_) => PathBuf::from(url.as_str()),
+        }
+    }
+}
+
+/// [`Parser`] of the workspace, errors are recorded in the meta
+///
+/// The parser is a function pointer, so there is one for each scheme.
+pub fn note_parser(slug: SlugScheme) -> Parser {
+    match slug {
+        SlugScheme::Ascii => |text, meta| parse_note(text, meta, SlugScheme::Ascii),
+        SlugScheme::GitHub => |text, meta| parse_note(text, meta, SlugScheme::GitHub),
+        SlugScheme::Unicode => |text, meta| parse_note(text, meta, SlugScheme::Unicode),
+    }
+}
+
+fn parse_note(text: &str, meta: &mut FileMeta, slug: SlugScheme) -> Result<ASTNode> {
+    let (ast, errors) = parse_text(text, slug);
+    errors.into_iter().for_each(|e| meta.push_lsp_diagnostics(e));
+    Ok(ast)
+}
+
+/// Parse the note, an empty document is returned if the parsing failed
+pub fn parse_text(text: &str, slug: SlugScheme) -> (ASTNode, Vec<NoteError>) {
+    let parser = NotedownParser { slug_scheme: slug, ..NotedownParser::default() };
+    let ast = parser.parse(text);
+    let mut errors = parser.take_errors();
+    match ast {
+        Ok(ast) => (ast, errors),
+        Err(e) => {
+            errors.insert(0, e);
+            (ASTKind::statements(vec![], None), errors)
+        }
+    }
+}
+
+#[async_std::test]
+async fn expand_includes() {
+    let root = std::env::temp_dir().join("notedown-cli-include");
+    std::fs::create_dir_all(&root).unwrap();
+    std::fs::write(root.join("main.note"), "# Main\n\n\\include[./part.note]\n\n\\include[./missing.note]").unwrap();
+    std::fs::write(root.join("part.note"), "## Part\n\n\\include[./main.note]").unwrap();
+    let mut workspace = Workspace::single(&root.join("main.note"), SlugScheme::Ascii).await.unwrap();
+    let url = Url::from_file_path(root.canonicalize().unwrap().join("main.note")).unwrap();
+    let (ast, errors) = workspace.expand(&url).await;
+    assert!(matches!(&ast.value, ASTKind::Statements(v) if matches!(v[1].value, ASTKind::IncludeNode(_))));
+    let rendered: Vec<String> = errors.iter().map(|e| workspace.render_error(e, &url)).collect();
+    assert_eq!(rendered.len(), 2);
+    assert!(rendered[0].starts_with("error[runtime-error]: Include cycle detected") && rendered[0].contains("--> part.note:3:1"));
+    assert!(rendered[1].starts_with("error[runtime-error]: Can not load the included file") && rendered[1].contains("--> main.note:5:1"));
+}
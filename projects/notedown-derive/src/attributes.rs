@@ -0,0 +1,71 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Attribute, Error, Ident, Lit, Meta, NestedMeta, Path, Result};
+
+/// `#[note(rename = "key", alias = "other", default)]`
+#[derive(Default)]
+pub struct NoteAttributes {
+    pub rename: Option<String>,
+    pub aliases: Vec<String>,
+    pub default: Option<Option<Path>>,
+}
+
+impl NoteAttributes {
+    pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut out = Self::default();
+        for attr in attrs.iter().filter(|f| f.path.is_ident("note")) {
+            let list = match attr.parse_meta()? {
+                Meta::List(list) => list,
+                other => return Err(Error::new_spanned(other, "expect `#[note(...)]`")),
+            };
+            for item in list.nested {
+                match item {
+                    NestedMeta::Meta(Meta::NameValue(kv)) if kv.path.is_ident("rename") => out.rename = Some(lit_string(&kv.lit)?),
+                    NestedMeta::Meta(Meta::NameValue(kv)) if kv.path.is_ident("alias") => out.aliases.push(lit_string(&kv.lit)?),
+                    NestedMeta::Meta(Meta::NameValue(kv)) if kv.path.is_ident("default") => {
+                        out.default = Some(Some(syn::parse_str(&lit_string(&kv.lit)?)?))
+                    }
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("default") => out.default = Some(None),
+                    other => return Err(Error::new_spanned(other, "unknown attribute, expect `rename`, `alias` or `default`")),
+                }
+            }
+        }
+        Ok(out)
+    }
+    /// The key in notedown, raw identifiers are stripped
+    pub fn key(&self, ident: &Ident) -> String {
+        match &self.rename {
+            Some(s) => s.to_owned(),
+            None => ident.to_string().trim_start_matches("r#").to_string(),
+        }
+    }
+    /// The key of an enum variant in notedown, `CamelCase` becomes `camel_case`
+    pub fn variant_key(&self, ident: &Ident) -> String {
+        match &self.rename {
+            Some(s) => s.to_owned(),
+            None => snake_case(&ident.to_string()),
+        }
+    }
+    pub fn aliases(&self) -> TokenStream {
+        let aliases = &self.aliases;
+        quote! { &[#(#aliases),*] }
+    }
+}
+
+fn lit_string(lit: &Lit) -> Result<String> {
+    match lit {
+        Lit::Str(s) => Ok(s.value()),
+        _ => Err(Error::new_spanned(lit, "expect a string literal")),
+    }
+}
+
+fn snake_case(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 4);
+    for (i, c) in s.chars().enumerate() {
+        if c.is_uppercase() && i != 0 {
+            out.push('_');
+        }
+        out.extend(c.to_lowercase());
+    }
+    return out;
+}
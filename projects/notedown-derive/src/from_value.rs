@@ -0,0 +1,80 @@
+use crate::attributes::NoteAttributes;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, Result};
+
+pub fn expand(input: DeriveInput) -> Result<TokenStream> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let body = match &input.data {
+        Data::Struct(data) => expand_struct(&input, &data.fields)?,
+        Data::Enum(data) => {
+            let mut keys = vec![];
+            let mut arms = vec![];
+            for variant in &data.variants {
+                if !matches!(variant.fields, Fields::Unit) {
+                    return Err(Error::new_spanned(variant, "only unit variants can be read from a string"));
+                }
+                let attrs = NoteAttributes::parse(&variant.attrs)?;
+                let ident = &variant.ident;
+                let key = attrs.variant_key(ident);
+                let aliases = &attrs.aliases;
+                arms.push(quote! { s if s.eq_ignore_ascii_case(#key) #(|| s.eq_ignore_ascii_case(#aliases))* => Ok(Self::#ident), });
+                keys.push(format!("`{}`", key));
+            }
+            let expect = keys.join(", ");
+            quote! {
+                let s = match &value.value {
+                    ::notedown_ast::Value::String(s) => s.as_str(),
+                    _ => return Err(::notedown_ast::value::expect_type("String", &value)),
+                };
+                match s {
+                    #(#arms)*
+                    _ => {
+                        let mut error = ::notedown_ast::NoteError::type_mismatch(format!("Expect one of {}, found `{}`", #expect, s));
+                        error.range = value.range.to_owned();
+                        Err(error)
+                    }
+                }
+            }
+        }
+        Data::Union(_) => return Err(Error::new_spanned(&input, "union is not supported")),
+    };
+    Ok(quote! {
+        impl #impl_generics ::notedown_ast::value::FromValue for #name #ty_generics #where_clause {
+            fn from_value(
+                value: ::notedown_ast::nodes::Literal<::notedown_ast::Value>,
+                warnings: &mut ::std::vec::Vec<::notedown_ast::NoteError>,
+            ) -> ::notedown_ast::Result<Self> {
+                #body
+            }
+        }
+    })
+}
+
+fn expand_struct(input: &DeriveInput, fields: &Fields) -> Result<TokenStream> {
+    let name = input.ident.to_string();
+    let fields = match fields {
+        Fields::Named(fields) => &fields.named,
+        _ => return Err(Error::new_spanned(input, "only struct with named fields can be read from an object")),
+    };
+    let mut items = vec![];
+    for field in fields {
+        let attrs = NoteAttributes::parse(&field.attrs)?;
+        let ident = field.ident.as_ref().unwrap();
+        let key = attrs.key(ident);
+        let aliases = attrs.aliases();
+        let read = match &attrs.default {
+            None => quote! { reader.field(#key, #aliases)? },
+            Some(None) => quote! { reader.field_or(#key, #aliases, ::std::default::Default::default)? },
+            Some(Some(path)) => quote! { reader.field_or(#key, #aliases, #path)? },
+        };
+        items.push(quote! { #ident: #read, })
+    }
+    Ok(quote! {
+        let mut reader = ::notedown_ast::value::ObjectReader::new(#name, value, warnings)?;
+        let out = Self { #(#items)* };
+        reader.finish();
+        Ok(out)
+    })
+}
@@ -0,0 +1,59 @@
+use crate::attributes::NoteAttributes;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, Result};
+
+pub fn expand(input: DeriveInput) -> Result<TokenStream> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let fields = match &data.fields {
+                Fields::Named(fields) => &fields.named,
+                _ => return Err(Error::new_spanned(&input, "only struct with named fields can be written into an object")),
+            };
+            let mut items = vec![];
+            for field in fields {
+                let attrs = NoteAttributes::parse(&field.attrs)?;
+                let ident = field.ident.as_ref().unwrap();
+                let key = attrs.key(ident);
+                items.push(quote! {
+                    out.insert(
+                        ::notedown_ast::nodes::Literal { value: ::std::string::String::from(#key), range: None },
+                        ::notedown_ast::nodes::Literal { value: ::notedown_ast::value::IntoValue::into_value(self.#ident), range: None },
+                    );
+                })
+            }
+            quote! {
+                let mut out = ::notedown_ast::value::OrderedMap::default();
+                #(#items)*
+                ::notedown_ast::Value::Object(::std::boxed::Box::new(out))
+            }
+        }
+        Data::Enum(data) => {
+            let mut arms = vec![];
+            for variant in &data.variants {
+                if !matches!(variant.fields, Fields::Unit) {
+                    return Err(Error::new_spanned(variant, "only unit variants can be written into a string"));
+                }
+                let attrs = NoteAttributes::parse(&variant.attrs)?;
+                let ident = &variant.ident;
+                let key = attrs.variant_key(ident);
+                arms.push(quote! { Self::#ident => ::notedown_ast::Value::string(#key), })
+            }
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(_) => return Err(Error::new_spanned(&input, "union is not supported")),
+    };
+    Ok(quote! {
+        impl #impl_generics ::notedown_ast::value::IntoValue for #name #ty_generics #where_clause {
+            fn into_value(self) -> ::notedown_ast::Value {
+                #body
+            }
+        }
+    })
+}
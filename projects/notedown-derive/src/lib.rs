@@ -0,0 +1,28 @@
+//! Derive macros for `FromValue` and `IntoValue` of `notedown_ast`
+#![allow(clippy::needless_return)]
+mod attributes;
+mod from_value;
+mod into_value;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+/// Read a struct from an object or an unit enum from a string
+#[proc_macro_derive(FromValue, attributes(note))]
+pub fn derive_from_value(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match from_value::expand(input) {
+        Ok(o) => o.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Write a struct into an object or an unit enum into a string
+#[proc_macro_derive(IntoValue, attributes(note))]
+pub fn derive_into_value(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match into_value::expand(input) {
+        Ok(o) => o.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
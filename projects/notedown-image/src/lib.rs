@@ -1,4 +1,6 @@
 mod errors;
+mod preview;
 pub mod store;
 
 pub use errors::{NotedownImageError, Result};
+pub use preview::ImagePreview;
@@ -0,0 +1,34 @@
+use crate::Result;
+use image::{imageops::FilterType, GenericImageView, ImageOutputFormat};
+use std::path::Path;
+
+/// Size and thumbnail of a local image
+#[derive(Clone, Debug)]
+pub struct ImagePreview {
+    /// Width of the original image
+    pub width: u32,
+    /// Height of the original image
+    pub height: u32,
+    /// PNG bytes which fit in the given size
+    pub thumbnail: Vec<u8>,
+}
+
+impl ImagePreview {
+    /// Decode the image and shrink it into `max_size` × `max_size`
+    pub fn open(path: impl AsRef<Path>, max_size: u32) -> Result<Self> {
+        let image = image::open(path)?;
+        let (width, height) = image.dimensions();
+        let image = match width > max_size || height > max_size {
+            true => image.resize(max_size, max_size, FilterType::Triangle),
+            false => image,
+        };
+        let mut thumbnail = vec![];
+        image.write_to(&mut thumbnail, ImageOutputFormat::Png)?;
+        Ok(Self { width, height, thumbnail })
+    }
+    /// `data:image/png;base64,...`
+    #[inline]
+    pub fn to_data_url(&self) -> String {
+        format!("data:image/png;base64,{}", base64::encode(&self.thumbnail))
+    }
+}
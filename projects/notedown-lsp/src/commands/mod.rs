@@ -1,4 +1,6 @@
 use cli_clipboard::{ClipboardContext, ClipboardProvider};
+use notedown_ast::nodes::MathNode;
+use notedown_math::{math_to_mathml, math_to_mathml_svg};
 use serde_json::Value;
 use std::{collections::HashSet, lazy::SyncLazy};
 use tower_lsp::{lsp_types::*, Client};
@@ -12,7 +14,8 @@ static SERVER_COMMANDS: SyncLazy<HashSet<&'static str>> = SyncLazy::new(|| {
     let mut s = HashSet::new();
     s.insert("notedown.inner.read-clipboard");
     s.insert("notedown.inner.get-web-view");
-    s.insert("notedown.inner.post-math-svg");
+    s.insert("notedown.inner.request-math-svg");
+    s.insert("notedown.inner.request-mathml");
     s.insert("notedown.rawPaste");
     s.insert("notedown.image.save2local");
     return s;
@@ -24,18 +27,32 @@ pub async fn command_provider(p: ExecuteCommandParams, c: &Client) -> Option<Val
     match p.command.as_ref() {
         "notedown.inner.read-clipboard" => read_clipboard(c).await,
         "notedown.inner.get-web-view" => get_web_view().await,
-        "notedown.inner.request-math-svg" => request_math_svg(c).await,
+        "notedown.inner.request-math-svg" => request_math(&p.arguments, c, true).await,
+        "notedown.inner.request-mathml" => request_math(&p.arguments, c, false).await,
         _ => {
             let err = format!("Unknown command: {}", p.command);
-            c.show_message(MessageType::Error, err).await;
+            c.show_message(MessageType::ERROR, err).await;
             return None;
         }
     }
 }
 
-async fn request_math_svg(c: &Client) -> Option<Value> {
-    let _ = c;
-    None
+/// Arguments: formula, optional format (`latex`, `ascii` or `mathml`)
+///
+/// The svg only wraps the MathML, request the MathML if the client can render it.
+async fn request_math(args: &[Value], c: &Client, svg: bool) -> Option<Value> {
+    let mut math = MathNode::math_display(args.get(0)?.as_str()?.to_string());
+    if let Some(format) = args.get(1).and_then(|v| v.as_str()) {
+        math.set_format(format);
+    }
+    let rendered = if svg { math_to_mathml_svg(&math) } else { math_to_mathml(&math) };
+    match rendered {
+        Ok(o) => Some(Value::String(o)),
+        Err(e) => {
+            c.show_message(MessageType::ERROR, e).await;
+            return None;
+        }
+    }
 }
 
 async fn read_clipboard(c: &Client) -> Option<Value> {
@@ -45,7 +62,7 @@ async fn read_clipboard(c: &Client) -> Option<Value> {
     let mut ctx: ClipboardContext = match ClipboardProvider::new() {
         Ok(o) => o,
         Err(e) => {
-            c.show_message(MessageType::Error, e).await;
+            c.show_message(MessageType::ERROR, e).await;
             return None;
         }
     };
@@ -53,7 +70,7 @@ async fn read_clipboard(c: &Client) -> Option<Value> {
     let s = match ctx.get_contents() {
         Ok(o) => o,
         Err(e) => {
-            c.show_message(MessageType::Error, e).await;
+            c.show_message(MessageType::ERROR, e).await;
             return None;
         }
     };
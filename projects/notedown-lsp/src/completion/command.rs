@@ -1,22 +1,20 @@
 use super::*;
 
-pub fn build_command(cmd: &str, short: &str, long: &str) -> CompletionItem {
-    let doc = MarkupContent { kind: MarkupKind::Markdown, value: String::from(long) };
+pub fn build_command(doc: &DocumentString) -> CompletionItem {
+    let doc_md = MarkupContent { kind: MarkupKind::Markdown, value: String::from(&doc.long) };
+    let parameters = build_parameters(&doc.as_definition().parameters, " = ");
+    let insert_text = match parameters.is_empty() {
+        true => format!("\\{}", doc.cmd),
+        false => format!("\\{}({})$0", doc.cmd, parameters.join(", ")),
+    };
     CompletionItem {
-        label: format!("\\{}", cmd),
-        kind: Some(CompletionItemKind::Function),
-        detail: Some(String::from(short)),
-        documentation: Some(Documentation::MarkupContent(doc)),
-        deprecated: None,
-        preselect: None,
-        sort_text: Some(format!("0{}", cmd)),
-        filter_text: None,
-        insert_text: Some(format!("\\{}", cmd)),
-        insert_text_format: None,
-        text_edit: None,
-        additional_text_edits: None,
-        command: None,
-        data: None,
-        tags: None,
+        label: format!("\\{}", doc.cmd),
+        kind: Some(CompletionItemKind::FUNCTION),
+        detail: Some(String::from(&doc.short)),
+        documentation: Some(Documentation::MarkupContent(doc_md)),
+        sort_text: Some(format!("0{}", doc.cmd)),
+        insert_text: Some(insert_text),
+        insert_text_format: Some(InsertTextFormat::SNIPPET),
+        ..CompletionItem::default()
     }
 }
@@ -0,0 +1,27 @@
+use super::*;
+use notedown_ast::utils::text_utils::parse_emoji;
+
+/// Shortcodes offered after `:`, the full list is too long to be useful
+const EMOJI_SHORTCODES: &str = "\
+    smile laughing blush wink heart_eyes joy sweat_smile thinking sob scream sunglasses heart broken_heart \
+    +1 -1 clap pray muscle wave ok_hand eyes fire star sparkles tada rocket bulb memo book \
+    bookmark link pushpin calendar clock1 hourglass lock key bell warning x question exclamation \
+    white_check_mark heavy_check_mark no_entry construction bug zap gear wrench hammer package recycle art \
+    coffee beer pizza cake gift trophy medal_sports 100 arrow_right arrow_left arrow_up arrow_down";
+
+pub fn complete_emoji() -> Vec<CompletionItem> {
+    EMOJI_SHORTCODES
+        .split_whitespace()
+        .filter_map(|name| {
+            let code = format!(":{}:", name);
+            let emoji = parse_emoji(&code)?;
+            Some(CompletionItem {
+                label: code,
+                kind: Some(CompletionItemKind::TEXT),
+                detail: Some(emoji.grapheme.to_string()),
+                filter_text: Some(format!(":{}", name)),
+                ..CompletionItem::default()
+            })
+        })
+        .collect()
+}
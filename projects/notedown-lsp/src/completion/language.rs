@@ -0,0 +1,28 @@
+use super::*;
+use notedown_rt::SYNTAX_SET;
+
+/// Languages known by the highlighter, offered after ```` ``` ````
+pub fn complete_language() -> Vec<CompletionItem> {
+    let mut items: Vec<CompletionItem> = SYNTAX_SET
+        .syntaxes()
+        .iter()
+        .map(|syntax| {
+            let name = syntax.name.to_ascii_lowercase();
+            // syntect finds the syntax by the name or the extension
+            let label = match (name.contains(char::is_whitespace), syntax.file_extensions.first()) {
+                (true, Some(ext)) => ext.to_owned(),
+                _ => name,
+            };
+            CompletionItem {
+                label,
+                kind: Some(CompletionItemKind::ENUM_MEMBER),
+                detail: Some(syntax.name.to_owned()),
+                filter_text: Some(format!("{} {}", syntax.name, syntax.file_extensions.join(" "))),
+                ..CompletionItem::default()
+            }
+        })
+        .collect();
+    items.sort_by(|a, b| a.label.cmp(&b.label));
+    items.dedup_by(|a, b| a.label == b.label);
+    return items;
+}
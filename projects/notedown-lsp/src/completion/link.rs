@@ -0,0 +1,129 @@
+use super::*;
+use crate::{
+    hint::list_headers,
+    io::{read_url, walk_files, WORKSPACE_FOLDERS},
+};
+use notedown_ast::{nodes::ImageLink, ASTKind};
+use std::path::{Path, PathBuf};
+
+impl CompletionContext<'_> {
+    /// `[path#id]`, `[[path > id]]`, `[!path]` or `[^tag]`, `start` is the offset of the last `[`
+    pub(super) async fn complete_link(&self, start: usize) -> Option<Vec<CompletionItem>> {
+        let content = &self.text[start + 1..self.offset];
+        // replace until the end of the link
+        let rest = &self.text[self.offset..];
+        let end = match rest.find(&[']', '\n'][..]) {
+            Some(i) if rest[i..].starts_with(']') => self.offset + i,
+            _ => self.offset,
+        };
+        if let Some(tag) = content.strip_prefix('^') {
+            return Some(self.replace(self.complete_tag(), self.offset - tag.len(), end));
+        }
+        if let Some(path) = content.strip_prefix('!') {
+            let items = self.complete_path(true).await;
+            return Some(self.replace(items, self.offset - path.len(), end));
+        }
+        let separator = match self.text[..start].ends_with('[') {
+            true => '>',
+            false => '#',
+        };
+        match content.split_once(separator) {
+            Some((path, id)) => {
+                let id = id.trim_start();
+                let items = self.complete_header_id(path.trim()).await;
+                Some(self.replace(items, self.offset - id.len(), end))
+            }
+            None => {
+                let mut items = self.complete_path(false).await;
+                items.extend(self.complete_header_id("").await.into_iter().map(|mut item| {
+                    item.label = format!("{}{}", separator, item.label);
+                    return item;
+                }));
+                Some(self.replace(items, self.offset - content.len(), end))
+            }
+        }
+    }
+    /// Tags of the footnotes defined in this document
+    pub(super) fn complete_tag(&self) -> Vec<CompletionItem> {
+        let children = match self.ast.as_ref().map(|ast| &ast.value) {
+            Some(ASTKind::Statements(v)) => v,
+            _ => return vec![],
+        };
+        children
+            .iter()
+            .filter_map(|node| match &node.value {
+                ASTKind::LinkNode(SmartLink::Reference(v)) if v.is_definition() => Some(CompletionItem {
+                    label: v.tag.to_owned(),
+                    kind: Some(CompletionItemKind::REFERENCE),
+                    detail: Some(v.text.join("\n")),
+                    ..CompletionItem::default()
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+    /// Ids of the headers in the linked document, this document if the path is empty
+    async fn complete_header_id(&self, path: &str) -> Vec<CompletionItem> {
+        let text = match path.is_empty() {
+            true => self.text.to_owned(),
+            false => match self.url.join(path) {
+                Ok(url) => match FILE_STORAGE.get().read().await.read(&url) {
+                    Some(s) => s,
+                    None => read_url(&url),
+                },
+                Err(_) => return vec![],
+            },
+        };
+        list_headers(&text)
+            .into_iter()
+            .map(|(header, range, id)| CompletionItem {
+                label: id,
+                kind: Some(CompletionItemKind::REFERENCE),
+                detail: Some(text[range].trim().to_string()),
+                sort_text: Some(format!("{}", header.level)),
+                ..CompletionItem::default()
+            })
+            .collect()
+    }
+    /// Files in the workspace relative to this document
+    async fn complete_path(&self, image: bool) -> Vec<CompletionItem> {
+        let document = match self.url.to_file_path() {
+            Ok(o) => o,
+            Err(_) => return vec![],
+        };
+        let base = document.parent().unwrap_or_else(|| Path::new("/"));
+        let mut roots: Vec<PathBuf> = WORKSPACE_FOLDERS.get().read().await.iter().filter_map(|url| url.to_file_path().ok()).collect();
+        if roots.is_empty() {
+            roots.push(base.to_path_buf())
+        }
+        let mut files = vec![];
+        for root in roots {
+            walk_files(&root, &mut files)
+        }
+        files
+            .into_iter()
+            .filter(|path| *path != document)
+            .filter(|path| !image || is_image(path))
+            .map(|path| CompletionItem {
+                label: relative_path(base, &path),
+                kind: Some(CompletionItemKind::FILE),
+                documentation: image.then(|| markdown(&format!("![]({})", Url::from_file_path(&path).map(String::from).unwrap_or_default()))),
+                ..CompletionItem::default()
+            })
+            .collect()
+    }
+}
+
+fn is_image(path: &Path) -> bool {
+    path.file_name().map(|name| ImageLink::is_image_path(&name.to_string_lossy())).unwrap_or(false)
+}
+
+/// Path of `target` used in a document under `base`, separated by `/`
+fn relative_path(base: &Path, target: &Path) -> String {
+    let base: Vec<_> = base.components().collect();
+    let target: Vec<_> = target.components().collect();
+    let common = base.iter().zip(&target).take_while(|(a, b)| a == b).count();
+    let mut parts = vec![String::from(".."); base.len() - common];
+    parts.extend(target[common..].iter().map(|c| c.as_os_str().to_string_lossy().to_string()));
+    parts.join("/")
+}
@@ -1,35 +1,52 @@
 mod command;
+mod emoji;
+mod language;
+mod link;
 mod open_close;
+mod options;
 mod self_close;
 mod structural;
 
-use crate::{completion::structural::complete_table, io::FILE_STORAGE};
+use crate::{
+    completion::structural::complete_table,
+    io::{get_offset, note_parser, FILE_STORAGE},
+};
 use command::build_command;
+use notedown_ast::{
+    nodes::SmartLink,
+    traits::{ContextAware, ContextKind},
+    utils::TextIndex,
+    ASTNode,
+};
+use notedown_plugin::{
+    get_definition, CommandParameter, DocumentString, COMMAND_DOCUMENTS, COMMAND_REGISTRY, COMPONENT_REGISTRY, OPEN_CLOSE_DOCUMENTS,
+    SELF_CLOSE_DOCUMENTS,
+};
 use open_close::build_open_close;
+pub use options::{markdown, open_options, split_arguments};
 use self_close::build_self_close;
-use serde::{Deserialize, Serialize};
-use std::{collections::VecDeque, lazy::SyncLazy};
+use std::lazy::SyncLazy;
 use tower_lsp::lsp_types::{
-    CompletionItem,
-    CompletionItemKind::{self, *},
-    CompletionOptions, CompletionParams, CompletionResponse, Documentation, InsertTextFormat, MarkupContent, MarkupKind,
-    Position, WorkDoneProgressOptions,
+    CompletionItem, CompletionItemKind, CompletionOptions, CompletionParams, CompletionResponse, CompletionTextEdit, Documentation,
+    InsertTextFormat, MarkupContent, MarkupKind, Position, Range, TextEdit, Url, WorkDoneProgressOptions,
 };
 use unicode_xid::UnicodeXID;
 
 pub static COMPLETION_OPTIONS: SyncLazy<CompletionOptions> = SyncLazy::new(|| {
-    let completion_trigger = vec!['.', '\\', '[', '<'];
+    let completion_trigger = vec!['.', '\\', '[', '<', '(', '^', '#', ':', '`'];
     CompletionOptions {
         resolve_provider: Some(false),
         trigger_characters: Some(completion_trigger.iter().map(ToString::to_string).collect()),
         work_done_progress_options: WorkDoneProgressOptions { work_done_progress: Some(false) },
+        ..CompletionOptions::default()
     }
 });
 
 pub async fn completion_provider(p: CompletionParams) -> Option<CompletionResponse> {
-    let text = FILE_STORAGE.get().read().await.read(&p.text_document_position.text_document.uri);
+    let url = p.text_document_position.text_document.uri;
+    let text = FILE_STORAGE.get().read().await.read(&url);
     match text {
-        Some(s) => completion_provider_dynamic(s, p.text_document_position.position),
+        Some(s) => completion_provider_dynamic(&url, &s, p.text_document_position.position).await,
         None => {
             let c = p.context.and_then(|e| e.trigger_character).and_then(|e| e.chars().next());
             completion_provider_static(c)
@@ -54,90 +71,110 @@ fn completion_provider_static(c: Option<char>) -> Option<CompletionResponse> {
     }
 }
 
-fn completion_provider_dynamic(text: String, position: Position) -> Option<CompletionResponse> {
-    let word = get_completion_word(text, position);
-    completion_provider_static(word.chars().nth(0))
+async fn completion_provider_dynamic(url: &Url, text: &str, position: Position) -> Option<CompletionResponse> {
+    let ctx = CompletionContext::new(url, text, get_offset(text, position)?);
+    // the cursor is after the last char of the element
+    let context = match &ctx.ast {
+        Some(ast) => ast.context_aware(ctx.offset.saturating_sub(1) as u32),
+        None => ContextKind::None,
+    };
+    let items = match &context {
+        ContextKind::CommandName(cmd) => ctx.complete_command_name(cmd)?,
+        ContextKind::ArgumentKey(cmd, _) => ctx.complete_argument_key(cmd)?,
+        ContextKind::ArgumentValue(cmd, Some(key)) => ctx.complete_argument_value(cmd, key)?,
+        ContextKind::Math(_) | ContextKind::LinkText(_) => return None,
+        ContextKind::CodeBody(_) if !ctx.line.trim_start().starts_with("```") => return None,
+        _ => ctx.complete_prefix().await?,
+    };
+    Some(CompletionResponse::Array(items))
 }
 
-fn get_completion_word(text: String, tp: Position) -> String {
-    let line = tp.line as usize;
-    let num = tp.character as usize;
-    text.lines().nth(line).map(|e| get_word(e, num)).unwrap_or_default()
+/// Everything known about the cursor
+pub struct CompletionContext<'a> {
+    url: &'a Url,
+    text: &'a str,
+    /// Byte offset of the cursor
+    offset: usize,
+    /// Text from the start of the line to the cursor
+    line: &'a str,
+    index: TextIndex,
+    ast: Option<ASTNode>,
 }
 
-fn get_word(line: &str, index: usize) -> String {
-    // FIXME: panic when dis-sync!!!
-    let num = index.min(line.len());
-    let (f, e) = (&line[..num], &line[num..]);
-    let mut v = VecDeque::new();
-    for c in f.chars().rev() {
-        if c.is_xid_continue() || c == '\\' || c == '<' {
-            v.push_front(c)
-        }
-        else {
-            break;
-        }
+impl<'a> CompletionContext<'a> {
+    fn new(url: &'a Url, text: &'a str, offset: usize) -> Self {
+        let line_start = text[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        Self { url, text, offset, line: &text[line_start..offset], index: TextIndex::new(text), ast: note_parser().parse(text).ok() }
     }
-    for c in e.chars() {
-        if c.is_xid_continue() {
-            v.push_back(c)
+    /// Offset of the identifier before the cursor
+    fn word_start(&self) -> usize {
+        self.offset - self.line.chars().rev().take_while(|c| is_ident(*c)).map(char::len_utf8).sum::<usize>()
+    }
+    /// Offset of the end of the identifier after the cursor
+    fn word_end(&self) -> usize {
+        self.offset + self.text[self.offset..].chars().take_while(|c| is_ident(*c)).map(char::len_utf8).sum::<usize>()
+    }
+    /// Complete by the text before the cursor, the element may be incomplete and can't be parsed
+    async fn complete_prefix(&self) -> Option<Vec<CompletionItem>> {
+        let start = self.word_start();
+        let end = self.word_end();
+        if let Some(rest) = self.line.trim_start().strip_prefix("```") {
+            return rest.chars().all(is_ident).then(|| self.replace(language::complete_language(), start, end));
         }
-        else {
-            break;
+        let before = &self.text[..start];
+        match before.chars().last() {
+            Some('\\') => {
+                let mut items = COMPLETE_COMMANDS.to_owned();
+                items.extend(complete_table());
+                return Some(self.replace(items, start - 1, end));
+            }
+            Some('<') => return Some(self.replace(COMPLETE_COMPONENTS.to_owned(), start, end)),
+            Some(':') if before[..before.len() - 1].chars().last().map(char::is_whitespace).unwrap_or(true) => {
+                return Some(self.replace(emoji::complete_emoji(), start - 1, end));
+            }
+            _ => {}
         }
+        if let Some(i) = self.line.rfind('[').filter(|i| !self.line[*i..].contains(']')) {
+            return self.complete_link(self.offset - self.line.len() + i).await;
+        }
+        self.complete_options()
+    }
+    /// Use the items as the replacement of the text between `start` and `end`
+    fn replace(&self, items: Vec<CompletionItem>, start: usize, end: usize) -> Vec<CompletionItem> {
+        let range = self.index.get_lsp_range(start, end);
+        items.into_iter().map(|item| with_edit(item, range)).collect()
     }
-    return v.iter().collect();
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct DocumentString {
-    cmd: String,
-    short: String,
-    long: String,
+fn with_edit(mut item: CompletionItem, range: Range) -> CompletionItem {
+    let new_text = item.insert_text.take().unwrap_or_else(|| item.label.to_owned());
+    item.text_edit = Some(CompletionTextEdit::Edit(TextEdit { range, new_text }));
+    return item;
 }
 
-impl DocumentString {
-    pub fn new(cmd: &str, short: &str, long: &str) -> DocumentString {
-        Self { cmd: String::from(cmd.trim()), short: String::from(short.trim()), long: String::from(long.trim()) }
-    }
+#[inline]
+fn is_ident(c: char) -> bool {
+    c.is_xid_continue() || c == '-'
 }
 
-fn load_md_doc(input: &str) -> Vec<DocumentString> {
-    let mut out = VecDeque::new();
-    let mut cmd = "";
-    let mut short = "";
-    let mut long = String::new();
-    let mut lines = input.lines();
-    while let Some(line) = lines.next() {
-        if line.starts_with("# ") {
-            out.push_back(DocumentString::new(cmd, short, &long));
-            cmd = &line[2..line.len()];
-            short = lines.next().unwrap();
-            long = String::new()
-        }
-        else {
-            long.push_str(line);
-            long.push('\n');
-        }
-    }
-    out.push_back(DocumentString::new(cmd, short, &long));
-    out.pop_front();
-    return Vec::from(out);
+/// `key = ${1:type}` for the arguments without default value
+fn build_parameters(parameters: &[CommandParameter], eq: &str) -> Vec<String> {
+    let required = parameters.iter().filter(|p| p.default.is_none());
+    required
+        .enumerate()
+        .map(|(i, p)| match p.values() {
+            values if !values.is_empty() => format!("{}{}${{{}|{}|}}", p.name, eq, i + 1, values.join(",")),
+            _ if p.kind == "string" => format!("{}{}\"${}\"", p.name, eq, i + 1),
+            _ => format!("{}{}${{{}:{}}}", p.name, eq, i + 1, p.kind),
+        })
+        .collect()
 }
 
-pub static COMPLETE_COMMANDS: SyncLazy<Vec<CompletionItem>> = SyncLazy::new(|| {
-    let parsed = load_md_doc(include_str!("command.md"));
-    parsed.iter().map(|doc| build_command(&doc.cmd, &doc.short, &doc.long)).collect()
-});
+pub static COMPLETE_COMMANDS: SyncLazy<Vec<CompletionItem>> = SyncLazy::new(|| COMMAND_DOCUMENTS.iter().map(build_command).collect());
 
 pub static COMPLETE_COMPONENTS: SyncLazy<Vec<CompletionItem>> = SyncLazy::new(|| {
-    let open_close = load_md_doc(include_str!("open_close.md"));
-    let self_close = load_md_doc(include_str!("self_close.md"));
-    open_close
-        .iter()
-        .map(|doc| build_open_close(&doc.cmd, &doc.short, &doc.long))
-        .chain(self_close.iter().map(|doc| build_self_close(&doc.cmd, &doc.short, &doc.long)))
-        .collect()
+    let open_close = OPEN_CLOSE_DOCUMENTS.iter().map(build_open_close);
+    open_close.chain(SELF_CLOSE_DOCUMENTS.iter().map(build_self_close)).collect()
 });
 
 #[allow(dead_code)]
@@ -146,37 +183,54 @@ fn list_completion_kinds() -> Vec<CompletionItem> {
         CompletionItem { label: format!("{:?}", e), kind: Some(e), ..CompletionItem::default() }
     }
     vec![
-        item(Text),
-        item(Method),
-        item(Function),
-        item(Constructor),
-        item(Field),
-        item(Variable),
-        item(Class),
-        item(Interface),
-        item(Module),
-        item(Property),
-        item(Unit),
-        item(Value),
-        item(Enum),
-        item(Keyword),
-        item(Snippet),
-        item(Color),
-        item(File),
-        item(Reference),
-        item(Folder),
-        item(EnumMember),
-        item(Constant),
-        item(Struct),
-        item(Event),
-        item(Operator),
-        item(TypeParameter),
+        item(CompletionItemKind::TEXT),
+        item(CompletionItemKind::METHOD),
+        item(CompletionItemKind::FUNCTION),
+        item(CompletionItemKind::CONSTRUCTOR),
+        item(CompletionItemKind::FIELD),
+        item(CompletionItemKind::VARIABLE),
+        item(CompletionItemKind::CLASS),
+        item(CompletionItemKind::INTERFACE),
+        item(CompletionItemKind::MODULE),
+        item(CompletionItemKind::PROPERTY),
+        item(CompletionItemKind::UNIT),
+        item(CompletionItemKind::VALUE),
+        item(CompletionItemKind::ENUM),
+        item(CompletionItemKind::KEYWORD),
+        item(CompletionItemKind::SNIPPET),
+        item(CompletionItemKind::COLOR),
+        item(CompletionItemKind::FILE),
+        item(CompletionItemKind::REFERENCE),
+        item(CompletionItemKind::FOLDER),
+        item(CompletionItemKind::ENUM_MEMBER),
+        item(CompletionItemKind::CONSTANT),
+        item(CompletionItemKind::STRUCT),
+        item(CompletionItemKind::EVENT),
+        item(CompletionItemKind::OPERATOR),
+        item(CompletionItemKind::TYPE_PARAMETER),
     ]
 }
 
-#[test]
-fn check_yaml() {
-    println!("{:#?}", load_md_doc(include_str!("command.md")));
-    println!("{:#?}", load_md_doc(include_str!("open_close.md")));
-    println!("{:#?}", load_md_doc(include_str!("self_close.md")));
+#[tokio::test]
+async fn check_context() {
+    async fn labels(text: &str) -> Vec<String> {
+        let url = Url::parse("file:///note/test.note").unwrap();
+        let offset = text.find('|').unwrap();
+        let text = text.replace('|', "");
+        let position = TextIndex::new(&text).get_lsp_range(offset, offset).start;
+        match completion_provider_dynamic(&url, &text, position).await {
+            Some(CompletionResponse::Array(items)) => items.into_iter().map(|item| item.label).collect(),
+            _ => vec![],
+        }
+    }
+    assert!(labels("text \\im|").await.contains(&String::from("\\img")));
+    assert!(labels("你好 \\im|").await.contains(&String::from("\\img")));
+    assert!(labels("<im|").await.contains(&String::from("<img>")));
+    assert!(labels("\\img(src = \"a.png\", la|").await.contains(&String::from("layout")));
+    assert!(labels("\\img(layout = |").await.contains(&String::from("\"center\"")));
+    assert!(labels("<img layout=|").await.contains(&String::from("\"left\"")));
+    assert_eq!(labels("text [^n|\n\n[^note]: footnote").await, vec!["note"]);
+    assert!(labels("text :sm|").await.contains(&String::from(":smile:")));
+    assert!(labels("```ru|").await.contains(&String::from("rust")));
+    assert!(labels("$x^|2$").await.is_empty());
 }
@@ -0,0 +1,166 @@
+use super::*;
+use notedown_ast::{command::Command, nodes::Literal};
+
+impl CompletionContext<'_> {
+    /// `\|cmd|` or `<|cmd|>`, the arguments and the body are kept if exist
+    pub(super) fn complete_command_name(&self, cmd: &Literal<Command>) -> Option<Vec<CompletionItem>> {
+        let range = cmd.range.as_ref()?;
+        let name = cmd.value.name_range(range)?;
+        let xml = matches!(cmd.value, Command::XML(_));
+        // the name in the close tag follows the open tag
+        if !name.contains(&(self.offset - 1)) {
+            return None;
+        }
+        // only the name is typed
+        if !xml && self.text[name.end..range.end].trim().is_empty() {
+            let mut items = COMPLETE_COMMANDS.to_owned();
+            items.extend(complete_table());
+            return Some(self.replace(items, name.start - 1, name.end));
+        }
+        let registry = match xml {
+            true => &*COMPONENT_REGISTRY,
+            false => &*COMMAND_REGISTRY,
+        };
+        let items = registry
+            .iter()
+            .map(|definition| CompletionItem {
+                label: definition.name.to_owned(),
+                kind: Some(CompletionItemKind::FUNCTION),
+                detail: Some(definition.signature()),
+                documentation: Some(markdown(&definition.documentation)),
+                ..CompletionItem::default()
+            })
+            .collect();
+        Some(self.replace(items, name.start, name.end))
+    }
+    /// `\cmd(|key| = value)`, keys already used are skipped
+    pub(super) fn complete_argument_key(&self, cmd: &Literal<Command>) -> Option<Vec<CompletionItem>> {
+        let options = cmd.value.get_options()?;
+        let (key, _) = options.kvs.iter_raw().find(|(key, _)| contains(&key.range, self.offset - 1))?;
+        let range = key.range.as_ref()?;
+        let exists: Vec<_> = options.kvs.keys().filter(|k| **k != key.value).collect();
+        let items = argument_keys(cmd.value.command(), matches!(cmd.value, Command::XML(_)), &exists, "")?;
+        Some(self.replace(items, range.start, range.end))
+    }
+    /// `\cmd(key = |value|)`
+    pub(super) fn complete_argument_value(&self, cmd: &Literal<Command>, key: &str) -> Option<Vec<CompletionItem>> {
+        let options = cmd.value.get_options()?;
+        let (_, value) = options.kvs.iter_raw().find(|(k, _)| k.value == key)?;
+        let range = value.range.as_ref()?;
+        let items = argument_values(cmd.value.command(), matches!(cmd.value, Command::XML(_)), key)?;
+        Some(self.replace(items, range.start, range.end))
+    }
+    /// `\cmd(key = |` or `<cmd |`, the arguments are not closed yet
+    pub(super) fn complete_options(&self) -> Option<Vec<CompletionItem>> {
+        let (name, xml, args) = open_options(self.line)?;
+        let (_, segment) = split_arguments(args, xml);
+        match segment.split_once('=') {
+            Some((key, value)) => {
+                let items = argument_values(name, xml, key.trim())?;
+                Some(self.replace(items, self.offset - value.trim_start().len(), self.offset))
+            }
+            None => {
+                let items = argument_keys(name, xml, &[], if xml { "=" } else { " = " })?;
+                Some(self.replace(items, self.word_start(), self.word_end()))
+            }
+        }
+    }
+}
+
+fn argument_keys(name: &str, xml: bool, exists: &[&String], suffix: &str) -> Option<Vec<CompletionItem>> {
+    let definition = get_definition(name, xml)?;
+    let items = definition
+        .parameters
+        .iter()
+        .filter(|p| !exists.contains(&&p.name))
+        .map(|p| CompletionItem {
+            label: p.name.to_owned(),
+            kind: Some(CompletionItemKind::PROPERTY),
+            detail: Some(p.signature()),
+            documentation: Some(markdown(&p.documentation)),
+            insert_text: Some(format!("{}{}", p.name, suffix)),
+            ..CompletionItem::default()
+        })
+        .collect();
+    Some(items)
+}
+
+fn argument_values(name: &str, xml: bool, key: &str) -> Option<Vec<CompletionItem>> {
+    let definition = get_definition(name, xml)?;
+    let parameter = definition.parameters.iter().find(|p| p.name == key)?;
+    let items = parameter
+        .values()
+        .into_iter()
+        .map(|value| CompletionItem {
+            detail: parameter.default.as_ref().filter(|d| **d == value).map(|_| String::from("default")),
+            label: value,
+            kind: Some(CompletionItemKind::ENUM_MEMBER),
+            ..CompletionItem::default()
+        })
+        .collect();
+    Some(items)
+}
+
+/// Name of the command whose arguments are not closed before the cursor, and the text of the arguments
+/// ````md
+/// \cmd(|args|
+/// <cmd |args|
+/// ```cmd(|args|
+/// ````
+pub fn open_options(line: &str) -> Option<(&str, bool, &str)> {
+    let paren = line.rfind('(').filter(|i| !line[*i..].contains(')'));
+    let angle = line.rfind('<').filter(|i| !line[*i..].contains('>'));
+    match (paren, angle) {
+        (Some(i), a) if a.map(|a| a < i).unwrap_or(true) => {
+            let head = &line[..i];
+            let name = &head[head.len() - head.chars().rev().take_while(|c| is_ident(*c)).map(char::len_utf8).sum::<usize>()..];
+            let prefix = &head[..head.len() - name.len()];
+            if name.is_empty() || !(prefix.ends_with('\\') || prefix.trim_start().starts_with("```")) {
+                return None;
+            }
+            Some((name, false, &line[i + 1..]))
+        }
+        (_, Some(i)) => {
+            let rest = &line[i + 1..];
+            let name = &rest[..rest.chars().take_while(|c| is_ident(*c)).map(char::len_utf8).sum::<usize>()];
+            let args = &rest[name.len()..];
+            if name.is_empty() || !args.starts_with(char::is_whitespace) {
+                return None;
+            }
+            Some((name, true, args))
+        }
+        _ => None,
+    }
+}
+
+/// Split the arguments into the finished ones and the one under the cursor, separators in strings or brackets are skipped
+pub fn split_arguments(args: &str, xml: bool) -> (&str, &str) {
+    let mut depth = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+    let mut split = 0;
+    for (i, c) in args.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            _ if quoted => {}
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 && !xml => split = i + 1,
+            _ if c.is_whitespace() && depth == 0 && xml => split = i + 1,
+            _ => {}
+        }
+    }
+    (&args[..split], &args[split..])
+}
+
+#[inline]
+fn contains(range: &Option<std::ops::Range<usize>>, offset: usize) -> bool {
+    range.as_ref().map(|r| r.contains(&offset)).unwrap_or(false)
+}
+
+#[inline]
+pub fn markdown(value: &str) -> Documentation {
+    Documentation::MarkupContent(MarkupContent { kind: MarkupKind::Markdown, value: String::from(value) })
+}
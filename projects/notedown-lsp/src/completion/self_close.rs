@@ -1,23 +1,17 @@
 use super::*;
 
-pub fn build_self_close(cmd: &str, short: &str, long: &str) -> CompletionItem {
-    let doc = MarkupContent { kind: MarkupKind::Markdown, value: String::from(long) };
+pub fn build_self_close(doc: &DocumentString) -> CompletionItem {
+    let doc_md = MarkupContent { kind: MarkupKind::Markdown, value: String::from(&doc.long) };
+    let parameters: String = build_parameters(&doc.as_definition().parameters, "=").iter().map(|p| format!(" {}", p)).collect();
     CompletionItem {
-        label: format!("<{}>", cmd),
-        kind: Some(CompletionItemKind::Class),
-        detail: Some(String::from(short)),
-        documentation: Some(Documentation::MarkupContent(doc)),
-        deprecated: None,
-        preselect: None,
-        sort_text: Some(format!("0{}", cmd)),
-        filter_text: None,
-        insert_text: Some(format!("{cmd}$1/>$0", cmd = cmd)),
-        insert_text_format: Some(InsertTextFormat::Snippet),
-        text_edit: None,
-        // text_edit: Some(CompletionTextEdit::Edit(TextEdit { range, new_text: "\\comment".to_string() })),
-        additional_text_edits: None,
-        command: None,
-        data: None,
-        tags: None,
+        label: format!("<{}>", doc.cmd),
+        kind: Some(CompletionItemKind::CLASS),
+        detail: Some(String::from(&doc.short)),
+        documentation: Some(Documentation::MarkupContent(doc_md)),
+        sort_text: Some(format!("0{}", doc.cmd)),
+        filter_text: Some(String::from(&doc.cmd)),
+        insert_text: Some(format!("{cmd}{parameters}/>$0", cmd = doc.cmd, parameters = parameters)),
+        insert_text_format: Some(InsertTextFormat::SNIPPET),
+        ..CompletionItem::default()
     }
 }
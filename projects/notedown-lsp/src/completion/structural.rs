@@ -17,21 +17,14 @@ fn build_table(a: usize, b: usize) -> CompletionItem {
     insert_text.extend_one(String::new());
     CompletionItem {
         label: format!("\u{200B}Table {} × {}", a, b),
-        kind: Some(CompletionItemKind::Keyword),
+        kind: Some(CompletionItemKind::KEYWORD),
         detail: Some(short),
         // documentation: Some(Documentation::MarkupContent(doc)),
-        documentation: None,
-        deprecated: None,
-        preselect: None,
         sort_text: Some(format!("9{}{}", a, b)),
         filter_text: Some(format!("\\table{}x{}", a, b)),
         insert_text: Some(insert_text.join("\n")),
-        insert_text_format: Some(InsertTextFormat::Snippet),
-        text_edit: None,
-        additional_text_edits: None,
-        command: None,
-        data: None,
-        tags: None,
+        insert_text_format: Some(InsertTextFormat::SNIPPET),
+        ..CompletionItem::default()
     }
 }
 
@@ -0,0 +1,50 @@
+use super::*;
+use notedown_ast::Result;
+use notedown_rt::{FileMeta, IncludeConfig, Parser, VMFileSystem};
+
+impl DiagnosticContext<'_> {
+    /// Included notes or sections which can not be found, and include cycles
+    ///
+    /// The included notes are read from the disk, they are checked again after saved.
+    pub(super) async fn check_includes(&mut self, nodes: &[&ASTNode]) {
+        let includes: Vec<&ASTNode> =
+            nodes.iter().copied().filter(|n| matches!(&n.value, ASTKind::Command(cmd) if cmd.is("include") || cmd.is("import"))).collect();
+        if includes.is_empty() {
+            return;
+        }
+        let parser: Parser = parse_note;
+        let config = IncludeConfig::default();
+        let mut fs = VMFileSystem::new(self.url.to_owned());
+        fs.load_text(self.url.to_owned(), self.text);
+        let _ = fs.update_ast(self.url.to_owned(), &parser).await;
+        // errors of the included notes are reported when they are opened
+        let _ = fs.load_includes(self.url, &parser, &config).await;
+        let url = self.url;
+        self.dependencies.extend(fs.get_urls().into_iter().filter(|u| u != url));
+        for node in includes {
+            let mut expanded = node.to_owned();
+            for error in fs.expand_includes(self.url, &mut expanded, &config) {
+                let file = match &error.file {
+                    Some(s) if s != self.url => s,
+                    _ => {
+                        self.push_error(&error);
+                        continue;
+                    }
+                };
+                // found in a nested include, shown at the include command of the document
+                let mut diagnostic = error.build_diagnostic(&self.index);
+                if let Some(range) = &node.range {
+                    diagnostic.range = self.index.get_lsp_range(range.start, range.end)
+                }
+                let text = TextIndex::new(fs.get_text(file).unwrap_or_default());
+                let location = Location { uri: file.to_owned(), range: error.get_lsp_range(&text) };
+                diagnostic.related_information = Some(vec![DiagnosticRelatedInformation { location, message: String::from("Included here") }]);
+                self.diagnostics.push(diagnostic)
+            }
+        }
+    }
+}
+
+fn parse_note(text: &str, _: &mut FileMeta) -> Result<ASTNode> {
+    note_parser().parse(text)
+}
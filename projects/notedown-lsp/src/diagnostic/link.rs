@@ -0,0 +1,113 @@
+use super::*;
+use crate::hint::list_headers;
+use notedown_ast::nodes::SmartLink;
+use std::path::Path;
+
+impl DiagnosticContext<'_> {
+    /// Local files and headers which can not be found
+    pub(super) async fn check_links(&mut self, nodes: &[&ASTNode]) {
+        for node in nodes {
+            let (link, range) = match (&node.value, &node.range) {
+                (ASTKind::LinkNode(link), Some(range)) => (link, range),
+                _ => continue,
+            };
+            match link {
+                SmartLink::Normal(v) if is_local(&v.src) => match v.src.split_once('#') {
+                    Some((path, id)) => self.check_target(path, Some(id), range).await,
+                    None => self.check_target(&v.src, None, range).await,
+                },
+                SmartLink::Image(v) if is_local(&v.source) => self.check_target(&v.source, None, range).await,
+                SmartLink::TwoWay(v) => self.check_target(v.get_link(), v.get_id(), range).await,
+                _ => {}
+            }
+        }
+    }
+    /// `[path#id]`, the path is relative to the document and the id is the slug of a header
+    async fn check_target(&mut self, path: &str, id: Option<&str>, span: &Span<usize>) {
+        let document = match path.is_empty() {
+            true => self.url.to_owned(),
+            false => match self.url.join(path) {
+                Ok(o) => without_fragment(&o),
+                Err(_) => return,
+            },
+        };
+        if document.scheme() != "file" {
+            return;
+        }
+        let text = match &document == self.url {
+            true => self.text.to_owned(),
+            false => {
+                self.dependencies.insert(document.to_owned());
+                match FILE_STORAGE.get().read().await.read(&document) {
+                    Some(s) => s,
+                    None if document.to_file_path().map(|p| p.exists()).unwrap_or(false) => read_url(&document),
+                    None => {
+                        self.push(span, DiagnosticSeverity::WARNING, "unresolved-link", format!("Can not find file `{}`", path));
+                        return;
+                    }
+                }
+            }
+        };
+        let id = match id.map(str::trim) {
+            Some(s) if !s.is_empty() => s,
+            _ => return,
+        };
+        if list_headers(&text).iter().any(|(_, _, slug)| slug == id) {
+            return;
+        }
+        let message = format!("Can not find header `#{}`", id);
+        let external = &document != self.url;
+        let diagnostic = self.push(span, DiagnosticSeverity::WARNING, "unresolved-header", message);
+        if external {
+            let location = Location { uri: document, range: Range::default() };
+            let related = DiagnosticRelatedInformation { location, message: format!("Headers of `{}`", path) };
+            diagnostic.related_information = Some(vec![related]);
+        }
+    }
+    /// References to undefined footnotes, unused and duplicate definitions
+    pub(super) fn check_footnotes(&mut self, nodes: &[&ASTNode]) {
+        let mut definitions: Vec<(&str, &Span<usize>)> = vec![];
+        let mut references: Vec<(&str, &Span<usize>)> = vec![];
+        for node in nodes {
+            let (tag, range) = match (&node.value, &node.range) {
+                (ASTKind::LinkNode(SmartLink::Reference(v)), Some(range)) => (v, range),
+                _ => continue,
+            };
+            if !tag.is_definition() {
+                // `[^tag][text]` defines the footnote inline
+                if tag.text.is_empty() {
+                    references.push((&tag.tag, range))
+                }
+                continue;
+            }
+            match definitions.iter().find(|(name, _)| *name == tag.tag) {
+                Some((_, first)) => {
+                    let location = Location { uri: self.url.to_owned(), range: self.index.get_lsp_range(first.start, first.end) };
+                    let message = format!("Footnote `[^{}]` is already defined", tag.tag);
+                    let diagnostic = self.push(range, DiagnosticSeverity::WARNING, "duplicate-footnote", message);
+                    let related = DiagnosticRelatedInformation { location, message: String::from("First defined here") };
+                    diagnostic.related_information = Some(vec![related]);
+                }
+                None => definitions.push((&tag.tag, range)),
+            }
+        }
+        for (tag, range) in &references {
+            if !definitions.iter().any(|(name, _)| name == tag) {
+                self.push(range, DiagnosticSeverity::WARNING, "undefined-footnote", format!("Footnote `[^{}]` is not defined", tag));
+            }
+        }
+        for (tag, range) in &definitions {
+            if !references.iter().any(|(name, _)| name == tag) {
+                let message = format!("Footnote `[^{}]` is never used", tag);
+                let diagnostic = self.push(range, DiagnosticSeverity::HINT, "unused-footnote", message);
+                diagnostic.tags = Some(vec![DiagnosticTag::UNNECESSARY]);
+            }
+        }
+    }
+}
+
+/// Targets which look like a path or a header id, other texts in brackets are not checked
+fn is_local(src: &str) -> bool {
+    let path = src.split('#').next().unwrap_or_default();
+    src.starts_with(&['#', '.', '/'][..]) || path.contains('/') || Path::new(path).extension().is_some()
+}
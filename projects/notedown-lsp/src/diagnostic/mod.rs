@@ -1,100 +1,146 @@
+mod include;
+mod link;
 mod toc;
 
 pub use toc::ToToc;
 
-use crate::io::read_url;
+use crate::io::{note_parser, read_text, read_url, DEPENDENCIES, FILE_STORAGE, LINT_CONFIG, WORKSPACE_INDEX};
+use notedown_ast::{utils::TextIndex, ASTKind, ASTNode, NoteError};
+use notedown_parser::NotedownParser;
+use notedown_rt::{check_commands, check_lints, flatten, Lint};
+use std::{collections::HashSet, ops::Range as Span};
 use tower_lsp::lsp_types::*;
 
-pub fn diagnostics_provider(url: &Url) -> Vec<Diagnostic> {
-    let _ = url;
+/// Check the document, the files it links to are recorded as its dependencies
+pub async fn diagnostics_provider(url: &Url) -> Vec<Diagnostic> {
+    let text = read_text(url).await;
+    let mut ctx = DiagnosticContext::new(url, &text);
+    let parser = NotedownParser { file_url: Some(url.to_owned()), ..note_parser() };
+    match parser.parse(&text) {
+        Ok(ast) => {
+            WORKSPACE_INDEX.get().write().await.update(url, &ast);
+            let mut nodes = vec![];
+            flatten(&ast, &mut nodes);
+            for lint in check_commands(&nodes) {
+                ctx.push_lint(&lint)
+            }
+            ctx.check_links(&nodes).await;
+            ctx.check_includes(&nodes).await;
+            ctx.check_footnotes(&nodes);
+            for lint in check_lints(&text, &ast, &nodes, &*LINT_CONFIG.get().read().await) {
+                ctx.push_lint(&lint)
+            }
+        }
+        Err(e) => ctx.push_error(&e),
+    }
+    for e in parser.take_errors() {
+        ctx.push_error(&e)
+    }
+    DEPENDENCIES.get().write().await.insert(url.to_owned(), ctx.dependencies);
+    return ctx.diagnostics;
+}
 
-    return vec![
-        Diagnostic {
-            range: Range::new(Position::new(0, 0), Position::new(0, 100)),
-            severity: Some(DiagnosticSeverity::Error),
-            code: None,
-            source: Some("sourceeee ".to_string()),
-            message: "messsssage ".to_string(),
-            related_information: None,
-            tags: None,
-        },
-        Diagnostic {
-            range: Range::new(Position::new(1, 0), Position::new(1, 100)),
-            severity: Some(DiagnosticSeverity::Warning),
-            code: None,
-            source: Some("sourceeee ".to_string()),
-            message: "messsssage ".to_string(),
-            related_information: None,
-            tags: None,
-        },
-        Diagnostic {
-            range: Range::new(Position::new(2, 0), Position::new(2, 100)),
-            severity: Some(DiagnosticSeverity::Information),
-            code: None,
-            source: Some("sourceeee ".to_string()),
-            message: "messsssage ".to_string(),
-            related_information: None,
-            tags: None,
-        },
-        Diagnostic {
-            range: Range::new(Position::new(3, 0), Position::new(3, 100)),
-            severity: Some(DiagnosticSeverity::Hint),
-            code: None,
-            source: Some("sourceeee ".to_string()),
-            message: "messsssage ".to_string(),
-            related_information: None,
-            tags: None,
-        },
-        Diagnostic {
-            range: Range::new(Position::new(4, 0), Position::new(4, 100)),
-            severity: Some(DiagnosticSeverity::Hint),
-            code: None,
-            source: Some("sourceeee ".to_string()),
-            message: "messsssage ".to_string(),
-            related_information: None,
-            tags: Some(vec![DiagnosticTag::Unnecessary]),
-        },
-        Diagnostic {
-            range: Range::new(Position::new(5, 0), Position::new(5, 100)),
-            severity: Some(DiagnosticSeverity::Hint),
-            code: None,
-            source: Some("sourceeee ".to_string()),
-            message: "messsssage ".to_string(),
-            related_information: None,
-            tags: Some(vec![DiagnosticTag::Deprecated]),
-        },
-    ];
+/// Documents linking to the file, they should be checked again after the file changed
+pub async fn dependents(url: &Url) -> Vec<Url> {
+    let document = without_fragment(url);
+    let dependencies = DEPENDENCIES.get().read().await;
+    dependencies.iter().filter(|(k, v)| **k != document && v.contains(&document)).map(|(k, _)| k.to_owned()).collect()
 }
 
-#[allow(dead_code)]
-pub fn comma_problems(url: &Url) -> Vec<Diagnostic> {
-    let mut out = vec![];
-    for (line, s) in read_url(&url).lines().enumerate() {
-        let mut chars = s.chars().enumerate();
-        while let Some((pos, c)) = chars.next() {
-            match c {
-                ',' | '.' | ':' | '!' | '?' => {
-                    if let Some(c) = chars.next() {
-                        if c.1 != ' ' {
-                            let d = Diagnostic {
-                                range: Range::new(
-                                    Position::new(line as u64, pos as u64),
-                                    Position::new(line as u64, pos as u64 + 2),
-                                ),
-                                severity: Some(DiagnosticSeverity::Error),
-                                code: None,
-                                source: Some("sourceeee ".to_string()),
-                                message: "messsssage ".to_string(),
-                                related_information: None,
-                                tags: None,
-                            };
-                            out.push(d)
-                        }
-                    }
-                }
-                _ => {}
-            }
-        }
+#[inline]
+fn without_fragment(url: &Url) -> Url {
+    let mut url = url.to_owned();
+    url.set_fragment(None);
+    return url;
+}
+
+/// Everything known about the checked document
+pub struct DiagnosticContext<'a> {
+    url: &'a Url,
+    text: &'a str,
+    index: TextIndex,
+    diagnostics: Vec<Diagnostic>,
+    /// Files linked by the document
+    dependencies: HashSet<Url>,
+}
+
+impl<'a> DiagnosticContext<'a> {
+    fn new(url: &'a Url, text: &'a str) -> Self {
+        Self { url, text, index: TextIndex::new(text), diagnostics: vec![], dependencies: HashSet::new() }
+    }
+    fn push_error(&mut self, error: &NoteError) {
+        self.diagnostics.push(error.build_diagnostic(&self.index))
+    }
+    /// Push a diagnostic with the stable `code`, returns it for more information
+    fn push(&mut self, span: &Span<usize>, severity: DiagnosticSeverity, code: &str, message: String) -> &mut Diagnostic {
+        self.diagnostics.push(Diagnostic {
+            range: self.index.get_lsp_range(span.start, span.end),
+            severity: Some(severity),
+            code: Some(NumberOrString::String(code.to_string())),
+            source: Some("notedown".to_string()),
+            message,
+            ..Diagnostic::default()
+        });
+        self.diagnostics.last_mut().unwrap()
+    }
+    /// The fixes are kept in `data` as a list of `TextEdit`
+    fn push_lint(&mut self, lint: &Lint) {
+        let edits: Vec<_> = lint
+            .fixes
+            .iter()
+            .map(|fix| TextEdit { range: self.index.get_lsp_range(fix.range.start, fix.range.end), new_text: fix.new_text.to_owned() })
+            .collect();
+        let diagnostic = self.push(&lint.range, lint.severity, lint.rule, lint.message.to_owned());
+        diagnostic.data = (!edits.is_empty()).then(|| serde_json::to_value(edits).unwrap_or_default());
+    }
+}
+
+#[tokio::test]
+async fn check_diagnostics() {
+    use crate::io::{initialize_global_storages, FileStateUpdate, SLUG_SCHEME};
+    async fn codes(name: &str, text: &str) -> Vec<String> {
+        let url = Url::parse("file:///note/").unwrap().join(name).unwrap();
+        let document = TextDocumentItem::new(url.to_owned(), String::from("note"), 0, String::from(text));
+        FILE_STORAGE.get().write().await.update(DidOpenTextDocumentParams { text_document: document });
+        let diagnostics = diagnostics_provider(&url).await;
+        diagnostics
+            .into_iter()
+            .filter_map(|d| match d.code {
+                Some(NumberOrString::String(s)) => Some(s),
+                _ => None,
+            })
+            .collect()
     }
-    return out;
+    initialize_global_storages();
+    assert_eq!(codes("syntax.note", "<note>body</other>").await, vec!["syntax-error"]);
+    assert_eq!(codes("command.note", "\\unknown text").await, vec!["unknown-command"]);
+    assert_eq!(codes("option.note", "\\toc(max_depth = \"a\")").await, vec!["type-mismatch"]);
+    assert_eq!(codes("choice.note", "\\img(\"a.png\", layout = \"top\", size = 1)").await, vec!["type-mismatch", "unknown-option"]);
+    assert!(codes("valid.note", "\\img(\"a.png\", layout = \"left\")\n\n[#title]\n\n# Title").await.is_empty());
+    assert!(codes("set.note", "\\set[name]: value\n\n\\set(width = 100, title = \"Notes\")").await.is_empty());
+    assert_eq!(codes("footnote.note", "text[^a]\n\n[^b]: text").await, vec!["undefined-footnote", "unused-footnote"]);
+    assert_eq!(codes("header.note", "[#missing]\n\n# Title").await, vec!["unresolved-header"]);
+    assert_eq!(codes("link.note", "[./missing.note]").await, vec!["unresolved-link"]);
+    assert_eq!(codes("slug.note", "[#中文-标题]\n\n# 中文 标题").await, vec!["unresolved-header"]);
+    *SLUG_SCHEME.get().write().unwrap() = notedown_ast::traits::SlugScheme::Unicode;
+    assert!(codes("slug.note", "[#中文-标题]\n\n# 中文 标题").await.is_empty());
+    *SLUG_SCHEME.get().write().unwrap() = Default::default();
+    let missing = Url::parse("file:///note/missing.note").unwrap();
+    assert_eq!(dependents(&missing).await, vec![Url::parse("file:///note/link.note").unwrap()]);
+    assert_eq!(codes("include.note", "\\include[./missing.note]").await, vec!["runtime-error"]);
+    // the cycle is found in the included note, and shown at the include command
+    let root = std::env::temp_dir().join("notedown-lsp-include");
+    std::fs::create_dir_all(&root).unwrap();
+    std::fs::write(root.join("part.note"), "## Part\n\n\\include[./main.note]").unwrap();
+    let main = Url::from_file_path(root.join("main.note")).unwrap();
+    let document = TextDocumentItem::new(main.to_owned(), String::from("note"), 0, String::from("# Main\n\n\\include[./part.note]"));
+    FILE_STORAGE.get().write().await.update(DidOpenTextDocumentParams { text_document: document });
+    let diagnostics = diagnostics_provider(&main).await;
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].message.contains("Include cycle detected"));
+    assert_eq!(diagnostics[0].range.start.line, 2);
+    let related = diagnostics[0].related_information.to_owned().unwrap_or_default();
+    assert_eq!(related[0].location.uri, Url::from_file_path(root.join("part.note")).unwrap());
+    assert_eq!(related[0].location.range.start.line, 2);
+    assert_eq!(dependents(&related[0].location.uri).await, vec![main]);
 }
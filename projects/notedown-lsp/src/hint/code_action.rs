@@ -0,0 +1,388 @@
+use super::list_headers;
+use crate::io::{get_offset, note_parser, read_url, FILE_STORAGE};
+use notedown_ast::{nodes::SmartLink, traits::ContextAware, utils::TextIndex, ASTNode};
+use std::{collections::HashMap, ops::Range, path::Path};
+use tower_lsp::lsp_types::*;
+
+/// Kinds of the actions, the client may only ask for some of them
+pub const CODE_ACTION_KINDS: &[CodeActionKind] =
+    &[CodeActionKind::QUICKFIX, CodeActionKind::REFACTOR_EXTRACT, CodeActionKind::REFACTOR_REWRITE];
+
+/// Fix the diagnostics published by the server, and refactor the element under the cursor
+pub async fn code_action_provider(p: CodeActionParams) -> Option<CodeActionResponse> {
+    let url = p.text_document.uri;
+    let text = FILE_STORAGE.get().read().await.read(&url)?;
+    let start = get_offset(&text, p.range.start)?;
+    let mut actions: Vec<CodeAction> = vec![];
+    let ast = note_parser().parse(&text).ok();
+    for diagnostic in p.context.diagnostics.iter().filter(|d| d.source.as_deref() == Some("notedown")) {
+        actions.extend(quick_fix(&url, &text, ast.as_ref(), diagnostic).await)
+    }
+    if let Some(ast) = &ast {
+        actions.extend(bare_link_to_link(&url, &text, ast, start));
+    }
+    actions.extend(extract_section(&url, &text, start).await);
+    actions.extend(table_to_list(&url, &text, start));
+    actions.extend(list_to_table(&url, &text, start));
+    Some(filter_kinds(actions, &p.context.only))
+}
+
+/// Keep the actions of the kinds asked by the client, `refactor` contains `refactor.extract`
+fn filter_kinds(mut actions: Vec<CodeAction>, only: &Option<Vec<CodeActionKind>>) -> CodeActionResponse {
+    if let Some(only) = only {
+        actions.retain(|a| a.kind.as_ref().map(|k| only.iter().any(|o| k.as_str().starts_with(o.as_str()))).unwrap_or(false))
+    }
+    actions.into_iter().map(CodeActionOrCommand::CodeAction).collect()
+}
+
+async fn quick_fix(url: &Url, text: &str, ast: Option<&ASTNode>, diagnostic: &Diagnostic) -> Option<CodeAction> {
+    let code = match &diagnostic.code {
+        Some(NumberOrString::String(s)) => s.as_str(),
+        _ => return None,
+    };
+    let mut action = match code {
+        "unresolved-header" => create_header(url, text, &link_at(text, ast?, diagnostic)?).await?,
+        "unresolved-link" => create_file(url, &link_at(text, ast?, diagnostic)?)?,
+        "undefined-footnote" => define_footnote(url, text, &link_at(text, ast?, diagnostic)?)?,
+        // the lints keep the fixes in `data`
+        _ => {
+            let edits: Vec<TextEdit> = serde_json::from_value(diagnostic.data.to_owned()?).ok()?;
+            let title = format!("Fix: {}", diagnostic.message);
+            code_action(title, CodeActionKind::QUICKFIX, changes(url, edits))
+        }
+    };
+    action.diagnostics = Some(vec![diagnostic.to_owned()]);
+    action.is_preferred = Some(true);
+    Some(action)
+}
+
+/// `[[path#id]]` or `[#id]`, append the header to the linked document
+async fn create_header(url: &Url, text: &str, link: &SmartLink) -> Option<CodeAction> {
+    let (path, id) = link_target(link)?;
+    let id = id.filter(|s| !s.is_empty())?;
+    let document = match path.is_empty() {
+        true => url.to_owned(),
+        false => without_fragment(url.join(&path).ok()?),
+    };
+    let target = match &document == url {
+        true => text.to_owned(),
+        false => match FILE_STORAGE.get().read().await.read(&document) {
+            Some(s) => s,
+            None => read_url(&document),
+        },
+    };
+    let header = new_header(&target, &id);
+    let title = format!("Create header `#{}`", id);
+    Some(code_action(title, CodeActionKind::QUICKFIX, changes(&document, vec![append(&target, &header)])))
+}
+
+/// `[./path.note]`, create the note and the linked header
+fn create_file(url: &Url, link: &SmartLink) -> Option<CodeAction> {
+    let (path, id) = link_target(link)?;
+    if Path::new(&path).extension().map(|e| e != "note").unwrap_or(true) {
+        return None;
+    }
+    let document = without_fragment(url.join(&path).ok()?);
+    let text = id.filter(|s| !s.is_empty()).map(|id| new_header("", &id)).unwrap_or_default();
+    let title = format!("Create file `{}`", path);
+    Some(code_action(title, CodeActionKind::QUICKFIX, create(document, text)))
+}
+
+/// Header appended to the text whose id is `id`, the id is given by `\args` if the title can not produce it
+fn new_header(text: &str, id: &str) -> String {
+    let header = format!("# {}\n", id.replace('-', " "));
+    // the parser gives the id with the same slug scheme and numbering as the links are checked
+    match list_headers(&format!("{}\n\n{}", text, header)).last() {
+        Some((_, _, new)) if new == id => header,
+        _ => format!("\\args(id = \"{}\")\n{}", id, header),
+    }
+}
+
+/// `[^tag]`, append the definition to the end of the document
+fn define_footnote(url: &Url, text: &str, link: &SmartLink) -> Option<CodeAction> {
+    let tag = match link {
+        SmartLink::Reference(v) => &v.tag,
+        _ => return None,
+    };
+    let title = format!("Define footnote `[^{}]`", tag);
+    let edit = append(text, &format!("[^{}]: \n", tag));
+    Some(code_action(title, CodeActionKind::QUICKFIX, changes(url, vec![edit])))
+}
+
+/// `https://example.com` to `[example.com][https://example.com]`
+fn bare_link_to_link(url: &Url, text: &str, ast: &ASTNode, offset: usize) -> Option<CodeAction> {
+    let context = ast.context_aware(offset as u32);
+    let link = context.link()?;
+    let src = match &link.value {
+        SmartLink::Normal(v) if v.is_bare => &v.src,
+        _ => return None,
+    };
+    let range = link.range.to_owned()?;
+    let name = src.split_once("://").map(|(_, rest)| rest).unwrap_or(src).trim_end_matches('/');
+    let edit = replace(text, range, format!("[{}][{}]", name, src));
+    Some(code_action(String::from("Convert to link"), CodeActionKind::REFACTOR_REWRITE, changes(url, vec![edit])))
+}
+
+/// Move the section of the header under the cursor to `./slug.note`, and include it back
+async fn extract_section(url: &Url, text: &str, offset: usize) -> Option<CodeAction> {
+    let headers = list_headers(text);
+    let index = headers.iter().position(|(_, range, _)| range.start <= offset && offset <= range.end)?;
+    let (header, range, slug) = &headers[index];
+    if slug.is_empty() {
+        return None;
+    }
+    let end = headers[index + 1..].iter().find(|(h, _, _)| h.level <= header.level).map(|(_, r, _)| r.start).unwrap_or(text.len());
+    let path = format!("./{}.note", slug);
+    let document = url.join(&path).ok()?;
+    let exists = FILE_STORAGE.get().read().await.read(&document).is_some() || document.to_file_path().map(|p| p.exists()).unwrap_or(false);
+    if exists {
+        return None;
+    }
+    let section = &text[range.start..end];
+    let content = section.trim_end();
+    let rest = match &section[content.len()..] {
+        "" => "\n",
+        s => s,
+    };
+    let mut edit = create(document, format!("{}\n", content));
+    let include = replace(text, range.start..end, format!("\\include[{}]{}", path, rest));
+    if let Some(DocumentChanges::Operations(operations)) = &mut edit.document_changes {
+        operations.push(document_edit(url, vec![include]))
+    }
+    let title = format!("Extract section to `{}`", path);
+    Some(code_action(title, CodeActionKind::REFACTOR_EXTRACT, edit))
+}
+
+/// Each row becomes an item, the cells are separated by `|`
+fn table_to_list(url: &Url, text: &str, offset: usize) -> Option<CodeAction> {
+    let (range, lines) = block_lines(text, offset, |line| line.trim_start().starts_with('|'))?;
+    let items: Vec<String> = lines
+        .iter()
+        .map(|line| split_cells(line.trim().trim_start_matches('|')))
+        .filter(|cells| !is_separator(cells))
+        .map(|cells| format!("- {}", cells.join(" | ")))
+        .collect();
+    let edit = replace(text, range, items.join("\n"));
+    Some(code_action(String::from("Convert table to list"), CodeActionKind::REFACTOR_REWRITE, changes(url, vec![edit])))
+}
+
+/// The first item is the head, the cells of items are separated by `|`
+fn list_to_table(url: &Url, text: &str, offset: usize) -> Option<CodeAction> {
+    let (range, lines) = block_lines(text, offset, |line| list_item(line).is_some())?;
+    let rows: Vec<Vec<String>> = lines.iter().filter_map(|line| list_item(line)).map(split_cells).collect();
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(1);
+    let row = |cells: &[String]| {
+        let mut out = String::from("|");
+        for i in 0..columns {
+            out.push_str(&format!(" {} |", cells.get(i).map(String::as_str).unwrap_or_default()));
+        }
+        return out;
+    };
+    let mut table = vec![row(&rows[0]), format!("|{}", " --- |".repeat(columns))];
+    table.extend(rows[1..].iter().map(|cells| row(cells)));
+    let edit = replace(text, range, table.join("\n"));
+    Some(code_action(String::from("Convert list to table"), CodeActionKind::REFACTOR_REWRITE, changes(url, vec![edit])))
+}
+
+/// Text after the mark of `- item`, `+ item` or `1. item`
+fn list_item(line: &str) -> Option<&str> {
+    let line = line.trim_start();
+    let rest = match line.strip_prefix(&['-', '+'][..]) {
+        Some(s) => s,
+        None => {
+            let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+            line[digits..].strip_prefix('.').filter(|_| digits > 0)?
+        }
+    };
+    match rest.starts_with(' ') {
+        true => Some(rest.trim()),
+        false => None,
+    }
+}
+
+/// Cells of a row without the leading `|`, `\|` is not a separator
+fn split_cells(row: &str) -> Vec<String> {
+    let mut cells = vec![];
+    let mut cell = String::new();
+    let mut chars = row.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                cell.push(c);
+                cell.extend(chars.next())
+            }
+            '|' => cells.push(std::mem::take(&mut cell)),
+            _ => cell.push(c),
+        }
+    }
+    cells.push(cell);
+    let mut cells: Vec<String> = cells.into_iter().map(|s| s.trim().to_string()).collect();
+    if cells.len() > 1 && cells.last().map(String::is_empty).unwrap_or(false) {
+        cells.pop();
+    }
+    return cells;
+}
+
+/// `| --- | :-: |`
+fn is_separator(cells: &[String]) -> bool {
+    cells.iter().all(|s| !s.is_empty() && s.chars().all(|c| matches!(c, '-' | ':' | ' ')))
+}
+
+/// The lines around the offset which match the condition, the range excludes the last line break
+fn block_lines(text: &str, offset: usize, matches: impl Fn(&str) -> bool) -> Option<(Range<usize>, Vec<&str>)> {
+    let mut lines = vec![];
+    let mut start = 0;
+    for line in text.split_inclusive('\n') {
+        lines.push((start, line.trim_end_matches(&['\r', '\n'][..])));
+        start += line.len();
+    }
+    let index = lines.iter().rposition(|(start, _)| *start <= offset)?;
+    if !matches(lines[index].1) {
+        return None;
+    }
+    let first = lines[..index].iter().rposition(|(_, s)| !matches(s)).map(|i| i + 1).unwrap_or(0);
+    let last = lines[index..].iter().position(|(_, s)| !matches(s)).map(|i| index + i - 1).unwrap_or(lines.len() - 1);
+    let range = lines[first].0..lines[last].0 + lines[last].1.len();
+    Some((range, lines[first..=last].iter().map(|(_, s)| *s).collect()))
+}
+
+/// The link under the start of the diagnostic, link diagnostics cover the whole link
+fn link_at(text: &str, ast: &ASTNode, diagnostic: &Diagnostic) -> Option<SmartLink> {
+    let offset = get_offset(text, diagnostic.range.start)?;
+    let context = ast.context_aware(offset as u32);
+    context.link().map(|link| link.value.to_owned())
+}
+
+/// Path and header id of the link
+fn link_target(link: &SmartLink) -> Option<(String, Option<String>)> {
+    let (path, id) = match link {
+        SmartLink::Normal(v) => match v.src.split_once('#') {
+            Some((path, id)) => (path, Some(id)),
+            None => (v.src.as_str(), None),
+        },
+        SmartLink::TwoWay(v) => (v.get_link(), v.get_id()),
+        _ => return None,
+    };
+    Some((path.trim().to_string(), id.map(|s| s.trim().to_string())))
+}
+
+#[inline]
+fn without_fragment(mut url: Url) -> Url {
+    url.set_fragment(None);
+    return url;
+}
+
+fn code_action(title: String, kind: CodeActionKind, edit: WorkspaceEdit) -> CodeAction {
+    CodeAction { title, kind: Some(kind), edit: Some(edit), ..CodeAction::default() }
+}
+
+fn replace(text: &str, range: Range<usize>, new_text: String) -> TextEdit {
+    TextEdit { range: TextIndex::new(text).get_lsp_range(range.start, range.end), new_text }
+}
+
+/// Add the block after an empty line at the end of the text
+fn append(text: &str, block: &str) -> TextEdit {
+    let separator = match text {
+        "" => "",
+        _ if text.ends_with("\n\n") => "",
+        _ if text.ends_with('\n') => "\n",
+        _ => "\n\n",
+    };
+    replace(text, text.len()..text.len(), format!("{}{}", separator, block))
+}
+
+fn changes(url: &Url, edits: Vec<TextEdit>) -> WorkspaceEdit {
+    WorkspaceEdit { changes: Some(vec![(url.to_owned(), edits)].into_iter().collect::<HashMap<_, _>>()), ..WorkspaceEdit::default() }
+}
+
+/// Create the file with the text, nothing is done if it exists
+fn create(url: Url, text: String) -> WorkspaceEdit {
+    let options = CreateFileOptions { overwrite: Some(false), ignore_if_exists: Some(true) };
+    let mut operations =
+        vec![DocumentChangeOperation::Op(ResourceOp::Create(CreateFile { uri: url.to_owned(), options: Some(options), annotation_id: None }))];
+    if !text.is_empty() {
+        operations.push(document_edit(&url, vec![replace("", 0..0, text)]))
+    }
+    WorkspaceEdit { document_changes: Some(DocumentChanges::Operations(operations)), ..WorkspaceEdit::default() }
+}
+
+fn document_edit(url: &Url, edits: Vec<TextEdit>) -> DocumentChangeOperation {
+    DocumentChangeOperation::Edit(TextDocumentEdit {
+        text_document: OptionalVersionedTextDocumentIdentifier { uri: url.to_owned(), version: None },
+        edits: edits.into_iter().map(OneOf::Left).collect(),
+    })
+}
+
+#[tokio::test]
+async fn check_code_actions() {
+    use crate::{
+        diagnostic::diagnostics_provider,
+        io::{initialize_global_storages, FileStateUpdate},
+    };
+    /// Titles and the new texts of the actions at the position
+    async fn actions(name: &str, text: &str, line: u32, character: u32) -> Vec<(String, String)> {
+        let url = Url::parse("file:///actions/").unwrap().join(name).unwrap();
+        let document = TextDocumentItem::new(url.to_owned(), String::from("note"), 0, String::from(text));
+        FILE_STORAGE.get().write().await.update(DidOpenTextDocumentParams { text_document: document });
+        let diagnostics = diagnostics_provider(&url).await;
+        let position = Position::new(line, character);
+        let p = CodeActionParams {
+            text_document: TextDocumentIdentifier::new(url),
+            range: tower_lsp::lsp_types::Range::new(position, position),
+            context: CodeActionContext { diagnostics, only: None },
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+        let mut out = vec![];
+        for action in code_action_provider(p).await.unwrap_or_default() {
+            if let CodeActionOrCommand::CodeAction(action) = action {
+                let edit = action.edit.unwrap_or_default();
+                let mut edits: Vec<_> = edit.changes.unwrap_or_default().into_iter().flat_map(|(_, v)| v).map(|e| e.new_text).collect();
+                if let Some(DocumentChanges::Operations(operations)) = edit.document_changes {
+                    for operation in operations {
+                        if let DocumentChangeOperation::Edit(e) = operation {
+                            edits.extend(e.edits.into_iter().map(|e| match e {
+                                OneOf::Left(e) => e.new_text,
+                                OneOf::Right(e) => e.text_edit.new_text,
+                            }))
+                        }
+                    }
+                }
+                out.push((action.title, edits.join("|")))
+            }
+        }
+        return out;
+    }
+    fn pair(title: &str, text: &str) -> (String, String) {
+        (String::from(title), String::from(text))
+    }
+    initialize_global_storages();
+    assert_eq!(actions("header.note", "[#missing-part]", 0, 0).await, vec![pair("Create header `#missing-part`", "\n\n# missing part\n")]);
+    assert_eq!(actions("numbered.note", "# Part\n\n[#part-1]", 2, 0).await, vec![pair("Create header `#part-1`", "\n\n# part 1\n")]);
+    assert_eq!(
+        actions("explicit.note", "[#Part_One]", 0, 0).await,
+        vec![pair("Create header `#Part_One`", "\n\n\\args(id = \"Part_One\")\n# Part_One\n")]
+    );
+    assert_eq!(list_headers("\\args(id = \"Part_One\")\n# Part_One\n")[0].2, "Part_One");
+    assert_eq!(actions("footnote.note", "text[^a]\n", 0, 0).await, vec![pair("Define footnote `[^a]`", "\n[^a]: \n")]);
+    assert_eq!(actions("link.note", "[./new.note#intro]", 0, 0).await, vec![pair("Create file `./new.note`", "# intro\n")]);
+    assert_eq!(actions("lint.note", "the the end", 0, 0).await, vec![pair("Fix: Repeated word `the`", "")]);
+    assert_eq!(
+        actions("url.note", "https://example.com/ now", 0, 8).await,
+        vec![pair("Convert to link", "[example.com][https://example.com/]")]
+    );
+    assert_eq!(
+        actions("section.note", "# Intro\n\ntext\n\n# Next", 0, 2).await,
+        vec![pair("Extract section to `./intro.note`", "# Intro\n\ntext\n|\\include[./intro.note]\n\n")]
+    );
+    let table = "text\n\n| a | b |\n| - | - |\n| 1 | 2 \\| 3 |\n";
+    assert_eq!(actions("table.note", table, 3, 0).await, vec![pair("Convert table to list", "- a | b\n- 1 | 2 \\| 3")]);
+    assert_eq!(
+        actions("list.note", "- a | b\n- 1 | 2 \\| 3\n2. c", 1, 0).await,
+        vec![pair("Convert list to table", "| a | b |\n| --- | --- |\n| 1 | 2 \\| 3 |\n| c |  |")]
+    );
+    assert_eq!(
+        actions("fixed.note", "- the the a | b\n- c", 0, 0).await,
+        vec![pair("Fix: Repeated word `the`", ""), pair("Convert list to table", "| the the a | b |\n| --- | --- |\n| c |  |")]
+    );
+}
@@ -1,18 +1,79 @@
-use crate::{diagnostic::ToToc, io::read_url};
-use notedown_parser::ParserConfig;
+use crate::{
+    diagnostic::ToToc,
+    io::{note_parser, read_text},
+};
+use notedown_ast::utils::TextIndex;
 use tower_lsp::lsp_types::{DocumentSymbolParams, DocumentSymbolResponse};
 
+/// Outline of the headers, each symbol covers the section of the header
 #[allow(deprecated)]
-pub fn document_symbol_provider(args: DocumentSymbolParams) -> Option<DocumentSymbolResponse> {
-    let cfg = ParserConfig::default();
-    let ast = match cfg.parse(&read_url(&args.text_document.uri)) {
+pub async fn document_symbol_provider(args: DocumentSymbolParams) -> Option<DocumentSymbolResponse> {
+    let text = read_text(&args.text_document.uri).await;
+    let ast = match note_parser().parse(&text) {
         Ok(o) => o,
         Err(_) => return None,
     };
+    let nested = ast.to_toc(&TextIndex::new(text)).children.unwrap_or_default();
+    Some(DocumentSymbolResponse::Nested(nested))
+}
 
-    let nested = match ast.to_toc().children {
-        Some(v) => v,
-        None => vec![],
+#[tokio::test]
+async fn check_document_symbols() {
+    use crate::io::{initialize_global_storages, FileStateUpdate, FILE_STORAGE};
+    use notedown_ast::traits::{TableOfContent, TocConfig, TocNode};
+    use tower_lsp::lsp_types::{DidOpenTextDocumentParams, DocumentSymbol, TextDocumentIdentifier, TextDocumentItem, Url};
+    /// Name, detail, lines of the section and line of the header
+    fn outline(symbols: &[DocumentSymbol], out: &mut Vec<(String, String, u32, u32, u32)>) {
+        for s in symbols {
+            out.push((
+                s.name.to_owned(),
+                s.detail.to_owned().unwrap_or_default(),
+                s.range.start.line,
+                s.range.end.line,
+                s.selection_range.start.line,
+            ));
+            outline(s.children.as_deref().unwrap_or_default(), out)
+        }
+    }
+    fn names(node: &TocNode, out: &mut Vec<String>) {
+        for child in &node.children {
+            out.push(child.name.to_owned());
+            names(child, out)
+        }
+    }
+    fn row(name: &str, detail: &str, start: u32, end: u32, header: u32) -> (String, String, u32, u32, u32) {
+        (String::from(name), String::from(detail), start, end, header)
+    }
+    initialize_global_storages();
+    let text = "# Guide\n\nintro\n\n## Install\n\nrun it\n\n\\toc_ignore\n\n## Skipped\n\n### Deep *part*\n\ntext\n\n## Usage";
+    let url = Url::parse("file:///document-symbol/guide.note").unwrap();
+    let document = TextDocumentItem::new(url.to_owned(), String::from("note"), 0, String::from(text));
+    FILE_STORAGE.get().write().await.update(DidOpenTextDocumentParams { text_document: document });
+    let p = DocumentSymbolParams {
+        text_document: TextDocumentIdentifier::new(url),
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
     };
-    Some(DocumentSymbolResponse::Nested(nested))
+    let symbols = match document_symbol_provider(p).await {
+        Some(DocumentSymbolResponse::Nested(v)) => v,
+        _ => vec![],
+    };
+    let mut out = vec![];
+    outline(&symbols, &mut out);
+    assert_eq!(
+        out,
+        vec![
+            row("Guide", "guide", 0, 16, 0),
+            row("Install", "install", 4, 8, 4),
+            row("Deep part", "deep-part", 12, 14, 12),
+            row("Usage", "usage", 16, 16, 16),
+        ]
+    );
+    // the subsections of hidden headers are moved up
+    let children: Vec<_> = symbols[0].children.iter().flatten().map(|s| s.name.as_str()).collect();
+    assert_eq!(children, vec!["Install", "Deep part", "Usage"]);
+    let ast = notedown_parser::NotedownParser::default().parse(text).unwrap();
+    let mut out = vec![];
+    names(&ast.toc_configurable(&TocConfig { min_depth: 2, max_depth: 2, numbering: true }), &mut out);
+    assert_eq!(out, vec!["1 Install", "2 Usage"]);
 }
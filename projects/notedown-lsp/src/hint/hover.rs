@@ -0,0 +1,198 @@
+use crate::io::{get_offset, note_parser, read_url, FILE_STORAGE, MATH_PREVIEW};
+use notedown_ast::{
+    command::Command,
+    nodes::{Header, MathNode, SmartLink, TagReference},
+    traits::{ContextAware, ContextKind},
+    utils::TextIndex,
+    ASTKind, ASTNode, NoteError,
+};
+use notedown_image::ImagePreview;
+use notedown_math::{math_to_mathml, math_to_mathml_svg};
+use notedown_plugin::get_definition;
+use std::{ops::Range, str::FromStr};
+use tower_lsp::lsp_types::{Hover, HoverContents, HoverParams, MarkupContent, MarkupKind, Url};
+
+/// Max width and height of the image thumbnail
+const THUMBNAIL_SIZE: u32 = 256;
+/// Max lines of the linked header preview
+const PREVIEW_LINES: usize = 12;
+
+/// Show the preview of the element under the cursor
+pub async fn hover_provider(p: HoverParams) -> Option<Hover> {
+    let url = p.text_document_position_params.text_document.uri;
+    let text = FILE_STORAGE.get().read().await.read(&url)?;
+    let offset = get_offset(&text, p.text_document_position_params.position)?;
+    let ast = note_parser().parse(&text).ok()?;
+    let context = ast.context_aware(offset as u32);
+    let value = match &context {
+        ContextKind::Math(math) => hover_math(&math.value),
+        _ if context.command().is_some() => hover_command(&context.command()?.value)?,
+        _ if context.link().is_some() => hover_link(&context.link()?.value, &url, &text, &ast).await?,
+        _ => return None,
+    };
+    let range = context.range().map(|r| TextIndex::new(text).get_lsp_range(r.start, r.end));
+    Some(Hover { contents: HoverContents::Markup(MarkupContent { kind: MarkupKind::Markdown, value }), range })
+}
+
+/// How the formulas are previewed on hover, from `notedown.math`
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum MathPreview {
+    /// MathML wrapped in an svg image, for clients which strip html from markdown
+    #[default]
+    Svg,
+    /// MathML in the markdown, for clients which render MathML
+    MathML,
+}
+
+impl FromStr for MathPreview {
+    type Err = NoteError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "svg" => Ok(Self::Svg),
+            "mathml" => Ok(Self::MathML),
+            _ => Err(NoteError::type_mismatch(format!("Unknown math preview `{}`, expect `svg` or `mathml`", s))),
+        }
+    }
+}
+
+/// Preview the formula, or show why it can't be rendered
+fn hover_math(math: &MathNode) -> String {
+    let preview = MATH_PREVIEW.try_get().and_then(|s| s.read().ok().map(|s| *s)).unwrap_or_default();
+    let rendered = match preview {
+        MathPreview::Svg => {
+            math_to_mathml_svg(math).map(|svg| format!("![{}](data:image/svg+xml;base64,{})", math.raw.trim(), base64::encode(svg)))
+        }
+        MathPreview::MathML => math_to_mathml(math),
+    };
+    match rendered {
+        Ok(o) => o,
+        Err(e) => format!("```note\n{}\n```\n---\n{}", math, e),
+    }
+}
+
+/// Show the signature and the documentation of the command
+fn hover_command(cmd: &Command) -> Option<String> {
+    let definition = get_definition(cmd.command(), matches!(cmd, Command::XML(_)))?;
+    let mut out = format!("```note\n{}\n```", definition.signature());
+    for param in definition.parameters.iter().filter(|p| !p.documentation.is_empty()) {
+        out.push_str(&format!("\n- `{}`: {}", param.name, param.documentation));
+    }
+    out.push_str("\n---\n");
+    out.push_str(&definition.documentation);
+    Some(out)
+}
+
+async fn hover_link(link: &SmartLink, url: &Url, text: &str, ast: &ASTNode) -> Option<String> {
+    match link {
+        SmartLink::Normal(v) => hover_target(&v.src, url, text).await,
+        SmartLink::TwoWay(v) => match v.get_id() {
+            Some(id) => hover_target(&format!("{}#{}", v.get_link(), id), url, text).await,
+            None => hover_target(v.get_link(), url, text).await,
+        },
+        SmartLink::Image(v) => Some(hover_image(&v.source, v.description.as_deref().unwrap_or_default(), url)),
+        SmartLink::Reference(v) => hover_reference(v, ast),
+        SmartLink::EMail(v) => Some(format!("mailto:{}", v.name)),
+        SmartLink::ExternalResource(_) => None,
+    }
+}
+
+/// Show the resolved target, and the linked header if the target is a note
+async fn hover_target(src: &str, url: &Url, text: &str) -> Option<String> {
+    let target = url.join(src).ok()?;
+    let mut out = format!("[{}]({})", target, target);
+    if target.scheme() != "file" {
+        return Some(out);
+    }
+    let mut document = target.to_owned();
+    document.set_fragment(None);
+    let preview = match &document == url {
+        true => header_preview(text, target.fragment()),
+        false => {
+            let text = match FILE_STORAGE.get().read().await.read(&document) {
+                Some(s) => s,
+                None => read_url(&document),
+            };
+            header_preview(&text, target.fragment())
+        }
+    };
+    if let Some(s) = preview {
+        out.push_str("\n---\n");
+        out.push_str(&s);
+    }
+    Some(out)
+}
+
+/// Source of the header whose slug is `id` and the text under it, the first header if no id
+fn header_preview(text: &str, id: Option<&str>) -> Option<String> {
+    let headers = list_headers(text);
+    let index = headers.iter().position(|(_, _, slug)| id.map(|id| slug == id).unwrap_or(true))?;
+    let (header, range, _) = &headers[index];
+    // stop at the next header of the same or higher level
+    let end = headers[index + 1..].iter().find(|(h, _, _)| h.level <= header.level).map(|(_, r, _)| r.start).unwrap_or(text.len());
+    let section: Vec<_> = text[range.start..end].trim().lines().take(PREVIEW_LINES).collect();
+    Some(section.join("\n"))
+}
+
+/// Top level headers with the range and the id used by links, the id is empty if the header has no text
+pub fn list_headers(text: &str) -> Vec<(Header, Range<usize>, String)> {
+    let children = match note_parser().parse(text).map(|ast| ast.value) {
+        Ok(ASTKind::Statements(v)) => v,
+        _ => return vec![],
+    };
+    children
+        .into_iter()
+        .filter_map(|node| match (node.value, node.range) {
+            (ASTKind::Header(header), Some(range)) => {
+                let id = header.id.to_owned().unwrap_or_default();
+                Some((*header, range, id))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Show the size and the thumbnail of a local image
+fn hover_image(source: &str, alt: &str, url: &Url) -> String {
+    let target = match url.join(source) {
+        Ok(o) => o,
+        Err(_) => return format!("`{}`", source),
+    };
+    let path = match target.to_file_path() {
+        Ok(o) => o,
+        Err(_) => return format!("![{}]({})\n\n[{}]({})", alt, target, target, target),
+    };
+    match ImagePreview::open(&path, THUMBNAIL_SIZE) {
+        Ok(image) => format!("![{}]({})\n\n{} × {}\n\n[{}]({})", alt, image.to_data_url(), image.width, image.height, target, target),
+        Err(_) => format!("Can not open image `{}`", path.display()),
+    }
+}
+
+/// Show the footnote text
+fn hover_reference(reference: &TagReference, ast: &ASTNode) -> Option<String> {
+    if reference.is_definition() {
+        return None;
+    }
+    if !reference.text.is_empty() {
+        return Some(reference.text.join("\n"));
+    }
+    let children = match &ast.value {
+        ASTKind::Statements(v) => v,
+        _ => return None,
+    };
+    children.iter().find_map(|node| match &node.value {
+        ASTKind::LinkNode(SmartLink::Reference(v)) if v.is_definition() && v.tag == reference.tag => Some(v.text.join("\n")),
+        _ => None,
+    })
+}
+
+#[test]
+fn check_math_preview() {
+    crate::io::initialize_global_storages();
+    let math = MathNode::math_display("x^2".to_string());
+    assert!(hover_math(&math).starts_with("![x^2](data:image/svg+xml;base64,"));
+    *MATH_PREVIEW.get().write().unwrap() = "MathML".parse().unwrap();
+    assert!(hover_math(&math).starts_with("<math"));
+    *MATH_PREVIEW.get().write().unwrap() = Default::default();
+    assert!("glyphs".parse::<MathPreview>().is_err());
+}
@@ -0,0 +1,47 @@
+use crate::io::{get_offset, note_parser, FILE_STORAGE};
+use notedown_ast::{command::Command, utils::TextIndex, ASTKind, ASTNode};
+use std::ops::Range;
+use tower_lsp::lsp_types::{LinkedEditingRangeParams, LinkedEditingRanges};
+
+/// Rename the open tag and the close tag of a xml command at the same time
+/// ```note
+/// <|cmd|>body</|cmd|>
+/// ```
+pub async fn linked_editing_range_provider(p: LinkedEditingRangeParams) -> Option<LinkedEditingRanges> {
+    let url = p.text_document_position_params.text_document.uri;
+    let text = FILE_STORAGE.get().read().await.read(&url)?;
+    let offset = get_offset(&text, p.text_document_position_params.position)?;
+    let ast = note_parser().parse(&text).ok()?;
+    let (start, end) = find_xml_names(&ast, offset)?;
+    let index = TextIndex::new(text);
+    let ranges = vec![index.get_lsp_range(start.start, start.end), index.get_lsp_range(end.start, end.end)];
+    Some(LinkedEditingRanges { ranges, word_pattern: None })
+}
+
+/// Find the xml command whose tag name contains the offset
+fn find_xml_names(node: &ASTNode, offset: usize) -> Option<(Range<usize>, Range<usize>)> {
+    let children = match &node.value {
+        ASTKind::Statements(v) | ASTKind::Paragraph(v) => v,
+        ASTKind::Header(v) => &v.children,
+        ASTKind::StyledSpan(v) => &v.children,
+        ASTKind::Command(v) => match &**v {
+            Command::XML(xml) => {
+                if let (start, Some(end)) = xml.name_ranges() {
+                    if contains(&start, offset) || contains(&end, offset) {
+                        return Some((start, end));
+                    }
+                }
+                &xml.body
+            }
+            _ => return None,
+        },
+        _ => return None,
+    };
+    children.iter().find_map(|node| find_xml_names(node, offset))
+}
+
+/// The cursor can be placed at both sides of the name
+#[inline]
+fn contains(range: &Range<usize>, offset: usize) -> bool {
+    range.start <= offset && offset <= range.end
+}
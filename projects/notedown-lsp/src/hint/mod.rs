@@ -1,25 +1,22 @@
 use serde_json::Value;
 use tower_lsp::lsp_types::*;
 
+mod code_action;
 mod document_symbol;
+mod hover;
+mod linked_editing;
+mod navigation;
+mod rename;
+mod signature_help;
+mod workspace_symbol;
+pub use code_action::{code_action_provider, CODE_ACTION_KINDS};
 pub use document_symbol::document_symbol_provider;
-
-pub fn code_action_provider(p: CodeActionParams) -> Option<CodeActionResponse> {
-    let _ = p;
-    let out = image_action();
-    return Some(out);
-}
-
-pub fn hover_provider(p: HoverParams) -> Option<Hover> {
-    let _ = p;
-    Some(Hover {
-        contents: HoverContents::Markup(MarkupContent {
-            kind: MarkupKind::Markdown,
-            value: "![](https://projecteuler.net/images/icons/info.png)".to_string(),
-        }),
-        range: None,
-    })
-}
+pub use hover::{hover_provider, list_headers, MathPreview};
+pub use linked_editing::linked_editing_range_provider;
+pub use navigation::{definition_provider, document_link_provider, references_provider, watched_files_provider};
+pub use rename::{prepare_rename_provider, rename_provider};
+pub use signature_help::signature_help_provider;
+pub use workspace_symbol::workspace_symbol_provider;
 
 pub fn code_lens_provider(p: CodeLensParams) -> Option<Vec<CodeLens>> {
     let _ = p;
@@ -30,27 +27,3 @@ pub fn code_lens_provider(p: CodeLensParams) -> Option<Vec<CodeLens>> {
     };
     Some(vec![len])
 }
-
-fn image_action() -> Vec<CodeActionOrCommand> {
-    vec![
-        cmd("💾 Save image to local", "vscode-notedown.image.save2local", vec![Value::Bool(true)]),
-        cmd("💾 Save image to workspace", "vscode-notedown.image.save2ws", vec![Value::Bool(true)]),
-        cmd("⏫ Upload image", "vscode-notedown.image.upload2remote", vec![Value::Bool(true)]),
-    ]
-}
-
-fn cmd(show: &str, run: &str, arg: Vec<Value>) -> CodeActionOrCommand {
-    CodeActionOrCommand::Command(Command { title: String::from(show), command: String::from(run), arguments: Some(arg) })
-}
-
-#[allow(dead_code)]
-fn action() -> CodeAction {
-    CodeAction {
-        title: "Save image to local".to_string(),
-        kind: Some(CodeActionKind::SOURCE_ORGANIZE_IMPORTS),
-        diagnostics: None,
-        edit: None,
-        command: None,
-        is_preferred: Some(true),
-    }
-}
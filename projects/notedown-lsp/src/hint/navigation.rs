@@ -0,0 +1,228 @@
+use crate::io::{get_offset, note_parser, read_text, workspace_notes, FILE_STORAGE, WORKSPACE_INDEX};
+use notedown_ast::utils::TextIndex;
+use notedown_rt::LinkTarget;
+use std::ops::Range;
+use tower_lsp::lsp_types::{
+    DocumentLink, DocumentLinkParams, FileChangeType, FileEvent, GotoDefinitionParams, GotoDefinitionResponse, Location, ReferenceParams, Url,
+};
+
+/// `[[link > id]]`, `[./file#id]` or `[^tag]` to the header or the footnote
+pub async fn definition_provider(p: GotoDefinitionParams) -> Option<GotoDefinitionResponse> {
+    let url = p.text_document_position_params.text_document.uri;
+    let text = FILE_STORAGE.get().read().await.read(&url)?;
+    let offset = get_offset(&text, p.text_document_position_params.position)?;
+    index_note(&url).await;
+    let target = WORKSPACE_INDEX.get().read().await.get(&url)?.link_at(offset)?.target.to_owned();
+    if let LinkTarget::Note { url, .. } = &target {
+        index_note(url).await
+    }
+    let (document, range) = WORKSPACE_INDEX.get().read().await.definition(&url, offset)?;
+    Some(GotoDefinitionResponse::Scalar(location(document, range).await))
+}
+
+/// Links to the header or the footnote under the cursor, or to the target of the link
+pub async fn references_provider(p: ReferenceParams) -> Option<Vec<Location>> {
+    let url = p.text_document_position.text_document.uri;
+    let text = FILE_STORAGE.get().read().await.read(&url)?;
+    let offset = get_offset(&text, p.text_document_position.position)?;
+    index_note(&url).await;
+    let document = WORKSPACE_INDEX.get().read().await.get(&url)?.to_owned();
+    let declaration = p.context.include_declaration;
+    let mut out = vec![];
+    // footnotes are local to the note
+    let tag = match document.footnote_at(offset) {
+        Some(s) => Some(s.tag.to_owned()),
+        None => match document.link_at(offset).map(|l| &l.target) {
+            Some(LinkTarget::Footnote(tag)) => Some(tag.to_owned()),
+            _ => None,
+        },
+    };
+    if let Some(tag) = tag {
+        for link in document.links.iter().filter(|l| l.target == LinkTarget::Footnote(tag.to_owned())) {
+            out.push((url.to_owned(), link.range.to_owned()))
+        }
+        if let Some(definition) = document.footnote(&tag).filter(|_| declaration) {
+            out.insert(0, (url.to_owned(), definition.range.to_owned()))
+        }
+    }
+    else {
+        let (target, id) = match (document.header_at(offset), document.link_at(offset).map(|l| &l.target)) {
+            (Some(header), _) => (url.to_owned(), Some(header.id.to_owned())),
+            (None, Some(LinkTarget::Note { url, id })) => (url.to_owned(), id.to_owned()),
+            _ => return None,
+        };
+        // only the notes mentioning the id can link to the header
+        let mention = id.to_owned().unwrap_or_else(|| target.path_segments().and_then(|mut s| s.next_back()).unwrap_or_default().to_string());
+        index_workspace(|text| text.contains(&mention)).await;
+        let index = WORKSPACE_INDEX.get().read().await;
+        if declaration {
+            let header = id.as_deref().and_then(|id| index.get(&target)?.header(id));
+            out.extend(header.map(|h| (target.to_owned(), h.range.to_owned())))
+        }
+        out.extend(index.references(&target, id.as_deref()));
+    }
+    let mut locations = vec![];
+    for (url, range) in out {
+        locations.push(location(url, range).await)
+    }
+    Some(locations)
+}
+
+/// Resolved targets of all links, headers are opened at the line
+pub async fn document_link_provider(p: DocumentLinkParams) -> Option<Vec<DocumentLink>> {
+    let url = p.text_document.uri;
+    let text = FILE_STORAGE.get().read().await.read(&url)?;
+    index_note(&url).await;
+    let document = WORKSPACE_INDEX.get().read().await.get(&url)?.to_owned();
+    let index = TextIndex::new(&text);
+    let mut out = vec![];
+    for link in &document.links {
+        let target = match &link.target {
+            LinkTarget::Note { url: target, id: Some(id) } => {
+                index_note(target).await;
+                let header = WORKSPACE_INDEX.get().read().await.get(target).and_then(|d| d.header(id)).map(|h| h.range.to_owned());
+                match header {
+                    Some(range) => at_line(target, range).await,
+                    None => target.to_owned(),
+                }
+            }
+            LinkTarget::Note { url, id: None } | LinkTarget::External(url) => url.to_owned(),
+            LinkTarget::Footnote(tag) => match document.footnote(tag) {
+                Some(definition) => at_line(&url, definition.range.to_owned()).await,
+                None => continue,
+            },
+        };
+        out.push(DocumentLink { range: index.get_lsp_range(link.range.start, link.range.end), target: Some(target), tooltip: None, data: None })
+    }
+    Some(out)
+}
+
+/// Parse the note if it is not indexed yet
+async fn index_note(url: &Url) {
+    if WORKSPACE_INDEX.get().read().await.contains(url) {
+        return;
+    }
+    let text = read_text(url).await;
+    if let Ok(ast) = note_parser().parse(&text) {
+        WORKSPACE_INDEX.get().write().await.update(url, &ast)
+    }
+}
+
+/// Index the notes changed outside of the editor again, and forget the deleted ones
+pub async fn watched_files_provider(changes: &[FileEvent]) {
+    for change in changes {
+        WORKSPACE_INDEX.get().write().await.remove(&change.uri);
+        if change.typ != FileChangeType::DELETED {
+            index_note(&change.uri).await
+        }
+    }
+}
+
+/// Parse the notes in the workspace which are not indexed yet and may be related
+pub(super) async fn index_workspace(related: impl Fn(&str) -> bool) {
+    for url in workspace_notes().await {
+        if WORKSPACE_INDEX.get().read().await.contains(&url) {
+            continue;
+        }
+        let text = read_text(&url).await;
+        if !related(&text) {
+            continue;
+        }
+        if let Ok(ast) = note_parser().parse(&text) {
+            WORKSPACE_INDEX.get().write().await.update(&url, &ast)
+        }
+    }
+}
+
+async fn location(url: Url, range: Range<usize>) -> Location {
+    let text = read_text(&url).await;
+    let range = TextIndex::new(&text).get_lsp_range(range.start, range.end);
+    Location { uri: url, range }
+}
+
+/// `file.note#L12`, editors open the file at the line
+async fn at_line(url: &Url, range: Range<usize>) -> Url {
+    let line = location(url.to_owned(), range).await.range.start.line;
+    let mut url = url.to_owned();
+    url.set_fragment(Some(&format!("L{}", line + 1)));
+    return url;
+}
+
+#[tokio::test]
+async fn check_navigation() {
+    use crate::io::{initialize_global_storages, FileStateUpdate};
+    use tower_lsp::lsp_types::{
+        DidOpenTextDocumentParams, Position, ReferenceContext, TextDocumentIdentifier, TextDocumentItem, TextDocumentPositionParams,
+    };
+    async fn open(name: &str, text: &str) -> Url {
+        let url = Url::parse("file:///navigation/").unwrap().join(name).unwrap();
+        let document = TextDocumentItem::new(url.to_owned(), String::from("note"), 0, String::from(text));
+        FILE_STORAGE.get().write().await.update(DidOpenTextDocumentParams { text_document: document });
+        return url;
+    }
+    fn position(url: &Url, line: u32, character: u32) -> TextDocumentPositionParams {
+        TextDocumentPositionParams { text_document: TextDocumentIdentifier::new(url.to_owned()), position: Position::new(line, character) }
+    }
+    /// File names and lines of the locations
+    fn lines(locations: Vec<Location>) -> Vec<(String, u32)> {
+        locations.into_iter().map(|l| (l.uri.path().trim_start_matches("/navigation/").to_string(), l.range.start.line)).collect()
+    }
+    async fn definition(url: &Url, line: u32, character: u32) -> Vec<(String, u32)> {
+        let p = GotoDefinitionParams {
+            text_document_position_params: position(url, line, character),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+        match definition_provider(p).await {
+            Some(GotoDefinitionResponse::Scalar(location)) => lines(vec![location]),
+            _ => vec![],
+        }
+    }
+    async fn references(url: &Url, line: u32, character: u32, include_declaration: bool) -> Vec<(String, u32)> {
+        let p = ReferenceParams {
+            text_document_position: position(url, line, character),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+            context: ReferenceContext { include_declaration },
+        };
+        lines(references_provider(p).await.unwrap_or_default())
+    }
+    fn at(name: &str, line: u32) -> (String, u32) {
+        (String::from(name), line)
+    }
+    /// Ids of the indexed headers, `None` if the note is not indexed
+    async fn headers(url: &Url) -> Option<Vec<String>> {
+        Some(WORKSPACE_INDEX.get().read().await.get(url)?.headers.iter().map(|h| h.id.to_owned()).collect())
+    }
+    initialize_global_storages();
+    let a = open("a.note", "# Intro\n\ntext[^n] and [#intro]\n\n[^n]: note").await;
+    let b = open("b.note", "[./a.note#intro] and [[./a.note > intro]]\n\n[./a.note] [https://example.com]").await;
+    assert_eq!(definition(&b, 0, 3).await, vec![at("a.note", 0)]);
+    assert_eq!(definition(&b, 0, 25).await, vec![at("a.note", 0)]);
+    assert_eq!(definition(&a, 2, 6).await, vec![at("a.note", 4)]);
+    assert_eq!(definition(&b, 2, 15).await, vec![]);
+    assert_eq!(references(&a, 0, 3, false).await, vec![at("a.note", 2), at("b.note", 0), at("b.note", 0)]);
+    assert_eq!(references(&b, 0, 3, true).await, vec![at("a.note", 0), at("a.note", 2), at("b.note", 0), at("b.note", 0)]);
+    assert_eq!(references(&a, 4, 1, true).await, vec![at("a.note", 4), at("a.note", 2)]);
+    let links = document_link_provider(DocumentLinkParams {
+        text_document: TextDocumentIdentifier::new(b),
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    });
+    let targets: Vec<String> = links.await.unwrap_or_default().into_iter().filter_map(|l| l.target).map(String::from).collect();
+    assert_eq!(
+        targets,
+        vec!["file:///navigation/a.note#L1", "file:///navigation/a.note#L1", "file:///navigation/a.note", "https://example.com/"]
+    );
+    let path = std::env::temp_dir().join("notedown-watched.note");
+    let url = Url::from_file_path(&path).unwrap();
+    std::fs::write(&path, "# Old").unwrap();
+    watched_files_provider(&[FileEvent::new(url.to_owned(), FileChangeType::CREATED)]).await;
+    assert_eq!(headers(&url).await, Some(vec![String::from("old")]));
+    std::fs::write(&path, "# New").unwrap();
+    watched_files_provider(&[FileEvent::new(url.to_owned(), FileChangeType::CHANGED)]).await;
+    assert_eq!(headers(&url).await, Some(vec![String::from("new")]));
+    std::fs::remove_file(&path).unwrap();
+    watched_files_provider(&[FileEvent::new(url.to_owned(), FileChangeType::DELETED)]).await;
+    assert_eq!(headers(&url).await, None);
+}
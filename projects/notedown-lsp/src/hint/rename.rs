@@ -0,0 +1,276 @@
+use super::list_headers;
+use crate::io::{get_offset, note_parser, read_text, workspace_notes, FILE_STORAGE};
+use notedown_ast::{command::Command, nodes::SmartLink, utils::TextIndex, ASTKind, ASTNode};
+use notedown_rt::flatten;
+use std::{collections::HashMap, ops::Range};
+use tower_lsp::lsp_types::{PrepareRenameResponse, RenameParams, TextDocumentPositionParams, TextEdit, Url, WorkspaceEdit};
+
+/// Element under the cursor which can be renamed
+enum RenameTarget {
+    /// Header of the document, the new name is the text of the header, and links use the new slug
+    Header { document: Url, id: String },
+    /// `[^tag]`, footnotes are local to the document
+    Footnote(String),
+    /// Names in the open tag and the close tag
+    XmlTag(Range<usize>, Option<Range<usize>>),
+}
+
+/// Name under the cursor and the text shown in the rename box
+pub async fn prepare_rename_provider(p: TextDocumentPositionParams) -> Option<PrepareRenameResponse> {
+    let url = p.text_document.uri;
+    let text = FILE_STORAGE.get().read().await.read(&url)?;
+    let offset = get_offset(&text, p.position)?;
+    let (_, range, placeholder) = find_target(&url, &text, offset).await?;
+    let range = TextIndex::new(&text).get_lsp_range(range.start, range.end);
+    Some(PrepareRenameResponse::RangeWithPlaceholder { range, placeholder })
+}
+
+/// Rename the header with the links to it in the workspace, the footnote or the xml command
+pub async fn rename_provider(p: RenameParams) -> Option<WorkspaceEdit> {
+    let url = p.text_document_position.text_document.uri;
+    let text = FILE_STORAGE.get().read().await.read(&url)?;
+    let offset = get_offset(&text, p.text_document_position.position)?;
+    let new_name = p.new_name.trim();
+    let (target, _, _) = find_target(&url, &text, offset).await?;
+    let mut edits = RenameEdits::default();
+    match target {
+        RenameTarget::Header { document, id } => {
+            if new_name.is_empty() || new_name.contains('\n') {
+                return None;
+            }
+            let target = read_text(&document).await;
+            let title = headers_of(&target).into_iter().find(|(_, slug)| *slug == id)?.0;
+            let renamed = renamed_ids(&target, &title, new_name)?;
+            edits.push(&document, &target, title, new_name);
+            for note in workspace_notes().await {
+                let text = read_text(&note).await;
+                // only the notes mentioning the ids are parsed
+                if !renamed.keys().any(|id| text.contains(id.as_str())) {
+                    continue;
+                }
+                let ast = match note_parser().parse(&text) {
+                    Ok(o) => o,
+                    Err(_) => continue,
+                };
+                for node in nodes(&ast) {
+                    if let Some((link, anchor, span)) = link_anchor(node, &text) {
+                        match renamed.get(anchor) {
+                            Some(new_id) if resolve(&note, &link).as_ref() == Some(&document) => edits.push(&note, &text, span, new_id),
+                            _ => continue,
+                        }
+                    }
+                }
+            }
+        }
+        RenameTarget::Footnote(tag) => {
+            if new_name.is_empty() || new_name.contains(|c: char| c.is_whitespace() || c == ']') {
+                return None;
+            }
+            let ast = note_parser().parse(&text).ok()?;
+            for (name, span) in nodes(&ast).into_iter().filter_map(|node| footnote_tag(node, &text)) {
+                if name == tag {
+                    edits.push(&url, &text, span, new_name)
+                }
+            }
+        }
+        RenameTarget::XmlTag(start, end) => {
+            if new_name.is_empty() || !new_name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                return None;
+            }
+            edits.push(&url, &text, start, new_name);
+            if let Some(end) = end {
+                edits.push(&url, &text, end, new_name)
+            }
+        }
+    }
+    Some(WorkspaceEdit { changes: Some(edits.changes), ..WorkspaceEdit::default() })
+}
+
+/// The renamed element, the range of the name under the cursor and the current name
+async fn find_target(url: &Url, text: &str, offset: usize) -> Option<(RenameTarget, Range<usize>, String)> {
+    for (range, id) in headers_of(text) {
+        if range.start <= offset && offset <= range.end {
+            let target = RenameTarget::Header { document: url.to_owned(), id };
+            return Some((target, range.to_owned(), text[range].to_string()));
+        }
+    }
+    let ast = note_parser().parse(text).ok()?;
+    for node in nodes(&ast) {
+        let range = node.range.to_owned()?;
+        if offset < range.start || range.end < offset {
+            continue;
+        }
+        if let ASTKind::Command(cmd) = &node.value {
+            if let Command::XML(xml) = &**cmd {
+                let (start, end) = xml.name_ranges();
+                let under = std::iter::once(&start).chain(end.as_ref()).find(|r| r.start <= offset && offset <= r.end).cloned();
+                if let Some(under) = under {
+                    return Some((RenameTarget::XmlTag(start, end), under, cmd.command().to_string()));
+                }
+            }
+        }
+        if let Some((tag, span)) = footnote_tag(node, text) {
+            return Some((RenameTarget::Footnote(tag.to_string()), span, tag.to_string()));
+        }
+        if let Some((link, id, span)) = link_anchor(node, text) {
+            // the header must exist, the rename box shows its text
+            let document = resolve(url, &link)?;
+            let target = read_text(&document).await;
+            let (title, _) = headers_of(&target).into_iter().find(|(_, slug)| *slug == id)?;
+            let placeholder = target[title].to_string();
+            return Some((RenameTarget::Header { document, id: id.to_string() }, span, placeholder));
+        }
+    }
+    return None;
+}
+
+#[derive(Default)]
+struct RenameEdits {
+    changes: HashMap<Url, Vec<TextEdit>>,
+}
+
+impl RenameEdits {
+    fn push(&mut self, url: &Url, text: &str, range: Range<usize>, new_text: &str) {
+        let edit = TextEdit { range: TextIndex::new(text).get_lsp_range(range.start, range.end), new_text: new_text.to_string() };
+        let edits = self.changes.entry(url.to_owned()).or_default();
+        if !edits.contains(&edit) {
+            edits.push(edit)
+        }
+    }
+}
+
+/// Range of the header text without `#`, and the slug
+fn headers_of(text: &str) -> Vec<(Range<usize>, String)> {
+    list_headers(text)
+        .into_iter()
+        .map(|(_, range, id)| {
+            let source = &text[range.to_owned()];
+            let start = range.start + source.len() - source.trim_start_matches(&['#', ' ', '\t'][..]).len();
+            let end = range.start + source.trim_end().len();
+            (start..end.max(start), id)
+        })
+        .collect()
+}
+
+/// Ids which change when the header is renamed, other headers may be renumbered like `intro-1`
+fn renamed_ids(text: &str, title: &Range<usize>, new_name: &str) -> Option<HashMap<String, String>> {
+    let renamed = format!("{}{}{}", &text[..title.start], new_name, &text[title.end..]);
+    let (old, new) = (headers_of(text), headers_of(&renamed));
+    let index = old.iter().position(|(range, _)| range == title)?;
+    // the new name must stay the text of the same header
+    if old.len() != new.len() || new[index].1.is_empty() {
+        return None;
+    }
+    Some(old.into_iter().zip(new).filter(|(old, new)| old.1 != new.1).map(|(old, new)| (old.1, new.1)).collect())
+}
+
+#[inline]
+fn nodes(ast: &ASTNode) -> Vec<&ASTNode> {
+    let mut out = vec![];
+    flatten(ast, &mut out);
+    return out;
+}
+
+/// `[^tag]` or `[^tag]: text`, and the range of the tag
+fn footnote_tag<'a>(node: &'a ASTNode, text: &str) -> Option<(&'a str, Range<usize>)> {
+    let (tag, range) = match (&node.value, &node.range) {
+        (ASTKind::LinkNode(SmartLink::Reference(v)), Some(range)) => (v, range),
+        _ => return None,
+    };
+    let start = range.start + text.get(range.to_owned())?.find("[^")? + 2;
+    Some((&tag.tag, start..start + tag.tag.len()))
+}
+
+/// `[path#id]` or `[[path > id]]`, the link, the header id and the range of the id
+fn link_anchor<'a>(node: &'a ASTNode, text: &str) -> Option<(String, &'a str, Range<usize>)> {
+    let (link, range) = match (&node.value, &node.range) {
+        (ASTKind::LinkNode(link), Some(range)) => (link, range),
+        _ => return None,
+    };
+    let (path, id) = match link {
+        SmartLink::Normal(v) if !v.is_bare => v.src.split_once('#')?,
+        SmartLink::TwoWay(v) => (v.get_link(), v.get_id()?),
+        _ => return None,
+    };
+    let id = id.trim();
+    let start = range.start + text.get(range.to_owned())?.rfind(id)?;
+    Some((path.trim().to_string(), id, start..start + id.len()))
+}
+
+/// The linked document, the document itself if the path is empty
+fn resolve(url: &Url, path: &str) -> Option<Url> {
+    if path.is_empty() {
+        return Some(url.to_owned());
+    }
+    let mut document = url.join(path).ok()?;
+    document.set_fragment(None);
+    Some(document)
+}
+
+#[tokio::test]
+async fn check_rename() {
+    use crate::io::{initialize_global_storages, FileStateUpdate};
+    use tower_lsp::lsp_types::{DidOpenTextDocumentParams, Position, TextDocumentIdentifier, TextDocumentItem};
+    async fn open(name: &str, text: &str) -> Url {
+        let url = Url::parse("file:///rename/").unwrap().join(name).unwrap();
+        let document = TextDocumentItem::new(url.to_owned(), String::from("note"), 0, String::from(text));
+        FILE_STORAGE.get().write().await.update(DidOpenTextDocumentParams { text_document: document });
+        return url;
+    }
+    fn position(url: &Url, line: u32, character: u32) -> TextDocumentPositionParams {
+        TextDocumentPositionParams { text_document: TextDocumentIdentifier::new(url.to_owned()), position: Position::new(line, character) }
+    }
+    /// Texts of the documents after the rename
+    async fn rename(url: &Url, line: u32, character: u32, new_name: &str) -> Vec<(String, String)> {
+        let p = RenameParams {
+            text_document_position: position(url, line, character),
+            new_name: String::from(new_name),
+            work_done_progress_params: Default::default(),
+        };
+        let mut out = vec![];
+        for (url, mut edits) in rename_provider(p).await.and_then(|e| e.changes).unwrap_or_default() {
+            let mut text = read_text(&url).await;
+            edits.sort_by_key(|e| std::cmp::Reverse(e.range.start));
+            for edit in edits {
+                let range = get_offset(&text, edit.range.start).unwrap()..get_offset(&text, edit.range.end).unwrap();
+                text.replace_range(range, &edit.new_text)
+            }
+            out.push((url.path().to_string(), text))
+        }
+        out.sort();
+        return out;
+    }
+    fn pair(path: &str, text: &str) -> (String, String) {
+        (String::from(path), String::from(text))
+    }
+    initialize_global_storages();
+    let a = open("a.note", "# First Part\n\ntext[^a] and [#first-part]\n\n[^a]: note\n\n<note>body</note>").await;
+    let b = open("b.note", "[./a.note#first-part] and [[./a.note > first-part]]").await;
+    let placeholder = |p| match p {
+        Some(PrepareRenameResponse::RangeWithPlaceholder { placeholder, .. }) => placeholder,
+        _ => String::new(),
+    };
+    assert_eq!(placeholder(prepare_rename_provider(position(&b, 0, 14)).await), "First Part");
+    assert_eq!(placeholder(prepare_rename_provider(position(&a, 2, 6)).await), "a");
+    assert_eq!(placeholder(prepare_rename_provider(position(&a, 6, 2)).await), "note");
+    assert!(prepare_rename_provider(position(&a, 2, 1)).await.is_none());
+    assert_eq!(
+        rename(&b, 0, 40, "Second Part").await,
+        vec![
+            pair("/rename/a.note", "# Second Part\n\ntext[^a] and [#second-part]\n\n[^a]: note\n\n<note>body</note>"),
+            pair("/rename/b.note", "[./a.note#second-part] and [[./a.note > second-part]]"),
+        ]
+    );
+    assert_eq!(
+        rename(&a, 4, 2, "b").await,
+        vec![pair("/rename/a.note", "# First Part\n\ntext[^b] and [#first-part]\n\n[^b]: note\n\n<note>body</note>")]
+    );
+    assert_eq!(
+        rename(&a, 6, 14, "tip").await,
+        vec![pair("/rename/a.note", "# First Part\n\ntext[^a] and [#first-part]\n\n[^a]: note\n\n<tip>body</tip>")]
+    );
+    assert!(rename(&a, 6, 2, "not valid").await.is_empty());
+    // the next header with the same name is numbered
+    let c = open("c.note", "# Intro\n\n# Setup\n\n[#intro] and [#setup]").await;
+    assert_eq!(rename(&c, 0, 3, "Setup").await, vec![pair("/rename/c.note", "# Setup\n\n# Setup\n\n[#setup] and [#setup-1]")]);
+}
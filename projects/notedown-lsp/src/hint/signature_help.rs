@@ -0,0 +1,121 @@
+use crate::{
+    completion::{markdown, open_options, split_arguments},
+    io::{get_offset, note_parser, FILE_STORAGE},
+};
+use notedown_ast::{command::CommandOptions, ASTKind, ASTNode};
+use notedown_plugin::{get_definition, CommandDefinition, CommandParameter};
+use tower_lsp::lsp_types::{ParameterInformation, ParameterLabel, SignatureHelp, SignatureHelpParams, SignatureInformation};
+
+/// Show the parameters of the command whose arguments are under the cursor
+/// ````md
+/// \cmd(|args|)
+/// <cmd |args|>
+/// ```cmd(|args|)
+/// ````
+pub async fn signature_help_provider(p: SignatureHelpParams) -> Option<SignatureHelp> {
+    let url = p.text_document_position_params.text_document.uri;
+    let text = FILE_STORAGE.get().read().await.read(&url)?;
+    let offset = get_offset(&text, p.text_document_position_params.position)?;
+    let line = &text[text[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0)..offset];
+    let (name, xml, args) = open_options(line)?;
+    let definition = get_definition(name, xml)?;
+    let (finished, current) = split_arguments(args, xml);
+    let options = parse_options(name, xml, finished);
+    let active = active_parameter(definition, &options, current);
+    let signature = build_signature(definition, xml, active);
+    Some(SignatureHelp { signatures: vec![signature], active_signature: Some(0), active_parameter: active })
+}
+
+/// Parse the finished arguments as a complete command, errors are ignored
+fn parse_options(name: &str, xml: bool, args: &str) -> CommandOptions {
+    let args = args.trim().trim_end_matches(',');
+    let input = match xml {
+        true => format!("<{} {}/>", name, args),
+        false => format!("\\{}({})", name, args),
+    };
+    match note_parser().parse(&input) {
+        Ok(ast) => find_options(&ast).unwrap_or_default(),
+        Err(_) => CommandOptions::default(),
+    }
+}
+
+fn find_options(node: &ASTNode) -> Option<CommandOptions> {
+    match &node.value {
+        ASTKind::Statements(children) | ASTKind::Paragraph(children) => children.iter().find_map(find_options),
+        ASTKind::Command(cmd) => cmd.get_options().cloned(),
+        _ => None,
+    }
+}
+
+/// The named parameter if the argument has a key, otherwise the next parameter which is not named
+fn active_parameter(definition: &CommandDefinition, options: &CommandOptions, current: &str) -> Option<u32> {
+    if let Some((key, _)) = current.split_once('=') {
+        return definition.parameters.iter().position(|p| p.name == key.trim()).map(|i| i as u32);
+    }
+    let positional = options.args.iter_sparse().count();
+    let unnamed = definition.parameters.iter().enumerate().filter(|(_, p)| options.kvs.get(&p.name).is_none());
+    unnamed.map(|(i, _)| i as u32).nth(positional)
+}
+
+/// ```md
+/// \cmd(key: Type = default, ...)
+/// <cmd key: Type = default ...>
+/// ```
+fn build_signature(definition: &CommandDefinition, xml: bool, active: Option<u32>) -> SignatureInformation {
+    let (mut label, separator, close) = match xml {
+        true => (format!("<{} ", definition.name), " ", ">"),
+        false => (format!("\\{}(", definition.name), ", ", ")"),
+    };
+    let mut parameters = vec![];
+    for (i, parameter) in definition.parameters.iter().enumerate() {
+        if i != 0 {
+            label.push_str(separator);
+        }
+        let start = label.encode_utf16().count() as u32;
+        label.push_str(&parameter_label(parameter));
+        let end = label.encode_utf16().count() as u32;
+        parameters.push(ParameterInformation {
+            label: ParameterLabel::LabelOffsets([start, end]),
+            documentation: Some(markdown(&parameter.documentation)),
+        });
+    }
+    label.push_str(close);
+    SignatureInformation {
+        label,
+        documentation: Some(markdown(&definition.documentation)),
+        parameters: Some(parameters),
+        active_parameter: active,
+    }
+}
+
+/// `key: Type = default`, the type hint is used if not a known value type
+fn parameter_label(parameter: &CommandParameter) -> String {
+    let kind = match parameter.value_type() {
+        Some(t) => t.to_string(),
+        None => parameter.kind.to_owned(),
+    };
+    match &parameter.default {
+        Some(default) => format!("{}: {} = {}", parameter.name, kind, default),
+        None => format!("{}: {}", parameter.name, kind),
+    }
+}
+
+#[test]
+fn check_active_parameter() {
+    fn active(line: &str) -> Option<u32> {
+        let (name, xml, args) = open_options(line)?;
+        let definition = get_definition(name, xml)?;
+        let (finished, current) = split_arguments(args, xml);
+        active_parameter(definition, &parse_options(name, xml, finished), current)
+    }
+    // src, alt, layout, force_caption
+    assert_eq!(active("\\img("), Some(0));
+    assert_eq!(active("\\img(\"a.png\", "), Some(1));
+    assert_eq!(active("\\img(\"a, b.png\", layout = "), Some(2));
+    assert_eq!(active("\\img(alt = \"text\", "), Some(0));
+    assert_eq!(active("\\img(alt = \"text\", \"a.png\", "), Some(2));
+    assert_eq!(active("<img src=\"a.png\" lay"), Some(1));
+    assert_eq!(active("```img(layout = \"left\", "), Some(0));
+    let signature = build_signature(get_definition("toc", false).unwrap(), false, Some(0));
+    assert_eq!(signature.label, "\\toc(max_depth: Integer = 0)");
+}
@@ -0,0 +1,75 @@
+use super::navigation::index_workspace;
+use crate::io::{read_text, WORKSPACE_INDEX};
+use notedown_ast::utils::TextIndex;
+use notedown_rt::SymbolEntryKind;
+use std::collections::HashMap;
+use tower_lsp::lsp_types::{Location, SymbolInformation, SymbolKind, Url, WorkspaceSymbolParams};
+
+/// Editors filter the results again, the best matches are enough
+const MAX_SYMBOLS: usize = 256;
+
+/// Titles, headers, footnotes and variables in the workspace matching the query
+#[allow(deprecated)]
+pub async fn workspace_symbol_provider(p: WorkspaceSymbolParams) -> Option<Vec<SymbolInformation>> {
+    index_workspace(|_| true).await;
+    let index = WORKSPACE_INDEX.get().read().await;
+    let mut texts: HashMap<&Url, TextIndex> = HashMap::new();
+    let mut out = vec![];
+    for (url, symbol) in index.search(&p.query).into_iter().take(MAX_SYMBOLS) {
+        if !texts.contains_key(url) {
+            texts.insert(url, TextIndex::new(read_text(url).await));
+        }
+        let range = texts[url].get_lsp_range(symbol.range.start, symbol.range.end);
+        let kind = match symbol.kind {
+            SymbolEntryKind::Title => SymbolKind::FILE,
+            SymbolEntryKind::Header(_) => SymbolKind::NAMESPACE,
+            SymbolEntryKind::Footnote => SymbolKind::KEY,
+            SymbolEntryKind::Variable => SymbolKind::VARIABLE,
+        };
+        out.push(SymbolInformation {
+            name: symbol.name.to_owned(),
+            kind,
+            tags: None,
+            deprecated: None,
+            location: Location { uri: url.to_owned(), range },
+            container_name: Some(symbol.detail.to_owned()),
+        })
+    }
+    Some(out)
+}
+
+#[tokio::test]
+async fn check_workspace_symbols() {
+    use crate::io::{initialize_global_storages, FileStateUpdate, FILE_STORAGE};
+    use tower_lsp::lsp_types::{DidOpenTextDocumentParams, TextDocumentItem};
+    async fn open(name: &str, text: &str) {
+        let url = Url::parse("file:///workspace-symbol/").unwrap().join(name).unwrap();
+        let document = TextDocumentItem::new(url.to_owned(), String::from("note"), 0, String::from(text));
+        FILE_STORAGE.get().write().await.update(DidOpenTextDocumentParams { text_document: document });
+        let ast = notedown_parser::NotedownParser::default().parse(text).unwrap();
+        WORKSPACE_INDEX.get().write().await.update(&url, &ast);
+    }
+    /// Names and lines of the symbols in the workspace
+    async fn search(query: &str) -> Vec<(String, u32)> {
+        let p = WorkspaceSymbolParams { query: String::from(query), ..Default::default() };
+        let symbols = workspace_symbol_provider(p).await.unwrap_or_default();
+        symbols
+            .into_iter()
+            .filter(|s| s.location.uri.path().starts_with("/workspace-symbol/"))
+            .map(|s| (s.name, s.location.range.start.line))
+            .collect()
+    }
+    fn at(name: &str, line: u32) -> (String, u32) {
+        (String::from(name), line)
+    }
+    initialize_global_storages();
+    open("guide.note", "# User Guide\n\n## Install\n\n### Use the installer\n\ntext[^cite]\n\n[^cite]: source\n\n\\set[version]: 2").await;
+    open("notes.note", "## Installation notes\n\n\\toc_ignore\n\n## Hidden\n\n\\set[author]: me").await;
+    assert_eq!(search("install").await, vec![at("Install", 2), at("Installation notes", 0), at("Use the installer", 4)]);
+    assert_eq!(search("ug").await, vec![at("User Guide", 0), at("User Guide", 0)]);
+    assert_eq!(search("version").await, vec![at("version", 10)]);
+    assert_eq!(search("author").await, vec![at("author", 6)]);
+    assert_eq!(search("cite").await, vec![at("cite", 8)]);
+    assert_eq!(search("notes").await, vec![at("notes", 0), at("Installation notes", 0)]);
+    assert!(search("hidden").await.is_empty());
+}
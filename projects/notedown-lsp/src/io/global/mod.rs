@@ -1,12 +1,29 @@
+use crate::hint::MathPreview;
+use notedown_ast::traits::SlugScheme;
+use notedown_rt::{LintConfig, WorkspaceIndex};
 use state::Storage;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fmt::{self, Debug, Formatter},
 };
 use tokio::sync::RwLock;
 use tower_lsp::lsp_types::{Url, *};
 
 pub static FILE_STORAGE: Storage<RwLock<FileStateMap>> = Storage::new();
+/// Root folders opened by the editor
+pub static WORKSPACE_FOLDERS: Storage<RwLock<Vec<Url>>> = Storage::new();
+/// Files linked by each document, the document is checked again when they change
+pub static DEPENDENCIES: Storage<RwLock<HashMap<Url, HashSet<Url>>>> = Storage::new();
+/// Settings of the writing lints from `notedown.lint`
+pub static LINT_CONFIG: Storage<RwLock<LintConfig>> = Storage::new();
+/// How the ids of headers are generated, from `notedown.slug`
+///
+/// The lock is not async, the headers are also listed out of async functions.
+pub static SLUG_SCHEME: Storage<std::sync::RwLock<SlugScheme>> = Storage::new();
+/// How the formulas are previewed on hover, from `notedown.math`
+pub static MATH_PREVIEW: Storage<std::sync::RwLock<MathPreview>> = Storage::new();
+/// Headers, footnotes and links of the parsed notes
+pub static WORKSPACE_INDEX: Storage<RwLock<WorkspaceIndex>> = Storage::new();
 
 pub trait FileStateUpdate<T> {
     fn update(&mut self, p: T);
@@ -53,7 +70,7 @@ impl FileStateUpdate<DidOpenTextDocumentParams> for FileStateMap {
 impl FileStateUpdate<DidChangeTextDocumentParams> for FileStateMap {
     fn update(&mut self, p: DidChangeTextDocumentParams) {
         let url = p.text_document.uri;
-        let v = p.text_document.version.unwrap_or(0) as usize;
+        let v = p.text_document.version as usize;
         let text = p.content_changes.iter().rev().nth(0).map(|e| e.text.clone()).unwrap_or_default();
         self.update_versioned(&url, v, text)
     }
@@ -86,8 +103,18 @@ impl FileStateMap {
     pub fn read(&self, url: &Url) -> Option<String> {
         self.inner.get(url).map(|e| e.text.to_owned())
     }
+    /// All documents opened in the editor
+    pub fn urls(&self) -> Vec<Url> {
+        self.inner.keys().cloned().collect()
+    }
 }
 
 pub fn initialize_global_storages() {
     FILE_STORAGE.set(RwLock::new(FileStateMap::default()));
+    WORKSPACE_FOLDERS.set(RwLock::new(vec![]));
+    DEPENDENCIES.set(RwLock::new(HashMap::new()));
+    LINT_CONFIG.set(RwLock::new(LintConfig::default()));
+    SLUG_SCHEME.set(std::sync::RwLock::new(SlugScheme::default()));
+    MATH_PREVIEW.set(std::sync::RwLock::new(MathPreview::default()));
+    WORKSPACE_INDEX.set(RwLock::new(WorkspaceIndex::default()));
 }
@@ -1,9 +1,99 @@
-use std::fs;
-use tower_lsp::lsp_types::Url;
+use notedown_parser::NotedownParser;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use tower_lsp::lsp_types::{Position, Url};
 
 mod global;
-pub use global::{initialize_global_storages, FileStateUpdate, FILE_STORAGE};
+pub use global::{
+    initialize_global_storages, FileStateUpdate, DEPENDENCIES, FILE_STORAGE, LINT_CONFIG, MATH_PREVIEW, SLUG_SCHEME, WORKSPACE_FOLDERS,
+    WORKSPACE_INDEX,
+};
+
+/// Folders never walked into
+const IGNORED_FOLDERS: &[&str] = &["target", "node_modules"];
+const MAX_DEPTH: usize = 8;
+const MAX_FILES: usize = 1000;
 
 pub fn read_url(url: &Url) -> String {
     url.to_file_path().ok().and_then(|e| fs::read_to_string(e).ok()).unwrap_or_default()
 }
+
+/// Parser with the settings of the editor
+pub fn note_parser() -> NotedownParser {
+    let slug_scheme = SLUG_SCHEME.try_get().and_then(|s| s.read().ok().map(|s| *s)).unwrap_or_default();
+    NotedownParser { slug_scheme, ..NotedownParser::default() }
+}
+
+/// Text in the editor, or the file on disk if not opened
+pub async fn read_text(url: &Url) -> String {
+    match FILE_STORAGE.get().read().await.read(url) {
+        Some(s) => s,
+        None => read_url(url),
+    }
+}
+
+/// Convert the lsp position to byte offset, the character is counted in utf-16 code units
+pub fn get_offset(text: &str, position: Position) -> Option<usize> {
+    let mut offset = 0;
+    for (line, s) in text.split_inclusive('\n').enumerate() {
+        if line == position.line as usize {
+            let mut units = 0;
+            for (index, c) in s.trim_end_matches(&['\r', '\n'][..]).char_indices() {
+                if units >= position.character as usize {
+                    return Some(offset + index);
+                }
+                units += c.len_utf16();
+            }
+            return Some(offset + s.trim_end_matches(&['\r', '\n'][..]).len());
+        }
+        offset += s.len();
+    }
+    match position.line as usize == text.lines().count() {
+        true => Some(text.len()),
+        false => None,
+    }
+}
+
+/// Files under the folder, hidden and ignored folders are skipped
+pub fn walk_files(dir: &Path, out: &mut Vec<PathBuf>) {
+    walk_files_in(dir, 0, out)
+}
+
+fn walk_files_in(dir: &Path, depth: usize, out: &mut Vec<PathBuf>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(o) if depth <= MAX_DEPTH => o,
+        _ => return,
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with('.') || IGNORED_FOLDERS.contains(&name.as_str()) || out.len() >= MAX_FILES {
+            continue;
+        }
+        let path = entry.path();
+        match path.is_dir() {
+            true => walk_files_in(&path, depth + 1, out),
+            false => out.push(path),
+        }
+    }
+}
+
+/// Notes in the workspace folders and the opened documents
+pub async fn workspace_notes() -> Vec<Url> {
+    let mut files = vec![];
+    for root in WORKSPACE_FOLDERS.get().read().await.iter().filter_map(|url| url.to_file_path().ok()) {
+        walk_files(&root, &mut files)
+    }
+    let mut out: Vec<Url> = files
+        .into_iter()
+        .filter(|path| path.extension().map(|e| e == "note").unwrap_or(false))
+        .filter_map(|path| Url::from_file_path(path).ok())
+        .collect();
+    for url in FILE_STORAGE.get().read().await.urls() {
+        if !out.contains(&url) {
+            out.push(url)
+        }
+    }
+    return out;
+}
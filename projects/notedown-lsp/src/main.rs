@@ -4,16 +4,18 @@
 use crate::{
     commands::{command_provider, server_commands},
     completion::{completion_provider, COMPLETION_OPTIONS},
-    diagnostic::diagnostics_provider,
-    hint::{code_action_provider, code_lens_provider, document_symbol_provider, hover_provider},
-    io::{initialize_global_storages, FileStateUpdate, FILE_STORAGE},
+    diagnostic::{dependents, diagnostics_provider},
+    hint::{
+        code_action_provider, code_lens_provider, definition_provider, document_link_provider, document_symbol_provider, hover_provider,
+        linked_editing_range_provider, prepare_rename_provider, references_provider, rename_provider, signature_help_provider,
+        watched_files_provider, workspace_symbol_provider, CODE_ACTION_KINDS,
+    },
+    io::{
+        initialize_global_storages, FileStateUpdate, FILE_STORAGE, LINT_CONFIG, MATH_PREVIEW, SLUG_SCHEME, WORKSPACE_FOLDERS, WORKSPACE_INDEX,
+    },
 };
 use serde_json::Value;
-use tower_lsp::{
-    jsonrpc::Result,
-    lsp_types::{*},
-    Client, LanguageServer, LspService, Server,
-};
+use tower_lsp::{jsonrpc::Result, lsp_types::*, Client, LanguageServer, LspService, Server};
 
 mod commands;
 mod completion;
@@ -28,37 +30,58 @@ struct Backend {
 
 #[tower_lsp::async_trait]
 impl LanguageServer for Backend {
-    async fn initialize(&self, _: InitializeParams) -> Result<InitializeResult> {
+    async fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
+        let folders = params.workspace_folders.unwrap_or_default().into_iter().map(|f| f.uri);
+        let mut roots: Vec<Url> = params.root_uri.into_iter().chain(folders).collect();
+        roots.dedup();
+        *WORKSPACE_FOLDERS.get().write().await = roots;
         let server_info = ServerInfo {
             name: format!("Notedown LSP"),
             // should read from cargo.toml
             version: Some(format!("V{}", env!("CARGO_PKG_VERSION"))),
         };
-        let ws = WorkspaceCapability {
-            workspace_folders: Some(WorkspaceFolderCapability {
+        let ws = WorkspaceServerCapabilities {
+            workspace_folders: Some(WorkspaceFoldersServerCapabilities {
                 supported: Some(true),
-                change_notifications: Some(WorkspaceFolderCapabilityChangeNotifications::Bool(true)),
+                change_notifications: Some(OneOf::Left(true)),
             }),
+            file_operations: None,
         };
         let init = InitializeResult {
             server_info: Some(server_info),
+            offset_encoding: None,
             capabilities: ServerCapabilities {
-                text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::Full)),
+                text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
                 hover_provider: Some(HoverProviderCapability::Simple(true)),
                 completion_provider: Some(COMPLETION_OPTIONS.to_owned()),
                 signature_help_provider: Some(SignatureHelpOptions {
-                    trigger_characters: None,
-                    retrigger_characters: None,
+                    trigger_characters: Some(vec![String::from("("), String::from(","), String::from(" ")]),
+                    retrigger_characters: Some(vec![String::from("=")]),
                     work_done_progress_options: Default::default(),
                 }),
                 selection_range_provider: Some(SelectionRangeProviderCapability::Simple(true)),
-                code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+                code_action_provider: Some(CodeActionProviderCapability::Options(CodeActionOptions {
+                    code_action_kinds: Some(CODE_ACTION_KINDS.to_vec()),
+                    work_done_progress_options: Default::default(),
+                    resolve_provider: None,
+                })),
                 code_lens_provider: Some(CodeLensOptions { resolve_provider: None }),
-                document_highlight_provider: Some(false),
+                document_highlight_provider: Some(OneOf::Left(false)),
                 // semantic_highlighting: None,
-                document_symbol_provider: Some(true),
-                document_formatting_provider: Some(true),
-                workspace_symbol_provider: Some(true),
+                document_symbol_provider: Some(OneOf::Left(true)),
+                definition_provider: Some(OneOf::Left(true)),
+                references_provider: Some(OneOf::Left(true)),
+                document_link_provider: Some(DocumentLinkOptions {
+                    resolve_provider: Some(false),
+                    work_done_progress_options: Default::default(),
+                }),
+                document_formatting_provider: Some(OneOf::Left(true)),
+                workspace_symbol_provider: Some(OneOf::Left(true)),
+                rename_provider: Some(OneOf::Right(RenameOptions {
+                    prepare_provider: Some(true),
+                    work_done_progress_options: Default::default(),
+                })),
+                linked_editing_range_provider: Some(LinkedEditingRangeServerCapabilities::Simple(true)),
                 execute_command_provider: Some(server_commands()),
                 workspace: Some(ws),
                 ..ServerCapabilities::default()
@@ -67,114 +90,183 @@ impl LanguageServer for Backend {
         return Ok(init);
     }
     async fn initialized(&self, _: InitializedParams) {
-        self.client.log_message(MessageType::Info, "Notedown server initialized!").await;
+        self.client.log_message(MessageType::INFO, "Notedown server initialized!").await;
+        // notes changed by other programs, git checkout for example
+        let watchers = vec![FileSystemWatcher { glob_pattern: String::from("**/*.note"), kind: None }];
+        let registration = Registration {
+            id: String::from("notedown-watched-files"),
+            method: String::from("workspace/didChangeWatchedFiles"),
+            register_options: serde_json::to_value(DidChangeWatchedFilesRegistrationOptions { watchers }).ok(),
+        };
+        if let Err(e) = self.client.register_capability(vec![registration]).await {
+            self.client.log_message(MessageType::WARNING, format!("Can not watch the notes: {}", e)).await;
+        }
     }
 
     async fn shutdown(&self) -> Result<()> {
         Ok(())
     }
     async fn did_change_configuration(&self, params: DidChangeConfigurationParams) {
-        self.client.log_message(MessageType::Info, format!("{:#?}", params)).await;
+        self.client.log_message(MessageType::INFO, format!("{:#?}", params)).await;
+        let settings = match params.settings.get("notedown") {
+            Some(s) => s,
+            None => return,
+        };
+        if let Some(slug) = settings.get("slug").and_then(|s| s.as_str()) {
+            match slug.parse() {
+                Ok(o) => {
+                    *SLUG_SCHEME.get().write().unwrap() = o;
+                }
+                Err(e) => self.client.log_message(MessageType::ERROR, format!("Invalid `notedown.slug`: {}", e)).await,
+            }
+        }
+        if let Some(math) = settings.get("math").and_then(|s| s.as_str()) {
+            match math.parse() {
+                Ok(o) => {
+                    *MATH_PREVIEW.get().write().unwrap() = o;
+                }
+                Err(e) => self.client.log_message(MessageType::ERROR, format!("Invalid `notedown.math`: {}", e)).await,
+            }
+        }
+        if let Some(lint) = settings.get("lint") {
+            match serde_json::from_value(lint.to_owned()) {
+                Ok(o) => *LINT_CONFIG.get().write().await = o,
+                Err(e) => self.client.log_message(MessageType::ERROR, format!("Invalid `notedown.lint`: {}", e)).await,
+            }
+        }
+        // headers may have new ids, the index is built again
+        *WORKSPACE_INDEX.get().write().await = Default::default();
+        let urls = FILE_STORAGE.get().read().await.urls();
+        for url in urls {
+            self.check_the_file(&url).await
+        }
+    }
+    async fn did_change_watched_files(&self, params: DidChangeWatchedFilesParams) {
+        watched_files_provider(&params.changes).await;
+        for change in &params.changes {
+            self.check_dependents(&change.uri).await
+        }
     }
     async fn symbol(&self, params: WorkspaceSymbolParams) -> Result<Option<Vec<SymbolInformation>>> {
-        self.client.log_message(MessageType::Info, format!("{:#?}", params)).await;
-        Ok(None)
+        Ok(workspace_symbol_provider(params).await)
     }
     async fn execute_command(&self, params: ExecuteCommandParams) -> Result<Option<Value>> {
-        self.client.log_message(MessageType::Info, format!("{:#?}", params)).await;
+        self.client.log_message(MessageType::INFO, format!("{:#?}", params)).await;
         Ok(command_provider(params, &self.client).await)
     }
     async fn did_open(&self, params: DidOpenTextDocumentParams) {
-        // self.client.log_message(MessageType::Info, format!("{:#?}", params)).await;
-        self.check_the_file(&params.text_document.uri).await;
+        // self.client.log_message(MessageType::INFO, format!("{:#?}", params)).await;
+        let url = params.text_document.uri.to_owned();
         FILE_STORAGE.get().write().await.update(params);
+        self.check_the_file(&url).await;
     }
     async fn did_change(&self, params: DidChangeTextDocumentParams) {
-        // self.client.log_message(MessageType::Info, format!("{:#?}", params)).await;
+        // self.client.log_message(MessageType::INFO, format!("{:#?}", params)).await;
+        let url = params.text_document.uri.to_owned();
         FILE_STORAGE.get().write().await.update(params);
+        self.check_the_file(&url).await;
+        self.check_dependents(&url).await;
     }
     async fn did_save(&self, params: DidSaveTextDocumentParams) {
-        // self.client.log_message(MessageType::Info, format!("{:#?}", params)).await;
+        // self.client.log_message(MessageType::INFO, format!("{:#?}", params)).await;
         self.check_the_file(&params.text_document.uri).await;
+        self.check_dependents(&params.text_document.uri).await;
         FILE_STORAGE.get().write().await.update(params);
     }
     async fn did_close(&self, params: DidCloseTextDocumentParams) {
-        // self.client.log_message(MessageType::Info, format!("{:#?}", params)).await;
+        // self.client.log_message(MessageType::INFO, format!("{:#?}", params)).await;
         self.check_the_file(&params.text_document.uri).await;
         FILE_STORAGE.get().write().await.update(params);
     }
     async fn completion(&self, params: CompletionParams) -> Result<Option<CompletionResponse>> {
-        self.client.log_message(MessageType::Info, format!("{:#?}", params)).await;
+        self.client.log_message(MessageType::INFO, format!("{:#?}", params)).await;
         Ok(completion_provider(params).await)
     }
     async fn completion_resolve(&self, params: CompletionItem) -> Result<CompletionItem> {
-        // self.client.log_message(MessageType::Info, format!("{:#?}", params)).await;
+        // self.client.log_message(MessageType::INFO, format!("{:#?}", params)).await;
         Ok(params)
     }
     async fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
-        // self.client.log_message(MessageType::Info, format!("{:#?}", params)).await;
-        Ok(hover_provider(params))
+        // self.client.log_message(MessageType::INFO, format!("{:#?}", params)).await;
+        Ok(hover_provider(params).await)
     }
     async fn signature_help(&self, params: SignatureHelpParams) -> Result<Option<SignatureHelp>> {
-        self.client.log_message(MessageType::Info, format!("{:#?}", params)).await;
-        Ok(None)
+        Ok(signature_help_provider(params).await)
     }
     /// 当光标在位置 x 时, 哪些内容要被选中
     async fn document_highlight(&self, _: DocumentHighlightParams) -> Result<Option<Vec<DocumentHighlight>>> {
-        // self.client.log_message(MessageType::Info, format!("{:#?}", hp)).await;
+        // self.client.log_message(MessageType::INFO, format!("{:#?}", hp)).await;
         Ok(None)
     }
 
     async fn document_symbol(&self, params: DocumentSymbolParams) -> Result<Option<DocumentSymbolResponse>> {
-        // self.client.log_message(MessageType::Info, format!("{:#?}", sp)).await;
-        Ok(document_symbol_provider(params))
+        // self.client.log_message(MessageType::INFO, format!("{:#?}", sp)).await;
+        Ok(document_symbol_provider(params).await)
     }
 
     /// Alt 键列出可执行的命令
     async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
-        // self.client.log_message(MessageType::Info, format!("{:#?}", params)).await;
-        Ok(code_action_provider(params))
+        // self.client.log_message(MessageType::INFO, format!("{:#?}", params)).await;
+        Ok(code_action_provider(params).await)
     }
     /// 单独一行的特殊注释
     async fn code_lens(&self, params: CodeLensParams) -> Result<Option<Vec<CodeLens>>> {
-        // self.client.log_message(MessageType::Info, format!("{:#?}", params)).await;
+        // self.client.log_message(MessageType::INFO, format!("{:#?}", params)).await;
         Ok(code_lens_provider(params))
     }
+    /// 链接到的文件, 标题与脚注
     async fn document_link(&self, params: DocumentLinkParams) -> Result<Option<Vec<DocumentLink>>> {
-        self.client.log_message(MessageType::Info, format!("{:#?}", params)).await;
-        Ok(None)
+        Ok(document_link_provider(params).await)
+    }
+    async fn goto_definition(&self, params: GotoDefinitionParams) -> Result<Option<GotoDefinitionResponse>> {
+        Ok(definition_provider(params).await)
+    }
+    async fn references(&self, params: ReferenceParams) -> Result<Option<Vec<Location>>> {
+        Ok(references_provider(params).await)
     }
 
     async fn document_color(&self, params: DocumentColorParams) -> Result<Vec<ColorInformation>> {
-        self.client.log_message(MessageType::Info, format!("{:#?}", params)).await;
+        self.client.log_message(MessageType::INFO, format!("{:#?}", params)).await;
         Ok(vec![])
     }
 
     async fn formatting(&self, params: DocumentFormattingParams) -> Result<Option<Vec<TextEdit>>> {
-        self.client.log_message(MessageType::Info, format!("{:#?}", params)).await;
+        self.client.log_message(MessageType::INFO, format!("{:#?}", params)).await;
         Ok(None)
     }
 
+    /// 标题, 脚注与 XML 标签重命名
+    async fn prepare_rename(&self, params: TextDocumentPositionParams) -> Result<Option<PrepareRenameResponse>> {
+        Ok(prepare_rename_provider(params).await)
+    }
     async fn rename(&self, params: RenameParams) -> Result<Option<WorkspaceEdit>> {
-        self.client.log_message(MessageType::Info, format!("{:#?}", params)).await;
-        Ok(None)
+        Ok(rename_provider(params).await)
     }
 
     async fn selection_range(&self, params: SelectionRangeParams) -> Result<Option<Vec<SelectionRange>>> {
-        self.client.log_message(MessageType::Info, format!("{:#?}", params)).await;
+        self.client.log_message(MessageType::INFO, format!("{:#?}", params)).await;
         Ok(None)
     }
+    /// 同时修改开闭标签的名称
+    async fn linked_editing_range(&self, params: LinkedEditingRangeParams) -> Result<Option<LinkedEditingRanges>> {
+        Ok(linked_editing_range_provider(params).await)
+    }
 }
 
 impl Backend {
     pub async fn check_the_file(&self, url: &Url) {
-        let _ = url;
         // self.execute_command(ExecuteCommandParams {
         //     command: "notedown.inner.request-math-svg".to_string(),
         //     arguments: vec![Value::String("x^2".to_string())],
         //     work_done_progress_params: Default::default()
         // }).await;
-        self.client.publish_diagnostics(url.clone(), diagnostics_provider(&url), None).await
+        self.client.publish_diagnostics(url.clone(), diagnostics_provider(url).await, None).await
+    }
+    /// Links to the file may be resolved or broken by the change
+    pub async fn check_dependents(&self, url: &Url) {
+        for document in dependents(url).await {
+            self.check_the_file(&document).await
+        }
     }
 }
 
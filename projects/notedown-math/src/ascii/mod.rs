@@ -0,0 +1,272 @@
+mod symbols;
+
+use self::symbols::{AsciiBinary, AsciiToken, AsciiUnary, ASCII_SYMBOLS};
+use crate::MathExpr;
+use notedown_ast::{NoteError, Result};
+
+/// Parse the AsciiMath formula, ranges of errors are offsets in the formula
+pub fn parse_ascii_math(input: &str) -> Result<MathExpr> {
+    let mut parser = AsciiParser { input, offset: 0 };
+    let row = parser.parse_expression()?;
+    parser.skip_whitespace();
+    if let Some((token, len)) = parser.peek_token() {
+        let start = parser.offset;
+        return match token {
+            AsciiToken::Right(_) => parser.error(format!("Unmatched `{}`", &input[start..start + len]), start, start + len),
+            _ => Err(NoteError::unreachable()),
+        };
+    }
+    Ok(MathExpr::row(row))
+}
+
+struct AsciiParser<'i> {
+    input: &'i str,
+    offset: usize,
+}
+
+/// A token and the bytes it takes
+type Token = (AsciiToken, usize);
+
+impl<'i> AsciiParser<'i> {
+    /// `E ::= I E | I / I E`
+    fn parse_expression(&mut self) -> Result<Vec<MathExpr>> {
+        let mut items = vec![];
+        loop {
+            self.skip_whitespace();
+            match self.peek_token() {
+                None | Some((AsciiToken::Right(_), _)) => break,
+                _ => {}
+            }
+            let item = self.parse_intermediate()?;
+            self.skip_whitespace();
+            match self.peek_token() {
+                Some((AsciiToken::Divide, len)) => {
+                    let start = self.offset;
+                    self.offset += len;
+                    let denominator = self.parse_intermediate_at("/", start)?.remove_parentheses();
+                    let numerator = Box::new(item.remove_parentheses());
+                    items.push(MathExpr::Fraction { numerator, denominator: Box::new(denominator), line: true })
+                }
+                _ => items.push(item),
+            }
+        }
+        Ok(items)
+    }
+    /// `I ::= S_S | S^S | S_S^S | S`
+    fn parse_intermediate(&mut self) -> Result<MathExpr> {
+        let base = self.parse_simple()?;
+        let limits = matches!(&base, MathExpr::LargeOperator(s) if !"∫∮".contains(s.as_str()));
+        let mut sub = None;
+        let mut sup = None;
+        self.skip_whitespace();
+        if let Some((AsciiToken::Sub, len)) = self.peek_token() {
+            let start = self.offset;
+            self.offset += len;
+            sub = Some(Box::new(self.parse_simple_at("_", start)?.remove_parentheses()));
+            self.skip_whitespace();
+        }
+        if let Some((AsciiToken::Sup, len)) = self.peek_token() {
+            let start = self.offset;
+            self.offset += len;
+            sup = Some(Box::new(self.parse_simple_at("^", start)?.remove_parentheses()));
+        }
+        if sub.is_none() && sup.is_none() {
+            return Ok(base);
+        }
+        let base = Box::new(base);
+        match limits {
+            true => Ok(MathExpr::Limits { base, under: sub, over: sup }),
+            false => Ok(MathExpr::Scripts { base, sub, sup }),
+        }
+    }
+    /// Intermediate expression required by an operator
+    fn parse_intermediate_at(&mut self, name: &str, start: usize) -> Result<MathExpr> {
+        self.expect_operand(name, start)?;
+        self.parse_intermediate()
+    }
+    /// Simple expression required by an operator
+    fn parse_simple_at(&mut self, name: &str, start: usize) -> Result<MathExpr> {
+        self.expect_operand(name, start)?;
+        self.parse_simple()
+    }
+    fn expect_operand(&mut self, name: &str, start: usize) -> Result<()> {
+        self.skip_whitespace();
+        match self.peek_token() {
+            None | Some((AsciiToken::Right(_), _)) => self.error(format!("Missing operand for `{}`", name), start, self.offset),
+            _ => Ok(()),
+        }
+    }
+    /// `S ::= v | l E r | u S | b S S`
+    fn parse_simple(&mut self) -> Result<MathExpr> {
+        self.skip_whitespace();
+        let start = self.offset;
+        if self.peek() == Some('"') {
+            return self.parse_quoted();
+        }
+        let (token, len) = match self.peek_token() {
+            Some(s) => s,
+            None => return self.error("Missing expression", start, start),
+        };
+        self.offset += len;
+        let raw = &self.input[start..self.offset];
+        let out = match token {
+            AsciiToken::Identifier(s) => MathExpr::Identifier(s.to_string()),
+            AsciiToken::Operator(s) => MathExpr::Operator(s.to_string()),
+            AsciiToken::LargeOperator(s) => MathExpr::LargeOperator(s.to_string()),
+            AsciiToken::Space(s) => MathExpr::Space(s),
+            AsciiToken::Number => MathExpr::Number(raw.to_string()),
+            AsciiToken::Char if raw == "\\" => return self.parse_macro(start),
+            AsciiToken::Char if raw.chars().all(char::is_alphabetic) => MathExpr::Identifier(raw.to_string()),
+            AsciiToken::Char | AsciiToken::Sub | AsciiToken::Sup | AsciiToken::Divide => MathExpr::Operator(raw.to_string()),
+            AsciiToken::Left(open) => self.parse_bracket(open, raw, start)?,
+            AsciiToken::Right(_) => return self.error(format!("Unmatched `{}`", raw), start, self.offset),
+            AsciiToken::Unary(unary) => self.parse_unary(unary, raw, start)?,
+            AsciiToken::Binary(binary) => {
+                let first = self.parse_simple_at(raw, start)?.remove_parentheses();
+                let second = self.parse_simple_at(raw, start)?.remove_parentheses();
+                match binary {
+                    AsciiBinary::Frac => MathExpr::Fraction { numerator: Box::new(first), denominator: Box::new(second), line: true },
+                    AsciiBinary::Root => MathExpr::Root { base: Box::new(second), index: Some(Box::new(first)) },
+                    AsciiBinary::Overset => MathExpr::Limits { base: Box::new(second), under: None, over: Some(Box::new(first)) },
+                    AsciiBinary::Underset => MathExpr::Limits { base: Box::new(second), under: Some(Box::new(first)), over: None },
+                }
+            }
+        };
+        Ok(out)
+    }
+    fn parse_bracket(&mut self, open: &str, raw: &str, start: usize) -> Result<MathExpr> {
+        let body = self.parse_expression()?;
+        self.skip_whitespace();
+        let close = match self.peek_token() {
+            Some((AsciiToken::Right(close), len)) => {
+                self.offset += len;
+                close
+            }
+            _ => return self.error(format!("Unclosed `{}`", raw), start, start + raw.len()),
+        };
+        let body = match as_matrix(&body) {
+            Some(rows) => MathExpr::Table { rows, align: "center" },
+            None => MathExpr::row(body),
+        };
+        Ok(MathExpr::Fenced { open: open.to_string(), close: close.to_string(), body: Box::new(body) })
+    }
+    fn parse_unary(&mut self, unary: AsciiUnary, raw: &str, start: usize) -> Result<MathExpr> {
+        if let AsciiUnary::Text = unary {
+            return self.parse_text(start);
+        }
+        let argument = Box::new(self.parse_simple_at(raw, start)?.remove_parentheses());
+        let out = match unary {
+            AsciiUnary::Sqrt => MathExpr::Root { base: argument, index: None },
+            AsciiUnary::Font(variant) => MathExpr::Style { variant, body: argument },
+            AsciiUnary::Accent(accent, over) => MathExpr::Accent { base: argument, accent: accent.to_string(), over },
+            AsciiUnary::Fence(open, close) => MathExpr::Fenced { open: open.to_string(), close: close.to_string(), body: argument },
+            AsciiUnary::Text => return Err(NoteError::unreachable()),
+        };
+        Ok(out)
+    }
+    /// `text(raw)`
+    fn parse_text(&mut self, start: usize) -> Result<MathExpr> {
+        self.skip_whitespace();
+        if self.peek() != Some('(') {
+            return self.error("Missing `(` after `text`", start, self.offset);
+        }
+        let open = self.offset;
+        match self.input[open..].find(')') {
+            Some(end) => {
+                self.offset = open + end + 1;
+                Ok(MathExpr::Text(self.input[open + 1..open + end].to_string()))
+            }
+            None => self.error("Unclosed `(`", open, open + 1),
+        }
+    }
+    /// LaTeX macros are not allowed in AsciiMath
+    fn parse_macro(&mut self, start: usize) -> Result<MathExpr> {
+        let name = self.rest().find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(self.rest().len());
+        if name == 0 {
+            return Ok(MathExpr::Operator("\\".to_string()));
+        }
+        self.offset += name;
+        self.error(format!("Unsupported macro `{}` in AsciiMath", &self.input[start..self.offset]), start, self.offset)
+    }
+    /// `"raw"`
+    fn parse_quoted(&mut self) -> Result<MathExpr> {
+        let open = self.offset;
+        match self.input[open + 1..].find('"') {
+            Some(end) => {
+                self.offset = open + end + 2;
+                Ok(MathExpr::Text(self.input[open + 1..open + end + 1].to_string()))
+            }
+            None => self.error("Unclosed `\"`", open, open + 1),
+        }
+    }
+}
+
+impl<'i> AsciiParser<'i> {
+    #[inline]
+    fn rest(&self) -> &'i str {
+        &self.input[self.offset..]
+    }
+    #[inline]
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.offset += rest.len() - rest.trim_start().len();
+    }
+    /// Symbols take the longest match, then numbers, then a single char
+    fn peek_token(&self) -> Option<Token> {
+        let rest = self.rest();
+        let first = rest.chars().next()?;
+        if first.is_ascii_digit() {
+            let mut end = 0;
+            for (i, c) in rest.char_indices() {
+                match c {
+                    '0'..='9' => end = i + 1,
+                    '.' if rest[i + 1..].starts_with(|c: char| c.is_ascii_digit()) => end = i + 1,
+                    _ => break,
+                }
+            }
+            return Some((AsciiToken::Number, end));
+        }
+        let symbol = ASCII_SYMBOLS.iter().filter(|(name, _)| rest.starts_with(name)).max_by_key(|(name, _)| name.len());
+        if let Some((name, token)) = symbol {
+            return Some((*token, name.len()));
+        }
+        Some((AsciiToken::Char, first.len_utf8()))
+    }
+    fn error<T>(&self, msg: impl Into<String>, start: usize, end: usize) -> Result<T> {
+        let mut error = NoteError::syntax_error(msg);
+        error.set_range(start, end);
+        Err(error)
+    }
+}
+
+/// `[[a,b],[c,d]]` or `((a,b),(c,d))`
+fn as_matrix(items: &[MathExpr]) -> Option<Vec<Vec<MathExpr>>> {
+    let mut rows = vec![];
+    let mut bracket = None;
+    for (i, item) in items.iter().enumerate() {
+        match item {
+            MathExpr::Operator(s) if i % 2 == 1 && s == "," => continue,
+            MathExpr::Fenced { open, close, body } if i % 2 == 0 && !open.is_empty() => {
+                if *bracket.get_or_insert((open, close)) != (open, close) {
+                    return None;
+                }
+                let cells = match body.as_ref() {
+                    MathExpr::Row(cells) => {
+                        cells.split(|c| matches!(c, MathExpr::Operator(s) if s == ",")).map(|c| MathExpr::row(c.to_vec())).collect()
+                    }
+                    cell => vec![cell.clone()],
+                };
+                rows.push(cells);
+            }
+            _ => return None,
+        }
+    }
+    let columns = rows.first()?.len();
+    match rows.len() > 1 && columns > 1 && rows.iter().all(|r| r.len() == columns) {
+        true => Some(rows),
+        false => None,
+    }
+}
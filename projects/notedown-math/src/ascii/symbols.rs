@@ -0,0 +1,271 @@
+use crate::MathVariant;
+
+#[derive(Copy, Clone, Debug)]
+pub enum AsciiToken {
+    Identifier(&'static str),
+    Operator(&'static str),
+    LargeOperator(&'static str),
+    Space(f32),
+    Left(&'static str),
+    Right(&'static str),
+    Unary(AsciiUnary),
+    Binary(AsciiBinary),
+    Sub,
+    Sup,
+    Divide,
+    /// Digits not in the symbol table
+    Number,
+    /// Single char not in the symbol table
+    Char,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum AsciiUnary {
+    Sqrt,
+    Text,
+    Font(MathVariant),
+    Accent(&'static str, bool),
+    Fence(&'static str, &'static str),
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum AsciiBinary {
+    Frac,
+    Root,
+    Overset,
+    Underset,
+}
+
+/// Symbols in AsciiMath, the longest match wins
+pub const ASCII_SYMBOLS: &[(&str, AsciiToken)] = {
+    use AsciiBinary::*;
+    use AsciiToken::*;
+    use AsciiUnary::*;
+    &[
+        // scripts
+        ("_", Sub),
+        ("^", Sup),
+        ("/", Divide),
+        // operators
+        ("+", Operator("+")),
+        ("-", Operator("−")),
+        ("*", Operator("⋅")),
+        ("**", Operator("∗")),
+        ("***", Operator("⋆")),
+        ("//", Operator("/")),
+        ("\\\\", Operator("\\")),
+        ("setminus", Operator("\\")),
+        ("xx", Operator("×")),
+        ("|><", Operator("⋉")),
+        ("><|", Operator("⋊")),
+        ("|><|", Operator("⋈")),
+        ("-:", Operator("÷")),
+        ("@", Operator("∘")),
+        ("o+", Operator("⊕")),
+        ("ox", Operator("⊗")),
+        ("o.", Operator("⊙")),
+        ("^^", Operator("∧")),
+        ("vv", Operator("∨")),
+        ("nn", Operator("∩")),
+        ("uu", Operator("∪")),
+        ("sum", LargeOperator("∑")),
+        ("prod", LargeOperator("∏")),
+        ("^^^", LargeOperator("⋀")),
+        ("vvv", LargeOperator("⋁")),
+        ("nnn", LargeOperator("⋂")),
+        ("uuu", LargeOperator("⋃")),
+        ("int", LargeOperator("∫")),
+        ("oint", LargeOperator("∮")),
+        ("lim", LargeOperator("lim")),
+        ("Lim", LargeOperator("Lim")),
+        // relations
+        ("=", Operator("=")),
+        ("!=", Operator("≠")),
+        (":=", Operator(":=")),
+        ("<", Operator("<")),
+        ("lt", Operator("<")),
+        ("<=", Operator("≤")),
+        ("le", Operator("≤")),
+        ("lt=", Operator("≤")),
+        (">", Operator(">")),
+        ("gt", Operator(">")),
+        (">=", Operator("≥")),
+        ("ge", Operator("≥")),
+        ("gt=", Operator("≥")),
+        ("-<", Operator("≺")),
+        (">-", Operator("≻")),
+        ("in", Operator("∈")),
+        ("!in", Operator("∉")),
+        ("sub", Operator("⊂")),
+        ("sup", Operator("⊃")),
+        ("sube", Operator("⊆")),
+        ("supe", Operator("⊇")),
+        ("-=", Operator("≡")),
+        ("~=", Operator("≅")),
+        ("~~", Operator("≈")),
+        ("~", Operator("∼")),
+        ("prop", Operator("∝")),
+        // logical
+        ("not", Operator("¬")),
+        ("=>", Operator("⇒")),
+        ("<=>", Operator("⇔")),
+        ("AA", Operator("∀")),
+        ("EE", Operator("∃")),
+        ("_|_", Operator("⊥")),
+        ("TT", Operator("⊤")),
+        ("|--", Operator("⊢")),
+        ("|==", Operator("⊨")),
+        // arrows
+        ("uarr", Operator("↑")),
+        ("darr", Operator("↓")),
+        ("rarr", Operator("→")),
+        ("->", Operator("→")),
+        ("to", Operator("→")),
+        (">->", Operator("↣")),
+        ("->>", Operator("↠")),
+        ("|->", Operator("↦")),
+        ("larr", Operator("←")),
+        ("harr", Operator("↔")),
+        ("rArr", Operator("⇒")),
+        ("lArr", Operator("⇐")),
+        ("hArr", Operator("⇔")),
+        // misc
+        ("+-", Operator("±")),
+        ("-+", Operator("∓")),
+        (",", Operator(",")),
+        ("del", Identifier("∂")),
+        ("partial", Identifier("∂")),
+        ("grad", Identifier("∇")),
+        ("nabla", Identifier("∇")),
+        ("O/", Identifier("∅")),
+        ("oo", Identifier("∞")),
+        ("aleph", Identifier("ℵ")),
+        (":.", Operator("∴")),
+        (":'", Operator("∵")),
+        ("/_", Operator("∠")),
+        ("/_\\", Operator("△")),
+        ("'", Operator("′")),
+        ("...", Operator("…")),
+        ("cdots", Operator("⋯")),
+        ("vdots", Operator("⋮")),
+        ("ddots", Operator("⋱")),
+        ("diamond", Operator("⋄")),
+        ("square", Operator("□")),
+        ("CC", Identifier("ℂ")),
+        ("NN", Identifier("ℕ")),
+        ("QQ", Identifier("ℚ")),
+        ("RR", Identifier("ℝ")),
+        ("ZZ", Identifier("ℤ")),
+        ("\\ ", Space(0.25)),
+        ("quad", Space(1.0)),
+        ("qquad", Space(2.0)),
+        // functions
+        ("sin", Identifier("sin")),
+        ("cos", Identifier("cos")),
+        ("tan", Identifier("tan")),
+        ("sec", Identifier("sec")),
+        ("csc", Identifier("csc")),
+        ("cot", Identifier("cot")),
+        ("arcsin", Identifier("arcsin")),
+        ("arccos", Identifier("arccos")),
+        ("arctan", Identifier("arctan")),
+        ("sinh", Identifier("sinh")),
+        ("cosh", Identifier("cosh")),
+        ("tanh", Identifier("tanh")),
+        ("log", Identifier("log")),
+        ("ln", Identifier("ln")),
+        ("exp", Identifier("exp")),
+        ("det", Identifier("det")),
+        ("dim", Identifier("dim")),
+        ("mod", Identifier("mod")),
+        ("gcd", Identifier("gcd")),
+        ("lcm", Identifier("lcm")),
+        ("min", Identifier("min")),
+        ("max", Identifier("max")),
+        // greek
+        ("alpha", Identifier("α")),
+        ("beta", Identifier("β")),
+        ("gamma", Identifier("γ")),
+        ("Gamma", Identifier("Γ")),
+        ("delta", Identifier("δ")),
+        ("Delta", Identifier("Δ")),
+        ("epsilon", Identifier("ε")),
+        ("varepsilon", Identifier("ɛ")),
+        ("zeta", Identifier("ζ")),
+        ("eta", Identifier("η")),
+        ("theta", Identifier("θ")),
+        ("Theta", Identifier("Θ")),
+        ("vartheta", Identifier("ϑ")),
+        ("iota", Identifier("ι")),
+        ("kappa", Identifier("κ")),
+        ("lambda", Identifier("λ")),
+        ("Lambda", Identifier("Λ")),
+        ("mu", Identifier("μ")),
+        ("nu", Identifier("ν")),
+        ("xi", Identifier("ξ")),
+        ("Xi", Identifier("Ξ")),
+        ("pi", Identifier("π")),
+        ("Pi", Identifier("Π")),
+        ("rho", Identifier("ρ")),
+        ("sigma", Identifier("σ")),
+        ("Sigma", Identifier("Σ")),
+        ("tau", Identifier("τ")),
+        ("upsilon", Identifier("υ")),
+        ("phi", Identifier("ϕ")),
+        ("Phi", Identifier("Φ")),
+        ("varphi", Identifier("φ")),
+        ("chi", Identifier("χ")),
+        ("psi", Identifier("ψ")),
+        ("Psi", Identifier("Ψ")),
+        ("omega", Identifier("ω")),
+        ("Omega", Identifier("Ω")),
+        // brackets
+        ("(", Left("(")),
+        (")", Right(")")),
+        ("[", Left("[")),
+        ("]", Right("]")),
+        ("{", Left("{")),
+        ("}", Right("}")),
+        ("(:", Left("⟨")),
+        (":)", Right("⟩")),
+        ("<<", Left("⟨")),
+        (">>", Right("⟩")),
+        ("{:", Left("")),
+        (":}", Right("")),
+        ("|", Operator("|")),
+        ("||", Operator("‖")),
+        // unary
+        ("sqrt", Unary(Sqrt)),
+        ("text", Unary(Text)),
+        ("bb", Unary(Font(MathVariant::Bold))),
+        ("bbb", Unary(Font(MathVariant::DoubleStruck))),
+        ("cc", Unary(Font(MathVariant::Script))),
+        ("tt", Unary(Font(MathVariant::Monospace))),
+        ("fr", Unary(Font(MathVariant::Fraktur))),
+        ("sf", Unary(Font(MathVariant::SansSerif))),
+        ("rm", Unary(Font(MathVariant::Normal))),
+        ("hat", Unary(Accent("^", true))),
+        ("bar", Unary(Accent("¯", true))),
+        ("overline", Unary(Accent("‾", true))),
+        ("vec", Unary(Accent("→", true))),
+        ("dot", Unary(Accent("˙", true))),
+        ("ddot", Unary(Accent("¨", true))),
+        ("tilde", Unary(Accent("~", true))),
+        ("obrace", Unary(Accent("⏞", true))),
+        ("overbrace", Unary(Accent("⏞", true))),
+        ("ul", Unary(Accent("_", false))),
+        ("underline", Unary(Accent("_", false))),
+        ("ubrace", Unary(Accent("⏟", false))),
+        ("underbrace", Unary(Accent("⏟", false))),
+        ("abs", Unary(Fence("|", "|"))),
+        ("norm", Unary(Fence("‖", "‖"))),
+        ("floor", Unary(Fence("⌊", "⌋"))),
+        ("ceil", Unary(Fence("⌈", "⌉"))),
+        // binary
+        ("frac", Binary(Frac)),
+        ("root", Binary(Root)),
+        ("stackrel", Binary(Overset)),
+        ("overset", Binary(Overset)),
+        ("underset", Binary(Underset)),
+    ]
+};
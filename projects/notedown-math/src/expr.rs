@@ -0,0 +1,333 @@
+use html_escape::encode_text;
+use std::fmt::{self, Write};
+
+/// Formula tree shared by all math backends
+#[derive(Clone, Debug, PartialEq)]
+pub enum MathExpr {
+    /// `<mrow>`
+    Row(Vec<MathExpr>),
+    /// `<mi>`
+    Identifier(String),
+    /// `<mn>`
+    Number(String),
+    /// `<mo>`
+    Operator(String),
+    /// `<mo>` which takes limits in display style, e.g. `∑`
+    LargeOperator(String),
+    /// `<mtext>`
+    Text(String),
+    /// `<mspace>`, width in em
+    Space(f32),
+    /// `<mfrac>`, no line for binomial coefficients
+    Fraction {
+        /// Upper part
+        numerator: Box<MathExpr>,
+        /// Lower part
+        denominator: Box<MathExpr>,
+        /// Draw the fraction line
+        line: bool,
+    },
+    /// `<msqrt>` or `<mroot>`
+    Root {
+        /// Radicand
+        base: Box<MathExpr>,
+        /// Degree of the root
+        index: Option<Box<MathExpr>>,
+    },
+    /// `<msub>`, `<msup>` or `<msubsup>`
+    Scripts {
+        /// Nucleus
+        base: Box<MathExpr>,
+        /// Subscript
+        sub: Option<Box<MathExpr>>,
+        /// Superscript
+        sup: Option<Box<MathExpr>>,
+    },
+    /// `<munder>`, `<mover>` or `<munderover>`
+    Limits {
+        /// Nucleus
+        base: Box<MathExpr>,
+        /// Under script
+        under: Option<Box<MathExpr>>,
+        /// Over script
+        over: Option<Box<MathExpr>>,
+    },
+    /// `<mover accent>` or `<munder accentunder>`
+    Accent {
+        /// Nucleus
+        base: Box<MathExpr>,
+        /// Accent mark
+        accent: String,
+        /// Accent above or below the nucleus
+        over: bool,
+    },
+    /// Stretchy brackets, empty string means no bracket
+    Fenced {
+        /// Left bracket
+        open: String,
+        /// Right bracket
+        close: String,
+        /// Content between brackets
+        body: Box<MathExpr>,
+    },
+    /// `<mtable>`
+    Table {
+        /// Cells of each row
+        rows: Vec<Vec<MathExpr>>,
+        /// Value of `columnalign`
+        align: &'static str,
+    },
+    /// Change the font of identifiers and numbers
+    Style {
+        /// Target font
+        variant: MathVariant,
+        /// Styled content
+        body: Box<MathExpr>,
+    },
+    /// Raw MathML, written as is
+    Raw(String),
+}
+
+/// Math alphanumeric fonts
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum MathVariant {
+    /// Upright
+    Normal,
+    /// `\mathbf`
+    Bold,
+    /// `\mathit`
+    Italic,
+    /// `\boldsymbol`
+    BoldItalic,
+    /// `\mathbb`
+    DoubleStruck,
+    /// `\mathcal`
+    Script,
+    /// `\mathfrak`
+    Fraktur,
+    /// `\mathsf`
+    SansSerif,
+    /// `\mathtt`
+    Monospace,
+}
+
+impl Default for MathExpr {
+    fn default() -> Self {
+        Self::Row(vec![])
+    }
+}
+
+impl MathExpr {
+    /// Wrap nodes in a row, unwrap if there's only one node
+    pub fn row(mut items: Vec<MathExpr>) -> Self {
+        match items.len() {
+            1 => items.remove(0),
+            _ => Self::Row(items),
+        }
+    }
+    /// Remove the `()` around arguments, e.g. `(a+b)/2` in AsciiMath
+    pub fn remove_parentheses(self) -> Self {
+        match self {
+            Self::Fenced { open, close, body } if open == "(" && close == ")" => *body,
+            _ => self,
+        }
+    }
+}
+
+impl MathExpr {
+    /// Render as `<math>` element
+    pub fn to_mathml(&self, block: bool, display: bool) -> String {
+        let mut out = String::new();
+        // writing into string never fails
+        let _ = self.write_math(&mut out, block, display);
+        return out;
+    }
+    /// Write as `<math>` element
+    pub fn write_math(&self, f: &mut impl Write, block: bool, display: bool) -> fmt::Result {
+        f.write_str(r#"<math xmlns="http://www.w3.org/1998/Math/MathML""#)?;
+        if block {
+            f.write_str(r#" display="block""#)?;
+        }
+        f.write_char('>')?;
+        match display {
+            true => f.write_str(r#"<mstyle displaystyle="true">"#)?,
+            false => f.write_str("<mrow>")?,
+        }
+        self.write_mathml(f, None)?;
+        match display {
+            true => f.write_str("</mstyle>")?,
+            false => f.write_str("</mrow>")?,
+        }
+        f.write_str("</math>")
+    }
+    /// Write the MathML of this node, identifiers and numbers use the given font
+    pub fn write_mathml(&self, f: &mut impl Write, variant: Option<MathVariant>) -> fmt::Result {
+        match self {
+            Self::Row(items) => {
+                f.write_str("<mrow>")?;
+                for item in items {
+                    item.write_mathml(f, variant)?;
+                }
+                f.write_str("</mrow>")
+            }
+            Self::Identifier(s) => match variant {
+                Some(MathVariant::Normal) => write!(f, r#"<mi mathvariant="normal">{}</mi>"#, encode_text(s)),
+                Some(v) => write!(f, "<mi>{}</mi>", encode_text(&v.apply(s))),
+                None => write!(f, "<mi>{}</mi>", encode_text(s)),
+            },
+            Self::Number(s) => match variant {
+                Some(v) => write!(f, "<mn>{}</mn>", encode_text(&v.apply(s))),
+                None => write!(f, "<mn>{}</mn>", encode_text(s)),
+            },
+            Self::Operator(s) => write!(f, "<mo>{}</mo>", encode_text(s)),
+            Self::LargeOperator(s) => write!(f, r#"<mo largeop="true" movablelimits="true">{}</mo>"#, encode_text(s)),
+            Self::Text(s) => write!(f, "<mtext>{}</mtext>", encode_text(s)),
+            Self::Space(em) => write!(f, r#"<mspace width="{}em"/>"#, em),
+            Self::Fraction { numerator, denominator, line } => {
+                match line {
+                    true => f.write_str("<mfrac>")?,
+                    false => f.write_str(r#"<mfrac linethickness="0">"#)?,
+                }
+                numerator.write_mathml(f, variant)?;
+                denominator.write_mathml(f, variant)?;
+                f.write_str("</mfrac>")
+            }
+            Self::Root { base, index: None } => {
+                f.write_str("<msqrt>")?;
+                base.write_mathml(f, variant)?;
+                f.write_str("</msqrt>")
+            }
+            Self::Root { base, index: Some(index) } => {
+                f.write_str("<mroot>")?;
+                base.write_mathml(f, variant)?;
+                index.write_mathml(f, variant)?;
+                f.write_str("</mroot>")
+            }
+            Self::Scripts { base, sub, sup } => write_scripts(f, ["msub", "msup", "msubsup"], base, sub, sup, variant),
+            Self::Limits { base, under, over } => write_scripts(f, ["munder", "mover", "munderover"], base, under, over, variant),
+            Self::Accent { base, accent, over } => {
+                let (tag, attr) = match over {
+                    true => ("mover", "accent"),
+                    false => ("munder", "accentunder"),
+                };
+                write!(f, r#"<{} {}="true">"#, tag, attr)?;
+                base.write_mathml(f, variant)?;
+                write!(f, r#"<mo stretchy="true">{}</mo>"#, encode_text(accent))?;
+                write!(f, "</{}>", tag)
+            }
+            Self::Fenced { open, close, body } => {
+                f.write_str("<mrow>")?;
+                if !open.is_empty() {
+                    write!(f, r#"<mo fence="true" stretchy="true">{}</mo>"#, encode_text(open))?;
+                }
+                body.write_mathml(f, variant)?;
+                if !close.is_empty() {
+                    write!(f, r#"<mo fence="true" stretchy="true">{}</mo>"#, encode_text(close))?;
+                }
+                f.write_str("</mrow>")
+            }
+            Self::Table { rows, align } => {
+                write!(f, r#"<mtable columnalign="{}">"#, align)?;
+                for row in rows {
+                    f.write_str("<mtr>")?;
+                    for cell in row {
+                        f.write_str("<mtd>")?;
+                        cell.write_mathml(f, variant)?;
+                        f.write_str("</mtd>")?;
+                    }
+                    f.write_str("</mtr>")?;
+                }
+                f.write_str("</mtable>")
+            }
+            Self::Style { variant, body } => body.write_mathml(f, Some(*variant)),
+            Self::Raw(s) => f.write_str(s),
+        }
+    }
+}
+
+fn write_scripts(
+    f: &mut impl Write,
+    tags: [&str; 3],
+    base: &MathExpr,
+    lower: &Option<Box<MathExpr>>,
+    upper: &Option<Box<MathExpr>>,
+    variant: Option<MathVariant>,
+) -> fmt::Result {
+    let tag = match (lower, upper) {
+        (Some(_), None) => tags[0],
+        (None, Some(_)) => tags[1],
+        (Some(_), Some(_)) => tags[2],
+        (None, None) => return base.write_mathml(f, variant),
+    };
+    write!(f, "<{}>", tag)?;
+    base.write_mathml(f, variant)?;
+    if let Some(s) = lower {
+        s.write_mathml(f, variant)?;
+    }
+    if let Some(s) = upper {
+        s.write_mathml(f, variant)?;
+    }
+    write!(f, "</{}>", tag)
+}
+
+impl MathVariant {
+    /// Map ascii letters and digits to the Mathematical Alphanumeric Symbols
+    pub fn apply(&self, s: &str) -> String {
+        s.chars().map(|c| self.map_char(c)).collect()
+    }
+    fn map_char(&self, c: char) -> char {
+        if let Some(s) = self.exception(c) {
+            return s;
+        }
+        let (upper, lower, digit) = match self {
+            Self::Normal | Self::Italic => return c,
+            Self::Bold => (0x1D400, 0x1D41A, Some(0x1D7CE)),
+            Self::BoldItalic => (0x1D468, 0x1D482, None),
+            Self::DoubleStruck => (0x1D538, 0x1D552, Some(0x1D7D8)),
+            Self::Script => (0x1D49C, 0x1D4B6, None),
+            Self::Fraktur => (0x1D504, 0x1D51E, None),
+            Self::SansSerif => (0x1D5A0, 0x1D5BA, Some(0x1D7E2)),
+            Self::Monospace => (0x1D670, 0x1D68A, Some(0x1D7F6)),
+        };
+        let code = match c {
+            'A'..='Z' => upper + (c as u32 - 'A' as u32),
+            'a'..='z' => lower + (c as u32 - 'a' as u32),
+            '0'..='9' => match digit {
+                Some(d) => d + (c as u32 - '0' as u32),
+                None => return c,
+            },
+            _ => return c,
+        };
+        char::from_u32(code).unwrap_or(c)
+    }
+    /// Letters which are in the Letterlike Symbols block
+    fn exception(&self, c: char) -> Option<char> {
+        let out = match (self, c) {
+            (Self::DoubleStruck, 'C') => 'ℂ',
+            (Self::DoubleStruck, 'H') => 'ℍ',
+            (Self::DoubleStruck, 'N') => 'ℕ',
+            (Self::DoubleStruck, 'P') => 'ℙ',
+            (Self::DoubleStruck, 'Q') => 'ℚ',
+            (Self::DoubleStruck, 'R') => 'ℝ',
+            (Self::DoubleStruck, 'Z') => 'ℤ',
+            (Self::Script, 'B') => 'ℬ',
+            (Self::Script, 'E') => 'ℰ',
+            (Self::Script, 'F') => 'ℱ',
+            (Self::Script, 'H') => 'ℋ',
+            (Self::Script, 'I') => 'ℐ',
+            (Self::Script, 'L') => 'ℒ',
+            (Self::Script, 'M') => 'ℳ',
+            (Self::Script, 'R') => 'ℛ',
+            (Self::Script, 'e') => 'ℯ',
+            (Self::Script, 'g') => 'ℊ',
+            (Self::Script, 'o') => 'ℴ',
+            (Self::Fraktur, 'C') => 'ℭ',
+            (Self::Fraktur, 'H') => 'ℌ',
+            (Self::Fraktur, 'I') => 'ℑ',
+            (Self::Fraktur, 'R') => 'ℜ',
+            (Self::Fraktur, 'Z') => 'ℨ',
+            _ => return None,
+        };
+        Some(out)
+    }
+}
@@ -0,0 +1,427 @@
+mod symbols;
+
+use self::symbols::{latex_accent, latex_delimiter, latex_delimiter_macro, latex_font, latex_symbol};
+use crate::MathExpr;
+use notedown_ast::{NoteError, Result};
+
+/// Parse the LaTeX formula, ranges of errors are offsets in the formula
+pub fn parse_latex(input: &str) -> Result<MathExpr> {
+    LaTeXParser { input, offset: 0 }.parse()
+}
+
+struct LaTeXParser<'i> {
+    input: &'i str,
+    offset: usize,
+}
+
+impl<'i> LaTeXParser<'i> {
+    fn parse(&mut self) -> Result<MathExpr> {
+        let mut lines = vec![];
+        loop {
+            let row = self.parse_row(None)?;
+            lines.push(MathExpr::row(row));
+            let start = self.offset;
+            match self.peek() {
+                None => break,
+                Some('}') => return self.error("Unmatched `}`", start, start + 1),
+                Some('&') => return self.error("Unexpected `&` outside of environment", start, start + 1),
+                _ => {}
+            }
+            match self.read_macro_name()? {
+                "\\" => continue,
+                "right" => return self.error("Unexpected `\\right` without `\\left`", start, self.offset),
+                "end" => {
+                    let name = self.read_raw_group("end", start)?;
+                    return self.error(format!("Unexpected `\\end{{{}}}` without `\\begin{{{}}}`", name, name), start, self.offset);
+                }
+                _ => return Err(NoteError::unreachable()),
+            }
+        }
+        if lines.len() == 1 {
+            return Ok(lines.remove(0));
+        }
+        let rows = lines.into_iter().map(|line| vec![line]).collect();
+        Ok(MathExpr::Table { rows, align: "center" })
+    }
+    /// Parse until `}`, `&`, `\\`, `\right`, `\end` or the given close char
+    fn parse_row(&mut self, close: Option<char>) -> Result<Vec<MathExpr>> {
+        let mut items = vec![];
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                None | Some('}') | Some('&') => break,
+                Some(c) if Some(c) == close => break,
+                Some('\\') if matches!(self.peek_macro_name(), Some("\\" | "right" | "end")) => break,
+                _ => items.push(self.parse_scripts()?),
+            }
+        }
+        Ok(items)
+    }
+    fn parse_scripts(&mut self) -> Result<MathExpr> {
+        let base = match self.peek() {
+            Some('^' | '_') => MathExpr::default(),
+            _ => self.parse_atom()?,
+        };
+        let mut limits = matches!(&base, MathExpr::LargeOperator(s) if !"∫∬∭∮".contains(s.as_str()));
+        let mut sub = None;
+        let mut sup = None;
+        let mut primes = None;
+        loop {
+            self.skip_whitespace();
+            let start = self.offset;
+            match self.peek() {
+                Some('^') => {
+                    self.bump();
+                    if sup.is_some() {
+                        return self.error("Double superscript", start, start + 1);
+                    }
+                    sup = Some(self.parse_argument(start)?)
+                }
+                Some('_') => {
+                    self.bump();
+                    if sub.is_some() {
+                        return self.error("Double subscript", start, start + 1);
+                    }
+                    sub = Some(self.parse_argument(start)?)
+                }
+                Some('\'') => {
+                    let mut count = 0;
+                    while self.peek() == Some('\'') {
+                        self.bump();
+                        count += 1;
+                    }
+                    primes = Some(MathExpr::Operator("′".repeat(count)))
+                }
+                Some('\\') if matches!(self.peek_macro_name(), Some("limits" | "nolimits")) => {
+                    limits = self.read_macro_name()? == "limits";
+                }
+                _ => break,
+            }
+        }
+        let sup = match (primes, sup) {
+            (Some(p), Some(s)) => Some(MathExpr::Row(vec![p, s])),
+            (p, s) => p.or(s),
+        };
+        if sub.is_none() && sup.is_none() {
+            return Ok(base);
+        }
+        let (base, sub, sup) = (Box::new(base), sub.map(Box::new), sup.map(Box::new));
+        match limits {
+            true => Ok(MathExpr::Limits { base, under: sub, over: sup }),
+            false => Ok(MathExpr::Scripts { base, sub, sup }),
+        }
+    }
+    fn parse_atom(&mut self) -> Result<MathExpr> {
+        match self.peek() {
+            Some('{') => self.parse_group(),
+            Some('\\') => self.parse_macro(),
+            _ => self.parse_char(false),
+        }
+    }
+    /// Argument of macros and scripts, a group, a macro or a single char
+    fn parse_argument(&mut self, start: usize) -> Result<MathExpr> {
+        self.skip_whitespace();
+        match self.peek() {
+            None | Some('}' | '&' | '^' | '_') => {
+                let name = match self.input[start..].starts_with('\\') {
+                    true => &self.input[start..start + 1 + self.macro_name_at(start + 1).len()],
+                    false => &self.input[start..start + 1],
+                };
+                self.error(format!("Missing argument for `{}`", name), start, self.offset)
+            }
+            Some('{') => self.parse_group(),
+            Some('\\') => self.parse_macro(),
+            _ => self.parse_char(true),
+        }
+    }
+    fn parse_group(&mut self) -> Result<MathExpr> {
+        let start = self.offset;
+        self.bump();
+        let row = self.parse_row(None)?;
+        match self.peek() {
+            Some('}') => {
+                self.bump();
+                Ok(MathExpr::row(row))
+            }
+            _ => self.error("Unclosed `{`, missing `}`", start, start + 1),
+        }
+    }
+    fn parse_char(&mut self, single: bool) -> Result<MathExpr> {
+        let start = self.offset;
+        let c = match self.bump() {
+            Some(c) => c,
+            None => return Err(NoteError::unreachable()),
+        };
+        if c.is_ascii_digit() {
+            if !single {
+                while matches!(self.peek(), Some('0'..='9'))
+                    || (self.peek() == Some('.') && matches!(self.peek_nth(1), Some(c) if c.is_ascii_digit()))
+                {
+                    self.bump();
+                }
+            }
+            return Ok(MathExpr::Number(self.input[start..self.offset].to_string()));
+        }
+        let out = match c {
+            '-' => MathExpr::Operator("−".to_string()),
+            '*' => MathExpr::Operator("∗".to_string()),
+            '\'' => MathExpr::Operator("′".to_string()),
+            '~' => MathExpr::Space(0.25),
+            '#' | '$' => return self.error(format!("Unexpected `{}` in formula", c), start, self.offset),
+            _ if c.is_alphabetic() => MathExpr::Identifier(c.to_string()),
+            _ if c.is_numeric() => MathExpr::Number(c.to_string()),
+            _ => MathExpr::Operator(c.to_string()),
+        };
+        Ok(out)
+    }
+    fn parse_macro(&mut self) -> Result<MathExpr> {
+        let start = self.offset;
+        let name = self.read_macro_name()?;
+        if let Some(s) = latex_symbol(name) {
+            return Ok(s);
+        }
+        if let Some(variant) = latex_font(name) {
+            let body = Box::new(self.parse_argument(start)?);
+            return Ok(MathExpr::Style { variant, body });
+        }
+        if let Some((accent, over)) = latex_accent(name) {
+            let base = Box::new(self.parse_argument(start)?);
+            return Ok(MathExpr::Accent { base, accent: accent.to_string(), over });
+        }
+        let out = match name {
+            "frac" | "dfrac" | "tfrac" | "cfrac" => {
+                let numerator = Box::new(self.parse_argument(start)?);
+                let denominator = Box::new(self.parse_argument(start)?);
+                MathExpr::Fraction { numerator, denominator, line: true }
+            }
+            "binom" | "dbinom" | "tbinom" => {
+                let numerator = Box::new(self.parse_argument(start)?);
+                let denominator = Box::new(self.parse_argument(start)?);
+                let body = Box::new(MathExpr::Fraction { numerator, denominator, line: false });
+                MathExpr::Fenced { open: "(".to_string(), close: ")".to_string(), body }
+            }
+            "sqrt" => {
+                let index = self.parse_optional()?.map(Box::new);
+                let base = Box::new(self.parse_argument(start)?);
+                MathExpr::Root { base, index }
+            }
+            "overset" | "stackrel" => {
+                let over = Some(Box::new(self.parse_argument(start)?));
+                let base = Box::new(self.parse_argument(start)?);
+                MathExpr::Limits { base, under: None, over }
+            }
+            "underset" => {
+                let under = Some(Box::new(self.parse_argument(start)?));
+                let base = Box::new(self.parse_argument(start)?);
+                MathExpr::Limits { base, under, over: None }
+            }
+            "text" | "textrm" | "textnormal" | "textit" | "textbf" | "textsf" | "texttt" | "mbox" => {
+                MathExpr::Text(self.read_raw_group(name, start)?)
+            }
+            "operatorname" => MathExpr::Identifier(self.read_raw_group(name, start)?),
+            "left" => self.parse_fenced(start)?,
+            "big" | "Big" | "bigg" | "Bigg" | "bigl" | "Bigl" | "biggl" | "Biggl" | "bigr" | "Bigr" | "biggr" | "Biggr" | "bigm" | "Bigm" => {
+                MathExpr::Operator(self.parse_delimiter(name, start)?.to_string())
+            }
+            "begin" => self.parse_environment(start)?,
+            "displaystyle" | "textstyle" | "scriptstyle" | "limits" | "nolimits" => MathExpr::default(),
+            "\\" => return self.error("Unexpected line break `\\\\`", start, self.offset),
+            "right" => return self.error("Unexpected `\\right` without `\\left`", start, self.offset),
+            "end" => return self.error("Unexpected `\\end` without `\\begin`", start, self.offset),
+            _ => return self.error(format!("Unsupported macro `\\{}`", name), start, self.offset),
+        };
+        Ok(out)
+    }
+    /// `[index]` of `\sqrt`
+    fn parse_optional(&mut self) -> Result<Option<MathExpr>> {
+        self.skip_whitespace();
+        if self.peek() != Some('[') {
+            return Ok(None);
+        }
+        let start = self.offset;
+        self.bump();
+        let row = self.parse_row(Some(']'))?;
+        match self.peek() {
+            Some(']') => {
+                self.bump();
+                Ok(Some(MathExpr::row(row)))
+            }
+            _ => self.error("Unclosed `[`, missing `]`", start, start + 1),
+        }
+    }
+    /// `\left( body \right)`
+    fn parse_fenced(&mut self, start: usize) -> Result<MathExpr> {
+        let open = self.parse_delimiter("left", start)?;
+        let body = Box::new(MathExpr::row(self.parse_row(None)?));
+        let right = self.offset;
+        if self.peek() != Some('\\') || self.peek_macro_name() != Some("right") {
+            return self.error("Unclosed `\\left`, missing `\\right`", start, start + 5);
+        }
+        self.read_macro_name()?;
+        let close = self.parse_delimiter("right", right)?;
+        Ok(MathExpr::Fenced { open: open.to_string(), close: close.to_string(), body })
+    }
+    fn parse_delimiter(&mut self, name: &str, start: usize) -> Result<&'static str> {
+        self.skip_whitespace();
+        let delimiter = self.offset;
+        let out = match self.peek() {
+            Some('\\') => latex_delimiter_macro(self.read_macro_name()?),
+            Some(c) => {
+                let out = latex_delimiter(c);
+                if out.is_some() {
+                    self.bump();
+                }
+                out
+            }
+            None => None,
+        };
+        match out {
+            Some(s) => Ok(s),
+            None if delimiter == self.offset => self.error(format!("Missing delimiter after `\\{}`", name), start, self.offset),
+            None => {
+                self.error(format!("Invalid delimiter `{}` after `\\{}`", &self.input[delimiter..self.offset], name), delimiter, self.offset)
+            }
+        }
+    }
+    /// `\begin{name} a & b \\ c & d \end{name}`
+    fn parse_environment(&mut self, start: usize) -> Result<MathExpr> {
+        let name = self.read_raw_group("begin", start)?;
+        let head = self.offset;
+        let (open, close, align) = match name.as_str() {
+            "matrix" | "smallmatrix" | "gathered" | "gather" | "gather*" => ("", "", "center"),
+            "pmatrix" => ("(", ")", "center"),
+            "bmatrix" => ("[", "]", "center"),
+            "Bmatrix" => ("{", "}", "center"),
+            "vmatrix" => ("|", "|", "center"),
+            "Vmatrix" => ("‖", "‖", "center"),
+            "cases" => ("{", "", "left"),
+            "aligned" | "align" | "align*" | "split" => ("", "", "right left"),
+            "array" => {
+                self.read_raw_group("begin{array}", start)?;
+                ("", "", "center")
+            }
+            _ => return self.error(format!("Unsupported environment `{}`", name), start, head),
+        };
+        let mut rows = vec![];
+        let mut row = vec![];
+        loop {
+            let cell = self.parse_row(None)?;
+            row.push(MathExpr::row(cell));
+            if self.peek() == Some('&') {
+                self.bump();
+                continue;
+            }
+            if self.peek() == Some('\\') && self.peek_macro_name() == Some("\\") {
+                self.read_macro_name()?;
+                rows.push(std::mem::take(&mut row));
+                continue;
+            }
+            break;
+        }
+        // trailing `\\` before `\end`
+        if !(row.len() == 1 && row[0] == MathExpr::default()) || rows.is_empty() {
+            rows.push(row);
+        }
+        let end = self.offset;
+        if self.peek() != Some('\\') || self.peek_macro_name() != Some("end") {
+            return self.error(format!("Unclosed `\\begin{{{}}}`, missing `\\end{{{}}}`", name, name), start, head);
+        }
+        self.read_macro_name()?;
+        let end_name = self.read_raw_group("end", end)?;
+        if end_name != name {
+            return self.error(format!("Mismatched `\\end{{{}}}`, expect `\\end{{{}}}`", end_name, name), end, self.offset);
+        }
+        let table = MathExpr::Table { rows, align };
+        if open.is_empty() && close.is_empty() {
+            return Ok(table);
+        }
+        Ok(MathExpr::Fenced { open: open.to_string(), close: close.to_string(), body: Box::new(table) })
+    }
+}
+
+impl<'i> LaTeXParser<'i> {
+    #[inline]
+    fn rest(&self) -> &'i str {
+        &self.input[self.offset..]
+    }
+    #[inline]
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+    #[inline]
+    fn peek_nth(&self, n: usize) -> Option<char> {
+        self.rest().chars().nth(n)
+    }
+    #[inline]
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.offset += c.len_utf8();
+        Some(c)
+    }
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            match c {
+                '%' => while !matches!(self.bump(), None | Some('\n')) {},
+                _ if c.is_whitespace() => {
+                    self.bump();
+                }
+                _ => break,
+            }
+        }
+    }
+    /// Name of the macro at current position, without `\`
+    fn peek_macro_name(&self) -> Option<&'i str> {
+        self.rest().strip_prefix('\\')?;
+        let name = self.macro_name_at(self.offset + 1);
+        match name.is_empty() {
+            true => None,
+            false => Some(name),
+        }
+    }
+    /// Letters, or a single non-letter char
+    fn macro_name_at(&self, offset: usize) -> &'i str {
+        let rest = &self.input[offset..];
+        match rest.chars().next() {
+            Some(c) if c.is_ascii_alphabetic() => &rest[..rest.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(rest.len())],
+            Some(c) => &rest[..c.len_utf8()],
+            None => "",
+        }
+    }
+    fn read_macro_name(&mut self) -> Result<&'i str> {
+        match self.peek_macro_name() {
+            Some(name) => {
+                self.offset += name.len() + 1;
+                Ok(name)
+            }
+            None => self.error("Incomplete macro `\\`", self.offset, self.offset + 1),
+        }
+    }
+    /// Text in braces without parsing, e.g. `\text{raw}`
+    fn read_raw_group(&mut self, name: &str, start: usize) -> Result<String> {
+        self.skip_whitespace();
+        let open = self.offset;
+        if self.bump() != Some('{') {
+            self.offset = open;
+            return self.error(format!("Missing argument for `\\{}`", name), start, open);
+        }
+        let mut depth = 0;
+        loop {
+            match self.bump() {
+                Some('\\') => {
+                    self.bump();
+                }
+                Some('{') => depth += 1,
+                Some('}') if depth == 0 => break,
+                Some('}') => depth -= 1,
+                Some(_) => {}
+                None => return self.error("Unclosed `{`, missing `}`", open, open + 1),
+            }
+        }
+        Ok(self.input[open + 1..self.offset - 1].to_string())
+    }
+    fn error<T>(&self, msg: impl Into<String>, start: usize, end: usize) -> Result<T> {
+        let mut error = NoteError::syntax_error(msg);
+        error.set_range(start, end);
+        Err(error)
+    }
+}
@@ -0,0 +1,296 @@
+use crate::{MathExpr, MathVariant};
+
+/// Macros which take no arguments
+pub fn latex_symbol(name: &str) -> Option<MathExpr> {
+    if let Some(s) = greek_letter(name) {
+        return Some(MathExpr::Identifier(s.to_string()));
+    }
+    if let Some(s) = large_operator(name) {
+        return Some(MathExpr::LargeOperator(s.to_string()));
+    }
+    if let Some(s) = operator(name) {
+        return Some(MathExpr::Operator(s.to_string()));
+    }
+    if let Some(s) = identifier(name) {
+        return Some(MathExpr::Identifier(s.to_string()));
+    }
+    if let Some(s) = space(name) {
+        return Some(MathExpr::Space(s));
+    }
+    if FUNCTIONS.contains(&name) {
+        return Some(MathExpr::Identifier(name.to_string()));
+    }
+    if LIMIT_FUNCTIONS.contains(&name) {
+        return Some(MathExpr::LargeOperator(name.to_string()));
+    }
+    None
+}
+
+/// Upright functions, e.g. `\sin`
+pub const FUNCTIONS: &[&str] = &[
+    "arccos", "arcsin", "arctan", "arg", "cos", "cosh", "cot", "coth", "csc", "deg", "dim", "exp", "hom", "ker", "lg", "ln", "log", "sec",
+    "sin", "sinh", "tan", "tanh",
+];
+
+/// Upright functions which take limits, e.g. `\lim`
+pub const LIMIT_FUNCTIONS: &[&str] = &["det", "gcd", "inf", "lim", "liminf", "limsup", "max", "min", "Pr", "sup"];
+
+fn greek_letter(name: &str) -> Option<&'static str> {
+    let s = match name {
+        "alpha" => "α",
+        "beta" => "β",
+        "gamma" => "γ",
+        "delta" => "δ",
+        "epsilon" => "ϵ",
+        "varepsilon" => "ε",
+        "zeta" => "ζ",
+        "eta" => "η",
+        "theta" => "θ",
+        "vartheta" => "ϑ",
+        "iota" => "ι",
+        "kappa" => "κ",
+        "lambda" => "λ",
+        "mu" => "μ",
+        "nu" => "ν",
+        "xi" => "ξ",
+        "pi" => "π",
+        "varpi" => "ϖ",
+        "rho" => "ρ",
+        "varrho" => "ϱ",
+        "sigma" => "σ",
+        "varsigma" => "ς",
+        "tau" => "τ",
+        "upsilon" => "υ",
+        "phi" => "ϕ",
+        "varphi" => "φ",
+        "chi" => "χ",
+        "psi" => "ψ",
+        "omega" => "ω",
+        "Gamma" => "Γ",
+        "Delta" => "Δ",
+        "Theta" => "Θ",
+        "Lambda" => "Λ",
+        "Xi" => "Ξ",
+        "Pi" => "Π",
+        "Sigma" => "Σ",
+        "Upsilon" => "Υ",
+        "Phi" => "Φ",
+        "Psi" => "Ψ",
+        "Omega" => "Ω",
+        _ => return None,
+    };
+    Some(s)
+}
+
+fn identifier(name: &str) -> Option<&'static str> {
+    let s = match name {
+        "infty" => "∞",
+        "partial" => "∂",
+        "nabla" => "∇",
+        "hbar" => "ℏ",
+        "ell" => "ℓ",
+        "aleph" => "ℵ",
+        "Re" => "ℜ",
+        "Im" => "ℑ",
+        "wp" => "℘",
+        "emptyset" | "varnothing" => "∅",
+        "imath" => "ı",
+        "jmath" => "ȷ",
+        _ => return None,
+    };
+    Some(s)
+}
+
+fn large_operator(name: &str) -> Option<&'static str> {
+    let s = match name {
+        "sum" => "∑",
+        "prod" => "∏",
+        "coprod" => "∐",
+        "int" => "∫",
+        "iint" => "∬",
+        "iiint" => "∭",
+        "oint" => "∮",
+        "bigcup" => "⋃",
+        "bigcap" => "⋂",
+        "bigvee" => "⋁",
+        "bigwedge" => "⋀",
+        "bigoplus" => "⨁",
+        "bigotimes" => "⨂",
+        "bigsqcup" => "⨆",
+        _ => return None,
+    };
+    Some(s)
+}
+
+fn operator(name: &str) -> Option<&'static str> {
+    let s = match name {
+        // binary
+        "pm" => "±",
+        "mp" => "∓",
+        "times" => "×",
+        "div" => "÷",
+        "cdot" => "⋅",
+        "ast" => "∗",
+        "star" => "⋆",
+        "circ" => "∘",
+        "bullet" => "∙",
+        "oplus" => "⊕",
+        "ominus" => "⊖",
+        "otimes" => "⊗",
+        "oslash" => "⊘",
+        "odot" => "⊙",
+        "cup" => "∪",
+        "cap" => "∩",
+        "setminus" => "∖",
+        "wedge" | "land" => "∧",
+        "vee" | "lor" => "∨",
+        "neg" | "lnot" => "¬",
+        // relation
+        "leq" | "le" => "≤",
+        "geq" | "ge" => "≥",
+        "neq" | "ne" => "≠",
+        "ll" => "≪",
+        "gg" => "≫",
+        "approx" => "≈",
+        "equiv" => "≡",
+        "sim" => "∼",
+        "simeq" => "≃",
+        "cong" => "≅",
+        "propto" => "∝",
+        "in" => "∈",
+        "notin" => "∉",
+        "ni" => "∋",
+        "subset" => "⊂",
+        "supset" => "⊃",
+        "subseteq" => "⊆",
+        "supseteq" => "⊇",
+        "perp" => "⊥",
+        "parallel" => "∥",
+        "mid" => "∣",
+        "forall" => "∀",
+        "exists" => "∃",
+        "nexists" => "∄",
+        // arrow
+        "to" | "rightarrow" => "→",
+        "gets" | "leftarrow" => "←",
+        "leftrightarrow" => "↔",
+        "Rightarrow" | "implies" => "⇒",
+        "Leftarrow" => "⇐",
+        "Leftrightarrow" | "iff" => "⇔",
+        "mapsto" => "↦",
+        "uparrow" => "↑",
+        "downarrow" => "↓",
+        "longrightarrow" => "⟶",
+        "longleftarrow" => "⟵",
+        "Longrightarrow" => "⟹",
+        // dots
+        "ldots" | "dots" => "…",
+        "cdots" => "⋯",
+        "vdots" => "⋮",
+        "ddots" => "⋱",
+        // delimiter
+        "langle" => "⟨",
+        "rangle" => "⟩",
+        "lbrace" => "{",
+        "rbrace" => "}",
+        "lfloor" => "⌊",
+        "rfloor" => "⌋",
+        "lceil" => "⌈",
+        "rceil" => "⌉",
+        "vert" => "|",
+        "Vert" => "‖",
+        // escaped
+        "{" => "{",
+        "}" => "}",
+        "|" => "‖",
+        "%" => "%",
+        "$" => "$",
+        "#" => "#",
+        "&" => "&",
+        "_" => "_",
+        "prime" => "′",
+        "angle" => "∠",
+        "triangle" => "△",
+        "therefore" => "∴",
+        "because" => "∵",
+        _ => return None,
+    };
+    Some(s)
+}
+
+fn space(name: &str) -> Option<f32> {
+    let s = match name {
+        "," | "thinspace" => 0.1667,
+        ":" | ">" | "medspace" => 0.2222,
+        ";" | "thickspace" => 0.2778,
+        " " | "space" => 0.25,
+        "quad" => 1.0,
+        "qquad" => 2.0,
+        "!" | "negthinspace" => -0.1667,
+        _ => return None,
+    };
+    Some(s)
+}
+
+/// Accent macros and whether the accent is above the nucleus
+pub fn latex_accent(name: &str) -> Option<(&'static str, bool)> {
+    let s = match name {
+        "hat" | "widehat" => ("^", true),
+        "check" | "widecheck" => ("ˇ", true),
+        "tilde" | "widetilde" => ("~", true),
+        "acute" => ("´", true),
+        "grave" => ("`", true),
+        "dot" => ("˙", true),
+        "ddot" => ("¨", true),
+        "breve" => ("˘", true),
+        "bar" => ("¯", true),
+        "vec" => ("→", true),
+        "overline" => ("‾", true),
+        "overrightarrow" => ("→", true),
+        "overleftarrow" => ("←", true),
+        "overbrace" => ("⏞", true),
+        "underline" => ("_", false),
+        "underbrace" => ("⏟", false),
+        _ => return None,
+    };
+    Some(s)
+}
+
+/// Font macros
+pub fn latex_font(name: &str) -> Option<MathVariant> {
+    let s = match name {
+        "mathrm" | "rm" => MathVariant::Normal,
+        "mathbf" | "bf" => MathVariant::Bold,
+        "mathit" | "it" => MathVariant::Italic,
+        "boldsymbol" | "bm" => MathVariant::BoldItalic,
+        "mathbb" | "Bbb" => MathVariant::DoubleStruck,
+        "mathcal" | "cal" | "mathscr" => MathVariant::Script,
+        "mathfrak" => MathVariant::Fraktur,
+        "mathsf" | "sf" => MathVariant::SansSerif,
+        "mathtt" | "tt" => MathVariant::Monospace,
+        _ => return None,
+    };
+    Some(s)
+}
+
+/// Delimiters after `\left`, `\right` and `\big`, `None` for invalid delimiter
+pub fn latex_delimiter(c: char) -> Option<&'static str> {
+    let s = match c {
+        '(' => "(",
+        ')' => ")",
+        '[' => "[",
+        ']' => "]",
+        '|' => "|",
+        '/' => "/",
+        '<' => "⟨",
+        '>' => "⟩",
+        '.' => "",
+        _ => return None,
+    };
+    Some(s)
+}
+
+/// Delimiter macros, e.g. `\langle`
+pub fn latex_delimiter_macro(name: &str) -> Option<&'static str> {
+    operator(name).filter(|s| "{}⟨⟩⌊⌋⌈⌉|‖↑↓".contains(*s))
+}
@@ -0,0 +1,70 @@
+#![allow(clippy::needless_return)]
+mod ascii;
+mod expr;
+mod latex;
+mod mathml_svg;
+
+pub use self::{
+    ascii::parse_ascii_math,
+    expr::{MathExpr, MathVariant},
+    latex::parse_latex,
+};
+use notedown_ast::{
+    nodes::{MathBackend, MathNode},
+    ASTKind, ASTNode, NoteError, Result,
+};
+use std::ops::Range;
+
+/// Parse the formula with its backend
+pub fn parse_math(math: &MathNode) -> Result<MathExpr> {
+    match math.format {
+        MathBackend::LaTeX => parse_latex(&math.raw),
+        MathBackend::AsciiMath => parse_ascii_math(&math.raw),
+        MathBackend::MathML => Ok(MathExpr::Raw(math.raw.trim().to_string())),
+    }
+}
+
+/// `<math>` element for html export
+pub fn math_to_mathml(math: &MathNode) -> Result<String> {
+    let expr = parse_math(math)?;
+    Ok(expr.to_mathml(math.kind.is_block(), math.kind.is_display()))
+}
+
+/// MathML wrapped in svg for viewers that only accept images, see [`MathExpr::to_mathml_svg`]
+///
+/// This is not a glyph rendering, send the output of [`math_to_mathml`] to viewers that support MathML.
+pub fn math_to_mathml_svg(math: &MathNode) -> Result<String> {
+    let expr = parse_math(math)?;
+    Ok(expr.to_mathml_svg(math.kind.is_display()))
+}
+
+/// Render the math node in document to MathML, ranges of errors are mapped back to the document
+pub fn node_to_mathml(node: &ASTNode) -> Result<String> {
+    match &node.value {
+        ASTKind::MathNode(math) => math_to_mathml(math).map_err(|e| map_error_range(e, math, &node.range)),
+        _ => Err(NoteError::runtime_error("Expect a math node")),
+    }
+}
+
+/// Render the math node in document to MathML wrapped in svg, ranges of errors are mapped back to the document
+pub fn node_to_mathml_svg(node: &ASTNode) -> Result<String> {
+    match &node.value {
+        ASTKind::MathNode(math) => math_to_mathml_svg(math).map_err(|e| map_error_range(e, math, &node.range)),
+        _ => Err(NoteError::runtime_error("Expect a math node")),
+    }
+}
+
+/// Map the offsets in formula to the offsets in document, the node range starts at the `$`
+pub fn map_error_range(mut error: NoteError, math: &MathNode, node: &Option<Range<usize>>) -> NoteError {
+    let start = match node {
+        Some(s) => s.start + math.kind.delimiter().len(),
+        None => {
+            error.range = None;
+            return error;
+        }
+    };
+    if let Some(range) = &error.range {
+        error.set_range(start + range.start, start + range.end);
+    }
+    return error;
+}
@@ -0,0 +1,91 @@
+use crate::MathExpr;
+
+/// Font size of the svg in px
+const FONT_SIZE: f32 = 16.0;
+
+impl MathExpr {
+    /// MathML wrapped in the `<foreignObject>` of an svg, a fallback for viewers that only accept images
+    ///
+    /// No glyph is drawn by the svg itself, the viewer must be able to lay out html and MathML inside svg,
+    /// e.g. browsers and the webviews of editors. The size is estimated from the formula tree, so the formula may be clipped or padded.
+    pub fn to_mathml_svg(&self, display: bool) -> String {
+        let (width, height) = self.estimate_size();
+        let width = ((width + 0.5) * FONT_SIZE).ceil();
+        let height = ((height + 0.5) * FONT_SIZE).ceil();
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}"><foreignObject x="0" y="0" width="{w}" height="{h}"><div xmlns="http://www.w3.org/1999/xhtml" style="font-size:{size}px">{math}</div></foreignObject></svg>"#,
+            w = width,
+            h = height,
+            size = FONT_SIZE,
+            math = self.to_mathml(false, display)
+        )
+    }
+    /// Width and height in em
+    pub fn estimate_size(&self) -> (f32, f32) {
+        match self {
+            Self::Row(items) => items.iter().map(|e| e.estimate_size()).fold((0.0, 1.2), |(w, h), (iw, ih)| (w + iw, h.max(ih))),
+            Self::Identifier(s) | Self::Number(s) | Self::Text(s) => (s.chars().count() as f32 * 0.6, 1.2),
+            Self::Operator(s) => (s.chars().count() as f32 * 0.6 + 0.4, 1.2),
+            Self::LargeOperator(s) => (s.chars().count().min(3) as f32 * 0.6 + 0.6, 1.8),
+            Self::Space(em) => (em.max(0.0), 0.0),
+            Self::Fraction { numerator, denominator, .. } => {
+                let (nw, nh) = numerator.estimate_size();
+                let (dw, dh) = denominator.estimate_size();
+                (nw.max(dw) + 0.2, nh + dh + 0.2)
+            }
+            Self::Root { base, index } => {
+                let (w, h) = base.estimate_size();
+                let iw = index.as_ref().map_or(0.0, |i| i.estimate_size().0 * 0.7);
+                (w + iw + 1.0, h + 0.2)
+            }
+            Self::Scripts { base, sub, sup } | Self::Limits { base, under: sub, over: sup } => {
+                let (w, h) = base.estimate_size();
+                let (sw, sh) = sub.as_ref().map_or((0.0, 0.0), |s| s.estimate_size());
+                let (pw, ph) = sup.as_ref().map_or((0.0, 0.0), |s| s.estimate_size());
+                match self {
+                    Self::Limits { .. } => (w.max(sw * 0.7).max(pw * 0.7), h + (sh + ph) * 0.7),
+                    _ => (w + sw.max(pw) * 0.7, h + (sh + ph) * 0.35),
+                }
+            }
+            Self::Accent { base, .. } => {
+                let (w, h) = base.estimate_size();
+                (w, h + 0.3)
+            }
+            Self::Fenced { open, close, body } => {
+                let (w, h) = body.estimate_size();
+                let brackets = [open, close].iter().filter(|s| !s.is_empty()).count() as f32;
+                (w + brackets * 0.5, h + 0.2)
+            }
+            Self::Table { rows, .. } => {
+                let mut columns: Vec<f32> = vec![];
+                let mut height = 0.0;
+                for row in rows {
+                    let mut row_height: f32 = 0.0;
+                    for (i, cell) in row.iter().enumerate() {
+                        let (w, h) = cell.estimate_size();
+                        match columns.get_mut(i) {
+                            Some(c) => *c = c.max(w),
+                            None => columns.push(w),
+                        }
+                        row_height = row_height.max(h);
+                    }
+                    height += row_height + 0.3;
+                }
+                (columns.iter().sum::<f32>() + columns.len() as f32 * 0.8, height)
+            }
+            Self::Style { body, .. } => body.estimate_size(),
+            Self::Raw(s) => {
+                let mut in_tag = false;
+                let text = s.chars().filter(|c| {
+                    match c {
+                        '<' => in_tag = true,
+                        '>' => in_tag = false,
+                        _ => return !in_tag,
+                    }
+                    false
+                });
+                (text.count() as f32 * 0.6, 1.2)
+            }
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use super::syntax_error;
+use notedown_math::{parse_ascii_math, parse_latex};
+
+fn mathml(input: &str) -> String {
+    parse_ascii_math(input).unwrap().to_mathml(false, false)
+}
+
+#[test]
+fn same_as_latex() {
+    assert_eq!(parse_ascii_math("(a+b)/2").unwrap(), parse_latex(r"\frac{a+b}{2}").unwrap());
+    assert_eq!(parse_ascii_math("sum_(i=1)^n i^2").unwrap(), parse_latex(r"\sum_{i=1}^n i^2").unwrap());
+    assert_eq!(parse_ascii_math("sqrt x != alpha").unwrap(), parse_latex(r"\sqrt x \neq \alpha").unwrap());
+}
+
+#[test]
+fn matrix() {
+    let out = mathml("[[1,2],[3,4]]");
+    assert!(out.contains("<mtable columnalign=\"center\"><mtr><mtd><mn>1</mn></mtd><mtd><mn>2</mn></mtd></mtr>"));
+    assert_eq!(
+        mathml(r#"text(a b) "c""#),
+        "<math xmlns=\"http://www.w3.org/1998/Math/MathML\"><mrow><mrow><mtext>a b</mtext><mtext>c</mtext></mrow></mrow></math>"
+    );
+}
+
+#[test]
+fn errors() {
+    assert_eq!(syntax_error(parse_ascii_math("x + (a/b").unwrap_err()), ("Unclosed `(`".to_string(), 4..5));
+    assert_eq!(syntax_error(parse_ascii_math("a+b)").unwrap_err()), ("Unmatched `)`".to_string(), 3..4));
+    assert_eq!(syntax_error(parse_ascii_math(r"1 + \frac").unwrap_err()), ("Unsupported macro `\\frac` in AsciiMath".to_string(), 4..9));
+}
@@ -0,0 +1,85 @@
+use super::syntax_error;
+use notedown_ast::{nodes::MathNode, ASTKind, ASTNode};
+use notedown_math::{math_to_mathml, node_to_mathml, parse_latex, MathExpr};
+use notedown_parser::NotedownParser;
+
+fn mathml(input: &str) -> String {
+    parse_latex(input).unwrap().to_mathml(false, false)
+}
+
+fn error_range(input: &str) -> (String, std::ops::Range<usize>) {
+    syntax_error(parse_latex(input).unwrap_err())
+}
+
+#[test]
+fn fraction() {
+    assert_eq!(
+        mathml(r"\frac{a}{2}"),
+        r#"<math xmlns="http://www.w3.org/1998/Math/MathML"><mrow><mfrac><mi>a</mi><mn>2</mn></mfrac></mrow></math>"#
+    );
+    assert_eq!(mathml(r"\frac12"), mathml(r"\frac{1}{2}"));
+}
+
+#[test]
+fn scripts() {
+    assert_eq!(
+        parse_latex("x_1^2").unwrap(),
+        MathExpr::Scripts {
+            base: Box::new(MathExpr::Identifier("x".to_string())),
+            sub: Some(Box::new(MathExpr::Number("1".to_string()))),
+            sup: Some(Box::new(MathExpr::Number("2".to_string()))),
+        }
+    );
+    assert!(mathml(r"\sum_{i=1}^n i").contains("<munderover><mo largeop"));
+    assert!(mathml(r"\int_0^1 x").contains("<msubsup><mo largeop"));
+}
+
+#[test]
+fn environment() {
+    let out = mathml(r"\begin{pmatrix} a & b \\ c & d \end{pmatrix}");
+    assert!(out.contains("<mtable columnalign=\"center\"><mtr><mtd><mi>a</mi></mtd><mtd><mi>b</mi></mtd></mtr>"));
+    assert!(out.contains(r#"<mo fence="true" stretchy="true">(</mo>"#));
+    assert!(mathml(r"\left\langle x \right|").contains("⟨"));
+    assert_eq!(mathml(r"\mathbb{R}"), mathml("ℝ"));
+}
+
+#[test]
+fn errors() {
+    assert_eq!(error_range(r"a + \foo b"), ("Unsupported macro `\\foo`".to_string(), 4..8));
+    assert_eq!(error_range(r"\frac{a}{b"), ("Unclosed `{`, missing `}`".to_string(), 8..9));
+    assert_eq!(error_range(r"a}"), ("Unmatched `}`".to_string(), 1..2));
+    assert_eq!(error_range(r"\frac{a}"), ("Missing argument for `\\frac`".to_string(), 0..8));
+    assert_eq!(error_range(r"\left( a"), ("Unclosed `\\left`, missing `\\right`".to_string(), 0..5));
+    assert_eq!(error_range(r"\begin{matrix} a \end{cases}"), ("Mismatched `\\end{cases}`, expect `\\end{matrix}`".to_string(), 17..28));
+}
+
+#[test]
+fn document_range() {
+    let input = "text $x^\\oops$";
+    let root = NotedownParser::default().parse(input).unwrap();
+    let node = find_math(&root).unwrap();
+    let range = node_to_mathml(&node).unwrap_err().range.unwrap();
+    assert_eq!(&input[range], "\\oops");
+    let mut math = MathNode::math_inline("<mi>x</mi>".to_string());
+    math.set_format("mathml");
+    assert_eq!(math_to_mathml(&math).unwrap(), r#"<math xmlns="http://www.w3.org/1998/Math/MathML"><mrow><mi>x</mi></mrow></math>"#);
+}
+
+fn find_math(node: &ASTNode) -> Option<ASTNode> {
+    match &node.value {
+        ASTKind::Statements(children) | ASTKind::Paragraph(children) => children.iter().find_map(find_math),
+        ASTKind::MathNode(_) => Some(node.to_owned()),
+        _ => None,
+    }
+}
+
+#[test]
+fn mathml_svg() {
+    let expr = parse_latex(r"\frac{a}{2}").unwrap();
+    let svg = expr.to_mathml_svg(true);
+    assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg""#), "{}", svg);
+    assert!(svg.contains("<foreignObject"), "{}", svg);
+    assert!(svg.contains(&expr.to_mathml(false, true)), "{}", svg);
+    let (width, height) = expr.estimate_size();
+    assert!(width > 0.0 && height > 1.2, "{} {}", width, height);
+}
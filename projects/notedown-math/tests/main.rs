@@ -0,0 +1,18 @@
+use notedown_ast::{NoteError, NoteErrorKind};
+use std::ops::Range;
+
+mod ascii;
+mod latex;
+
+#[test]
+fn ready() {
+    println!("it, works!")
+}
+
+/// Message and range of a syntax error
+pub fn syntax_error(e: NoteError) -> (String, Range<usize>) {
+    match *e.kind {
+        NoteErrorKind::SyntaxError(msg) => (msg, e.range.unwrap()),
+        _ => panic!("expect a syntax error"),
+    }
+}
@@ -29,8 +29,6 @@ pub fn try_render_code(cmd: String, args: &Vec<Value>, kvs: &HashMap<String, Val
 
 pub fn import(ctx: &Context, args: &Vec<Value>, kvs: &HashMap<String, Value>) -> Option<String> {
     let code = String::new();
-    let file_path = ctx.meta.file_path.clone();
-    println!("{:?}", file_path);
     return Some(code);
 }
 
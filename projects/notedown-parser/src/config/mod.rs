@@ -1,15 +1,37 @@
+use notedown_ast::{
+    nodes::{Literal, MaybeRanged},
+    traits::SlugScheme,
+    value::Scope,
+    NoteError,
+};
 use notedown_pest::Span;
-use std::ops::Range;
+use notedown_plugin::CommandRegistry;
+use std::{cell::RefCell, ops::Range};
 use url::Url;
 
 pub struct NotedownParser {
     pub file_url: Option<Url>,
     pub tab_size: usize,
+    /// Commands known by the parser, [`CommandRegistry::builtin`] by default
+    pub commands: CommandRegistry,
+    /// Variables defined by `\set`
+    pub scope: RefCell<Scope>,
+    /// Errors that do not stop the parsing
+    pub errors: RefCell<Vec<NoteError>>,
+    /// How the ids of headers are generated
+    pub slug_scheme: SlugScheme,
 }
 
 impl Default for NotedownParser {
     fn default() -> Self {
-        Self { file_url: None, tab_size: 4 }
+        Self {
+            file_url: None,
+            tab_size: 4,
+            commands: CommandRegistry::builtin(),
+            scope: Default::default(),
+            errors: Default::default(),
+            slug_scheme: Default::default(),
+        }
     }
 }
 
@@ -17,4 +39,18 @@ impl NotedownParser {
     pub fn get_position(&self, s: Span) -> MaybeRanged {
         Some(Range { start: s.start(), end: s.end() })
     }
+    pub fn get_literal(&self, s: Span) -> Literal<String> {
+        Literal { value: s.as_str().to_string(), range: self.get_position(s) }
+    }
+    /// Record an error and continue parsing
+    pub fn push_error(&self, mut error: NoteError) {
+        if let Some(url) = &self.file_url {
+            error.set_url(url.to_owned())
+        }
+        self.errors.borrow_mut().push(error)
+    }
+    /// Take all errors recorded in the last parsing
+    pub fn take_errors(&self) -> Vec<NoteError> {
+        self.errors.take()
+    }
 }
@@ -0,0 +1,345 @@
+use super::*;
+use notedown_ast::{
+    command::CommandOptions,
+    nodes::{Literal, MaybeRanged},
+    traits::IntoASTNode,
+    value::{Expression, ExpressionKind, InfixOperator, PrefixOperator, Scope},
+    NoteError, Result, Value,
+};
+use notedown_pest::{Assoc, Op, PrattParser};
+use num::{BigInt, BigUint, Zero};
+use rust_decimal::Decimal;
+use std::{ops::Range, str::FromStr};
+
+/// `||` < `&&` < `== != < <= > >=` < `|` < `&` < `++` < `+ -` < `* / %` < `- !` < `^`
+fn pratt() -> PrattParser<Rule> {
+    PrattParser::new()
+        .op(Op::infix(Rule::Or, Assoc::Left))
+        .op(Op::infix(Rule::And, Assoc::Left))
+        .op(Op::infix(Rule::Equal, Assoc::Left)
+            | Op::infix(Rule::NotEqual, Assoc::Left)
+            | Op::infix(Rule::Less, Assoc::Left)
+            | Op::infix(Rule::LessEqual, Assoc::Left)
+            | Op::infix(Rule::Greater, Assoc::Left)
+            | Op::infix(Rule::GreaterEqual, Assoc::Left))
+        .op(Op::infix(Rule::Vertical, Assoc::Left))
+        .op(Op::infix(Rule::Ampersand, Assoc::Left))
+        .op(Op::infix(Rule::Join, Assoc::Left))
+        .op(Op::infix(Rule::Plus, Assoc::Left) | Op::infix(Rule::Minus, Assoc::Left))
+        .op(Op::infix(Rule::Asterisk, Assoc::Left) | Op::infix(Rule::Slash, Assoc::Left) | Op::infix(Rule::Percent, Assoc::Left))
+        .op(Op::prefix(Rule::prefix))
+        .op(Op::infix(Rule::Caret, Assoc::Right))
+}
+
+impl NotedownParser {
+    /// Prefixes of the operands are fed to the pratt parser, so that `-2^2` is `-(2^2)`
+    pub(crate) fn parse_expression(&self, pairs: Pair<Rule>) -> Expression {
+        let pairs = pairs.into_inner().filter(|pair| pair.as_rule() != Rule::WHITE_SPACE).flat_map(|pair| {
+            let prefix: Vec<_> = match pair.as_rule() {
+                Rule::unary => pair.clone().into_inner().take_while(|inner| inner.as_rule() == Rule::prefix).collect(),
+                _ => vec![],
+            };
+            prefix.into_iter().chain(std::iter::once(pair))
+        });
+        pratt()
+            .map_primary(|pair| self.parse_operand(pair))
+            .map_prefix(|op, rhs| {
+                let r = rhs.range.as_ref().map(|f| Range { start: op.as_span().start(), end: f.end });
+                ExpressionKind::prefix(prefix_operator(&op), rhs, r)
+            })
+            .map_infix(|lhs, op, rhs| {
+                let op = match op.as_rule() {
+                    Rule::Join => InfixOperator::Join,
+                    Rule::Plus => InfixOperator::Plus,
+                    Rule::Minus => InfixOperator::Minus,
+                    Rule::Asterisk => InfixOperator::Multiply,
+                    Rule::Slash => InfixOperator::Divide,
+                    Rule::Percent => InfixOperator::Modulo,
+                    Rule::Caret => InfixOperator::Power,
+                    Rule::Vertical => InfixOperator::Union,
+                    Rule::Ampersand => InfixOperator::Intersection,
+                    Rule::Equal => InfixOperator::Equal,
+                    Rule::NotEqual => InfixOperator::NotEqual,
+                    Rule::Less => InfixOperator::Less,
+                    Rule::LessEqual => InfixOperator::LessEqual,
+                    Rule::Greater => InfixOperator::Greater,
+                    Rule::GreaterEqual => InfixOperator::GreaterEqual,
+                    Rule::And => InfixOperator::And,
+                    Rule::Or => InfixOperator::Or,
+                    _ => {
+                        self.unexpected(op);
+                        return lhs;
+                    }
+                };
+                ExpressionKind::infix(op, lhs, rhs)
+            })
+            .parse(pairs)
+    }
+    /// A single operand with its prefixes, used by attributes
+    fn parse_unary(&self, pairs: Pair<Rule>) -> Expression {
+        let prefix: Vec<_> = pairs.clone().into_inner().take_while(|pair| pair.as_rule() == Rule::prefix).collect();
+        let mut base = self.parse_operand(pairs);
+        for op in prefix.into_iter().rev() {
+            let r = base.range.as_ref().map(|f| Range { start: op.as_span().start(), end: f.end });
+            base = ExpressionKind::prefix(prefix_operator(&op), base, r)
+        }
+        return base;
+    }
+    /// The term and its suffixes, prefixes are skipped
+    fn parse_operand(&self, pairs: Pair<Rule>) -> Expression {
+        let mut base = ExpressionKind::value(Value::Null, None);
+        for pair in pairs.into_inner() {
+            let r = self.get_position(pair.as_span());
+            base = match pair.as_rule() {
+                Rule::prefix => continue,
+                Rule::group => self.parse_inner_expression(pair),
+                Rule::Integer => ExpressionKind::value(Value::Integer(BigInt::from_str(pair.as_str()).unwrap_or_default()), r),
+                Rule::Decimal => self.parse_decimal(pair),
+                Rule::array => self.parse_array(pair),
+                Rule::object => self.parse_object(pair),
+                Rule::ordered_set => self.parse_set(pair),
+                Rule::String => ExpressionKind::value(Value::String(unescape_string(pair.as_str())), r),
+                Rule::Keywords => ExpressionKind::value(parse_keyword(pair.as_str()), r),
+                Rule::SYMBOL => ExpressionKind::symbol(pair.as_str(), r),
+                Rule::index => ExpressionKind::index(base, self.parse_inner_expression(pair), r),
+                Rule::method => self.parse_method(base, pair),
+                _ => {
+                    self.unexpected(pair);
+                    continue;
+                }
+            };
+        }
+        return base;
+    }
+    fn parse_inner_expression(&self, pairs: Pair<Rule>) -> Expression {
+        match pairs.into_inner().find(|pair| pair.as_rule() == Rule::expression) {
+            Some(pair) => self.parse_expression(pair),
+            None => ExpressionKind::value(Value::Null, None),
+        }
+    }
+    fn parse_method(&self, base: Expression, pairs: Pair<Rule>) -> Expression {
+        let r = self.get_position(pairs.as_span());
+        let mut name = Literal::default();
+        let mut args = vec![];
+        for pair in pairs.into_inner() {
+            match pair.as_rule() {
+                Rule::Dot | Rule::Comma | Rule::PATTERN_WHITE_SPACE => continue,
+                Rule::SYMBOL => name = self.get_literal(pair.as_span()),
+                Rule::expression => args.push(self.parse_expression(pair)),
+                _ => {
+                    self.unexpected(pair);
+                }
+            }
+        }
+        ExpressionKind::method(base, name, args, r)
+    }
+    fn parse_decimal(&self, pairs: Pair<Rule>) -> Expression {
+        let r = self.get_position(pairs.as_span());
+        match Decimal::from_str(pairs.as_str()) {
+            Ok(o) => ExpressionKind::value(Value::Decimal(o), r),
+            Err(e) => {
+                let mut error = NoteError::from(e);
+                error.range = r.to_owned();
+                self.push_error(error);
+                ExpressionKind::value(Value::Null, r)
+            }
+        }
+    }
+    /// ```note
+    /// [a, b, 5 = c,]
+    /// ```
+    fn parse_array(&self, pairs: Pair<Rule>) -> Expression {
+        let r = self.get_position(pairs.as_span());
+        let mut items = vec![];
+        for pair in pairs.into_inner().filter(|pair| pair.as_rule() == Rule::array_item) {
+            let mut index = None;
+            for inner in pair.into_inner() {
+                match inner.as_rule() {
+                    Rule::PATTERN_WHITE_SPACE | Rule::Set => continue,
+                    Rule::Integer => index = Some(self.parse_index(inner)),
+                    Rule::expression => items.push((index.take(), self.parse_expression(inner))),
+                    _ => {
+                        self.unexpected(inner);
+                    }
+                }
+            }
+        }
+        ExpressionKind::array(items, r)
+    }
+    /// ```note
+    /// {a = 1, "b c" = 2,}
+    /// ```
+    fn parse_object(&self, pairs: Pair<Rule>) -> Expression {
+        let r = self.get_position(pairs.as_span());
+        let mut items = vec![];
+        for pair in pairs.into_inner().filter(|pair| pair.as_rule() == Rule::object_item) {
+            let mut key = Literal::default();
+            for inner in pair.into_inner() {
+                match inner.as_rule() {
+                    Rule::PATTERN_WHITE_SPACE | Rule::Set => continue,
+                    Rule::key => key = self.parse_key(inner),
+                    Rule::expression => items.push((key.to_owned(), self.parse_expression(inner))),
+                    _ => {
+                        self.unexpected(inner);
+                    }
+                }
+            }
+        }
+        ExpressionKind::object(items, r)
+    }
+    /// ```note
+    /// {a, b,}
+    /// ```
+    fn parse_set(&self, pairs: Pair<Rule>) -> Expression {
+        let r = self.get_position(pairs.as_span());
+        let items = pairs.into_inner().filter(|pair| pair.as_rule() == Rule::expression).map(|pair| self.parse_expression(pair)).collect();
+        ExpressionKind::set(items, r)
+    }
+    fn parse_index(&self, pair: Pair<Rule>) -> Literal<BigUint> {
+        Literal { value: BigUint::from_str(pair.as_str()).unwrap_or_default(), range: self.get_position(pair.as_span()) }
+    }
+}
+
+impl NotedownParser {
+    /// Evaluate in the current scope, error will be recorded and returns `null`
+    pub(crate) fn evaluate(&self, f: impl FnOnce(&Scope) -> Result<Value>) -> Value {
+        match f(&self.scope.borrow()) {
+            Ok(o) => o,
+            Err(e) => {
+                self.push_error(e);
+                Value::Null
+            }
+        }
+    }
+    /// ```note
+    /// \{name ++ "!"}
+    /// ```
+    pub(crate) fn parse_interpolation(&self, pairs: Pair<Rule>) -> ASTNode {
+        let r = self.get_position(pairs.as_span());
+        let expr = self.parse_inner_expression(pairs);
+        match self.evaluate(|scope| expr.evaluate(scope)) {
+            Value::String(s) => ASTKind::text(s, r),
+            value => value.into_node(r),
+        }
+    }
+    /// ```note
+    /// \cmd(a, 3 = c, key = value,)
+    /// <cmd a 3=c key=value/>
+    /// ```
+    pub(crate) fn parse_argument(&self, pairs: Pair<Rule>, options: &mut CommandOptions) {
+        let mut key = None;
+        let mut index = None;
+        for pair in pairs.into_inner() {
+            match pair.as_rule() {
+                Rule::WHITE_SPACE | Rule::PATTERN_WHITE_SPACE | Rule::Set | Rule::Comma => continue,
+                Rule::key => match pair.clone().into_inner().next() {
+                    Some(inner) if inner.as_rule() == Rule::Integer => index = Some(self.parse_index(inner)),
+                    _ => key = Some(self.parse_key(pair)),
+                },
+                Rule::value | Rule::unary => {
+                    let value = self.parse_value(pair);
+                    match (key.take(), index.take()) {
+                        (Some(key), _) => {
+                            options.kvs.insert(key, value);
+                        }
+                        (None, Some(i)) if i.value.is_zero() => {
+                            let mut error = NoteError::syntax_error("Index of argument starts from 1");
+                            error.range = i.range;
+                            self.push_error(error)
+                        }
+                        (None, Some(i)) => {
+                            options.args.insert(i.value, value);
+                        }
+                        (None, None) => options.args.push(value),
+                    }
+                }
+                _ => {
+                    self.unexpected(pair);
+                }
+            }
+        }
+    }
+    /// ```note
+    /// {key = value}
+    /// ```
+    pub(crate) fn parse_options_object(&self, pairs: Pair<Rule>, options: &mut CommandOptions) {
+        let expr = self.parse_object(pairs);
+        if let Value::Object(map) = self.evaluate(|scope| expr.evaluate_argument(scope)) {
+            for (key, value) in map.iter_raw() {
+                options.kvs.insert(key.to_owned(), value.to_owned());
+            }
+        }
+    }
+    fn parse_key(&self, pairs: Pair<Rule>) -> Literal<String> {
+        let r = self.get_position(pairs.as_span());
+        let value = match pairs.into_inner().next() {
+            Some(pair) if pair.as_rule() == Rule::String => unescape_string(pair.as_str()),
+            Some(pair) => pair.as_str().to_string(),
+            None => String::new(),
+        };
+        Literal { value, range: r }
+    }
+    fn parse_value(&self, pairs: Pair<Rule>) -> Literal<Value> {
+        let r = self.get_position(pairs.as_span());
+        if let Rule::unary = pairs.as_rule() {
+            let expr = self.parse_unary(pairs);
+            return Literal { value: self.evaluate(|scope| expr.evaluate_argument(scope)), range: r };
+        }
+        let value = match pairs.into_inner().next() {
+            Some(pair) if pair.as_rule() == Rule::expression => {
+                let expr = self.parse_expression(pair);
+                self.evaluate(|scope| expr.evaluate_argument(scope))
+            }
+            _ => {
+                let mut error = NoteError::syntax_error("Command can not be used as an argument");
+                error.range = r.to_owned();
+                self.push_error(error);
+                Value::Null
+            }
+        };
+        Literal { value, range: r }
+    }
+    /// ```note
+    /// \set[name]: value
+    /// ```
+    pub(crate) fn define_variable(&self, name: Option<&Literal<String>>, value: Literal<Value>, range: &MaybeRanged) {
+        match name {
+            Some(name) => {
+                self.scope.borrow_mut().define(name.value.trim(), value);
+            }
+            None => {
+                let mut error = NoteError::syntax_error("Missing variable name, try `\\set[name]: value`");
+                error.range = range.to_owned();
+                self.push_error(error)
+            }
+        }
+    }
+}
+
+fn prefix_operator(pair: &Pair<Rule>) -> PrefixOperator {
+    match pair.as_str() {
+        "!" => PrefixOperator::Not,
+        _ => PrefixOperator::Negative,
+    }
+}
+
+fn parse_keyword(s: &str) -> Value {
+    match s {
+        "true" => Value::Boolean(true),
+        "false" => Value::Boolean(false),
+        _ => Value::Null,
+    }
+}
+
+/// `"a\"b"` => `a"b`
+fn unescape_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s[1..s.len() - 1].chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if matches!(chars.peek(), Some('\\' | '"' | '\'')) => out.extend(chars.next()),
+            _ => out.push(c),
+        }
+    }
+    return out;
+}
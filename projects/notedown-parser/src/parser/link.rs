@@ -0,0 +1,53 @@
+use super::*;
+use notedown_ast::nodes::{EmailLink, ImageLink, MaybeRanged, SmartLink, TwoWayLink};
+
+impl NotedownParser {
+    /// ```note
+    /// [[link > id]]
+    /// [^tag][text]
+    /// [!alt][source]
+    /// [text][source]
+    /// ```
+    pub(crate) fn parse_link(&self, pairs: Pair<Rule>) -> ASTNode {
+        let r = self.get_position(pairs.as_span());
+        let rule = pairs.as_rule();
+        let mut parts = pairs.into_inner().filter(|pair| matches!(pair.as_rule(), Rule::LinkText | Rule::LinkTag)).map(|pair| pair.as_str());
+        let (first, second) = (parts.next().unwrap_or_default(), parts.next());
+        match (rule, second) {
+            (Rule::TwoWayLink, _) => TwoWayLink::new(first).into_node(r),
+            (Rule::TagLink, None) => ASTKind::tag_reference(first, r),
+            (Rule::TagLink, Some(text)) => ASTKind::tag_inline(first, text, r),
+            (Rule::ImageLink, None) => ASTKind::image_link(first.trim(), r),
+            (Rule::ImageLink, Some(source)) => ASTKind::image_link_alt(source.trim(), first, r),
+            (_, Some(source)) => ASTKind::hyper_link_text(source.trim(), first, r),
+            (_, None) => parse_smart_link(first.trim(), r),
+        }
+    }
+    /// ```note
+    /// [^tag]: text
+    /// ```
+    pub(crate) fn parse_tag_block(&self, pairs: Pair<Rule>) -> ASTNode {
+        let r = self.get_position(pairs.as_span());
+        let mut tag = "";
+        let mut text = "";
+        for pair in pairs.into_inner() {
+            match pair.as_rule() {
+                Rule::LinkTag => tag = pair.as_str(),
+                Rule::RestOfLine => text = pair.as_str(),
+                _ => continue,
+            }
+        }
+        ASTKind::tag_definition(tag, text.trim(), r)
+    }
+}
+
+/// `[path]` is an image, an email or a hyper link
+fn parse_smart_link(src: &str, r: MaybeRanged) -> ASTNode {
+    if ImageLink::is_image_path(src) {
+        return ASTKind::image_link(src, r);
+    }
+    if src.contains('@') && !src.contains(&['/', ':', ' '][..]) {
+        return SmartLink::EMail(Box::new(EmailLink { is_bare: false, name: src.to_string() })).into_node(r);
+    }
+    ASTKind::hyper_link(src, r)
+}
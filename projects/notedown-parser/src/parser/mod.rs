@@ -1,23 +1,25 @@
+mod expression;
+mod link;
 mod regroup;
+mod xml;
 
 use crate::{
-    parser::regroup::{regroup_list_view, regroup_table_view},
+    parser::regroup::{regroup_list_view, regroup_table_view, table_align, ListLine},
     NotedownParser, Result,
 };
-use notedown_ast::{command::Command, nodes::CodeNode, ASTKind, ASTNode};
+use notedown_ast::{
+    command::{Command, CommandOptions, CommandPattern, EscapedCommand},
+    nodes::{CodeNode, Delimiter, ListPrefixSymbol, Literal},
+    traits::IntoASTNode,
+    ASTKind, ASTNode, NoteError, Value,
+};
 use notedown_pest::{NoteDownParser, Pair, Pairs, Parser, Rule};
 
-macro_rules! debug_cases {
-    ($i:ident) => {{
-        println!("Rule::{:?}=>continue,", $i.as_rule());
-        println!("Span: {:?}", $i.as_span());
-        println!("Text: {}", $i.as_str());
-        unreachable!();
-    }};
-}
-
 impl NotedownParser {
     pub fn parse(&self, input: &str) -> Result<ASTNode> {
+        // variables and errors of the last parsing
+        self.scope.take();
+        self.errors.take();
         // let input = input.replace("\r\n", "\n").replace("\\\n", "").replace("\t", &" ".repeat(self.tab_size));
         let pairs = NoteDownParser::parse(Rule::program, &input)?;
         self.parse_program(pairs)
@@ -30,111 +32,178 @@ impl NotedownParser {
                 Rule::EOI => continue,
                 Rule::WHITE_SPACE => continue,
                 Rule::LINE_SEPARATOR => continue,
-                Rule::HorizontalRule => {
-                    unimplemented!();
-                    // AST::from("<hr/>")
-                }
+                Rule::HorizontalRule => Delimiter::HorizontalRule.into_node(self.get_position(pair.as_span())),
                 Rule::Header => self.parse_header(pair),
                 Rule::TextBlock => self.parse_paragraph(pair),
                 Rule::List => {
                     codes.extend(self.parse_list(pair));
                     continue;
                 }
-                Rule::Table => {
-                    codes.extend(self.parse_table(pair));
-                    continue;
-                }
+                Rule::Table => self.parse_table(pair),
                 Rule::Code => self.parse_code_block(pair),
                 Rule::CommandBlock => self.parse_command_block(pair),
                 Rule::CommandLine => self.parse_command_line(pair),
-                _ => debug_cases!(pair),
+                Rule::HTMLOpenClose | Rule::HTMLSelfClose => self.parse_xml(pair),
+                Rule::TagBlock => self.parse_tag_block(pair),
+                _ => self.unexpected(pair),
             };
-            // println!("{:?}", code);
             codes.push(code);
         }
 
         // FIXME: fix range
-        Ok(ASTKind::statements(codes, None))
+        let mut ast = ASTKind::statements(codes, None);
+        ast.set_header_ids(self.slug_scheme);
+        Ok(ast)
     }
     fn parse_list(&self, pairs: Pair<Rule>) -> Vec<ASTNode> {
-        // let r = self.get_position(pairs.as_span());
-        let mut list_terms: Vec<(usize, &str, Vec<ASTNode>)> = vec![];
+        let mut lines = vec![];
         for pair in pairs.into_inner() {
             match pair.as_rule() {
                 Rule::LINE_SEPARATOR => continue,
                 Rule::ListFirstLine | Rule::ListRestLine => {
-                    let mut kind = "";
+                    let span = pair.as_span();
                     let mut indent = 0;
+                    let mut prefix = None;
                     let mut inner = pair.into_inner();
-                    while let Some(n) = inner.next() {
+                    for n in inner.by_ref() {
                         match n.as_rule() {
-                            Rule::WHITE_SPACE => indent += 1,
-                            Rule::ListMark | Rule::Vertical => {
-                                kind = n.as_str();
+                            Rule::WHITE_SPACE => indent += n.as_str().len(),
+                            Rule::ListMark => {
+                                let symbol = ListPrefixSymbol::parse(n.as_str());
+                                prefix = Some(Literal { value: symbol, range: self.get_position(n.as_span()) });
                                 break;
                             }
-                            _ => debug_cases!(n),
+                            Rule::Vertical => break,
+                            _ => {
+                                self.unexpected(n);
+                            }
                         }
                     }
                     let terms = inner.map(|pair| self.parse_span_term(pair)).collect();
-                    list_terms.push((indent, kind, terms))
+                    lines.push(ListLine { indent, prefix, terms, range: span.start()..span.end() })
+                }
+                _ => {
+                    self.unexpected(pair);
                 }
-                _ => debug_cases!(pair),
             };
         }
-        return regroup_list_view(list_terms);
+        return regroup_list_view(lines);
     }
-    fn parse_table(&self, pairs: Pair<Rule>) -> Vec<ASTNode> {
-        // let r = self.get_position(pairs.as_span());
-        let mut table_terms = vec![];
+    fn parse_table(&self, pairs: Pair<Rule>) -> ASTNode {
+        let r = self.get_position(pairs.as_span());
+        let mut rows = vec![];
+        let mut align = None;
         for pair in pairs.into_inner() {
             match pair.as_rule() {
-                Rule::LINE_SEPARATOR => continue,
+                Rule::WHITE_SPACE | Rule::LINE_SEPARATOR => continue,
                 Rule::TableFirstLine | Rule::TableRestLine => {
+                    if pair.as_str().trim().is_empty() {
+                        continue;
+                    }
+                    // `|---|:-:|` after the head
+                    if rows.len() == 1 && align.is_none() {
+                        align = table_align(pair.as_str());
+                        if align.is_some() {
+                            continue;
+                        }
+                    }
                     let mut line = vec![];
-                    let mut inner = pair.into_inner();
-                    let head = inner.next().unwrap();
-                    let mut item = match head.as_rule() {
-                        Rule::Vertical => vec![],
-                        _ => vec![self.parse_span_term(head)],
-                    };
-                    for n in inner {
+                    let mut item = vec![];
+                    let mut inner = pair.into_inner().peekable();
+                    // the leading `|` does not open a cell
+                    inner.next_if(|n| n.as_rule() == Rule::Vertical);
+                    while let Some(n) = inner.next() {
                         match n.as_rule() {
-                            Rule::Vertical => {
-                                line.push(item);
-                                item = vec![]
-                            }
+                            Rule::Vertical => line.push(std::mem::take(&mut item)),
+                            // `\|` in the cell
+                            Rule::Escape => match inner.next_if(|n| n.as_rule() == Rule::Vertical) {
+                                Some(v) => item.push(ASTKind::escaped_char('|', Some(n.as_span().start()..v.as_span().end()))),
+                                None => item.push(self.parse_normal_text(n)),
+                            },
                             _ => item.push(self.parse_span_term(n)),
                         }
                     }
-                    if item.is_empty() {
-                        table_terms.push(line)
-                    }
-                    else {
-                        line.push(item);
-                        table_terms.push(line)
+                    if !item.is_empty() {
+                        line.push(item)
                     }
+                    rows.push(line)
+                }
+                _ => {
+                    self.unexpected(pair);
                 }
-                _ => unreachable!(),
             };
         }
-        return regroup_table_view(&table_terms);
+        return regroup_table_view(rows, align, r);
     }
+    /// ````note
+    /// ```lang(key = value) {file = "main.rs"}
+    /// code
+    /// ```
+    /// ````
     pub fn parse_code_block(&self, pairs: Pair<Rule>) -> ASTNode {
         let r = self.get_position(pairs.as_span());
-        let mut lang = String::new();
-        let mut code = String::new();
+        let mut start = pairs.as_span().start();
+        let mut end = pairs.as_span().end();
+        let mut level = 0;
+        let mut action = "";
+        let mut lang = Literal::default();
+        let mut options = CommandOptions::default();
+        let mut body = Literal::default();
         for pair in pairs.into_inner() {
             match pair.as_rule() {
-                Rule::WHITE_SPACE => continue,
-                Rule::CodeLevel => continue,
-                Rule::CodeMark => continue,
-                Rule::SYMBOL => lang = pair.as_str().to_string(),
-                Rule::CodeText => code = pair.as_str().to_string(),
-                _ => debug_cases!(pair),
+                Rule::WHITE_SPACE | Rule::PATTERN_WHITE_SPACE | Rule::CodeMark => continue,
+                Rule::CodeLevel => {
+                    start = pair.as_span().start();
+                    level = pair.as_str().len();
+                    end -= level;
+                }
+                Rule::CodeAction => action = pair.as_str(),
+                Rule::SYMBOL => lang = self.get_literal(pair.as_span()),
+                Rule::argument | Rule::key_value => self.parse_argument(pair, &mut options),
+                Rule::object => self.parse_options_object(pair, &mut options),
+                Rule::CodeText => body = self.parse_code_text(pair),
+                _ => {
+                    self.unexpected(pair);
+                }
             };
         }
-        CodeNode::code_block(lang, code).into_node(r)
+        // ```@cmd or registered ```cmd
+        if action == "@" || self.commands.is_escaped(&lang.value) {
+            if lang.value.is_empty() {
+                let mut error = NoteError::syntax_error("Missing command name, try ```@cmd");
+                error.range = Some(start..start + level + 1);
+                self.push_error(error)
+            }
+            let cmd = EscapedCommand::new(lang.value, level as u8, CommandPattern::default(), options, body, start, end);
+            return Command::Escaped(cmd).into_node(r);
+        }
+        let mut code = CodeNode::code_block(lang.value, body.value);
+        match action {
+            "#" => code.show_line_number = Some(1),
+            "*" => code.highlight = false,
+            _ => {}
+        }
+        let mut warnings = vec![];
+        if let Err(e) = code.set_options(&options, &mut warnings) {
+            self.push_error(e)
+        }
+        for warning in warnings {
+            self.push_error(warning)
+        }
+        code.into_node(r)
+    }
+    /// Remove the line breaks after the open fence and before the close fence
+    fn parse_code_text(&self, pairs: Pair<Rule>) -> Literal<String> {
+        let mut start = pairs.as_span().start();
+        let mut text = pairs.as_str();
+        if let Some(s) = text.strip_prefix("\r\n").or_else(|| text.strip_prefix('\n')) {
+            start += text.len() - s.len();
+            text = s
+        }
+        if let Some(s) = text.strip_suffix('\n') {
+            text = s.strip_suffix('\r').unwrap_or(s)
+        }
+        Literal { value: text.to_string(), range: Some(start..start + text.len()) }
     }
 
     fn parse_header(&self, pairs: Pair<Rule>) -> ASTNode {
@@ -153,28 +222,59 @@ impl NotedownParser {
     pub fn parse_command_block(&self, pairs: Pair<Rule>) -> ASTNode {
         let r = self.get_position(pairs.as_span());
         let mut cmd = String::new();
+        let mut pattern = CommandPattern::default();
+        let mut options = CommandOptions::default();
         for pair in pairs.into_inner() {
             match pair.as_rule() {
-                Rule::Escape => continue,
+                Rule::Escape | Rule::WHITE_SPACE | Rule::PATTERN_WHITE_SPACE => continue,
                 Rule::SYMBOL => cmd = pair.as_str().to_string(),
-                _ => debug_cases!(pair),
+                Rule::argument_literal => pattern.push(self.get_literal(pair.as_span())),
+                Rule::argument | Rule::key_value => self.parse_argument(pair, &mut options),
+                _ => {
+                    self.unexpected(pair);
+                }
             };
         }
-        unimplemented!()
+        // \set(name = value)
+        if cmd == "set" {
+            for (key, value) in options.kvs.iter_raw() {
+                self.define_variable(Some(key), value.to_owned(), &r)
+            }
+        }
+        Command::one_line(cmd, pattern, options, Default::default()).into_node(r)
     }
     pub fn parse_command_line(&self, pairs: Pair<Rule>) -> ASTNode {
         let r = self.get_position(pairs.as_span());
         let mut cmd = String::new();
-        let mut rest = String::new();
+        let mut pattern = CommandPattern::default();
+        let mut options = CommandOptions::default();
+        let mut body = Literal::default();
+        let mut value = None;
         for pair in pairs.into_inner() {
             match pair.as_rule() {
-                Rule::Escape | Rule::Colon => continue,
+                Rule::Escape | Rule::Colon | Rule::WHITE_SPACE => continue,
                 Rule::SYMBOL => cmd = pair.as_str().to_string(),
-                Rule::RestOfLine => rest = pair.as_str().to_string(),
-                _ => debug_cases!(pair),
+                Rule::argument_literal => pattern.push(self.get_literal(pair.as_span())),
+                Rule::RestOfLine => body = self.get_literal(pair.as_span()),
+                Rule::expression => {
+                    body = self.get_literal(pair.as_span());
+                    if cmd == "set" {
+                        let expr = self.parse_expression(pair);
+                        value = Some(Literal { value: self.evaluate(|scope| expr.evaluate_argument(scope)), range: expr.range });
+                    }
+                }
+                _ => {
+                    self.unexpected(pair);
+                }
             };
         }
-        ASTKind::command_line(cmd, rest, r)
+        // \set[name]: value
+        if cmd == "set" {
+            let value = value.unwrap_or_else(|| Literal { value: Value::string(body.value.trim()), range: body.range.to_owned() });
+            options.args.push(value.to_owned());
+            self.define_variable(pattern.get(0), value, &r)
+        }
+        Command::one_line(cmd, pattern, options, body).into_node(r)
     }
     pub fn parse_paragraph(&self, pairs: Pair<Rule>) -> ASTNode {
         let r = self.get_position(pairs.as_span());
@@ -193,6 +293,8 @@ impl NotedownParser {
                 ASTKind::TextSpan(_) => {}
                 ASTKind::StyledSpan(_) => {}
                 ASTKind::Command(_) => {}
+                ASTKind::QuoteNode(_) => {}
+                ASTKind::IncludeNode(_) => {}
                 ASTKind::Value(_) => {}
             }
         }
@@ -210,15 +312,29 @@ impl NotedownParser {
             Rule::TildeLine => self.parse_tilde_text(pair),
             Rule::Raw => self.parse_raw_text(pair),
             Rule::Math => self.parse_math_text(pair),
-            Rule::RawRest | Rule::StyleRest | Rule::TildeRest | Rule::MathRest => self.parse_normal_text(pair),
+            Rule::RawRest | Rule::StyleRest | Rule::TildeRest | Rule::MathRest | Rule::HTMLRest | Rule::LinkRest => {
+                self.parse_normal_text(pair)
+            }
             Rule::WHITE_SPACE | Rule::LINE_SEPARATOR => self.parse_normal_text(pair),
             Rule::Escaped => self.parse_escaped(pair),
             Rule::CommandBlock => self.parse_command_block(pair),
+            Rule::CommandLine => self.parse_command_line(pair),
+            Rule::Interpolation => self.parse_interpolation(pair),
+            Rule::HTMLOpenClose | Rule::HTMLSelfClose => self.parse_xml(pair),
             Rule::URL => ASTKind::bare_link(pair.as_str(), r),
-            _ => debug_cases!(pair),
+            Rule::TwoWayLink | Rule::TagLink | Rule::ImageLink | Rule::NormalLink => self.parse_link(pair),
+            _ => self.unexpected(pair),
         }
     }
 
+    /// Record the rule the parser does not support yet, keep its text
+    fn unexpected(&self, pair: Pair<Rule>) -> ASTNode {
+        let r = self.get_position(pair.as_span());
+        let mut error = NoteError::syntax_error(format!("Unsupported syntax `{:?}`", pair.as_rule()));
+        error.range = r.to_owned();
+        self.push_error(error);
+        ASTKind::text(pair.as_str(), r)
+    }
     fn parse_normal_text(&self, pairs: Pair<Rule>) -> ASTNode {
         let r = self.get_position(pairs.as_span());
         ASTKind::text(pairs.as_str().to_string(), r)
@@ -233,7 +349,9 @@ impl NotedownParser {
                 Rule::Asterisk => continue,
                 Rule::StyleLevel => level += pair.as_str().len(),
                 Rule::StyleText => text.extend(self.parse_span(pair)),
-                _ => debug_cases!(pair),
+                _ => {
+                    self.unexpected(pair);
+                }
             };
         }
         match level {
@@ -253,7 +371,9 @@ impl NotedownParser {
                 Rule::Tilde => continue,
                 Rule::TildeLevel => level += pair.as_str().len(),
                 Rule::TildeText => text = self.parse_span(pair),
-                _ => debug_cases!(pair),
+                _ => {
+                    self.unexpected(pair);
+                }
             };
         }
         match level {
@@ -286,6 +406,13 @@ impl NotedownParser {
     }
     fn parse_escaped(&self, pairs: Pair<Rule>) -> ASTNode {
         let r = self.get_position(pairs.as_span());
-        ASTKind::escaped(pairs.as_str(), r)
+        match ASTKind::escaped(pairs.as_str(), r.to_owned()) {
+            Ok(o) => o,
+            Err(mut e) => {
+                e.range = r.to_owned();
+                self.push_error(e);
+                ASTKind::text(pairs.as_str(), r)
+            }
+        }
     }
 }
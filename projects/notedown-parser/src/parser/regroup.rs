@@ -1,21 +1,186 @@
 use crate::parser::ASTNode;
-use notedown_ast::{nodes::ListItem, ASTNodes};
+use notedown_ast::{
+    nodes::{ListItem, ListPrefixSymbol, ListView, Literal, MaybeRanged, StyleNode, TableView},
+    traits::IntoASTNode,
+    ASTKind, ASTNodes,
+};
+use std::ops::Range;
 
-/// ident:usize
-/// kind: &str
-/// term: ASTNodes
-pub fn regroup_list_view(lists: Vec<(usize, &str, ASTNodes)>) -> Vec<ASTNode> {
-    println!("{:#?}", lists);
-    ListItem { prefix: Default::default(), rest: vec![] };
+/// A line of [`Rule::List`](notedown_pest::Rule::List)
+pub struct ListLine {
+    pub indent: usize,
+    /// `None` for the `|` continuation line
+    pub prefix: Option<Literal<ListPrefixSymbol>>,
+    pub terms: ASTNodes,
+    pub range: Range<usize>,
+}
 
-    todo!()
+/// Consecutive items of the same kind at the same indent
+struct ListGroup {
+    symbol: ListPrefixSymbol,
+    items: Vec<ListItem>,
+    /// Deeper lines of the last item
+    nested: Vec<ListLine>,
+    range: Range<usize>,
 }
 
-pub fn regroup_table_view(table: &[Vec<Vec<ASTNode>>]) -> Vec<ASTNode> {
-    for line in table {
-        for item in line {
-            println!("{:?}", item)
+/// Group the lines into lists and quotes, deeper lines are nested in the last item
+///
+/// ```note
+/// - item
+///   1. nested
+/// | continue the item
+/// > quote
+/// ```
+pub fn regroup_list_view(lines: Vec<ListLine>) -> Vec<ASTNode> {
+    let base = lines.iter().map(|line| line.indent).min().unwrap_or_default();
+    let mut out = vec![];
+    let mut group: Option<ListGroup> = None;
+    for line in lines {
+        let prefix = match (&mut group, line.prefix.to_owned()) {
+            (Some(g), _) if line.indent > base => {
+                g.range.end = line.range.end;
+                g.nested.push(line);
+                continue;
+            }
+            (Some(g), None) => {
+                g.flush_nested();
+                g.range.end = line.range.end;
+                if let Some(item) = g.items.last_mut() {
+                    item.rest.push(ASTKind::soft_break(None));
+                    item.rest.extend(line.terms)
+                }
+                continue;
+            }
+            (None, None) => {
+                let r = Some(line.range);
+                out.push(ASTKind::paragraph(line.terms, r));
+                continue;
+            }
+            (_, Some(prefix)) => prefix,
+        };
+        match &mut group {
+            Some(g) if g.accept(&prefix.value) => {
+                g.flush_nested();
+                g.range.end = line.range.end;
+                g.items.push(ListItem { prefix, rest: line.terms })
+            }
+            _ => {
+                if let Some(g) = group.take() {
+                    out.push(g.finish())
+                }
+                let symbol = prefix.value.to_owned();
+                let items = vec![ListItem { prefix, rest: line.terms }];
+                group = Some(ListGroup { symbol, items, nested: vec![], range: line.range })
+            }
+        }
+    }
+    if let Some(g) = group {
+        out.push(g.finish())
+    }
+    return out;
+}
+
+impl ListGroup {
+    fn accept(&self, symbol: &ListPrefixSymbol) -> bool {
+        self.symbol.is_quote() == symbol.is_quote() && self.symbol.is_ordered() == symbol.is_ordered()
+    }
+    fn flush_nested(&mut self) {
+        if self.nested.is_empty() {
+            return;
         }
+        let nested = regroup_list_view(std::mem::take(&mut self.nested));
+        if let Some(item) = self.items.last_mut() {
+            item.rest.extend(nested)
+        }
+    }
+    fn finish(mut self) -> ASTNode {
+        self.flush_nested();
+        let r = Some(self.range);
+        if !self.symbol.is_quote() {
+            let list = ListView { ignore_global_list_style: false, first_symbol: self.symbol, children: self.items };
+            return list.into_node(r);
+        }
+        // lines of the quote are joined into paragraphs, nested blocks break the paragraph
+        let mut body = vec![];
+        let mut paragraph: ASTNodes = vec![];
+        for item in self.items {
+            for node in item.rest {
+                match node.value {
+                    ASTKind::ListView(_) | ASTKind::QuoteNode(_) => {
+                        push_paragraph(&mut body, &mut paragraph);
+                        body.push(node)
+                    }
+                    _ => paragraph.push(node),
+                }
+            }
+            paragraph.push(ASTKind::soft_break(None))
+        }
+        push_paragraph(&mut body, &mut paragraph);
+        ASTKind::quote(body, r)
+    }
+}
+
+fn push_paragraph(body: &mut ASTNodes, paragraph: &mut ASTNodes) {
+    while paragraph.last().is_some_and(|node| node.range.is_none()) {
+        paragraph.pop();
     }
-    todo!()
+    if !paragraph.is_empty() {
+        let r = span(paragraph);
+        body.push(ASTKind::paragraph(std::mem::take(paragraph), r))
+    }
+}
+
+/// Build the table, the first row is the head if the alignment line follows
+///
+/// Alignment of the columns: `0` for unspecified, `1` for `:--`, `2` for `:-:`, `3` for `--:`
+pub fn regroup_table_view(rows: Vec<Vec<ASTNodes>>, align: Option<Vec<u8>>, r: MaybeRanged) -> ASTNode {
+    let mut column = rows.iter().map(|row| row.len()).max().unwrap_or_default();
+    let mut rows = rows.into_iter().map(|row| row.into_iter().map(table_cell).collect::<Vec<_>>());
+    let (head, align) = match align {
+        Some(align) => (rows.next().unwrap_or_default(), align),
+        None => (vec![], vec![]),
+    };
+    column = column.max(align.len());
+    TableView::SimpleTable { head, align, terms: rows.collect(), column }.into_node(r)
+}
+
+/// Parse `:--|:-:|--:` as the alignment of the table
+pub fn table_align(line: &str) -> Option<Vec<u8>> {
+    let line = line.trim();
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = line.strip_suffix('|').unwrap_or(line);
+    let mut align = vec![];
+    for cell in line.split('|') {
+        let cell = cell.trim();
+        let dashes = cell.trim_start_matches(':').trim_end_matches(':');
+        if dashes.is_empty() || !dashes.chars().all(|c| c == '-') {
+            return None;
+        }
+        let align_code = match (cell.starts_with(':'), cell.ends_with(':')) {
+            (false, false) => 0,
+            (true, false) => 1,
+            (true, true) => 2,
+            (false, true) => 3,
+        };
+        align.push(align_code)
+    }
+    Some(align)
+}
+
+/// A cell with several terms is wrapped in a transparent style
+fn table_cell(mut terms: ASTNodes) -> ASTNode {
+    match terms.len() {
+        1 => terms.remove(0),
+        _ => {
+            let r = span(&terms);
+            StyleNode::plain(terms).into_node(r)
+        }
+    }
+}
+
+fn span(nodes: &[ASTNode]) -> MaybeRanged {
+    let start = nodes.iter().find_map(|node| node.range.as_ref())?.start;
+    let end = nodes.iter().rev().find_map(|node| node.range.as_ref())?.end;
+    Some(start..end)
 }
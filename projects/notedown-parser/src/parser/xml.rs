@@ -0,0 +1,62 @@
+use super::*;
+use notedown_ast::{command::XMLCommand, NoteError};
+
+impl NotedownParser {
+    /// ```note
+    /// <cmd[pattern] key=value>body</cmd>
+    /// <cmd[pattern] key=value/>
+    /// ```
+    pub(crate) fn parse_xml(&self, pairs: Pair<Rule>) -> ASTNode {
+        let r = self.get_position(pairs.as_span());
+        let xml = match pairs.as_rule() {
+            Rule::HTMLSelfClose => {
+                let span = pairs.as_span();
+                let (cmd, pattern, options) = self.parse_xml_tag(pairs);
+                XMLCommand::self_close(cmd, pattern, options, span.start(), span.end() - 2)
+            }
+            _ => {
+                let mut inner = pairs.clone().into_inner();
+                let (open, body, close) = match (inner.next(), inner.next(), inner.next()) {
+                    (Some(open), Some(body), Some(close)) => (open, body, close),
+                    _ => return self.unexpected(pairs),
+                };
+                let start = open.as_span().start();
+                let middle = open.as_span().end() - 1;
+                let end = close.as_span().start();
+                let (cmd, pattern, options) = self.parse_xml_tag(open);
+                self.scope.borrow_mut().push();
+                let body = self.parse_span(body);
+                self.scope.borrow_mut().pop();
+                self.check_close_tag(&cmd, close);
+                XMLCommand::open_close(cmd, pattern, options, body, start, middle, end)
+            }
+        };
+        Command::XML(xml).into_node(r)
+    }
+    fn parse_xml_tag(&self, pairs: Pair<Rule>) -> (String, CommandPattern, CommandOptions) {
+        let mut cmd = String::new();
+        let mut pattern = CommandPattern::default();
+        let mut options = CommandOptions::default();
+        for pair in pairs.into_inner() {
+            match pair.as_rule() {
+                Rule::PATTERN_WHITE_SPACE => continue,
+                Rule::SYMBOL => cmd = pair.as_str().to_string(),
+                Rule::argument_literal => pattern.push(self.get_literal(pair.as_span())),
+                Rule::attribute => self.parse_argument(pair, &mut options),
+                _ => {
+                    self.unexpected(pair);
+                }
+            };
+        }
+        return (cmd, pattern, options);
+    }
+    fn check_close_tag(&self, cmd: &str, pairs: Pair<Rule>) {
+        let r = self.get_position(pairs.as_span());
+        let name = pairs.into_inner().find(|pair| pair.as_rule() == Rule::SYMBOL).map(|pair| pair.as_str()).unwrap_or_default();
+        if name != cmd {
+            let mut error = NoteError::syntax_error(format!("Mismatched close tag `</{}>`, expect `</{}>`", name, cmd));
+            error.range = r;
+            self.push_error(error)
+        }
+    }
+}
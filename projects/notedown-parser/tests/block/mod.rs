@@ -0,0 +1,82 @@
+use notedown_ast::{
+    nodes::{Delimiter, ListPrefixSymbol, TableView, TextSpan},
+    ASTKind, ASTNodes,
+};
+use notedown_parser::NotedownParser;
+
+fn parse_blocks(input: &str) -> ASTNodes {
+    let parser = NotedownParser::default();
+    let ast = parser.parse(input).unwrap();
+    assert!(parser.take_errors().is_empty());
+    match ast.value {
+        ASTKind::Statements(terms) => terms,
+        _ => vec![],
+    }
+}
+
+#[test]
+fn list() {
+    let blocks = parse_blocks("- a\n- b\n\n1. one\n   - nested\n| more\n2. two");
+    assert_eq!(blocks.len(), 2);
+    let list = match &blocks[0].value {
+        ASTKind::ListView(list) => list,
+        _ => panic!("expect a list"),
+    };
+    assert_eq!(list.first_symbol, ListPrefixSymbol::Hyphen);
+    assert_eq!(list.children.len(), 2);
+    assert_eq!(blocks[0].range, Some(0..7));
+    let list = match &blocks[1].value {
+        ASTKind::ListView(list) => list,
+        _ => panic!("expect a list"),
+    };
+    assert!(list.first_symbol.is_ordered());
+    assert_eq!(list.children.len(), 2);
+    let first = &list.children[0].rest;
+    assert!(first.iter().any(|node| matches!(&node.value, ASTKind::ListView(nested) if nested.children.len() == 1)));
+    assert!(first.iter().any(|node| matches!(&node.value, ASTKind::TextSpan(text) if **text == TextSpan::Normal(" more".to_string()))));
+}
+
+#[test]
+fn quote() {
+    let blocks = parse_blocks("> quote\n> more\n- item");
+    assert_eq!(blocks.len(), 2);
+    match &blocks[0].value {
+        ASTKind::QuoteNode(quote) => {
+            assert_eq!(quote.body.len(), 1);
+            assert!(matches!(&quote.body[0].value, ASTKind::Paragraph(terms) if terms.len() == 3));
+        }
+        _ => panic!("expect a quote"),
+    }
+    assert!(matches!(&blocks[1].value, ASTKind::ListView(_)));
+}
+
+#[test]
+fn table() {
+    let blocks = parse_blocks("|a|b|\n|---|---|\n|1|2 *x*|\n|3|");
+    match &blocks[0].value {
+        ASTKind::TableView(table) => match &**table {
+            TableView::SimpleTable { head, align, terms, column } => {
+                assert_eq!(head.len(), 2);
+                assert_eq!(align, &vec![0, 0]);
+                assert_eq!(terms.len(), 2);
+                assert_eq!(terms[1].len(), 1);
+                assert_eq!(*column, 2);
+            }
+        },
+        _ => panic!("expect a table"),
+    }
+    match &parse_blocks("|a|b|\n|c|d|")[0].value {
+        ASTKind::TableView(table) => match &**table {
+            TableView::SimpleTable { head, terms, .. } => assert_eq!((head.len(), terms.len()), (0, 2)),
+        },
+        _ => panic!("expect a table"),
+    }
+}
+
+#[test]
+fn horizontal_rule() {
+    let blocks = parse_blocks("text\n\n---\n\ntext");
+    assert_eq!(blocks.len(), 3);
+    assert_eq!(blocks[1].value, ASTKind::Delimiter(Box::new(Delimiter::HorizontalRule)));
+    assert_eq!(blocks[1].range, Some(6..9));
+}
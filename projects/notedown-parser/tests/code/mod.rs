@@ -0,0 +1,58 @@
+use notedown_ast::{command::Command, ASTKind, ASTNode, Value};
+use notedown_parser::NotedownParser;
+use notedown_plugin::CommandDefinition;
+
+fn first_child(node: &ASTNode) -> &ASTKind {
+    match &node.value {
+        ASTKind::Statements(children) => &children[0].value,
+        other => other,
+    }
+}
+
+#[test]
+fn escaped_command() {
+    let mut parser = NotedownParser::default();
+    parser.commands.register(CommandDefinition::escaped("mermaid"));
+    let ast = parser.parse("````mermaid(theme = \"dark\")\ngraph TD\n````").unwrap();
+    let cmd = match first_child(&ast) {
+        ASTKind::Command(cmd) => match &**cmd {
+            Command::Escaped(cmd) => cmd.to_owned(),
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    };
+    assert_eq!(cmd.level, 4);
+    assert_eq!(cmd.body.value, "graph TD");
+    assert_eq!(cmd.body.range, Some(28..36));
+    assert_eq!((cmd.start, cmd.end), (0, 37));
+    assert_eq!(cmd.options.kvs.get("theme"), Some(Value::string("dark")));
+}
+
+#[test]
+fn code_options() {
+    let parser = NotedownParser::default();
+    let ast = parser.parse("```#rust {file = \"main.rs\", highlight = [1, \"3-4\"], size = 1}\nfn main() {}\n```").unwrap();
+    let code = match first_child(&ast) {
+        ASTKind::CodeNode(code) => code.to_owned(),
+        _ => unreachable!(),
+    };
+    assert_eq!(code.code, "fn main() {}");
+    assert_eq!(code.show_file_name.as_deref(), Some("main.rs"));
+    assert_eq!(code.show_line_number, Some(1));
+    assert_eq!(code.highlight_lines, vec![1..=1, 3..=4]);
+    // unknown key `size`
+    assert_eq!(parser.take_errors().len(), 1);
+}
+
+#[test]
+fn builtin_escaped_command() {
+    let parser = NotedownParser::default();
+    let ast = parser.parse("```comment\nnot **rendered**\n```").unwrap();
+    match first_child(&ast) {
+        ASTKind::Command(cmd) => match &**cmd {
+            Command::Escaped(cmd) => assert_eq!((cmd.cmd.as_str(), cmd.body.value.as_str()), ("comment", "not **rendered**")),
+            other => panic!("{:?}", other),
+        },
+        other => panic!("{:?}", other),
+    }
+}
@@ -0,0 +1,57 @@
+use notedown_ast::traits::{ContextAware, ContextKind};
+use notedown_parser::NotedownParser;
+
+fn context_at(input: &str, offset: u32) -> ContextKind {
+    let ast = NotedownParser::default().parse(input).unwrap();
+    ast.context_aware(offset)
+}
+
+#[test]
+fn command() {
+    let input = r#"\img(width = 1, "a.png")"#;
+    assert!(matches!(context_at(input, 2), ContextKind::CommandName(_)));
+    assert!(matches!(context_at(input, 6), ContextKind::ArgumentKey(_, key) if key == "width"));
+    assert!(matches!(context_at(input, 13), ContextKind::ArgumentValue(_, Some(key)) if key == "width"));
+    assert!(matches!(context_at(input, 18), ContextKind::ArgumentValue(_, None)));
+    let input = "<note level=2>body</note>";
+    assert!(matches!(context_at(input, 2), ContextKind::CommandName(_)));
+    assert!(matches!(context_at(input, 7), ContextKind::ArgumentKey(_, key) if key == "level"));
+    assert!(matches!(context_at(input, 15), ContextKind::Text));
+    assert!(matches!(context_at(input, 21), ContextKind::CommandName(_)));
+}
+
+#[test]
+fn text() {
+    assert_eq!(context_at("## head $x$\ntext", 4), ContextKind::HeaderText(2));
+    assert!(matches!(context_at("## head $x$\ntext", 9), ContextKind::Math(_)));
+    assert!(matches!(context_at("see [text][file.note]", 6), ContextKind::LinkText(_)));
+    assert!(matches!(context_at("see [text][file.note]", 12), ContextKind::LinkTarget(_)));
+    assert!(matches!(context_at("[^tag][some text]", 3), ContextKind::LinkTarget(_)));
+    assert!(matches!(context_at("[^tag][some text]", 8), ContextKind::LinkText(_)));
+    assert!(matches!(context_at("see [[other > id]]", 4), ContextKind::LinkTarget(_)));
+}
+
+#[test]
+fn context_range() {
+    let ast = NotedownParser::default().parse("text $x^2$ text").unwrap();
+    let context = ast.context_aware(6);
+    assert!(matches!(context, ContextKind::Math(_)));
+    assert_eq!(context.range(), Some(5..10));
+    assert_eq!(ast.context_aware(2), ContextKind::Text);
+}
+
+#[test]
+fn blocks() {
+    let table = "| a | b |\n|---|---|\n| c | d |";
+    assert_eq!(context_at(table, 2), ContextKind::TableCell { row: 0, column: 0 });
+    assert_eq!(context_at(table, 6), ContextKind::TableCell { row: 0, column: 1 });
+    assert_eq!(context_at(table, 26), ContextKind::TableCell { row: 1, column: 1 });
+    let list = "- one\n- two\n- three";
+    assert_eq!(context_at(list, 3), ContextKind::ListItem(0));
+    assert_eq!(context_at(list, 14), ContextKind::ListItem(2));
+    let code = "```rust\nfn main() {}\n```";
+    assert_eq!(context_at(code, 10), ContextKind::CodeBody(String::from("rust")));
+    let escaped = "```comment\nnot **rendered**\n```";
+    assert!(matches!(context_at(escaped, 5), ContextKind::CommandName(_)));
+    assert_eq!(context_at(escaped, 15), ContextKind::CodeBody(String::from("comment")));
+}
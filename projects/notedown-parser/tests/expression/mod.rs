@@ -0,0 +1,100 @@
+use notedown_ast::{nodes::TextSpan, ASTKind, ASTNode, NoteError, Value};
+use notedown_parser::NotedownParser;
+
+/// Texts and values of the interpolations in the last paragraph
+fn interpolate(parser: &NotedownParser, input: &str) -> (Vec<ASTKind>, Vec<NoteError>) {
+    let ast: ASTNode = parser.parse(input).unwrap();
+    let last = match ast.value {
+        ASTKind::Statements(terms) => terms.into_iter().last(),
+        _ => None,
+    };
+    let terms = match last.map(|node| node.value) {
+        Some(ASTKind::Paragraph(terms)) => terms.into_iter().map(|node| node.value).collect(),
+        _ => vec![],
+    };
+    (terms, parser.take_errors())
+}
+
+fn text(s: &str) -> ASTKind {
+    ASTKind::TextSpan(Box::new(TextSpan::Normal(s.to_string())))
+}
+
+#[test]
+fn set_and_interpolate() {
+    let parser = NotedownParser::default();
+    let (terms, errors) = interpolate(&parser, "\\set[name]: \"note\"\n\\set(n = 2 + 3 * 4)\n\n\\{name ++ \"!\"}\\{n}");
+    assert!(errors.is_empty(), "{:?}", errors);
+    assert_eq!(terms, vec![text("note!"), ASTKind::Value(Box::new(Value::integer(14)))]);
+}
+
+#[test]
+fn undefined_variable() {
+    let parser = NotedownParser::default();
+    let (terms, errors) = interpolate(&parser, "\\{missing}");
+    assert_eq!(terms, vec![ASTKind::Value(Box::new(Value::Null))]);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind.code(), "undefined-variable");
+}
+
+#[test]
+fn type_error() {
+    let parser = NotedownParser::default();
+    let (terms, errors) = interpolate(&parser, "\\set[name]: \"note\"\n\n\\{name - 1}");
+    assert_eq!(terms, vec![ASTKind::Value(Box::new(Value::Null))]);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind.code(), "type-mismatch");
+}
+
+#[test]
+fn reset_state() {
+    let parser = NotedownParser::default();
+    let (_, errors) = interpolate(&parser, "\\set[name]: 1\n\n\\{name}\\{missing}");
+    assert_eq!(errors.len(), 1);
+    // variables and errors do not leak into the next parsing
+    parser.parse("\\{missing}").unwrap();
+    parser.parse("text").unwrap();
+    assert!(parser.take_errors().is_empty());
+    let (_, errors) = interpolate(&parser, "\\{name}");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind.code(), "undefined-variable");
+}
+
+#[test]
+fn power_precedence() {
+    let parser = NotedownParser::default();
+    let (terms, errors) = interpolate(&parser, "\\{-2^2}\\{(-2)^2}\\{2^-1}\\{-2 * 3}\\{2^3^2}");
+    assert!(errors.is_empty(), "{:?}", errors);
+    let values: Vec<_> = terms
+        .into_iter()
+        .filter_map(|term| {
+            if let ASTKind::Value(v) = term {
+                Some(*v)
+            }
+            else {
+                None
+            }
+        })
+        .collect();
+    let half = Value::decimal(rust_decimal::Decimal::new(5, 1));
+    assert_eq!(values, vec![Value::integer(-4), Value::integer(4), half, Value::integer(-6), Value::integer(512)]);
+}
+
+#[test]
+fn power_overflow() {
+    let parser = NotedownParser::default();
+    let (terms, errors) = interpolate(&parser, "\\{2^100000000000}");
+    assert_eq!(terms, vec![ASTKind::Value(Box::new(Value::Null))]);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].to_string().contains("Overflow"), "{}", errors[0]);
+}
+
+#[test]
+fn repeat_too_long() {
+    let parser = NotedownParser::default();
+    let (terms, errors) = interpolate(&parser, "\\{\"ab\".repeat(100000000000)}");
+    assert_eq!(terms, vec![ASTKind::Value(Box::new(Value::Null))]);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].to_string().contains("too long"), "{}", errors[0]);
+    let (terms, _) = interpolate(&parser, "\\{\"=\".repeat(3)}");
+    assert_eq!(terms, vec![ASTKind::text("===", None).value]);
+}
@@ -0,0 +1,44 @@
+use notedown_ast::{
+    nodes::{SmartLink, TagReference},
+    ASTKind, ASTNode,
+};
+use notedown_parser::NotedownParser;
+
+fn links(input: &str) -> Vec<SmartLink> {
+    let ast = NotedownParser::default().parse(input).unwrap();
+    let mut out = vec![];
+    collect_links(&ast, &mut out);
+    return out;
+}
+
+fn collect_links(node: &ASTNode, out: &mut Vec<SmartLink>) {
+    match &node.value {
+        ASTKind::Statements(children) | ASTKind::Paragraph(children) => children.iter().for_each(|node| collect_links(node, out)),
+        ASTKind::LinkNode(link) => out.push(link.to_owned()),
+        _ => {}
+    }
+}
+
+#[test]
+fn smart_link() {
+    match &links("see [text][./file.note#title] here")[..] {
+        [SmartLink::Normal(link)] => assert_eq!((link.src.as_str(), link.text.as_deref()), ("./file.note#title", Some("text"))),
+        e => panic!("{:?}", e),
+    }
+    match &links("[!logo][img/logo.png]")[..] {
+        [SmartLink::Image(image)] => assert_eq!((image.source.as_str(), image.description.as_deref()), ("img/logo.png", Some("logo"))),
+        e => panic!("{:?}", e),
+    }
+    assert!(matches!(&links("[img/logo.png]")[..], [SmartLink::Image(_)]));
+    assert!(matches!(&links("[[other > id]]")[..], [SmartLink::TwoWay(_)]));
+}
+
+#[test]
+fn tag_reference() {
+    let reference = TagReference { inline: true, tag: "note".to_string(), text: vec![] };
+    let definition = TagReference { inline: false, tag: "note".to_string(), text: vec!["footnote text".to_string()] };
+    assert_eq!(
+        links("text[^note] text\n\n[^note]: footnote text"),
+        vec![SmartLink::Reference(Box::new(reference)), SmartLink::Reference(Box::new(definition))]
+    );
+}
@@ -2,9 +2,17 @@
 
 use notedown_ast::Result;
 use notedown_parser::NotedownParser;
+mod block;
+mod code;
+mod context;
+mod expression;
+mod link;
 mod normal;
+mod options;
 mod simple;
+mod slugify;
 mod style;
+mod xml;
 // mod text;
 
 #[test]
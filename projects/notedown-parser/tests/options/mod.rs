@@ -0,0 +1,31 @@
+use notedown_ast::{command::CommandOptions, ASTKind, ASTNode, Value};
+use notedown_parser::NotedownParser;
+use num::BigUint;
+
+fn find_options(node: &ASTNode) -> Option<CommandOptions> {
+    match &node.value {
+        ASTKind::Statements(children) | ASTKind::Paragraph(children) => children.iter().find_map(find_options),
+        ASTKind::Command(cmd) => cmd.get_options().cloned(),
+        _ => None,
+    }
+}
+
+#[test]
+fn literal_options() {
+    let parser = NotedownParser::default();
+    let input = r#"\img[a](1, 3 = "x", list = [1, 2.5, 5 = y,], set = {1, 2}, map = {a = 1, "b c" = true},)"#;
+    let options = find_options(&parser.parse(input).unwrap()).unwrap();
+    assert!(parser.take_errors().is_empty());
+    assert_eq!(options.args.get(&BigUint::from(1u8)), Some(Value::integer(1)));
+    assert_eq!(options.args.get(&BigUint::from(3u8)), Some(Value::string("x")));
+    assert_eq!(options.args.get(&BigUint::from(2u8)), None);
+    let list = match options.kvs.get("list") {
+        Some(Value::Array(v)) => v,
+        _ => unreachable!(),
+    };
+    assert_eq!(list.last_index(), BigUint::from(5u8));
+    assert_eq!(list.get(&BigUint::from(2u8)).unwrap().get_type_name(), "Decimal");
+    assert_eq!(list.get_literal(&BigUint::from(5u8)).unwrap().range, Some(40..41));
+    assert!(matches!(options.kvs.get("set"), Some(Value::Set(s)) if s.len() == 2));
+    assert!(matches!(options.kvs.get("map"), Some(Value::Object(o)) if o.get("b c") == Some(Value::Boolean(true))));
+}
@@ -0,0 +1,40 @@
+use notedown_ast::{traits::SlugScheme, ASTKind, ASTNode};
+use notedown_parser::NotedownParser;
+
+fn header_ids(parser: &NotedownParser, input: &str) -> Vec<Option<String>> {
+    let ast: ASTNode = parser.parse(input).unwrap();
+    match ast.value {
+        ASTKind::Statements(terms) => terms
+            .into_iter()
+            .filter_map(|term| match term.value {
+                ASTKind::Header(header) => Some(header.id),
+                _ => None,
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+fn ids(ids: &[&str]) -> Vec<Option<String>> {
+    ids.iter().map(|s| Some(s.to_string())).collect()
+}
+
+#[test]
+fn unique_ids() {
+    let parser = NotedownParser::default();
+    let input = "# Intro\n\n## Intro\n\n\\args(id = \"intro-1\")\n# Custom\n\n## Intro\n\n# *Styled* `code` text";
+    assert_eq!(header_ids(&parser, input), ids(&["intro", "intro-2", "intro-1", "intro-3", "styled-code-text"]));
+}
+
+#[test]
+fn slug_schemes() {
+    let input = "# What's new? 中文 标题";
+    let ascii = NotedownParser::default();
+    assert_eq!(header_ids(&ascii, input), ids(&["what-s-new-zhong-wen-biao-ti"]));
+    let github = NotedownParser { slug_scheme: SlugScheme::GitHub, ..NotedownParser::default() };
+    assert_eq!(header_ids(&github, input), ids(&["whats-new-中文-标题"]));
+    let unicode = NotedownParser { slug_scheme: SlugScheme::Unicode, ..NotedownParser::default() };
+    assert_eq!(header_ids(&unicode, input), ids(&["what-s-new-中文-标题"]));
+    assert_eq!("GitHub".parse::<SlugScheme>().ok(), Some(SlugScheme::GitHub));
+    assert!("kebab".parse::<SlugScheme>().is_err());
+}
@@ -0,0 +1,48 @@
+use notedown_ast::{
+    command::{Command, XMLCommand},
+    ASTKind, ASTNode, Value,
+};
+use notedown_parser::NotedownParser;
+
+fn find_xml(node: &ASTNode) -> Option<XMLCommand> {
+    match &node.value {
+        ASTKind::Statements(children) | ASTKind::Paragraph(children) => children.iter().find_map(find_xml),
+        ASTKind::Command(cmd) => match &**cmd {
+            Command::XML(xml) => Some(xml.to_owned()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[test]
+fn open_close() {
+    let parser = NotedownParser::default();
+    let input = "<note[tip] level=2 title=\"a\">body *text*</note>";
+    let mut xml = find_xml(&parser.parse(input).unwrap()).unwrap();
+    assert!(parser.take_errors().is_empty());
+    assert_eq!(xml.cmd, "note");
+    assert_eq!(xml.options.kvs.get("level"), Some(Value::integer(2)));
+    assert_eq!(xml.body.len(), 2);
+    assert_eq!(xml.kind.body_range(), Some(29..40));
+    assert_eq!(xml.modify_name("tip".to_string()), (1..5, Some(42..46)));
+}
+
+#[test]
+fn self_close() {
+    let parser = NotedownParser::default();
+    let xml = find_xml(&parser.parse("see <img src=\"a.png\"/> here").unwrap()).unwrap();
+    assert_eq!(xml.cmd, "img");
+    assert_eq!(xml.name_ranges(), (5..8, None));
+    assert_eq!(xml.options.kvs.get("src"), Some(Value::string("a.png")));
+}
+
+#[test]
+fn mismatched_close() {
+    let parser = NotedownParser::default();
+    let xml = find_xml(&parser.parse("<a>x</b>").unwrap()).unwrap();
+    let errors = parser.take_errors();
+    assert_eq!(xml.cmd, "a");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].range, Some(4..8));
+}
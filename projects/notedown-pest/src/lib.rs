@@ -4,5 +4,6 @@ pub use note_down::{NoteDownParser, Rule};
 pub use pest::{
     error::Error,
     iterators::{Pair, Pairs},
+    pratt_parser::{Assoc, Op, PrattParser},
     Parser, Span,
 };
@@ -0,0 +1,124 @@
+use crate::{CommandDefinition, CommandParameter, CommandRegistry};
+use std::{collections::VecDeque, lazy::SyncLazy};
+
+/// Documentation of a command in the markdown files
+/// ```md
+/// # cmd
+/// short description
+/// @param key: type = default - description
+/// long description
+/// ```
+#[derive(Debug)]
+pub struct DocumentString {
+    pub cmd: String,
+    pub short: String,
+    pub long: String,
+    /// `@param key: type = default - description`
+    pub params: Vec<String>,
+}
+
+impl DocumentString {
+    pub fn new(cmd: &str, short: &str, long: &str, params: Vec<String>) -> DocumentString {
+        Self { cmd: String::from(cmd.trim()), short: String::from(short.trim()), long: String::from(long.trim()), params }
+    }
+    pub fn as_definition(&self) -> CommandDefinition {
+        let doc = format!("{}\n\n{}", self.short, self.long);
+        let mut out = CommandDefinition::new(&self.cmd).with_documentation(doc.trim());
+        out.parameters = self.params.iter().map(|param| parse_param(param)).collect();
+        return out;
+    }
+}
+
+/// `key: type = default - description`
+fn parse_param(input: &str) -> CommandParameter {
+    let (head, doc) = input.split_once(" - ").unwrap_or((input, ""));
+    let (head, default) = match head.split_once('=') {
+        Some((head, default)) => (head, Some(default.trim())),
+        None => (head, None),
+    };
+    let (name, kind) = head.split_once(':').unwrap_or((head, "any"));
+    let param = CommandParameter::new(name.trim(), kind.trim()).with_documentation(doc.trim());
+    match default {
+        Some(s) => param.with_default(s),
+        None => param,
+    }
+}
+
+fn load_md_doc(input: &str) -> Vec<DocumentString> {
+    let mut out = VecDeque::new();
+    let mut cmd = "";
+    let mut short = "";
+    let mut long = String::new();
+    let mut params = vec![];
+    let mut lines = input.lines();
+    while let Some(line) = lines.next() {
+        if line.starts_with("# ") {
+            out.push_back(DocumentString::new(cmd, short, &long, params));
+            cmd = &line[2..line.len()];
+            short = lines.next().unwrap();
+            long = String::new();
+            params = vec![]
+        }
+        else if let Some(param) = line.strip_prefix("@param ") {
+            params.push(param.trim().to_string())
+        }
+        else {
+            long.push_str(line);
+            long.push('\n');
+        }
+    }
+    out.push_back(DocumentString::new(cmd, short, &long, params));
+    out.pop_front();
+    return Vec::from(out);
+}
+
+/// Documentation of the `\cmd` commands
+pub static COMMAND_DOCUMENTS: SyncLazy<Vec<DocumentString>> = SyncLazy::new(|| load_md_doc(include_str!("command.md")));
+
+/// Documentation of the `<cmd></cmd>` components
+pub static OPEN_CLOSE_DOCUMENTS: SyncLazy<Vec<DocumentString>> = SyncLazy::new(|| load_md_doc(include_str!("open_close.md")));
+
+/// Documentation of the `<cmd/>` components
+pub static SELF_CLOSE_DOCUMENTS: SyncLazy<Vec<DocumentString>> = SyncLazy::new(|| load_md_doc(include_str!("self_close.md")));
+
+/// Commands in the `\cmd` form, the built-in commands keep their fenced form
+pub static COMMAND_REGISTRY: SyncLazy<CommandRegistry> = SyncLazy::new(|| {
+    let mut registry = CommandRegistry::builtin();
+    for doc in COMMAND_DOCUMENTS.iter() {
+        let mut definition = doc.as_definition();
+        definition.escaped = registry.is_escaped(&definition.name);
+        registry.register(definition);
+    }
+    registry
+});
+
+/// Commands in the `<cmd>` form
+pub static COMPONENT_REGISTRY: SyncLazy<CommandRegistry> = SyncLazy::new(|| {
+    let mut registry = CommandRegistry::default();
+    for doc in OPEN_CLOSE_DOCUMENTS.iter().chain(SELF_CLOSE_DOCUMENTS.iter()) {
+        registry.register(doc.as_definition());
+    }
+    registry
+});
+
+/// Find the definition of `\cmd` or `<cmd>`
+pub fn get_definition(name: &str, xml: bool) -> Option<&'static CommandDefinition> {
+    match xml {
+        true => COMPONENT_REGISTRY.get(name).or_else(|| COMMAND_REGISTRY.get(name)),
+        false => COMMAND_REGISTRY.get(name),
+    }
+}
+
+#[test]
+fn check_yaml() {
+    println!("{:#?}", *COMMAND_DOCUMENTS);
+    println!("{:#?}", *OPEN_CLOSE_DOCUMENTS);
+    println!("{:#?}", *SELF_CLOSE_DOCUMENTS);
+}
+
+#[test]
+fn builtin_registry() {
+    assert!(COMMAND_REGISTRY.is_escaped("comment"));
+    assert!(COMMAND_REGISTRY.get("comment").unwrap().documentation.contains("block mode"));
+    assert!(!COMMAND_REGISTRY.is_escaped("img"));
+}
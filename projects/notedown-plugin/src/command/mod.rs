@@ -1,10 +1,156 @@
-use notedown_ast::ASTNode;
+use notedown_ast::value::ValueType;
+use std::collections::{btree_map::Values, BTreeMap};
 
+/// Definition of a command which can be used in notedown
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct CommandDefinition {
-    name: String,
+    /// `\cmd`, `<cmd/>` or ```` ```cmd ````
+    pub name: String,
+    /// Accept the fenced block form, the body is kept as raw text
+    /// ````note
+    /// ```cmd(key = value)
+    /// raw text
+    /// ```
+    /// ````
+    pub escaped: bool,
+    /// Markdown shown in the editor
+    pub documentation: String,
+    /// Arguments accepted in `(key = value)`
+    pub parameters: Vec<CommandParameter>,
 }
 
-#[test]
-fn test() {
-    let _ = CommandDefinition { name: "sort".to_string() };
+/// Argument of a command
+/// ```note
+/// \cmd(key = value)
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CommandParameter {
+    /// Key of the argument
+    pub name: String,
+    /// Type hint, e.g. `string`, or the choices like `"left" | "right"`
+    pub kind: String,
+    /// Value used when the argument is missing
+    pub default: Option<String>,
+    /// Markdown shown in the editor
+    pub documentation: String,
+}
+
+/// All commands known by the parser
+#[derive(Clone, Debug, Default)]
+pub struct CommandRegistry {
+    inner: BTreeMap<String, CommandDefinition>,
+}
+
+impl CommandDefinition {
+    #[inline]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into(), ..Default::default() }
+    }
+    #[inline]
+    pub fn escaped(name: impl Into<String>) -> Self {
+        Self { name: name.into(), escaped: true, ..Default::default() }
+    }
+    #[inline]
+    pub fn with_documentation(mut self, documentation: impl Into<String>) -> Self {
+        self.documentation = documentation.into();
+        return self;
+    }
+    #[inline]
+    pub fn with_parameter(mut self, parameter: CommandParameter) -> Self {
+        self.parameters.push(parameter);
+        return self;
+    }
+    /// ```note
+    /// \cmd(key: type = default)
+    /// ```
+    pub fn signature(&self) -> String {
+        let parameters: Vec<_> = self.parameters.iter().map(|p| p.signature()).collect();
+        format!("\\{}({})", self.name, parameters.join(", "))
+    }
+}
+
+impl CommandParameter {
+    #[inline]
+    pub fn new(name: impl Into<String>, kind: impl Into<String>) -> Self {
+        Self { name: name.into(), kind: kind.into(), ..Default::default() }
+    }
+    #[inline]
+    pub fn with_default(mut self, default: impl Into<String>) -> Self {
+        self.default = Some(default.into());
+        return self;
+    }
+    #[inline]
+    pub fn with_documentation(mut self, documentation: impl Into<String>) -> Self {
+        self.documentation = documentation.into();
+        return self;
+    }
+    /// `key: type = default`
+    pub fn signature(&self) -> String {
+        match &self.default {
+            Some(default) => format!("{}: {} = {}", self.name, self.kind, default),
+            None => format!("{}: {}", self.name, self.kind),
+        }
+    }
+    /// Type of the value if the type hint is a primitive type
+    pub fn value_type(&self) -> Option<ValueType> {
+        let t = match self.kind.to_ascii_lowercase().as_str() {
+            "null" => ValueType::Null,
+            "bool" | "boolean" => ValueType::Boolean,
+            "int" | "integer" => ValueType::Integer,
+            "decimal" | "number" => ValueType::Decimal,
+            "string" => ValueType::String,
+            _ => return None,
+        };
+        Some(t)
+    }
+    /// All possible values if the type is an enumeration
+    /// ```note
+    /// key: "left" | "right"
+    /// ```
+    pub fn values(&self) -> Vec<String> {
+        if self.kind == "bool" {
+            return vec![String::from("true"), String::from("false")];
+        }
+        let choices: Vec<_> = self.kind.split('|').map(|s| s.trim()).collect();
+        match choices.iter().all(|s| s.len() >= 2 && s.starts_with('"') && s.ends_with('"')) {
+            true => choices.into_iter().map(String::from).collect(),
+            false => vec![],
+        }
+    }
+}
+
+impl CommandRegistry {
+    /// Commands handled by notedown itself, used by [`NotedownParser`](https://docs.rs/notedown_parser) by default
+    /// ````note
+    /// ```comment
+    /// removed from the rendering result
+    /// ```
+    /// ````
+    pub fn builtin() -> Self {
+        let mut out = Self::default();
+        out.register(CommandDefinition::escaped("comment").with_documentation("Remove from rendering result"));
+        return out;
+    }
+    /// Register a command, returns the old definition with the same name
+    #[inline]
+    pub fn register(&mut self, command: CommandDefinition) -> Option<CommandDefinition> {
+        self.inner.insert(command.name.to_owned(), command)
+    }
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&CommandDefinition> {
+        self.inner.get(name)
+    }
+    #[inline]
+    pub fn contains(&self, name: &str) -> bool {
+        self.inner.contains_key(name)
+    }
+    /// Check if the fenced block ```` ```name ```` should be parsed as a command
+    #[inline]
+    pub fn is_escaped(&self, name: &str) -> bool {
+        self.inner.get(name).map(|f| f.escaped).unwrap_or(false)
+    }
+    #[inline]
+    pub fn iter(&self) -> Values<'_, String, CommandDefinition> {
+        self.inner.values()
+    }
 }
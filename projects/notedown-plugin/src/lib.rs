@@ -1 +1,19 @@
+#![feature(once_cell)]
+
+mod builtin;
 mod command;
+
+pub use self::{
+    builtin::{get_definition, DocumentString, COMMAND_DOCUMENTS, COMMAND_REGISTRY, COMPONENT_REGISTRY, OPEN_CLOSE_DOCUMENTS, SELF_CLOSE_DOCUMENTS},
+    command::{CommandDefinition, CommandParameter, CommandRegistry},
+};
+
+#[test]
+fn test() {
+    let _ = CommandDefinition::new("sort");
+    let img = CommandDefinition::new("img").with_parameter(CommandParameter::new("src", "string")).with_parameter(CommandParameter::new("layout", "string").with_default("\"center\""));
+    assert_eq!(img.signature(), "\\img(src: string, layout: string = \"center\")");
+    assert_eq!(CommandParameter::new("layout", "\"left\" | \"right\"").values(), vec!["\"left\"", "\"right\""]);
+    assert!(CommandParameter::new("src", "string").values().is_empty());
+    assert_eq!(CommandParameter::new("max_depth", "integer").value_type().map(|t| t.to_string()), Some(String::from("Integer")));
+}
@@ -0,0 +1,235 @@
+use super::*;
+use notedown_ast::{
+    command::Command,
+    nodes::{IncludeNode, MaybeRanged},
+    traits::IntoASTNode,
+    ASTKind, ASTNodes,
+};
+use std::collections::{HashSet, VecDeque};
+
+/// Config of include expansion
+pub struct IncludeConfig {
+    /// Nested include deeper than this will be reported as error
+    pub max_depth: usize,
+    /// Header shift if the command does not specify `shift`
+    pub shift: i8,
+}
+
+impl Default for IncludeConfig {
+    fn default() -> Self {
+        Self { max_depth: 32, shift: 0 }
+    }
+}
+
+/// ```note
+/// \include[./path.note#header-id](shift = 1)
+/// ```
+struct IncludeTarget {
+    url: Url,
+    section: Option<String>,
+    shift: i8,
+}
+
+type IncludeKey = (Url, Option<String>);
+
+impl IncludeTarget {
+    fn is_include(cmd: &Command) -> bool {
+        cmd.is("include") || cmd.is("import")
+    }
+    fn resolve(cmd: &Command, base: &Url, config: &IncludeConfig) -> Result<Self> {
+        let path = match cmd.get_pattern().and_then(|f| f.get(0)) {
+            Some(s) => s.value.trim(),
+            None => return Err(NoteError::syntax_error("Missing include path, try `\\include[./path.note]`")),
+        };
+        let (path, section) = match path.split_once('#') {
+            Some((path, id)) => (path.trim(), Some(id.trim().to_string())),
+            None => (path, None),
+        };
+        let url = match path.is_empty() {
+            true => base.to_owned(),
+            false => match base.join(path) {
+                Ok(o) => o,
+                Err(e) => return Err(NoteError::runtime_error(format!("Can not resolve include path `{}`: {}", path, e))),
+            },
+        };
+        let shift = match cmd.get_options().and_then(|f| f.kvs.get("shift")) {
+            Some(s) => match i8::try_from(s) {
+                Ok(o) => o,
+                Err(_) => return Err(NoteError::type_mismatch("Option `shift` of `\\include` must be a small integer")),
+            },
+            None => config.shift,
+        };
+        Ok(Self { url, section, shift })
+    }
+    fn key(&self) -> IncludeKey {
+        (self.url.to_owned(), self.section.to_owned())
+    }
+}
+
+impl VMFileSystem {
+    /// Load and parse all files included by `url` transitively, return errors in the included files
+    pub async fn load_includes(&mut self, url: &Url, parser: &Parser, config: &IncludeConfig) -> Vec<NoteError> {
+        let mut errors = vec![];
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::from(vec![url.to_owned()]);
+        while let Some(next) = queue.pop_front() {
+            if !visited.insert(next.to_owned()) {
+                continue;
+            }
+            if !self.is_loaded(&next) {
+                // fail to load will be reported by the include command
+                if self.load_url(&next).await.is_err() {
+                    continue;
+                }
+                if let Err(mut e) = self.update_ast(next.to_owned(), parser).await {
+                    e.set_url(next.to_owned());
+                    errors.push(e);
+                }
+            }
+            let ast = match self.get_ast(&next) {
+                Some(s) => s,
+                None => continue,
+            };
+            let mut commands = vec![];
+            collect_includes(&ast, &mut commands);
+            for cmd in commands {
+                if let Ok(target) = IncludeTarget::resolve(cmd, &next, config) {
+                    queue.push_back(target.url)
+                }
+            }
+        }
+        return errors;
+    }
+    /// Replace all include commands in the ast with the content of the target file.
+    ///
+    /// Files must be loaded by [`VMFileSystem::load_includes`] first.
+    pub fn expand_includes(&self, url: &Url, ast: &mut ASTNode, config: &IncludeConfig) -> Vec<NoteError> {
+        let mut errors = vec![];
+        let mut stack = vec![(url.to_owned(), None)];
+        self.expand_node(ast, url, &mut stack, config, &mut errors);
+        return errors;
+    }
+    fn expand_node(&self, node: &mut ASTNode, base: &Url, stack: &mut Vec<IncludeKey>, config: &IncludeConfig, errors: &mut Vec<NoteError>) {
+        if let ASTKind::Command(cmd) = &node.value {
+            if IncludeTarget::is_include(cmd) {
+                match self.expand_include(cmd, &node.range, base, stack, config, errors) {
+                    Ok(o) => *node = o,
+                    Err(mut e) => {
+                        e.set_url(base.to_owned());
+                        e.range = node.range.to_owned();
+                        errors.push(e)
+                    }
+                }
+                return;
+            }
+        }
+        for child in children_mut(&mut node.value) {
+            self.expand_node(child, base, stack, config, errors)
+        }
+    }
+    fn expand_include(&self, cmd: &Command, range: &MaybeRanged, base: &Url, stack: &mut Vec<IncludeKey>, config: &IncludeConfig, errors: &mut Vec<NoteError>) -> Result<ASTNode> {
+        let target = IncludeTarget::resolve(cmd, base, config)?;
+        let key = target.key();
+        if stack.contains(&key) {
+            let chain = stack.iter().chain(std::iter::once(&key)).map(show_key).collect::<Vec<_>>();
+            return Err(NoteError::runtime_error(format!("Include cycle detected: {}", chain.join(" -> "))));
+        }
+        if stack.len() > config.max_depth {
+            return Err(NoteError::runtime_error(format!("Include depth exceeds the limit {}", config.max_depth)));
+        }
+        let root = match self.get_ast(&target.url) {
+            Some(s) => s,
+            None => return Err(NoteError::runtime_error(format!("Can not load the included file `{}`", target.url))),
+        };
+        let mut children = match root.value {
+            ASTKind::Statements(v) => v,
+            _ => vec![root],
+        };
+        if let Some(id) = &target.section {
+            children = match select_section(children, id) {
+                Some(s) => s,
+                None => return Err(NoteError::runtime_error(format!("Header `#{}` not found in `{}`", id, target.url))),
+            }
+        }
+        stack.push(key);
+        for child in children.iter_mut() {
+            self.expand_node(child, &target.url, stack, config, errors)
+        }
+        stack.pop();
+        shift_headers(&mut children, target.shift);
+        let mut node = IncludeNode::new(target.url, target.section, children);
+        node.shift = target.shift;
+        Ok(node.into_node(range.to_owned()))
+    }
+}
+
+fn show_key(key: &IncludeKey) -> String {
+    match &key.1 {
+        Some(id) => format!("{}#{}", key.0, id),
+        None => key.0.to_string(),
+    }
+}
+
+/// Take the header with given id and all nodes until the next header of same or higher level
+fn select_section(nodes: ASTNodes, id: &str) -> Option<ASTNodes> {
+    let mut out = vec![];
+    let mut level = None;
+    for node in nodes {
+        if let ASTKind::Header(header) = &node.value {
+            match level {
+                Some(l) if header.level <= l => break,
+                None if header.id.as_deref() == Some(id) => level = Some(header.level),
+                _ => {}
+            }
+        }
+        if level.is_some() {
+            out.push(node)
+        }
+    }
+    level.map(|_| out)
+}
+
+fn shift_headers(nodes: &mut ASTNodes, delta: i8) {
+    if delta == 0 {
+        return;
+    }
+    for node in nodes {
+        match &mut node.value {
+            ASTKind::Header(header) => {
+                header.shift_level(delta);
+            }
+            ASTKind::IncludeNode(include) => shift_headers(&mut include.children, delta),
+            _ => {}
+        }
+    }
+}
+
+fn collect_includes<'a>(node: &'a ASTNode, out: &mut Vec<&'a Command>) {
+    match &node.value {
+        ASTKind::Command(cmd) if IncludeTarget::is_include(cmd) => out.push(cmd),
+        ASTKind::Statements(v) | ASTKind::Paragraph(v) => v.iter().for_each(|f| collect_includes(f, out)),
+        ASTKind::QuoteNode(v) => v.head.iter().chain(v.body.iter()).for_each(|f| collect_includes(f, out)),
+        ASTKind::ListView(v) => v.children.iter().flat_map(|f| f.rest.iter()).for_each(|f| collect_includes(f, out)),
+        ASTKind::Command(cmd) => {
+            if let Command::XML(xml) = &**cmd {
+                xml.body.iter().for_each(|f| collect_includes(f, out))
+            }
+        }
+        _ => {}
+    }
+}
+
+fn children_mut(kind: &mut ASTKind) -> Vec<&mut ASTNode> {
+    match kind {
+        ASTKind::Statements(v) | ASTKind::Paragraph(v) => v.iter_mut().collect(),
+        ASTKind::QuoteNode(v) => v.head.iter_mut().chain(v.body.iter_mut()).collect(),
+        ASTKind::ListView(v) => v.children.iter_mut().flat_map(|f| f.rest.iter_mut()).collect(),
+        ASTKind::Command(cmd) => match &mut **cmd {
+            Command::XML(xml) => xml.body.iter_mut().collect(),
+            _ => vec![],
+        },
+        // already expanded, ranges of the children belong to another file
+        ASTKind::IncludeNode(_) => vec![],
+        _ => vec![],
+    }
+}
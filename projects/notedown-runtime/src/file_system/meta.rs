@@ -7,6 +7,7 @@ use notedown_ast::{
     },
 };
 
+#[derive(Default)]
 pub struct FileMeta {
     errors: Vec<NoteError>,
     toc: TocNode,
@@ -25,6 +26,11 @@ impl FileMeta {
     pub fn push_lsp_diagnostics(&mut self, e: NoteError) {
         self.errors.push(e)
     }
+    /// errors found in the last parsing
+    #[inline]
+    pub fn errors(&self) -> &[NoteError] {
+        &self.errors
+    }
     #[inline]
     pub fn as_lsp_diagnostics(&self, index: &TextIndex) -> Vec<Diagnostic> {
         self.errors.iter().map(|f| f.build_diagnostic(index)).collect()
@@ -36,6 +42,6 @@ impl FileMeta {
     }
     #[inline]
     pub fn as_lsp_toc(&self, text: &TextIndex) -> DocumentSymbolResponse {
-        DocumentSymbolResponse::Nested(vec![self.toc.as_document_symbol(text)])
+        DocumentSymbolResponse::Nested(self.toc.children.iter().map(|node| node.as_document_symbol(text)).collect())
     }
 }
@@ -1,15 +1,18 @@
+mod include;
 mod meta;
 mod state;
 
-pub use self::{meta::FileMeta, state::FileState};
+pub use self::{include::IncludeConfig, meta::FileMeta, state::FileState};
 
+use crate::WorkspaceIndex;
 use async_std::{fs::File, io::ReadExt};
 use globset::{Glob, GlobSet, GlobSetBuilder};
 use notedown_ast::{
     utils::{lsp_types::Url, DashMap},
     ASTNode, NoteError, Result,
 };
-use std::path::Path;
+use std::{ops::Deref, path::Path};
+use walkdir::WalkDir;
 
 pub type Parser = fn(&str, &mut FileMeta) -> Result<ASTNode>;
 
@@ -50,32 +53,91 @@ impl VMFileSystem {
     /// add a local file path to resolve
     #[inline]
     pub async fn load_path(&mut self, path: &Path) -> Result<()> {
-        let mut file = File::open(path).await?;
-        let mut contents = Vec::new();
-        file.read_to_end(&mut contents).await?;
-        let _ = Url::from_file_path(path)?;
-        todo!()
+        let url = Url::from_file_path(path)?;
+        self.load_url(&url).await
     }
     #[inline]
     /// add a file url to resolve
     pub async fn load_url(&mut self, url: &Url) -> Result<()> {
         let mut file = File::open(url.to_file_path()?).await?;
-        let mut contents = Vec::new();
-        file.read_to_end(&mut contents).await?;
-
-        todo!()
+        let mut contents = String::new();
+        file.read_to_string(&mut contents).await?;
+        self.file_cache.insert(url.to_owned(), FileState::new(&contents));
+        Ok(())
+    }
+    /// add a file with the text, which may be unsaved in the editor
+    #[inline]
+    pub fn load_text(&mut self, url: Url, text: &str) {
+        self.file_cache.insert(url, FileState::new(text));
     }
+    /// check if the file is already in the cache
+    #[inline]
+    pub fn is_loaded(&self, url: &Url) -> bool {
+        self.file_cache.contains_key(url)
+    }
+    /// get a copy of the parsed ast
+    #[inline]
+    pub fn get_ast(&self, url: &Url) -> Option<ASTNode> {
+        self.file_cache.get(url).map(|f| f.get_ast().to_owned())
+    }
+    /// get a copy of the text
+    #[inline]
+    pub fn get_text(&self, url: &Url) -> Option<String> {
+        self.file_cache.get(url).map(|f| f.get_text())
+    }
+    /// borrow the cached file, the cache is locked until it is dropped
+    #[inline]
+    pub fn get_file(&self, url: &Url) -> Option<impl Deref<Target = FileState> + '_> {
+        self.file_cache.get(url)
+    }
+    /// all the files in the cache, sorted by url
+    pub fn get_urls(&self) -> Vec<Url> {
+        let mut urls: Vec<Url> = self.file_cache.iter().map(|f| f.key().to_owned()).collect();
+        urls.sort();
+        return urls;
+    }
+    /// index all the parsed files in the cache
+    pub fn workspace_index(&self) -> WorkspaceIndex {
+        let mut index = WorkspaceIndex::default();
+        for file in self.file_cache.iter() {
+            index.update(file.key(), file.value().get_ast())
+        }
+        return index;
+    }
+    /// load the files matching the patterns, one glob per line, lines starting with `!` exclude the matches
     #[inline]
     pub async fn load_pattern_text(&mut self, patterns: &str) -> Result<()> {
-        let mut builder = GlobSetBuilder::new();
-        for row in patterns.lines() {
-            builder.add(Glob::new(row)?);
+        let mut include = GlobSetBuilder::new();
+        let mut exclude = GlobSetBuilder::new();
+        for row in patterns.lines().map(|s| s.trim()).filter(|s| !s.is_empty()) {
+            match row.strip_prefix('!') {
+                Some(s) => exclude.add(Glob::new(s.trim())?),
+                None => include.add(Glob::new(row)?),
+            };
         }
-        Ok(self.load_pattern(&builder.build()?).await)
+        self.load_pattern_excluding(&include.build()?, &exclude.build()?).await
     }
     #[inline]
-    pub async fn load_pattern(&mut self, patterns: &GlobSet) {
-        let _ = patterns;
-        todo!()
+    pub async fn load_pattern(&mut self, patterns: &GlobSet) -> Result<()> {
+        self.load_pattern_excluding(patterns, &GlobSet::empty()).await
+    }
+    /// load the files under the workspace root, the patterns match the paths relative to the root
+    ///
+    /// Hidden files and directories are skipped, excluded directories are not walked into.
+    pub async fn load_pattern_excluding(&mut self, include: &GlobSet, exclude: &GlobSet) -> Result<()> {
+        let root = self.workspace_root.to_file_path()?;
+        let walker = WalkDir::new(&root).sort_by_file_name().into_iter().filter_entry(|entry| {
+            let relative = entry.path().strip_prefix(&root).unwrap_or(entry.path());
+            let hidden = entry.depth() > 0 && entry.file_name().to_string_lossy().starts_with('.');
+            !hidden && !exclude.is_match(relative)
+        });
+        for entry in walker {
+            let entry = entry.map_err(std::io::Error::from)?;
+            let relative = entry.path().strip_prefix(&root).unwrap_or(entry.path());
+            if entry.file_type().is_file() && include.is_match(relative) {
+                self.load_path(entry.path()).await?
+            }
+        }
+        Ok(())
     }
 }
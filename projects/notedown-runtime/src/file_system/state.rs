@@ -1,5 +1,9 @@
 use super::*;
 use notedown_ast::utils::{lsp_types::Url, Rope};
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
 
 pub struct FileState {
     /// used to check weather the file needs re-parse
@@ -16,6 +20,24 @@ impl PartialEq for FileState {
 }
 
 impl FileState {
+    #[inline]
+    pub fn new(text: &str) -> Self {
+        let mut hasher = DefaultHasher::new();
+        text.hash(&mut hasher);
+        Self { fingerprint: hasher.finish() as u128, text: Rope::from_str(text), ast: Default::default(), meta: Default::default() }
+    }
+    #[inline]
+    pub fn get_text(&self) -> String {
+        self.text.chars().collect()
+    }
+    #[inline]
+    pub fn get_ast(&self) -> &ASTNode {
+        &self.ast
+    }
+    #[inline]
+    pub fn get_meta(&self) -> &FileMeta {
+        &self.meta
+    }
     pub async fn load_file(&mut self, path: &Path) -> Result<()> {
         let mut file = File::open(path).await?;
         let mut contents = Vec::new();
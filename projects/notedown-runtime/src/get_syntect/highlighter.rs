@@ -0,0 +1,168 @@
+use super::{SYNTAX_SET, THEME_SET};
+use html_escape::{encode_double_quoted_attribute, encode_text};
+use notedown_ast::{nodes::CodeNode, NoteError, Result};
+use std::{fmt::Write, ops::RangeInclusive};
+use syntect::{
+    easy::HighlightLines,
+    highlighting::{Color, Theme, ThemeSet},
+    html::{css_for_theme_with_class_style, line_tokens_to_classed_spans, styled_line_to_highlighted_html, ClassStyle, IncludeBackground},
+    parsing::{ParseState, Scope, ScopeStack, SyntaxReference, SyntaxSet},
+    util::LinesWithEndings,
+};
+
+const CLASS_STYLE: ClassStyle = ClassStyle::SpacedPrefixed { prefix: "syn-" };
+
+/// How the highlighted spans are styled
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum HighlightStyle {
+    /// `<span class="syn-keyword">`, colors come from [`SyntaxHighlighter::css`]
+    Classed,
+    /// `<span style="color:#000000;">`, colors come from the theme
+    Inline {
+        /// Name of the theme
+        theme: String,
+    },
+}
+
+/// Render [`CodeNode`] to highlighted html
+///
+/// ```html
+/// <pre class="code" data-lang="rust"><code><span class="line highlight"><span class="line-number">1</span>...</span>
+/// </code></pre>
+/// ```
+pub struct SyntaxHighlighter {
+    pub style: HighlightStyle,
+    syntaxes: &'static SyntaxSet,
+    themes: &'static ThemeSet,
+}
+
+impl Default for HighlightStyle {
+    fn default() -> Self {
+        Self::Classed
+    }
+}
+
+impl Default for SyntaxHighlighter {
+    fn default() -> Self {
+        Self::new(HighlightStyle::default())
+    }
+}
+
+impl SyntaxHighlighter {
+    /// Create a highlighter with the cached syntax set and theme set
+    #[inline]
+    pub fn new(style: HighlightStyle) -> Self {
+        Self { style, syntaxes: &SYNTAX_SET, themes: &THEME_SET }
+    }
+    /// Stylesheet for [`HighlightStyle::Classed`] output
+    pub fn css(&self, theme: &str) -> Result<String> {
+        let css = css_for_theme_with_class_style(self.get_theme(theme)?, CLASS_STYLE)?;
+        return Ok(css);
+    }
+    /// Render the code node, unknown languages are rendered as plain text
+    pub fn render(&self, code: &CodeNode) -> Result<String> {
+        let syntax = self.find_syntax(code);
+        let lines = match &self.style {
+            HighlightStyle::Classed => self.classed_lines(&code.code, syntax)?,
+            HighlightStyle::Inline { theme } => self.inline_lines(&code.code, syntax, self.get_theme(theme)?)?,
+        };
+        let lang = encode_double_quoted_attribute(&code.language);
+        let mut out = String::new();
+        if code.inline {
+            write!(out, "<code class=\"code\" data-lang=\"{}\">{}</code>", lang, lines.join(" "))?;
+            return Ok(out);
+        }
+        match &self.style {
+            HighlightStyle::Classed => write!(out, "<pre class=\"code\" data-lang=\"{}\">", lang)?,
+            HighlightStyle::Inline { theme } => {
+                let bg = self.get_theme(theme)?.settings.background.unwrap_or(Color::WHITE);
+                write!(out, "<pre class=\"code\" data-lang=\"{}\" style=\"background-color:#{:02x}{:02x}{:02x};\">", lang, bg.r, bg.g, bg.b)?
+            }
+        }
+        if let Some(name) = &code.show_file_name {
+            write!(out, "<div class=\"file-name\">{}</div>", encode_text(name))?;
+        }
+        out.push_str("<code>");
+        for (index, line) in lines.iter().enumerate() {
+            let number = index + 1;
+            if in_ranges(&code.hide_lines, number) {
+                continue;
+            }
+            match in_ranges(&code.highlight_lines, number) {
+                true => out.push_str("<span class=\"line highlight\">"),
+                false => out.push_str("<span class=\"line\">"),
+            }
+            if let Some(start) = code.show_line_number {
+                write!(out, "<span class=\"line-number\">{}</span>", start + index)?;
+            }
+            out.push_str(line);
+            out.push_str("</span>\n");
+        }
+        out.push_str("</code></pre>");
+        return Ok(out);
+    }
+}
+
+impl SyntaxHighlighter {
+    fn find_syntax(&self, code: &CodeNode) -> &SyntaxReference {
+        let plain = self.syntaxes.find_syntax_plain_text();
+        if !code.highlight {
+            return plain;
+        }
+        self.syntaxes.find_syntax_by_token(&code.language).unwrap_or(plain)
+    }
+    fn get_theme(&self, name: &str) -> Result<&Theme> {
+        match self.themes.themes.get(name) {
+            Some(s) => Ok(s),
+            None => Err(NoteError::runtime_error(format!("Theme `{}` not found", name))),
+        }
+    }
+    /// Every line is parsed even if it's hidden, so that the parse state keeps going
+    fn classed_lines(&self, code: &str, syntax: &SyntaxReference) -> Result<Vec<String>> {
+        let mut state = ParseState::new(syntax);
+        let mut stack = ScopeStack::new();
+        let mut out = vec![];
+        for line in LinesWithEndings::from(code) {
+            let ops = state.parse_line(line, self.syntaxes).map_err(syntect::Error::from)?;
+            // reopen the scopes left by the previous line
+            let mut html = String::new();
+            for scope in stack.as_slice() {
+                write!(html, "<span class=\"{}\">", scope_classes(*scope))?;
+            }
+            let (spans, _) = line_tokens_to_classed_spans(line, &ops, CLASS_STYLE, &mut stack)?;
+            html.push_str(&spans);
+            remove_last_newline(&mut html);
+            html.push_str(&"</span>".repeat(stack.len()));
+            out.push(html)
+        }
+        Ok(out)
+    }
+    fn inline_lines(&self, code: &str, syntax: &SyntaxReference, theme: &Theme) -> Result<Vec<String>> {
+        let mut highlighter = HighlightLines::new(syntax, theme);
+        let mut out = vec![];
+        for line in LinesWithEndings::from(code) {
+            let regions = highlighter.highlight_line(line, self.syntaxes)?;
+            let mut html = styled_line_to_highlighted_html(&regions, IncludeBackground::No)?;
+            remove_last_newline(&mut html);
+            out.push(html)
+        }
+        Ok(out)
+    }
+}
+
+fn scope_classes(scope: Scope) -> String {
+    let atoms: Vec<_> = scope.build_string().split('.').map(|atom| format!("syn-{}", atom)).collect();
+    atoms.join(" ")
+}
+
+/// Line breaks are added by the line wrapper, the newline is always the last text in the line
+fn remove_last_newline(html: &mut String) {
+    if let Some(i) = html.rfind('\n') {
+        html.remove(i);
+    }
+}
+
+#[inline]
+fn in_ranges(ranges: &[RangeInclusive<usize>], line: usize) -> bool {
+    ranges.iter().any(|r| r.contains(&line))
+}
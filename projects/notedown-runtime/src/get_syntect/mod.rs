@@ -1,29 +1,82 @@
+mod highlighter;
+
+pub use self::highlighter::{HighlightStyle, SyntaxHighlighter};
 use crate::get_root_path;
-use notedown_ast::Result;
+use notedown_ast::{NoteError, Result};
 use self_update::Download;
-use std::{fs::File, io::Read, path::PathBuf};
-use syntect::{dumps::from_binary, parsing::SyntaxSet};
+use std::{fs::File, lazy::SyncLazy, path::PathBuf};
+use syntect::{dumps::from_dump_file, highlighting::ThemeSet, parsing::SyntaxSet};
+
+/// Compiled syntax set, base pack with additions, falls back to the builtin syntaxes
+pub static SYNTAX_SET: SyncLazy<SyntaxSet> = SyncLazy::new(|| match get_syntect_languages() {
+    Ok(o) => o,
+    Err(_) => SyntaxSet::load_defaults_newlines(),
+});
 
+/// Builtin themes with all themes under `$NOTEDOWN_ROOT/syntect/themes`
+pub static THEME_SET: SyncLazy<ThemeSet> = SyncLazy::new(|| match get_syntect_themes() {
+    Ok(o) => o,
+    Err(_) => ThemeSet::load_defaults(),
+});
+
+/// Load `$NOTEDOWN_ROOT/syntect/languages/base.pack`, a missing or broken pack is an error
 pub fn get_syntect_language_pack() -> Result<SyntaxSet> {
     let file = lang_pack_path()?;
-    let mut f = File::open(&file)?;
-    let cap = std::fs::metadata(&file)?.len();
-    let mut buffer = vec![0; cap as usize];
-    f.read(&mut buffer)?;
-    let s: SyntaxSet = from_binary(&buffer);
-    Ok(s)
+    match from_dump_file(&file) {
+        Ok(o) => Ok(o),
+        Err(e) => Err(NoteError::runtime_error(format!("Failed to load syntect pack {}: {}", file.display(), e))),
+    }
 }
 
-pub fn get_syntect_language_addition() -> Result<()> {
+/// Load all `.sublime-syntax` files under `$NOTEDOWN_ROOT/syntect/languages` on top of the base pack
+///
+/// The builtin syntaxes are used as the base if the pack is missing or broken.
+pub fn get_syntect_languages() -> Result<SyntaxSet> {
+    let base = get_syntect_language_pack().unwrap_or_else(|_| SyntaxSet::load_defaults_newlines());
+    let mut builder = base.into_builder();
+    let additions = get_syntect_language_addition()?;
+    if additions.is_dir() {
+        builder.add_from_folder(&additions, true)?;
+    }
+    Ok(builder.build())
+}
+
+pub fn get_syntect_language_addition() -> Result<PathBuf> {
     let root = get_root_path()?;
-    root.join("syntect").join("languages");
-    Ok(())
+    Ok(root.join("syntect").join("languages"))
 }
 
-pub fn get_syntect_themes() {}
+pub fn get_syntect_themes() -> Result<ThemeSet> {
+    let mut themes = ThemeSet::load_defaults();
+    let folder = get_root_path()?.join("syntect").join("themes");
+    if folder.is_dir() {
+        themes.add_from_folder(&folder)?;
+    }
+    Ok(themes)
+}
 
-pub fn download_syntect_prebuilt(_: &str) -> Result<()> {
-    todo!()
+/// Download the prebuilt language pack from `url` to `$NOTEDOWN_ROOT/syntect/languages/base.pack`
+///
+/// The pack is written to a temporary file first, a failed download leaves the old pack untouched.
+pub fn download_syntect_prebuilt(url: &str) -> Result<()> {
+    let file = lang_pack_path()?;
+    if let Some(dir) = file.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let temp = file.with_extension("pack.download");
+    let mut dest = File::create(&temp)?;
+    match Download::from_url(url).show_progress(false).download_to(&mut dest) {
+        Ok(_) => {
+            drop(dest);
+            std::fs::rename(&temp, &file)?;
+            Ok(())
+        }
+        Err(e) => {
+            drop(dest);
+            let _ = std::fs::remove_file(&temp);
+            Err(NoteError::runtime_error(format!("Failed to download syntect pack from {}: {}", url, e)))
+        }
+    }
 }
 
 #[inline]
@@ -1,8 +1,19 @@
+#![feature(once_cell)]
+
 mod file_system;
+mod get_env;
+mod get_syntect;
 mod plugin;
 mod vm;
+mod workspace;
 
 pub use self::{
-    file_system::{FileMeta, FileState, VMFileSystem},
-    vm::NoteVM,
+    file_system::{FileMeta, FileState, IncludeConfig, Parser, VMFileSystem},
+    get_env::{get_root_path, set_root_path, NOTEDOWN_ROOT},
+    get_syntect::{download_syntect_prebuilt, get_syntect_language_addition, get_syntect_language_pack, get_syntect_languages, get_syntect_themes, HighlightStyle, SyntaxHighlighter, SYNTAX_SET, THEME_SET},
+    vm::{
+        diagnostic::{check_commands, check_lints, flatten, Lint, LintConfig, LintDocument, LintFix, LintLevel, LintRule, Suppressions, LINT_RULES},
+        NoteVM,
+    },
+    workspace::{fuzzy_score, resolve_link, DocumentIndex, FootnoteEntry, HeaderEntry, LinkEntry, LinkTarget, SymbolEntry, SymbolEntryKind, WorkspaceIndex},
 };
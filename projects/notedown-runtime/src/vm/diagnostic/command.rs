@@ -0,0 +1,97 @@
+use super::*;
+use notedown_ast::{nodes::Literal, value::ValueType, Value};
+use notedown_plugin::{get_definition, CommandDefinition, CommandParameter};
+
+/// Unknown commands, unknown options and option values of the wrong type
+pub fn check_commands(nodes: &[&ASTNode]) -> Vec<Lint> {
+    let mut out = vec![];
+    for node in nodes {
+        let (cmd, range) = match (&node.value, &node.range) {
+            (ASTKind::Command(cmd), Some(range)) => (&**cmd, range),
+            _ => continue,
+        };
+        let xml = matches!(cmd, Command::XML(_));
+        match get_definition(cmd.command(), xml) {
+            // the options of `\set` are the names of the variables
+            Some(_) if cmd.is("set") => {}
+            Some(definition) => check_options(cmd, definition, &mut out),
+            // unknown tags are kept as html
+            None if xml => {}
+            None => {
+                let name = cmd.name_range(range).unwrap_or_else(|| range.to_owned());
+                let message = format!("Unknown command `{}`", cmd.command());
+                out.push(Lint::problem("unknown-command", DiagnosticSeverity::WARNING, name, message));
+            }
+        }
+    }
+    return out;
+}
+
+fn check_options(cmd: &Command, definition: &CommandDefinition, out: &mut Vec<Lint>) {
+    let options = match cmd.get_options() {
+        Some(s) => s,
+        None => return,
+    };
+    for (key, value) in options.kvs.iter_raw() {
+        match definition.parameters.iter().find(|p| p.name == key.value) {
+            Some(parameter) => check_value(parameter, value, out),
+            None => {
+                let message = format!("Unknown option `{}` of `{}`", key.value, definition.name);
+                push_ranged(out, &key.range, DiagnosticSeverity::WARNING, "unknown-option", message)
+            }
+        }
+    }
+    // positional arguments fill the parameters which are not named
+    let mut unnamed = definition.parameters.iter().filter(|p| options.kvs.get(&p.name).is_none());
+    for (index, _) in options.args.iter_sparse() {
+        let value = match options.args.get_literal(index) {
+            Some(s) => s,
+            None => continue,
+        };
+        match unnamed.next() {
+            Some(parameter) => check_value(parameter, value, out),
+            None => {
+                let message = format!("Too many arguments of `{}`", definition.name);
+                push_ranged(out, &value.range, DiagnosticSeverity::WARNING, "unknown-option", message)
+            }
+        }
+    }
+}
+
+fn check_value(parameter: &CommandParameter, value: &Literal<Value>, out: &mut Vec<Lint>) {
+    let message = match parameter.value_type() {
+        Some(expected) => {
+            let found = value.value.get_type();
+            if is_assignable(&expected, &found) {
+                return;
+            }
+            format!("Expect `{}` for `{}`, found `{}`", expected, parameter.name, found)
+        }
+        None => {
+            let choices = parameter.values();
+            let found = match &value.value {
+                Value::String(s) => format!("{:?}", s),
+                v => v.to_string(),
+            };
+            if choices.is_empty() || choices.contains(&found) {
+                return;
+            }
+            format!("Expect one of {} for `{}`, found {}", choices.join(", "), parameter.name, found)
+        }
+    };
+    push_ranged(out, &value.range, DiagnosticSeverity::ERROR, "type-mismatch", message)
+}
+
+fn push_ranged(out: &mut Vec<Lint>, range: &Option<Span<usize>>, severity: DiagnosticSeverity, code: &'static str, message: String) {
+    if let Some(range) = range {
+        out.push(Lint::problem(code, severity, range.to_owned(), message));
+    }
+}
+
+/// Integers can be used as decimals
+fn is_assignable(expected: &ValueType, found: &ValueType) -> bool {
+    match (expected, found) {
+        (ValueType::Decimal, ValueType::Integer) => true,
+        _ => expected == found,
+    }
+}
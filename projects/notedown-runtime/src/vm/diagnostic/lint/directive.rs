@@ -0,0 +1,122 @@
+use super::*;
+
+/// Turn off the lints by commands, all rules if no id is given
+/// ```note
+/// \lint[off]: repeated-word
+/// \lint[on]: repeated-word
+/// \lint[next]
+/// \lint[file]: long-paragraph
+/// \comment: lint off repeated-word
+/// ```
+pub struct Suppressions {
+    directives: Vec<Directive>,
+    problems: Vec<(Span<usize>, String)>,
+}
+
+struct Directive {
+    mode: DirectiveMode,
+    rules: Vec<String>,
+    range: Span<usize>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum DirectiveMode {
+    /// Until `\lint[on]` or the end of the file
+    Off,
+    On,
+    /// The block containing the command, or the next block if the command is a block
+    Next(Option<Span<usize>>),
+    /// The whole file
+    File,
+}
+
+impl Suppressions {
+    pub fn new(document: &LintDocument, nodes: &[&ASTNode]) -> Self {
+        let mut out = Self { directives: vec![], problems: vec![] };
+        for node in nodes {
+            let (cmd, range) = match (&node.value, &node.range) {
+                (ASTKind::Command(cmd), Some(range)) => (&**cmd, range),
+                _ => continue,
+            };
+            let words = match directive_words(cmd) {
+                Some(s) => s,
+                None => continue,
+            };
+            let mut words = words.into_iter();
+            let mode = match words.next().as_deref() {
+                Some("off") => DirectiveMode::Off,
+                Some("on") => DirectiveMode::On,
+                Some("next") => DirectiveMode::Next(next_block(document, range)),
+                Some("file") => DirectiveMode::File,
+                Some(other) => {
+                    out.problems.push((range.to_owned(), format!("Unknown lint mode `{}`, expect `off`, `on`, `next` or `file`", other)));
+                    continue;
+                }
+                None => {
+                    out.problems.push((range.to_owned(), String::from("Missing lint mode, try `\\lint[off]`")));
+                    continue;
+                }
+            };
+            let rules: Vec<String> = words.collect();
+            for rule in rules.iter().filter(|id| !LINT_RULES.iter().any(|r| r.id() == id.as_str())) {
+                out.problems.push((range.to_owned(), format!("Unknown lint rule `{}`", rule)));
+            }
+            out.directives.push(Directive { mode, rules, range: range.to_owned() })
+        }
+        return out;
+    }
+    /// Wrong directives
+    pub fn problems(&self) -> &[(Span<usize>, String)] {
+        &self.problems
+    }
+    pub fn is_suppressed(&self, rule: &str, offset: usize) -> bool {
+        let applied: Vec<_> = self.directives.iter().filter(|d| d.rules.is_empty() || d.rules.iter().any(|r| r == rule)).collect();
+        for directive in &applied {
+            match &directive.mode {
+                DirectiveMode::File => return true,
+                DirectiveMode::Next(Some(block)) if block.contains(&offset) => return true,
+                _ => {}
+            }
+        }
+        // the last switch before the offset wins
+        let last = applied.iter().filter(|d| d.range.end <= offset).rfind(|d| matches!(d.mode, DirectiveMode::Off | DirectiveMode::On));
+        matches!(last.map(|d| &d.mode), Some(DirectiveMode::Off))
+    }
+}
+
+/// `\lint[mode]: rules` or `\comment: lint mode rules`
+fn directive_words(cmd: &Command) -> Option<Vec<String>> {
+    let v = match cmd {
+        Command::Normal(v) => v,
+        _ => return None,
+    };
+    let words = |s: &str| s.split_whitespace().map(String::from).collect::<Vec<_>>();
+    match v.cmd.as_str() {
+        "lint" => {
+            let mut out: Vec<String> = v.pattern.get(0).map(|p| words(&p.value)).unwrap_or_default();
+            out.extend(words(&v.body.value));
+            Some(out)
+        }
+        "comment" => {
+            let text = match v.pattern.get(0) {
+                Some(p) => &p.value,
+                None => &v.body.value,
+            };
+            let mut out = words(text);
+            match out.first().map(String::as_str) {
+                Some("lint") => Some(out.split_off(1)),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn next_block(document: &LintDocument, range: &Span<usize>) -> Option<Span<usize>> {
+    let mut blocks = document.blocks().map(|(_, r)| r);
+    let block = blocks.find(|r| r.start <= range.start && range.end <= r.end)?;
+    match block == range {
+        true => blocks.next().cloned(),
+        false => Some(block.to_owned()),
+    }
+}
@@ -0,0 +1,219 @@
+mod directive;
+mod prose;
+mod structure;
+
+pub use directive::Suppressions;
+
+use super::*;
+use notedown_ast::nodes::{CodeNode, MathNode, TextSpan};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// All built-in rules, in the order of checking
+pub static LINT_RULES: &[&dyn LintRule] = &[&prose::CjkLatinSpacing, &prose::PunctuationSpacing, &prose::RepeatedWord, &structure::TrailingWhitespace, &structure::HeadingIncrement, &structure::LongParagraph];
+
+/// A writing lint, checked after the document is parsed
+pub trait LintRule: Sync {
+    /// Stable id used in the settings and `\lint[off]: id`
+    fn id(&self) -> &'static str;
+    /// Severity if not configured
+    fn severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::INFORMATION
+    }
+    fn check(&self, document: &LintDocument, config: &LintConfig, out: &mut Vec<Lint>);
+}
+
+/// Problem found by a rule or by the command checks
+#[derive(Clone, Debug)]
+pub struct Lint {
+    /// Id of the rule, or the code of the check like `unknown-command`
+    pub rule: &'static str,
+    pub severity: DiagnosticSeverity,
+    pub range: Span<usize>,
+    pub message: String,
+    /// Edits applied together to fix the problem
+    pub fixes: Vec<LintFix>,
+}
+
+/// Replace the text in the range
+#[derive(Clone, Debug)]
+pub struct LintFix {
+    pub range: Span<usize>,
+    pub new_text: String,
+}
+
+/// Settings of the writing lints
+/// ```json
+/// "notedown.lint": {
+///     "rules": { "repeated-word": "error", "long-paragraph": "off" },
+///     "maxParagraphLength": 800
+/// }
+/// ```
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LintConfig {
+    /// Severity of the rules, the default severity is used if missing
+    pub rules: HashMap<String, LintLevel>,
+    /// Characters of a paragraph before `long-paragraph` is reported
+    pub max_paragraph_length: usize,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    Off,
+    Error,
+    Warning,
+    Information,
+    Hint,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self { rules: HashMap::new(), max_paragraph_length: 800 }
+    }
+}
+
+impl LintConfig {
+    /// Severity of the rule, none if turned off
+    pub fn severity(&self, rule: &dyn LintRule) -> Option<DiagnosticSeverity> {
+        match self.rules.get(rule.id()) {
+            None => Some(rule.severity()),
+            Some(LintLevel::Off) => None,
+            Some(LintLevel::Error) => Some(DiagnosticSeverity::ERROR),
+            Some(LintLevel::Warning) => Some(DiagnosticSeverity::WARNING),
+            Some(LintLevel::Information) => Some(DiagnosticSeverity::INFORMATION),
+            Some(LintLevel::Hint) => Some(DiagnosticSeverity::HINT),
+        }
+    }
+}
+
+impl Lint {
+    pub fn new(rule: &dyn LintRule, range: Span<usize>, message: impl Into<String>) -> Self {
+        Self { rule: rule.id(), severity: rule.severity(), range, message: message.into(), fixes: vec![] }
+    }
+    /// Problem which is not reported by a writing lint
+    pub fn problem(code: &'static str, severity: DiagnosticSeverity, range: Span<usize>, message: impl Into<String>) -> Self {
+        Self { rule: code, severity, range, message: message.into(), fixes: vec![] }
+    }
+    #[inline]
+    pub fn with_fix(mut self, range: Span<usize>, new_text: impl Into<String>) -> Self {
+        self.fixes.push(LintFix { range, new_text: new_text.into() });
+        return self;
+    }
+}
+
+/// The document seen by the rules
+pub struct LintDocument<'a> {
+    pub text: &'a str,
+    pub ast: &'a ASTNode,
+    /// Plain text with the offset, code, math and raw html are excluded
+    pub prose: Vec<(usize, &'a str)>,
+    /// Code and math whose lines are not checked
+    pub verbatim: Vec<Span<usize>>,
+}
+
+impl<'a> LintDocument<'a> {
+    pub fn new(text: &'a str, ast: &'a ASTNode) -> Self {
+        let mut document = Self { text, ast, prose: vec![], verbatim: vec![] };
+        let _ = ast.accept(&mut document);
+        return document;
+    }
+    /// Top level nodes with the range
+    pub fn blocks(&self) -> impl Iterator<Item = (&'a ASTNode, &'a Span<usize>)> {
+        let children = match &self.ast.value {
+            ASTKind::Statements(v) => v.as_slice(),
+            _ => &[],
+        };
+        children.iter().filter_map(|node| Some((node, node.range.as_ref()?)))
+    }
+    #[inline]
+    pub fn is_verbatim(&self, offset: usize) -> bool {
+        self.verbatim.iter().any(|r| r.contains(&offset))
+    }
+}
+
+impl<'a> Visitor<'a> for LintDocument<'a> {
+    fn visit_include(&mut self, _: &'a IncludeNode, _: &mut VisitContext<'a>) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+    fn visit_command(&mut self, cmd: &'a Command, ctx: &mut VisitContext<'a>) -> ControlFlow<()> {
+        if let Command::Escaped(_) = cmd {
+            self.verbatim.extend(ctx.node().and_then(|node| node.range.to_owned()))
+        }
+        walk_command(self, cmd, ctx)
+    }
+    fn visit_code(&mut self, _: &'a CodeNode, ctx: &mut VisitContext<'a>) -> ControlFlow<()> {
+        self.verbatim.extend(ctx.node().and_then(|node| node.range.to_owned()));
+        ControlFlow::Continue(())
+    }
+    fn visit_math(&mut self, _: &'a MathNode, ctx: &mut VisitContext<'a>) -> ControlFlow<()> {
+        self.verbatim.extend(ctx.node().and_then(|node| node.range.to_owned()));
+        ControlFlow::Continue(())
+    }
+    fn visit_text(&mut self, text: &'a TextSpan, ctx: &mut VisitContext<'a>) -> ControlFlow<()> {
+        let range = match ctx.node().and_then(|node| node.range.as_ref()) {
+            Some(r) => r.to_owned(),
+            None => return ControlFlow::Continue(()),
+        };
+        match text {
+            // the offsets are only reliable if the text is the source
+            TextSpan::Normal(s) if self.text.get(range.to_owned()) == Some(s.as_str()) => self.prose.push((range.start, &self.text[range])),
+            TextSpan::Raw(_) | TextSpan::HTMLRawInline(_) => self.verbatim.push(range),
+            _ => {}
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// Run the writing lints which are not turned off by the settings or the directives
+pub fn check_lints(text: &str, ast: &ASTNode, nodes: &[&ASTNode], config: &LintConfig) -> Vec<Lint> {
+    let document = LintDocument::new(text, ast);
+    let suppressions = Suppressions::new(&document, nodes);
+    let mut out = vec![];
+    for (range, message) in suppressions.problems() {
+        out.push(Lint::problem("lint-directive", DiagnosticSeverity::WARNING, range.to_owned(), message))
+    }
+    for rule in LINT_RULES {
+        let severity = match config.severity(*rule) {
+            Some(s) => s,
+            None => continue,
+        };
+        let mut lints = vec![];
+        rule.check(&document, config, &mut lints);
+        for lint in lints.into_iter().filter(|lint| !suppressions.is_suppressed(lint.rule, lint.range.start)) {
+            out.push(Lint { severity, ..lint })
+        }
+    }
+    return out;
+}
+
+#[test]
+fn lint_rules() {
+    /// Rules reported and the text after all fixes applied
+    fn lint(text: &str) -> (Vec<&'static str>, String) {
+        let ast = notedown_parser::NotedownParser::default().parse(text).unwrap();
+        let mut nodes = vec![];
+        flatten(&ast, &mut nodes);
+        let lints = check_lints(text, &ast, &nodes, &LintConfig::default());
+        let mut fixes: Vec<_> = lints.iter().flat_map(|lint| lint.fixes.iter()).collect();
+        fixes.sort_by_key(|fix| std::cmp::Reverse(fix.range.start));
+        let mut fixed = text.to_string();
+        for fix in fixes {
+            fixed.replace_range(fix.range.to_owned(), &fix.new_text)
+        }
+        (lints.iter().map(|lint| lint.rule).collect(), fixed)
+    }
+    assert_eq!(lint("使用Rust编写"), (vec!["cjk-latin-spacing", "cjk-latin-spacing"], String::from("使用 Rust 编写")));
+    assert_eq!(lint("a,b and c ,d !x"), (vec!["punctuation-spacing"; 3], String::from("a, b and c, d !x")));
+    assert_eq!(lint("e.g. file.note and 3.14, end.Next"), (vec!["punctuation-spacing"], String::from("e.g. file.note and 3.14, end. Next")));
+    assert_eq!(lint("it is the The end"), (vec!["repeated-word"], String::from("it is the end")));
+    assert_eq!(lint("text  \n\n```\ncode  \n```"), (vec!["trailing-whitespace"], String::from("text\n\n```\ncode  \n```")));
+    assert_eq!(lint("# A\n\n### B"), (vec!["heading-increment"], String::from("# A\n\n## B")));
+    assert_eq!(lint("the the `使用Rust` $a,b$").0, vec!["repeated-word"]);
+    assert!(lint("\\lint[off]: repeated-word\n\nthe the").0.is_empty());
+    assert_eq!(lint("\\lint[off]\n\nthe the\n\n\\lint[on]\n\nthe the").0, vec!["repeated-word"]);
+    assert_eq!(lint("\\comment: lint next\n\nthe the\n\nthe the").0, vec!["repeated-word"]);
+    assert!(lint("the the\n\n\\lint[file]: repeated-word").0.is_empty());
+    assert_eq!(lint(&"some text ".repeat(100)).0, vec!["trailing-whitespace", "long-paragraph"]);
+}
@@ -0,0 +1,115 @@
+use super::*;
+
+/// `中文English` should be `中文 English`
+pub struct CjkLatinSpacing;
+
+/// `a,b` should be `a, b`, and `a ,b` should be `a, b`
+pub struct PunctuationSpacing;
+
+/// `the the`
+pub struct RepeatedWord;
+
+impl LintRule for CjkLatinSpacing {
+    fn id(&self) -> &'static str {
+        "cjk-latin-spacing"
+    }
+    fn severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::HINT
+    }
+    fn check(&self, document: &LintDocument, _: &LintConfig, out: &mut Vec<Lint>) {
+        for (offset, text) in &document.prose {
+            for ((i, a), (j, b)) in text.char_indices().zip(text.char_indices().skip(1)) {
+                if (is_cjk(a) && b.is_ascii_alphanumeric()) || (a.is_ascii_alphanumeric() && is_cjk(b)) {
+                    let lint = Lint::new(self, offset + i..offset + j + b.len_utf8(), "Missing space between CJK and Latin characters");
+                    out.push(lint.with_fix(offset + j..offset + j, " "))
+                }
+            }
+        }
+    }
+}
+
+impl LintRule for PunctuationSpacing {
+    fn id(&self) -> &'static str {
+        "punctuation-spacing"
+    }
+    fn check(&self, document: &LintDocument, _: &LintConfig, out: &mut Vec<Lint>) {
+        for (offset, text) in &document.prose {
+            let chars: Vec<_> = text.char_indices().collect();
+            for (n, (i, c)) in chars.iter().copied().enumerate() {
+                let before = n.checked_sub(1).map(|n| chars[n].1);
+                let after = chars.get(n + 1).map(|(_, c)| *c);
+                let missing_after = match (c, after) {
+                    (',' | ';' | ':', Some(a)) => a.is_ascii_alphabetic(),
+                    // `!important` is not the end of a sentence
+                    ('!' | '?', Some(a)) => a.is_ascii_alphabetic() && before.map(char::is_alphanumeric).unwrap_or(false),
+                    // a full stop between sentences, `e.g.` or `file.note` is fine
+                    ('.', Some(a)) => a.is_ascii_uppercase() && before.map(|b| b.is_ascii_lowercase()).unwrap_or(false),
+                    _ => false,
+                };
+                if missing_after {
+                    let lint = Lint::new(self, offset + i..offset + i + 2, format!("Missing space after `{}`", c));
+                    out.push(lint.with_fix(offset + i + 1..offset + i + 1, " "))
+                }
+                // `word ,` but not ` !important`
+                let attached = after.map(|a| !a.is_whitespace()).unwrap_or(false);
+                if matches!(c, ',' | ';') || (matches!(c, '!' | '?') && !attached) {
+                    let spaces = text[..i].len() - text[..i].trim_end_matches(' ').len();
+                    let word = text[..i - spaces].chars().last().map(|c| c.is_alphanumeric()).unwrap_or(false);
+                    if spaces > 0 && word {
+                        let lint = Lint::new(self, offset + i - spaces..offset + i + 1, format!("Unexpected space before `{}`", c));
+                        out.push(lint.with_fix(offset + i - spaces..offset + i, ""))
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl LintRule for RepeatedWord {
+    fn id(&self) -> &'static str {
+        "repeated-word"
+    }
+    fn severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::WARNING
+    }
+    fn check(&self, document: &LintDocument, _: &LintConfig, out: &mut Vec<Lint>) {
+        for (offset, text) in &document.prose {
+            let mut last: Option<(usize, &str)> = None;
+            for (start, word) in words(text) {
+                if let Some((end, previous)) = last {
+                    let between = &text[end..start];
+                    if previous.eq_ignore_ascii_case(word) && !between.is_empty() && between.chars().all(char::is_whitespace) {
+                        let lint = Lint::new(self, offset + start..offset + start + word.len(), format!("Repeated word `{}`", word));
+                        out.push(lint.with_fix(offset + end..offset + start + word.len(), ""))
+                    }
+                }
+                last = Some((start + word.len(), word))
+            }
+        }
+    }
+}
+
+/// Words of letters with the offset, CJK characters are not words
+fn words(text: &str) -> Vec<(usize, &str)> {
+    let mut out = vec![];
+    let mut start = None;
+    for (i, c) in text.char_indices().chain(std::iter::once((text.len(), ' '))) {
+        let letter = (c.is_alphabetic() && !is_cjk(c)) || c == '\'';
+        match (start, letter) {
+            (None, true) => start = Some(i),
+            (Some(s), false) => {
+                out.push((s, &text[s..i]));
+                start = None
+            }
+            _ => {}
+        }
+    }
+    return out;
+}
+
+/// Han, kana and hangul, the CJK punctuations are not included
+fn is_cjk(c: char) -> bool {
+    matches!(c as u32,
+        0x2E80..=0x2FDF | 0x3040..=0x30FF | 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xAC00..=0xD7AF | 0xF900..=0xFAFF | 0x20000..=0x2FA1F
+    )
+}
@@ -0,0 +1,83 @@
+use super::*;
+
+/// Spaces or tabs at the end of lines, code and math are skipped
+pub struct TrailingWhitespace;
+
+/// `# Title` followed by `### Title`
+pub struct HeadingIncrement;
+
+/// Paragraphs longer than `maxParagraphLength` characters
+pub struct LongParagraph;
+
+impl LintRule for TrailingWhitespace {
+    fn id(&self) -> &'static str {
+        "trailing-whitespace"
+    }
+    fn severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::HINT
+    }
+    fn check(&self, document: &LintDocument, _: &LintConfig, out: &mut Vec<Lint>) {
+        let mut offset = 0;
+        for line in document.text.split_inclusive('\n') {
+            let content = line.trim_end_matches(&['\r', '\n'][..]);
+            let trimmed = content.trim_end_matches(&[' ', '\t'][..]);
+            let (start, end) = (offset + trimmed.len(), offset + content.len());
+            if start != end && !document.is_verbatim(start) {
+                out.push(Lint::new(self, start..end, "Trailing whitespace").with_fix(start..end, ""))
+            }
+            offset += line.len();
+        }
+    }
+}
+
+impl LintRule for HeadingIncrement {
+    fn id(&self) -> &'static str {
+        "heading-increment"
+    }
+    fn severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::WARNING
+    }
+    fn check(&self, document: &LintDocument, _: &LintConfig, out: &mut Vec<Lint>) {
+        let mut last: Option<u8> = None;
+        for (node, range) in document.blocks() {
+            let level = match &node.value {
+                ASTKind::Header(v) => v.level,
+                _ => continue,
+            };
+            if let Some(previous) = last.filter(|l| level > l + 1) {
+                let marks = range.start..range.start + level as usize;
+                let message = format!("Heading level jumps from {} to {}", previous, level);
+                let mut lint = Lint::new(self, marks.to_owned(), message);
+                if document.text.get(marks.to_owned()).map(|s| s.chars().all(|c| c == '#')).unwrap_or(false) {
+                    lint = lint.with_fix(marks, "#".repeat(previous as usize + 1))
+                }
+                out.push(lint)
+            }
+            last = Some(level)
+        }
+    }
+}
+
+impl LintRule for LongParagraph {
+    fn id(&self) -> &'static str {
+        "long-paragraph"
+    }
+    fn severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::HINT
+    }
+    fn check(&self, document: &LintDocument, config: &LintConfig, out: &mut Vec<Lint>) {
+        for (node, range) in document.blocks() {
+            if !matches!(node.value, ASTKind::Paragraph(_)) {
+                continue;
+            }
+            let text = &document.text[range.to_owned()];
+            let length = text.chars().count();
+            if length > config.max_paragraph_length {
+                // only the first line is marked
+                let end = range.start + text.find('\n').unwrap_or(text.len());
+                let message = format!("Paragraph has {} characters, more than {}", length, config.max_paragraph_length);
+                out.push(Lint::new(self, range.start..end, message))
+            }
+        }
+    }
+}
@@ -1 +1,40 @@
+mod command;
+mod lint;
 
+pub use self::{
+    command::check_commands,
+    lint::{check_lints, Lint, LintConfig, LintDocument, LintFix, LintLevel, LintRule, Suppressions, LINT_RULES},
+};
+
+use notedown_ast::{
+    command::Command,
+    nodes::{IncludeNode, SmartLink},
+    utils::lsp_types::DiagnosticSeverity,
+    visit::{walk_command, ControlFlow, VisitContext, Visitor},
+    ASTKind, ASTNode,
+};
+use std::ops::Range as Span;
+
+/// Commands and links in the document, texts of blocks are walked into
+pub fn flatten<'a>(node: &'a ASTNode, out: &mut Vec<&'a ASTNode>) {
+    let _ = node.accept(&mut Flatten { out });
+}
+
+struct Flatten<'a, 'o> {
+    out: &'o mut Vec<&'a ASTNode>,
+}
+
+impl<'a> Visitor<'a> for Flatten<'a, '_> {
+    /// Ranges of the included nodes are offsets in another file
+    fn visit_include(&mut self, _: &'a IncludeNode, _: &mut VisitContext<'a>) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+    fn visit_link(&mut self, _: &'a SmartLink, ctx: &mut VisitContext<'a>) -> ControlFlow<()> {
+        self.out.extend(ctx.node());
+        ControlFlow::Continue(())
+    }
+    fn visit_command(&mut self, cmd: &'a Command, ctx: &mut VisitContext<'a>) -> ControlFlow<()> {
+        self.out.extend(ctx.node());
+        walk_command(self, cmd, ctx)
+    }
+}
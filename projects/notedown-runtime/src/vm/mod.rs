@@ -2,7 +2,7 @@ use crate::VMFileSystem;
 use notedown_ast::utils::lsp_types::Url;
 use std::path::Path;
 
-mod diagnostic;
+pub mod diagnostic;
 
 pub struct NoteVM {
     pub fs: VMFileSystem,
@@ -0,0 +1,232 @@
+use notedown_ast::{
+    command::Command,
+    nodes::{SmartLink, TableView},
+    utils::lsp_types::Url,
+    ASTKind, ASTNode,
+};
+use std::{collections::HashMap, ops::Range, path::Path};
+
+mod symbol;
+
+pub use self::symbol::{fuzzy_score, SymbolEntry, SymbolEntryKind};
+
+/// Headers, footnotes, links and symbols of the notes in the workspace
+///
+/// The ranges are byte offsets in the source of each note.
+#[derive(Clone, Debug, Default)]
+pub struct WorkspaceIndex {
+    documents: HashMap<Url, DocumentIndex>,
+}
+
+/// Everything can be linked to or from a note
+#[derive(Clone, Debug, Default)]
+pub struct DocumentIndex {
+    /// Top level headers
+    pub headers: Vec<HeaderEntry>,
+    /// `[^tag]: text`
+    pub footnotes: Vec<FootnoteEntry>,
+    pub links: Vec<LinkEntry>,
+    /// Everything can be searched by name
+    pub symbols: Vec<SymbolEntry>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HeaderEntry {
+    pub id: String,
+    pub level: u8,
+    pub range: Range<usize>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FootnoteEntry {
+    pub tag: String,
+    pub range: Range<usize>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LinkEntry {
+    pub target: LinkTarget,
+    pub range: Range<usize>,
+}
+
+/// Resolved target of a link
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LinkTarget {
+    /// A note, or the header with the id
+    Note { url: Url, id: Option<String> },
+    /// Images, other files, websites and emails
+    External(Url),
+    /// `[^tag]`, defined in the same note
+    Footnote(String),
+}
+
+impl WorkspaceIndex {
+    /// Index the note again after it changed
+    pub fn update(&mut self, url: &Url, ast: &ASTNode) {
+        let mut document = DocumentIndex::default();
+        document.collect(url, ast, true);
+        document.collect_symbols(url, ast);
+        self.documents.insert(url.to_owned(), document);
+    }
+    #[inline]
+    pub fn remove(&mut self, url: &Url) {
+        self.documents.remove(url);
+    }
+    #[inline]
+    pub fn contains(&self, url: &Url) -> bool {
+        self.documents.contains_key(url)
+    }
+    #[inline]
+    pub fn get(&self, url: &Url) -> Option<&DocumentIndex> {
+        self.documents.get(url)
+    }
+    /// Where the target of the link under the offset is defined
+    pub fn definition(&self, url: &Url, offset: usize) -> Option<(Url, Range<usize>)> {
+        let document = self.get(url)?;
+        match &document.link_at(offset)?.target {
+            LinkTarget::Note { url, id: Some(id) } => Some((url.to_owned(), self.get(url)?.header(id)?.range.to_owned())),
+            LinkTarget::Note { url, id: None } => Some((url.to_owned(), 0..0)),
+            LinkTarget::External(url) if url.scheme() == "file" => Some((url.to_owned(), 0..0)),
+            LinkTarget::External(_) => None,
+            LinkTarget::Footnote(tag) => Some((url.to_owned(), document.footnote(tag)?.range.to_owned())),
+        }
+    }
+    /// Links to the header of the note in all notes, or to the note itself if no id
+    pub fn references(&self, url: &Url, id: Option<&str>) -> Vec<(Url, Range<usize>)> {
+        let mut out = vec![];
+        for (source, document) in &self.documents {
+            for link in &document.links {
+                if let LinkTarget::Note { url: target, id: target_id } = &link.target {
+                    if target == url && target_id.as_deref() == id {
+                        out.push((source.to_owned(), link.range.to_owned()))
+                    }
+                }
+            }
+        }
+        out.sort_by(|a, b| (a.0.as_str(), a.1.start).cmp(&(b.0.as_str(), b.1.start)));
+        return out;
+    }
+}
+
+impl DocumentIndex {
+    #[inline]
+    pub fn header(&self, id: &str) -> Option<&HeaderEntry> {
+        self.headers.iter().find(|h| h.id == id)
+    }
+    #[inline]
+    pub fn footnote(&self, tag: &str) -> Option<&FootnoteEntry> {
+        self.footnotes.iter().find(|f| f.tag == tag)
+    }
+    #[inline]
+    pub fn header_at(&self, offset: usize) -> Option<&HeaderEntry> {
+        self.headers.iter().find(|h| contains(&h.range, offset))
+    }
+    #[inline]
+    pub fn footnote_at(&self, offset: usize) -> Option<&FootnoteEntry> {
+        self.footnotes.iter().find(|f| contains(&f.range, offset))
+    }
+    #[inline]
+    pub fn link_at(&self, offset: usize) -> Option<&LinkEntry> {
+        self.links.iter().find(|l| contains(&l.range, offset))
+    }
+    fn collect(&mut self, url: &Url, node: &ASTNode, top: bool) {
+        match &node.value {
+            ASTKind::Statements(v) => v.iter().for_each(|f| self.collect(url, f, top)),
+            ASTKind::Paragraph(v) => self.collect_all(url, v),
+            ASTKind::Header(v) => {
+                if let (true, Some(id), Some(range)) = (top, &v.id, &node.range) {
+                    self.headers.push(HeaderEntry { id: id.to_owned(), level: v.level, range: range.to_owned() })
+                }
+                self.collect_all(url, &v.children)
+            }
+            ASTKind::StyledSpan(v) => self.collect_all(url, &v.children),
+            ASTKind::TableView(v) => match &**v {
+                TableView::SimpleTable { head, terms, .. } => {
+                    self.collect_all(url, head);
+                    terms.iter().for_each(|row| self.collect_all(url, row))
+                }
+            },
+            ASTKind::ListView(v) => v.children.iter().for_each(|item| self.collect_all(url, &item.rest)),
+            ASTKind::QuoteNode(v) => {
+                self.collect_all(url, &v.head);
+                self.collect_all(url, &v.body)
+            }
+            ASTKind::Command(cmd) => {
+                if let Command::XML(xml) = &**cmd {
+                    self.collect_all(url, &xml.body)
+                }
+            }
+            ASTKind::LinkNode(link) => {
+                let range = match &node.range {
+                    Some(s) => s.to_owned(),
+                    None => return,
+                };
+                match link {
+                    SmartLink::Reference(v) if v.is_definition() => self.footnotes.push(FootnoteEntry { tag: v.tag.to_owned(), range }),
+                    // `[^tag][text]` defines the footnote inline
+                    SmartLink::Reference(v) if !v.text.is_empty() => {}
+                    _ => {
+                        if let Some(target) = resolve_link(url, link) {
+                            self.links.push(LinkEntry { target, range })
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    #[inline]
+    fn collect_all(&mut self, url: &Url, nodes: &[ASTNode]) {
+        nodes.iter().for_each(|f| self.collect(url, f, false))
+    }
+}
+
+/// Resolve the link relative to the note, texts in brackets which are not paths are skipped
+pub fn resolve_link(base: &Url, link: &SmartLink) -> Option<LinkTarget> {
+    match link {
+        SmartLink::Normal(v) => resolve_path(base, &v.src, false),
+        SmartLink::TwoWay(v) => match v.get_id() {
+            Some(id) => resolve_path(base, &format!("{}#{}", v.get_link(), id), true),
+            None => resolve_path(base, v.get_link(), true),
+        },
+        SmartLink::Image(v) => match resolve_path(base, &v.source, false)? {
+            LinkTarget::Note { url, .. } => Some(LinkTarget::External(url)),
+            other => Some(other),
+        },
+        SmartLink::EMail(v) => Url::parse(&format!("mailto:{}", v.name)).ok().map(LinkTarget::External),
+        SmartLink::Reference(v) => Some(LinkTarget::Footnote(v.tag.to_owned())),
+        SmartLink::ExternalResource(_) => None,
+    }
+}
+
+/// `https://...`, `./path.note#id`, `#id` or `image.png`
+fn resolve_path(base: &Url, src: &str, is_note: bool) -> Option<LinkTarget> {
+    let src = src.trim();
+    if src.contains("://") {
+        return Url::parse(src).ok().map(LinkTarget::External);
+    }
+    let (path, id) = match src.split_once('#') {
+        Some((path, id)) => (path.trim(), Some(id.trim()).filter(|s| !s.is_empty())),
+        None => (src, None),
+    };
+    let extension = Path::new(path).extension().map(|e| e.to_string_lossy().to_string());
+    let is_local = is_note || src.starts_with(&['#', '.', '/'][..]) || path.contains('/') || extension.is_some();
+    if !is_local {
+        return None;
+    }
+    let mut url = match path.is_empty() {
+        true => base.to_owned(),
+        false => base.join(path).ok()?,
+    };
+    url.set_fragment(None);
+    match is_note || path.is_empty() || extension.as_deref() == Some("note") {
+        true => Some(LinkTarget::Note { url, id: id.map(String::from) }),
+        false => Some(LinkTarget::External(url)),
+    }
+}
+
+/// The cursor can be placed at both sides
+#[inline]
+fn contains(range: &Range<usize>, offset: usize) -> bool {
+    range.start <= offset && offset <= range.end
+}
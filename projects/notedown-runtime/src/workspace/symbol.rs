@@ -0,0 +1,175 @@
+use super::*;
+use notedown_ast::{
+    command::Command,
+    traits::{TableOfContent, TocNode},
+    Value,
+};
+use std::cmp::Reverse;
+
+/// Literal of the simple values, the type name of the others
+fn value_detail(value: &Value) -> String {
+    match value {
+        Value::Null | Value::Boolean(_) | Value::Integer(_) | Value::Decimal(_) => value.to_string(),
+        Value::String(s) => format!("{:?}", s),
+        _ => value.get_type().to_string(),
+    }
+}
+
+/// Something can be searched by name in the workspace
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SymbolEntry {
+    pub name: String,
+    pub kind: SymbolEntryKind,
+    /// Slug of the header, file name of the title, or value of the variable
+    pub detail: String,
+    pub range: Range<usize>,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SymbolEntryKind {
+    /// Text of the first top level header, or the file name
+    Title,
+    /// Header in the table of content, with the level
+    Header(u8),
+    /// `[^tag]: text`
+    Footnote,
+    /// `\set[name]: value` or `\set(name = value)`
+    Variable,
+}
+
+impl WorkspaceIndex {
+    /// Symbols matching the query in all notes, the best matches and the top level headers come first
+    pub fn search(&self, query: &str) -> Vec<(&Url, &SymbolEntry)> {
+        let mut out = vec![];
+        for (url, document) in &self.documents {
+            for symbol in &document.symbols {
+                if let Some(score) = fuzzy_score(query, &symbol.name) {
+                    out.push((score, url, symbol))
+                }
+            }
+        }
+        out.sort_by(|(s1, u1, e1), (s2, u2, e2)| {
+            let lhs = (Reverse(*s1), e1.kind.rank(), &e1.name, u1.as_str(), e1.range.start);
+            lhs.cmp(&(Reverse(*s2), e2.kind.rank(), &e2.name, u2.as_str(), e2.range.start))
+        });
+        out.into_iter().map(|(_, url, symbol)| (url, symbol)).collect()
+    }
+}
+
+impl SymbolEntryKind {
+    /// Titles and higher headers are preferred when the scores are equal
+    #[inline]
+    fn rank(&self) -> u8 {
+        match self {
+            Self::Title => 0,
+            Self::Header(level) => *level,
+            Self::Footnote => u8::MAX - 1,
+            Self::Variable => u8::MAX,
+        }
+    }
+}
+
+impl DocumentIndex {
+    /// Title, headers from the table of content, footnotes and variables
+    pub(super) fn collect_symbols(&mut self, url: &Url, ast: &ASTNode) {
+        let terms = match &ast.value {
+            ASTKind::Statements(v) => v.as_slice(),
+            _ => return,
+        };
+        let file = url.path_segments().and_then(|mut s| s.next_back()).unwrap_or_default().to_string();
+        let toc = ast.toc();
+        let mut headers = vec![];
+        flatten_toc(&toc, &mut headers);
+        let title = headers.iter().find(|n| n.level == 1).map(|n| n.name.to_owned());
+        let name = match title.filter(|s| !s.is_empty()) {
+            Some(s) => s,
+            None => file.trim_end_matches(".note").to_string(),
+        };
+        let range = ast.range.to_owned().unwrap_or_default();
+        self.symbols.push(SymbolEntry { name, kind: SymbolEntryKind::Title, detail: file, range });
+        for toc in headers {
+            let kind = SymbolEntryKind::Header(toc.level);
+            let range = toc.selection_range.to_owned();
+            self.symbols.push(SymbolEntry { name: toc.name.to_owned(), kind, detail: toc.detail.to_owned(), range })
+        }
+        for term in terms {
+            let range = match &term.range {
+                Some(s) => s.to_owned(),
+                None => continue,
+            };
+            match &term.value {
+                ASTKind::Command(cmd) if cmd.is("set") => {
+                    if let Command::Normal(v) = &**cmd {
+                        // \set[name]: value
+                        if let (Some(name), Some(value)) = (v.pattern.get(0), v.options.args.iter_sparse().next()) {
+                            let range = name.range.to_owned().unwrap_or(range.to_owned());
+                            let name = name.value.trim().to_string();
+                            self.symbols.push(SymbolEntry { name, kind: SymbolEntryKind::Variable, detail: value_detail(value.1), range })
+                        }
+                        // \set(name = value)
+                        for (key, value) in v.options.kvs.iter_raw() {
+                            let range = key.range.to_owned().unwrap_or(range.to_owned());
+                            let name = key.value.trim().to_string();
+                            self.symbols.push(SymbolEntry { name, kind: SymbolEntryKind::Variable, detail: value_detail(&value.value), range })
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        for footnote in &self.footnotes {
+            let name = footnote.tag.to_owned();
+            let symbol = SymbolEntry { name, kind: SymbolEntryKind::Footnote, detail: format!("[^{}]", footnote.tag), range: footnote.range.to_owned() };
+            self.symbols.push(symbol)
+        }
+    }
+}
+
+fn flatten_toc<'a>(node: &'a TocNode, out: &mut Vec<&'a TocNode>) {
+    for child in &node.children {
+        out.push(child);
+        flatten_toc(child, out)
+    }
+}
+
+/// Case insensitive subsequence match, `None` if some character of the query is missing
+///
+/// Exact names, prefixes, consecutive characters and starts of words score higher, and longer names score lower.
+pub fn fuzzy_score(query: &str, name: &str) -> Option<i32> {
+    let query: Vec<char> = query.trim().chars().flat_map(char::to_lowercase).filter(|c| !c.is_whitespace()).collect();
+    let name: Vec<char> = name.chars().flat_map(char::to_lowercase).collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+    let mut score = 0;
+    let mut last = None;
+    let mut rest = query.iter().peekable();
+    for (i, c) in name.iter().enumerate() {
+        let expected = match rest.peek() {
+            Some(s) => **s,
+            None => break,
+        };
+        if *c != expected {
+            continue;
+        }
+        rest.next();
+        score += 1;
+        if last.map(|l| l + 1 == i).unwrap_or(i == 0) {
+            score += 5
+        }
+        if i == 0 || !name[i - 1].is_alphanumeric() {
+            score += 3
+        }
+        last = Some(i);
+    }
+    if rest.peek().is_some() {
+        return None;
+    }
+    if name.iter().filter(|c| !c.is_whitespace()).eq(query.iter()) {
+        score += 100
+    }
+    else if name.starts_with(&query) {
+        score += 50
+    }
+    Some(score * 8 - (name.len() - query.len()) as i32)
+}
@@ -0,0 +1,103 @@
+use notedown_ast::nodes::CodeNode;
+use notedown_rt::{get_syntect_language_pack, get_syntect_languages, get_syntect_themes, set_root_path, HighlightStyle, SyntaxHighlighter};
+use std::{fs, path::PathBuf, sync::Mutex};
+use syntect::{dumps::dump_binary, parsing::SyntaxSet};
+
+fn code(lang: &str, text: &str) -> CodeNode {
+    CodeNode::code_block(lang.to_string(), text.to_string())
+}
+
+fn inline_style() -> HighlightStyle {
+    HighlightStyle::Inline { theme: String::from("InspiredGitHub") }
+}
+
+#[test]
+fn classed_and_inline() {
+    let input = code("rust", "fn main() {}");
+    let classed = SyntaxHighlighter::default().render(&input).unwrap();
+    assert!(classed.starts_with("<pre class=\"code\" data-lang=\"rust\"><code>"), "{}", classed);
+    assert!(classed.contains("class=\"syn-source syn-rust\""), "{}", classed);
+    assert!(!classed.contains("style="), "{}", classed);
+    let inline = SyntaxHighlighter::new(inline_style()).render(&input).unwrap();
+    assert!(inline.starts_with("<pre class=\"code\" data-lang=\"rust\" style=\"background-color:#"), "{}", inline);
+    assert!(inline.contains("<span style=\"color:#"), "{}", inline);
+    assert!(!inline.contains("syn-"), "{}", inline);
+    let missing = SyntaxHighlighter::new(HighlightStyle::Inline { theme: String::from("missing") });
+    assert!(missing.render(&input).is_err());
+}
+
+#[test]
+fn line_numbers_and_ranges() {
+    let mut input = code("text", "one\ntwo\nthree\nfour");
+    input.show_line_number = Some(10);
+    input.highlight_lines = vec![2..=2];
+    input.hide_lines = vec![3..=3];
+    let html = SyntaxHighlighter::default().render(&input).unwrap();
+    let lines: Vec<_> = html.lines().filter(|line| line.contains("class=\"line")).collect();
+    assert_eq!(lines.len(), 3, "{}", html);
+    assert!(lines[0].contains("<span class=\"line\"><span class=\"line-number\">10</span>"), "{}", lines[0]);
+    assert!(lines[1].contains("<span class=\"line highlight\"><span class=\"line-number\">11</span>"), "{}", lines[1]);
+    // lines after the hidden line keep their numbers
+    assert!(lines[2].contains("<span class=\"line-number\">13</span>") && lines[2].contains("four"), "{}", lines[2]);
+    assert!(!html.contains("three"), "{}", html);
+}
+
+#[test]
+fn unknown_language() {
+    let html = SyntaxHighlighter::default().render(&code("no-such-language", "a < b")).unwrap();
+    assert!(html.contains("data-lang=\"no-such-language\""), "{}", html);
+    assert!(html.contains("syn-text syn-plain"), "{}", html);
+    assert!(html.contains("a &lt; b"), "{}", html);
+    assert!(!html.contains("syn-source"), "{}", html);
+}
+
+#[test]
+fn theme_css() {
+    let highlighter = SyntaxHighlighter::default();
+    let css = highlighter.css("InspiredGitHub").unwrap();
+    assert!(css.contains(".syn-code"), "{}", css);
+    assert!(css.contains(".syn-keyword"), "{}", css);
+    assert!(highlighter.css("missing").is_err());
+}
+
+/// `$NOTEDOWN_ROOT` is global, the tests changing it run one by one
+static ROOT: Mutex<()> = Mutex::new(());
+
+/// Root with an extra syntax `note-test` under `syntect/languages`
+fn syntect_root(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("notedown-rt-{}-{}", name, std::process::id()));
+    let languages = root.join("syntect").join("languages");
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(&languages).unwrap();
+    let syntax = "%YAML 1.2\n---\nname: Note Test\nfile_extensions: [note-test]\nscope: source.note-test\ncontexts:\n  main:\n    - match: 'hello'\n      scope: keyword.note-test\n";
+    fs::write(languages.join("test.sublime-syntax"), syntax).unwrap();
+    set_root_path(&root).unwrap();
+    return languages;
+}
+
+#[test]
+fn languages_under_root() {
+    let _lock = ROOT.lock().unwrap_or_else(|e| e.into_inner());
+    let languages = syntect_root("syntect");
+    fs::write(languages.join("base.pack"), dump_binary(&SyntaxSet::load_defaults_newlines())).unwrap();
+    let set = get_syntect_languages().unwrap();
+    assert!(set.find_syntax_by_token("note-test").is_some());
+    assert!(set.find_syntax_by_token("rust").is_some());
+    assert!(get_syntect_themes().unwrap().themes.contains_key("InspiredGitHub"));
+}
+
+#[test]
+fn languages_without_pack() {
+    let _lock = ROOT.lock().unwrap_or_else(|e| e.into_inner());
+    let languages = syntect_root("syntect-no-pack");
+    assert!(get_syntect_language_pack().is_err());
+    let set = get_syntect_languages().unwrap();
+    assert!(set.find_syntax_by_token("note-test").is_some());
+    assert!(set.find_syntax_by_token("rust").is_some());
+    // a broken pack, e.g. an interrupted download
+    let pack = dump_binary(&SyntaxSet::load_defaults_newlines());
+    fs::write(languages.join("base.pack"), &pack[..pack.len() / 2]).unwrap();
+    assert!(get_syntect_language_pack().is_err());
+    let set = get_syntect_languages().unwrap();
+    assert!(set.find_syntax_by_token("note-test").is_some());
+}
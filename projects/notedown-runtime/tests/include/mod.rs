@@ -0,0 +1,94 @@
+use async_std::task::block_on;
+use notedown_ast::{
+    nodes::{Header, IncludeNode},
+    utils::lsp_types::Url,
+    visit::{ControlFlow, VisitContext, Visitor},
+    ASTNode, NoteError, Result,
+};
+use notedown_parser::NotedownParser;
+use notedown_rt::{FileMeta, IncludeConfig, Parser, VMFileSystem};
+use std::{fs, path::PathBuf};
+
+fn parse_note(text: &str, _: &mut FileMeta) -> Result<ASTNode> {
+    NotedownParser::default().parse(text)
+}
+
+/// Write the notes into a fresh directory
+fn workspace(name: &str, notes: &[(&str, &str)]) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("notedown-rt-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(&root).unwrap();
+    for (file, text) in notes {
+        fs::write(root.join(file), text).unwrap();
+    }
+    root
+}
+
+fn expand(root: &PathBuf, file: &str) -> (ASTNode, Vec<NoteError>) {
+    let url = Url::from_file_path(root.join(file)).unwrap();
+    let parser: Parser = parse_note;
+    let config = IncludeConfig::default();
+    let mut fs = VMFileSystem::new(Url::from_directory_path(root).unwrap());
+    let mut errors = block_on(fs.load_includes(&url, &parser, &config));
+    let mut ast = fs.get_ast(&url).unwrap();
+    errors.extend(fs.expand_includes(&url, &mut ast, &config));
+    (ast, errors)
+}
+
+#[derive(Default)]
+struct Collector {
+    includes: Vec<(Option<String>, i8)>,
+    headers: Vec<(u8, String)>,
+}
+
+impl<'ast> Visitor<'ast> for Collector {
+    fn visit_include(&mut self, include: &'ast IncludeNode, ctx: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.includes.push((include.section.to_owned(), include.shift));
+        notedown_ast::visit::walk_nodes(self, &include.children, ctx)
+    }
+    fn visit_header(&mut self, header: &'ast Header, _: &mut VisitContext<'ast>) -> ControlFlow<()> {
+        self.headers.push((header.level, header.id.to_owned().unwrap_or_default()));
+        ControlFlow::Continue(())
+    }
+}
+
+fn collect(ast: &ASTNode) -> Collector {
+    let mut collector = Collector::default();
+    let _ = ast.accept(&mut collector);
+    collector
+}
+
+#[test]
+fn include_file() {
+    let root = workspace("include", &[("main.note", "# Main\n\n\\include[part.note](shift = 1)\n\n<note>see \\include[part.note]</note>"), ("part.note", "# Part\n\ntext")]);
+    let (ast, errors) = expand(&root, "main.note");
+    assert!(errors.is_empty(), "{:?}", errors);
+    let collected = collect(&ast);
+    assert_eq!(collected.includes, vec![(None, 1), (None, 0)]);
+    assert_eq!(collected.headers, vec![(1, "main".to_string()), (2, "part".to_string()), (1, "part".to_string())]);
+}
+
+#[test]
+fn include_section() {
+    let root = workspace("section", &[("main.note", "\\include[part.note#second]"), ("part.note", "# First\n\none\n\n# Second\n\ntwo\n\n## Inner\n\nthree\n\n# Third\n\nfour")]);
+    let (ast, errors) = expand(&root, "main.note");
+    assert!(errors.is_empty(), "{:?}", errors);
+    let collected = collect(&ast);
+    assert_eq!(collected.includes, vec![(Some("second".to_string()), 0)]);
+    assert_eq!(collected.headers, vec![(1, "second".to_string()), (2, "inner".to_string())]);
+    let root = workspace("missing-section", &[("main.note", "\\include[part.note#none]"), ("part.note", "# First")]);
+    let (_, errors) = expand(&root, "main.note");
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].to_string().contains("Header `#none` not found"), "{}", errors[0]);
+}
+
+#[test]
+fn include_cycle() {
+    let root = workspace("cycle", &[("a.note", "# A\n\n\\include[b.note]"), ("b.note", "# B\n\n\\include[a.note]")]);
+    let (ast, errors) = expand(&root, "a.note");
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].to_string().contains("Include cycle detected"), "{}", errors[0]);
+    assert!(errors[0].file.as_ref().is_some_and(|url| url.path().ends_with("b.note")));
+    // the file included once is kept
+    assert_eq!(collect(&ast).includes.len(), 1);
+}
@@ -1,3 +1,6 @@
+mod highlight;
+mod include;
+
 #[test]
 fn ready() {
     println!("it, works!")